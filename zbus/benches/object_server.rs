@@ -0,0 +1,91 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+use zbus::{block_on, connection::Builder, dbus_interface, AuthMechanism, Connection, Guid};
+
+const OBJECTS: u32 = 1000;
+
+struct Object(u32);
+
+#[dbus_interface(name = "org.freedesktop.zbus.Bench")]
+impl Object {
+    #[dbus_interface(property)]
+    fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+// A pair of peer-to-peer connections, so that no bus is needed. Over TCP, since it's available on
+// all platforms.
+fn connections() -> (Connection, Connection) {
+    block_on(async {
+        let guid = Guid::generate();
+
+        #[cfg(not(feature = "tokio"))]
+        let (p0, p1) = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let p1 = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+            (listener.accept().unwrap().0, p1)
+        };
+        #[cfg(feature = "tokio")]
+        let (p0, p1) = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let p1 = tokio::net::TcpStream::connect(listener.local_addr().unwrap())
+                .await
+                .unwrap();
+
+            (listener.accept().await.unwrap().0, p1)
+        };
+
+        futures_util::try_join!(
+            Builder::tcp_stream(p0)
+                .server(&guid)
+                .p2p()
+                .auth_mechanisms(&[AuthMechanism::Anonymous])
+                .build(),
+            Builder::tcp_stream(p1).p2p().build(),
+        )
+        .unwrap()
+    })
+}
+
+fn path(i: u32) -> String {
+    format!("/org/freedesktop/zbus/Bench/{i}")
+}
+
+fn register_objects(c: &mut Criterion) {
+    let mut group = c.benchmark_group("register_1000_objects");
+    group.sample_size(20);
+
+    group.bench_function("individually", |b| {
+        b.iter_batched(
+            connections,
+            |(conn, _peer)| {
+                block_on(async {
+                    let object_server = conn.object_server();
+                    for i in 0..OBJECTS {
+                        object_server.at(path(i), Object(i)).await.unwrap();
+                    }
+                })
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.bench_function("at_many", |b| {
+        b.iter_batched(
+            connections,
+            |(conn, _peer)| {
+                block_on(async {
+                    let objects = (0..OBJECTS).map(|i| (path(i), Object(i)));
+                    conn.object_server().at_many(objects).await.unwrap();
+                })
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, register_objects);
+criterion_main!(benches);
@@ -0,0 +1,89 @@
+// A service that can be restarted without any downtime, by handing its name over to the new
+// instance.
+//
+// Run it in a terminal, then run it again in another one: the new instance replaces the old one as
+// the owner of the `org.zbus.HandoffExample` name and the old one exits as soon as it's done with
+// the calls it was already handling. Make some (slow) calls in the meantime to see it in action:
+//
+//   busctl --user call org.zbus.HandoffExample /org/zbus/HandoffExample \
+//       org.zbus.HandoffExample Work
+
+use std::{
+    error::Error,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use event_listener::Event;
+use futures_util::stream::StreamExt;
+use zbus::{dbus_interface, fdo::RequestNameFlags, Connection};
+
+const NAME: &str = "org.zbus.HandoffExample";
+
+// Keeps track of the calls in progress.
+#[derive(Default)]
+struct InFlight {
+    count: AtomicUsize,
+    done: Event,
+}
+
+struct Worker {
+    in_flight: Arc<InFlight>,
+}
+
+#[dbus_interface(name = "org.zbus.HandoffExample")]
+impl Worker {
+    /// Do some (slow) work and return the PID of the process that did it.
+    async fn work(&self) -> u32 {
+        self.in_flight.count.fetch_add(1, Ordering::SeqCst);
+        async_std::task::sleep(Duration::from_secs(5)).await;
+        if self.in_flight.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.in_flight.done.notify(usize::MAX);
+        }
+
+        std::process::id()
+    }
+}
+
+#[async_std::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    tracing_subscriber::fmt::init();
+
+    let in_flight = Arc::new(InFlight::default());
+    let worker = Worker {
+        in_flight: in_flight.clone(),
+    };
+    let connection = Connection::session().await?;
+    connection
+        .object_server()
+        .at("/org/zbus/HandoffExample", worker)
+        .await?;
+
+    // Take the name over from a running instance, if any, and let the next one do the same.
+    let mut lost_stream = connection.receive_name_lost(NAME).await?;
+    connection
+        .request_name_with_flags(
+            NAME,
+            RequestNameFlags::ReplaceExisting | RequestNameFlags::AllowReplacement,
+        )
+        .await?;
+    println!("Serving `{NAME}` (PID {})", std::process::id());
+
+    lost_stream.next().await;
+    println!("Lost `{NAME}` to a new instance, finishing the calls in progress");
+
+    // New calls go to the new instance now, so we only need to wait for the ones in progress.
+    loop {
+        let done = in_flight.done.listen();
+        if in_flight.count.load(Ordering::SeqCst) == 0 {
+            break;
+        }
+        done.await;
+    }
+    println!("Done, exiting");
+
+    Ok(())
+}
@@ -3,11 +3,18 @@
 //! Server addresses consist of a transport name followed by a colon, and then an optional,
 //! comma-separated list of keys and values in the form key=value.
 //!
+//! The `unix:`, `tcp:`, `nonce-tcp:`, `vsock:` (with the `vsock` or `tokio-vsock` feature),
+//! `autolaunch:` (on Windows) and `launchd:` (on macOS) transports are supported. The `kernel:`
+//! transport of [kdbus] is not, since kdbus never made it into the mainline Linux kernel; such an
+//! address is recognized but fails to parse with an [`Error::Address`] saying so. The same goes for
+//! the `unixexec:` transport, since zbus doesn't spawn the peer process.
+//!
 //! See also:
 //!
 //! * [Server addresses] in the D-Bus specification.
 //!
 //! [Server addresses]: https://dbus.freedesktop.org/doc/dbus-specification.html#addresses
+//! [kdbus]: https://www.freedesktop.org/wiki/Software/systemd/kdbus/
 
 #[cfg(target_os = "macos")]
 use crate::process::run;
@@ -93,7 +100,7 @@ impl TcpAddress {
             .ok_or_else(|| Error::Address("tcp address is missing `port`".into()))?;
         let port = port
             .parse::<u16>()
-            .map_err(|_| Error::Address("invalid tcp `port`".into()))?;
+            .map_err(|_| Error::Address(format!("invalid tcp `port`: {port}")))?;
         let family = opts
             .get("family")
             .map(|f| TcpAddressFamily::from_str(f))
@@ -432,8 +439,10 @@ impl Address {
     }
 
     /// Get the address for system bus respecting the DBUS_SYSTEM_BUS_ADDRESS environment
-    /// variable. If we don't recognize the value (or it's not set) we fall back to
-    /// /var/run/dbus/system_bus_socket
+    /// variable, e.g for containers that bind the system bus socket elsewhere. An error is returned
+    /// if its value isn't a valid address. If it's not set, we fall back to
+    /// `unix:path=/var/run/dbus/system_bus_socket` (on Unix, other platforms have their own
+    /// defaults).
     pub fn system() -> Result<Self> {
         match env::var("DBUS_SYSTEM_BUS_ADDRESS") {
             Ok(val) => Self::from_str(&val),
@@ -450,6 +459,33 @@ impl Address {
         }
     }
 
+    /// Get the address of the bus that started the current process through D-Bus activation.
+    ///
+    /// `DBUS_STARTER_ADDRESS` takes precedence: if it's set, its value is used as is. Otherwise,
+    /// `DBUS_STARTER_BUS_TYPE` (`session` or `system`) selects between [`Address::session`] and
+    /// [`Address::system`]. An error is returned if neither is set, i.e the process wasn't
+    /// activated by a bus, or if `DBUS_STARTER_BUS_TYPE` has any other value.
+    pub fn starter_bus() -> Result<Self> {
+        if let Ok(val) = env::var("DBUS_STARTER_ADDRESS") {
+            return Self::from_str(&val);
+        }
+
+        match env::var("DBUS_STARTER_BUS_TYPE") {
+            Ok(val) => match val.as_str() {
+                "session" => Self::session(),
+                "system" => Self::system(),
+                _ => Err(Error::Address(format!(
+                    "unknown bus type `{val}` in DBUS_STARTER_BUS_TYPE"
+                ))),
+            },
+            Err(_) => Err(Error::Address(
+                "neither DBUS_STARTER_ADDRESS nor DBUS_STARTER_BUS_TYPE is set; \
+                 the process was not started by a message bus"
+                    .to_string(),
+            )),
+        }
+    }
+
     // Helper for FromStr
     #[cfg(any(unix, not(feature = "tokio")))]
     fn from_unix(opts: HashMap<&str, &str>) -> Result<Self> {
@@ -467,7 +503,11 @@ impl Address {
             (None, None, Some(p), None) => Address::UnixDir(OsString::from(p)),
             (None, None, None, Some(p)) => Address::UnixTmpDir(OsString::from(p)),
             _ => {
-                return Err(Error::Address("unix: address is invalid".to_owned()));
+                return Err(Error::Address(
+                    "unix: address is invalid, it needs exactly one of `path`, `abstract`, `dir` \
+                     or `tmpdir`"
+                        .to_owned(),
+                ));
             }
         };
 
@@ -705,9 +745,9 @@ impl FromStr for Address {
                 let (k, v) = match kv.find('=') {
                     Some(eq) => (&kv[..eq], &kv[eq + 1..]),
                     None => {
-                        return Err(Error::Address(
-                            "missing = when parsing key/value".to_owned(),
-                        ))
+                        return Err(Error::Address(format!(
+                            "missing = when parsing key/value `{kv}`"
+                        )))
                     }
                 };
                 if options.insert(k, v).is_some() {
@@ -749,6 +789,13 @@ impl FromStr for Address {
                     .ok_or_else(|| Error::Address("missing env key".into()))?
                     .to_string(),
             )),
+            "kernel" => Err(Error::Address(
+                "unsupported transport 'kernel': kdbus is not supported".to_owned(),
+            )),
+            "unixexec" => Err(Error::Address(
+                "unsupported transport 'unixexec': spawning the peer process is not supported"
+                    .to_owned(),
+            )),
 
             _ => Err(Error::Address(format!(
                 "unsupported transport '{transport}'"
@@ -783,19 +830,25 @@ mod tests {
             _ => panic!(),
         }
         match Address::from_str("foo:opt").unwrap_err() {
-            Error::Address(e) => assert_eq!(e, "missing = when parsing key/value"),
+            Error::Address(e) => assert_eq!(e, "missing = when parsing key/value `opt`"),
             _ => panic!(),
         }
         match Address::from_str("foo:opt=1,opt=2").unwrap_err() {
             Error::Address(e) => assert_eq!(e, "Key `opt` specified multiple times"),
             _ => panic!(),
         }
+        match Address::from_str("kernel:path=/sys/fs/kdbus/0-system/bus").unwrap_err() {
+            Error::Address(e) => {
+                assert_eq!(e, "unsupported transport 'kernel': kdbus is not supported")
+            }
+            _ => panic!(),
+        }
         match Address::from_str("tcp:host=localhost").unwrap_err() {
             Error::Address(e) => assert_eq!(e, "tcp address is missing `port`"),
             _ => panic!(),
         }
         match Address::from_str("tcp:host=localhost,port=32f").unwrap_err() {
-            Error::Address(e) => assert_eq!(e, "invalid tcp `port`"),
+            Error::Address(e) => assert_eq!(e, "invalid tcp `port`: 32f"),
             _ => panic!(),
         }
         match Address::from_str("tcp:host=localhost,port=123,family=ipv7").unwrap_err() {
@@ -803,15 +856,26 @@ mod tests {
             _ => panic!(),
         }
         match Address::from_str("unix:foo=blah").unwrap_err() {
-            Error::Address(e) => assert_eq!(e, "unix: address is invalid"),
+            Error::Address(e) => assert!(e.starts_with("unix: address is invalid")),
             _ => panic!(),
         }
         match Address::from_str("unix:path=/tmp,abstract=foo").unwrap_err() {
             Error::Address(e) => {
-                assert_eq!(e, "unix: address is invalid")
+                assert_eq!(
+                    e,
+                    "unix: address is invalid, it needs exactly one of `path`, `abstract`, `dir` \
+                     or `tmpdir`"
+                )
             }
             _ => panic!(),
         }
+        match Address::from_str("unixexec:path=/usr/bin/dbus-daemon,argv1=--session").unwrap_err() {
+            Error::Address(e) => assert_eq!(
+                e,
+                "unsupported transport 'unixexec': spawning the peer process is not supported"
+            ),
+            _ => panic!(),
+        }
         assert_eq!(
             Address::Unix("/tmp/dbus-foo".into()),
             Address::from_str("unix:path=/tmp/dbus-foo").unwrap()
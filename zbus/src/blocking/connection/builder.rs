@@ -3,6 +3,7 @@ use static_assertions::assert_impl_all;
 use std::net::TcpStream;
 #[cfg(all(unix, not(feature = "tokio")))]
 use std::os::unix::net::UnixStream;
+use std::time::Duration;
 #[cfg(feature = "tokio")]
 use tokio::net::TcpStream;
 #[cfg(all(unix, feature = "tokio"))]
@@ -15,6 +16,8 @@ use zvariant::{ObjectPath, Str};
 use crate::{
     address::Address,
     blocking::Connection,
+    connection::{ReplyOrdering, SendQueueFullBehavior},
+    message,
     names::{UniqueName, WellKnownName},
     object_server::Interface,
     utils::block_on,
@@ -39,6 +42,14 @@ impl<'a> Builder<'a> {
         crate::connection::Builder::system().map(Self)
     }
 
+    /// Create a builder for the connection to the message bus that activated the current
+    /// process.
+    ///
+    /// See [`crate::Address::starter_bus`] for how the bus is determined.
+    pub fn starter_bus() -> Result<Self> {
+        crate::connection::Builder::starter_bus().map(Self)
+    }
+
     /// Create a builder for connection that will use the given [D-Bus bus address].
     ///
     /// [D-Bus bus address]: https://dbus.freedesktop.org/doc/dbus-specification.html#addresses
@@ -136,6 +147,59 @@ impl<'a> Builder<'a> {
         Self(self.0.max_queued(max))
     }
 
+    /// Limit the number of outgoing messages waiting to be written to the socket.
+    ///
+    /// See [`zbus::connection::Builder::send_queue_limit`] for details.
+    pub fn send_queue_limit(self, limit: usize) -> Self {
+        Self(self.0.send_queue_limit(limit))
+    }
+
+    /// Set what sending a message does when the outgoing queue limit is reached.
+    pub fn send_queue_full_behavior(self, behavior: SendQueueFullBehavior) -> Self {
+        Self(self.0.send_queue_full_behavior(behavior))
+    }
+
+    /// Limit how long to wait for the reply to a method call.
+    ///
+    /// See [`zbus::connection::Builder::method_timeout`] for details.
+    pub fn method_timeout(self, timeout: Duration) -> Self {
+        Self(self.0.method_timeout(timeout))
+    }
+
+    /// Set a policy for the addressing of the replies sent by the connection.
+    ///
+    /// See [`zbus::connection::Builder::reply_routing`] for details.
+    pub fn reply_routing<F>(self, routing: F) -> Self
+    where
+        F: for<'b> Fn(&message::Header<'_>, message::Builder<'b>) -> Result<message::Builder<'b>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self(self.0.reply_routing(routing))
+    }
+
+    /// Set in which order the object server replies to method calls.
+    ///
+    /// See [`zbus::connection::Builder::reply_ordering`] for details.
+    pub fn reply_ordering(self, ordering: ReplyOrdering) -> Self {
+        Self(self.0.reply_ordering(ordering))
+    }
+
+    /// Limit the number of method calls the object server handles concurrently.
+    ///
+    /// See [`zbus::connection::Builder::max_concurrent_calls`] for details.
+    pub fn max_concurrent_calls(self, limit: usize) -> Self {
+        Self(self.0.max_concurrent_calls(limit))
+    }
+
+    /// Periodically check that the peer is still there.
+    ///
+    /// See [`zbus::connection::Builder::keepalive`] for details.
+    pub fn keepalive(self, interval: Duration, timeout: Duration) -> Self {
+        Self(self.0.keepalive(interval, timeout))
+    }
+
     /// Register a D-Bus [`Interface`] to be served at a given path.
     ///
     /// This is similar to [`zbus::blocking::ObjectServer::at`], except that it allows you to have
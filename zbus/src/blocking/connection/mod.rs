@@ -2,17 +2,23 @@
 
 use enumflags2::BitFlags;
 use event_listener::EventListener;
+use futures_util::StreamExt;
 use static_assertions::assert_impl_all;
-use std::{io, num::NonZeroU32, ops::Deref, sync::Arc};
-use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, OwnedUniqueName, WellKnownName};
+use std::{io, num::NonZeroU32, ops::Deref, sync::Arc, time::Duration};
+use zbus_names::{
+    BusName, ErrorName, InterfaceName, MemberName, OwnedUniqueName, OwnedWellKnownName,
+    WellKnownName,
+};
 use zvariant::ObjectPath;
 
 use crate::{
-    blocking::ObjectServer,
-    fdo::{ConnectionCredentials, RequestNameFlags, RequestNameReply},
+    blocking::{MessageIterator, ObjectServer},
+    connection::{ConnectionEvent, ReplyOrdering, SendQueueFullBehavior},
+    fdo::{ConnectionCredentials, ReleaseNameReply, RequestNameFlags, RequestNameReply},
     message::Message,
+    proxy::RetryPolicy,
     utils::block_on,
-    DBusError, Error, Result,
+    DBusError, Error, OwnedMatchRule, Result,
 };
 
 mod builder;
@@ -44,6 +50,26 @@ impl Connection {
         block_on(crate::Connection::system()).map(Self::from)
     }
 
+    /// Create a `Connection` to the message bus that activated the current process.
+    ///
+    /// See [`crate::Address::starter_bus`] for how the bus is determined.
+    pub fn for_starter_bus() -> Result<Self> {
+        block_on(crate::Connection::for_starter_bus()).map(Self::from)
+    }
+
+    /// Create a `Connection` to the message bus at the given [D-Bus address].
+    ///
+    /// See [`crate::Connection::for_address`] for details.
+    ///
+    /// [D-Bus address]: https://dbus.freedesktop.org/doc/dbus-specification.html#addresses
+    pub fn for_address<A>(address: A) -> Result<Self>
+    where
+        A: TryInto<crate::Address>,
+        A::Error: Into<Error>,
+    {
+        block_on(crate::Connection::for_address(address)).map(Self::from)
+    }
+
     /// The capacity of the main (unfiltered) queue.
     pub fn max_queued(&self) -> usize {
         self.inner.max_queued()
@@ -54,6 +80,56 @@ impl Connection {
         self.inner.set_max_queued(max)
     }
 
+    /// The maximum number of messages waiting to be written to the socket, if any.
+    pub fn send_queue_limit(&self) -> Option<usize> {
+        self.inner.send_queue_limit()
+    }
+
+    /// Set the maximum number of messages waiting to be written to the socket.
+    pub fn set_send_queue_limit(&mut self, limit: Option<usize>) {
+        self.inner.set_send_queue_limit(limit)
+    }
+
+    /// What sending a message does when the outgoing queue is full.
+    pub fn send_queue_full_behavior(&self) -> SendQueueFullBehavior {
+        self.inner.send_queue_full_behavior()
+    }
+
+    /// Set what sending a message does when the outgoing queue is full.
+    pub fn set_send_queue_full_behavior(&mut self, behavior: SendQueueFullBehavior) {
+        self.inner.set_send_queue_full_behavior(behavior)
+    }
+
+    /// How long to wait for the reply to a method call, if there is a limit.
+    pub fn method_timeout(&self) -> Option<Duration> {
+        self.inner.method_timeout()
+    }
+
+    /// Set how long to wait for the reply to a method call.
+    pub fn set_method_timeout(&mut self, timeout: Option<Duration>) {
+        self.inner.set_method_timeout(timeout)
+    }
+
+    /// In which order the object server replies to method calls.
+    pub fn reply_ordering(&self) -> ReplyOrdering {
+        self.inner.reply_ordering()
+    }
+
+    /// Set in which order the object server replies to method calls.
+    pub fn set_reply_ordering(&mut self, ordering: ReplyOrdering) {
+        self.inner.set_reply_ordering(ordering)
+    }
+
+    /// The maximum number of method calls the object server handles concurrently, if any.
+    pub fn max_concurrent_calls(&self) -> Option<usize> {
+        self.inner.max_concurrent_calls()
+    }
+
+    /// Set the maximum number of method calls the object server handles concurrently.
+    pub fn set_max_concurrent_calls(&mut self, limit: Option<usize>) {
+        self.inner.set_max_concurrent_calls(limit)
+    }
+
     /// The server's GUID.
     pub fn server_guid(&self) -> &str {
         self.inner.server_guid()
@@ -109,6 +185,13 @@ impl Connection {
         )
     }
 
+    /// Send a pre-built method call message and wait for the reply.
+    ///
+    /// See [`crate::Connection::call`] for details.
+    pub fn call(&self, msg: Message) -> Result<Arc<Message>> {
+        block_on(self.inner.call(msg))
+    }
+
     /// Emit a signal.
     ///
     /// Create a signal message, and send it over the connection.
@@ -137,6 +220,26 @@ impl Connection {
         )
     }
 
+    /// Receive all the signals matching `rule`, as raw messages.
+    ///
+    /// Blocking version of [`crate::Connection::receive_all_signals`]. See docs there for more
+    /// details and caveats.
+    pub fn receive_all_signals<R>(&self, rule: R) -> Result<MessageIterator>
+    where
+        R: TryInto<OwnedMatchRule>,
+        R::Error: Into<Error>,
+    {
+        block_on(self.inner.receive_all_signals(rule)).map(MessageIterator::from_stream)
+    }
+
+    /// The match rules this connection is currently subscribed to, sorted.
+    ///
+    /// Blocking version of [`crate::Connection::active_match_rules`]. See docs there for more
+    /// details.
+    pub fn active_match_rules(&self) -> Vec<String> {
+        block_on(self.inner.active_match_rules())
+    }
+
     /// Reply to a message.
     ///
     /// Given an existing message (likely a method call), send a reply back to the caller with the
@@ -212,6 +315,61 @@ impl Connection {
         block_on(self.inner.request_name_with_flags(well_known_name, flags))
     }
 
+    /// Register a well-known name for this service on the bus, returning the reply of the bus as is.
+    ///
+    /// Blocking version of [`crate::Connection::request_name_full`]. See docs there for more
+    /// details and caveats.
+    pub fn request_name_full<'w, W>(
+        &self,
+        well_known_name: W,
+        flags: BitFlags<RequestNameFlags>,
+    ) -> Result<RequestNameReply>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        block_on(self.inner.request_name_full(well_known_name, flags))
+    }
+
+    /// Receive the `NameLost` signals for a well-known name owned by this connection.
+    ///
+    /// See [`crate::Connection::receive_name_lost`] for details.
+    pub fn receive_name_lost<'w, W>(
+        &self,
+        well_known_name: W,
+    ) -> Result<crate::blocking::fdo::NameLostIterator<'static>>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        let well_known_name = well_known_name.try_into().map_err(Into::into)?;
+
+        crate::blocking::fdo::DBusProxy::builder(self)
+            .cache_properties(crate::CacheProperties::No)
+            .build()?
+            .receive_name_lost_with_args(&[(0, well_known_name.as_str())])
+    }
+
+    /// Register a well-known name for this connection, retrying on transient failures.
+    ///
+    /// See [`crate::Connection::request_name_with_retry`] for details.
+    pub fn request_name_with_retry<'w, W>(
+        &self,
+        well_known_name: W,
+        flags: BitFlags<RequestNameFlags>,
+        retry: &RetryPolicy,
+        queue_timeout: Option<Duration>,
+    ) -> Result<RequestNameReply>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        block_on(
+            self.inner
+                .request_name_with_retry(well_known_name, flags, retry, queue_timeout),
+        )
+    }
+
     /// Deregister a previously registered well-known name for this service on the bus.
     ///
     /// Use this method to deregister a well-known name, registered through
@@ -228,6 +386,33 @@ impl Connection {
         block_on(self.inner.release_name(well_known_name))
     }
 
+    /// Deregister a well-known name for this service on the bus, returning the reply of the bus as
+    /// is.
+    ///
+    /// Blocking version of [`crate::Connection::release_name_full`]. See docs there for more
+    /// details.
+    pub fn release_name_full<'w, W>(&self, well_known_name: W) -> Result<ReleaseNameReply>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        block_on(self.inner.release_name_full(well_known_name))
+    }
+
+    /// The well-known names currently owned by this connection.
+    ///
+    /// See [`crate::Connection::owned_names`] for details.
+    pub fn owned_names(&self) -> Vec<OwnedWellKnownName> {
+        block_on(self.inner.owned_names())
+    }
+
+    /// Returns an iterator over the lifecycle events of this connection.
+    ///
+    /// See [`crate::Connection::events`] for details.
+    pub fn events(&self) -> Result<ConnectionEventIterator> {
+        block_on(self.inner.events()).map(ConnectionEventIterator)
+    }
+
     /// Checks if `self` is a connection to a message bus.
     ///
     /// This will return `false` for p2p connections.
@@ -270,6 +455,13 @@ impl Connection {
     pub fn peer_credentials(&self) -> io::Result<ConnectionCredentials> {
         block_on(self.inner.peer_credentials())
     }
+
+    /// Returns the credentials of the sender of `msg`.
+    ///
+    /// See [`crate::Connection::caller_credentials`] for details.
+    pub fn caller_credentials(&self, msg: &Message) -> Result<ConnectionCredentials> {
+        block_on(self.inner.caller_credentials(msg))
+    }
 }
 
 impl From<crate::Connection> for Connection {
@@ -278,6 +470,22 @@ impl From<crate::Connection> for Connection {
     }
 }
 
+/// An [`std::iter::Iterator`] implementation that yields the lifecycle events of a connection.
+///
+/// Use [`Connection::events`] to create an instance of this type.
+#[derive(Debug)]
+pub struct ConnectionEventIterator(crate::connection::ConnectionEvents);
+
+assert_impl_all!(ConnectionEventIterator: Send, Sync, Unpin);
+
+impl std::iter::Iterator for ConnectionEventIterator {
+    type Item = ConnectionEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        block_on(self.0.next())
+    }
+}
+
 #[cfg(all(test, unix))]
 mod tests {
     use ntest::timeout;
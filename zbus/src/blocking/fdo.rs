@@ -9,13 +9,13 @@ use zbus_names::{
     BusName, InterfaceName, OwnedBusName, OwnedInterfaceName, OwnedUniqueName, UniqueName,
     WellKnownName,
 };
-use zvariant::{ObjectPath, Optional, OwnedValue, Value};
+use zvariant::{ObjectPath, Optional, OwnedObjectPath, OwnedValue, Value};
 
 use crate::{
     dbus_proxy,
     fdo::{
-        ConnectionCredentials, ManagedObjects, ReleaseNameReply, RequestNameFlags,
-        RequestNameReply, Result,
+        ConnectionCredentials, ContainerInstanceInfo, ManagedObjects, OwnedPropertiesChangedArgs,
+        ReleaseNameReply, RequestNameFlags, RequestNameReply, Result, StartServiceReply,
     },
     Guid,
 };
@@ -26,6 +26,15 @@ assert_impl_all!(IntrospectableProxy<'_>: Send, Sync, Unpin);
 gen_properties_proxy!(false, true);
 assert_impl_all!(PropertiesProxy<'_>: Send, Sync, Unpin);
 
+impl PropertiesChanged {
+    /// Retrieve the signal arguments, as owned values with named fields.
+    ///
+    /// See [`crate::fdo::OwnedPropertiesChangedArgs`] for details.
+    pub fn owned_args(&self) -> crate::Result<OwnedPropertiesChangedArgs> {
+        OwnedPropertiesChangedArgs::try_from(&**self)
+    }
+}
+
 gen_object_manager_proxy!(false, true);
 assert_impl_all!(ObjectManagerProxy<'_>: Send, Sync, Unpin);
 
@@ -38,5 +47,8 @@ assert_impl_all!(MonitoringProxy<'_>: Send, Sync, Unpin);
 gen_stats_proxy!(false, true);
 assert_impl_all!(StatsProxy<'_>: Send, Sync, Unpin);
 
+gen_containers_proxy!(false, true);
+assert_impl_all!(ContainersProxy<'_>: Send, Sync, Unpin);
+
 gen_dbus_proxy!(false, true);
 assert_impl_all!(DBusProxy<'_>: Send, Sync, Unpin);
@@ -102,6 +102,12 @@ impl MessageIterator {
         .map(|s| Self { azync: s })
     }
 
+    pub(crate) fn from_stream(stream: crate::MessageStream) -> Self {
+        Self {
+            azync: Some(stream),
+        }
+    }
+
     /// The associated match rule, if any.
     pub fn match_rule(&self) -> Option<MatchRule<'_>> {
         self.azync
@@ -1,12 +1,18 @@
 //! The object server API.
 
+use futures_core::Future;
 use std::ops::Deref;
 
 use static_assertions::assert_impl_all;
-use zvariant::ObjectPath;
+use zbus_names::{InterfaceName, MemberName};
+use zvariant::{ObjectPath, OwnedObjectPath};
 
 use crate::{
-    object_server::{Interface, InterfaceDeref, InterfaceDerefMut, SignalContext},
+    fdo,
+    message::Message,
+    object_server::{
+        Interface, InterfaceDeref, InterfaceDerefMut, SignalContext, UnknownObjectAction,
+    },
     utils::block_on,
     Error, Result,
 };
@@ -75,6 +81,18 @@ where
     pub fn signal_context(&self) -> &SignalContext<'static> {
         self.azync.signal_context()
     }
+
+    /// Wait for the next call to the method `member` of this interface.
+    ///
+    /// See [`crate::ObjectServer::next_call`] for details.
+    pub fn next_call<'m, M>(&self, member: M) -> Result<Message>
+    where
+        I: Interface,
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+    {
+        block_on(self.azync.next_call(member))
+    }
 }
 
 /// A blocking wrapper of [`crate::ObjectServer`].
@@ -159,6 +177,49 @@ impl ObjectServer {
         block_on(self.azync.at(path, iface))
     }
 
+    /// Register many D-Bus [`Interface`]s of the same type at once.
+    ///
+    /// See [`crate::ObjectServer::at_many`] for details.
+    ///
+    /// [`Interface`]: trait.Interface.html
+    pub fn at_many<'p, P, I, O>(&self, objects: O) -> Result<usize>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+        O: IntoIterator<Item = (P, I)>,
+    {
+        block_on(self.azync.at_many(objects))
+    }
+
+    /// Register a D-Bus [`Interface`] for all paths under a given prefix.
+    ///
+    /// See [`crate::ObjectServer::at_prefix`] for details.
+    ///
+    /// [`Interface`]: trait.Interface.html
+    pub fn at_prefix<'p, P, I>(&self, prefix: P, iface: I) -> Result<bool>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.azync.at_prefix(prefix, iface))
+    }
+
+    /// Replace the instance of the D-Bus [`Interface`] registered at a given path.
+    ///
+    /// See [`crate::ObjectServer::replace`] for details.
+    ///
+    /// [`Interface`]: trait.Interface.html
+    pub fn replace<'p, P, I>(&self, path: P, iface: I) -> Result<I>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.azync.replace(path, iface))
+    }
+
     /// Unregister a D-Bus [`Interface`] at a given path.
     ///
     /// If there are no more interfaces left at that path, destroys the object as well.
@@ -174,6 +235,20 @@ impl ObjectServer {
         block_on(self.azync.remove::<I, P>(path))
     }
 
+    /// Unregister a D-Bus [`Interface`] registered for a path prefix.
+    ///
+    /// See [`crate::ObjectServer::remove_prefix`] for details.
+    ///
+    /// [`Interface`]: trait.Interface.html
+    pub fn remove_prefix<'p, I, P>(&self, prefix: P) -> Result<bool>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.azync.remove_prefix::<I, P>(prefix))
+    }
+
     /// Get the interface at the given path.
     ///
     /// # Errors
@@ -224,6 +299,72 @@ impl ObjectServer {
         })
     }
 
+    /// Get the introspection XML of the object at the given path.
+    ///
+    /// See [`crate::ObjectServer::introspect`] for details.
+    pub fn introspect<'p, P>(&self, path: P) -> Result<String>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.azync.introspect(path))
+    }
+
+    /// The paths of all the objects registered on the server, in sorted order.
+    ///
+    /// See [`crate::ObjectServer::paths`] for details.
+    pub fn paths(&self) -> Vec<OwnedObjectPath> {
+        block_on(self.azync.paths())
+    }
+
+    /// The names of the interfaces registered at the given path, in sorted order.
+    ///
+    /// See [`crate::ObjectServer::interfaces_at`] for details.
+    pub fn interfaces_at<'p, P>(&self, path: P) -> Result<Vec<InterfaceName<'static>>>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.azync.interfaces_at(path))
+    }
+
+    /// Override the introspection XML of the object at the given path.
+    ///
+    /// See [`crate::ObjectServer::set_introspection`] for details.
+    #[cfg(feature = "xml")]
+    pub fn set_introspection<'p, P>(&self, path: P, xml: Option<String>) -> Result<()>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        block_on(self.azync.set_introspection(path, xml))
+    }
+
+    /// Handle the method calls to paths with no object registered.
+    ///
+    /// See [`crate::ObjectServer::set_unknown_object_handler`] for details.
+    pub fn set_unknown_object_handler<F, Fut>(&self, handler: F)
+    where
+        F: Fn(crate::Connection, Message) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = fdo::Result<UnknownObjectAction>> + Send + 'static,
+    {
+        self.azync.set_unknown_object_handler(handler)
+    }
+
+    /// Wait for the next call to the method `member` of the interface `I` at the given path.
+    ///
+    /// See [`crate::ObjectServer::next_call`] for details.
+    pub fn next_call<'p, 'm, I, P, M>(&self, path: P, member: M) -> Result<Message>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+    {
+        block_on(self.azync.next_call::<I, P, M>(path, member))
+    }
+
     /// Get a reference to the underlying async ObjectServer.
     pub fn inner(&self) -> &crate::ObjectServer {
         &self.azync
@@ -60,6 +60,48 @@ impl<'a, T> Builder<'a, T> {
         Self(self.0.uncached_properties(properties))
     }
 
+    /// Specify the only properties (by name) which should be cached.
+    ///
+    /// See [`crate::proxy::Builder::cached_properties`] for details.
+    #[must_use]
+    pub fn cached_properties(self, properties: &[&'a str]) -> Self {
+        Self(self.0.cached_properties(properties))
+    }
+
+    /// Set whether method calls may trigger the activation of the destination service.
+    ///
+    /// See [`crate::proxy::Builder::auto_start`] for details.
+    #[must_use]
+    pub fn auto_start(self, auto_start: bool) -> Self {
+        Self(self.0.auto_start(auto_start))
+    }
+
+    /// Set whether property operations may trigger the activation of the destination service.
+    ///
+    /// See [`crate::proxy::Builder::auto_start_properties`] for details.
+    #[must_use]
+    pub fn auto_start_properties(self, auto_start: bool) -> Self {
+        Self(self.0.auto_start_properties(auto_start))
+    }
+
+    /// Set whether the destination may interactively prompt the user for authorization.
+    ///
+    /// See [`crate::proxy::Builder::allow_interactive_auth`] for details.
+    #[must_use]
+    pub fn allow_interactive_auth(self, allow: bool) -> Self {
+        Self(self.0.allow_interactive_auth(allow))
+    }
+
+    /// Check that the destination provides the interface as expected, when building the proxy.
+    ///
+    /// See [`crate::proxy::Builder::verify_introspection`] for details.
+    #[cfg(feature = "xml")]
+    #[doc(hidden)]
+    #[must_use]
+    pub fn verify_introspection(self, expected: String) -> Self {
+        Self(self.0.verify_introspection(expected))
+    }
+
     /// Build a proxy from the builder.
     ///
     /// # Panics
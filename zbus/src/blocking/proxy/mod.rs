@@ -4,11 +4,15 @@ use enumflags2::BitFlags;
 use futures_util::StreamExt;
 use static_assertions::assert_impl_all;
 use std::{ops::Deref, sync::Arc};
-use zbus_names::{BusName, InterfaceName, MemberName, UniqueName};
+use zbus_names::{BusName, InterfaceName, MemberName, OwnedUniqueName, UniqueName};
 use zvariant::{ObjectPath, OwnedValue, Value};
 
 use crate::{
-    blocking::Connection, message::Message, proxy::MethodFlags, utils::block_on, Error, Result,
+    blocking::Connection,
+    message::Message,
+    proxy::{MethodFlags, RetryPolicy},
+    utils::block_on,
+    Error, Result,
 };
 
 use crate::fdo;
@@ -190,6 +194,17 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().get_property(property_name))
     }
 
+    /// Get the property `property_name`, checking its type.
+    ///
+    /// See [`crate::Proxy::get_property_checked`] for details.
+    pub fn get_property_checked<T>(&self, property_name: &str) -> Result<T>
+    where
+        T: TryFrom<OwnedValue> + zvariant::Type,
+        T::Error: Into<zvariant::Error>,
+    {
+        block_on(self.inner().get_property_checked(property_name))
+    }
+
     /// Set the property `property_name`.
     ///
     /// Effectively, call the `Set` method of the `org.freedesktop.DBus.Properties` interface.
@@ -200,6 +215,44 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().set_property(property_name, value))
     }
 
+    /// Get the property `property_name` of another interface of the object.
+    ///
+    /// This is useful for objects that spread their properties over multiple interfaces. Unlike
+    /// [`Proxy::get_property`], the value is never taken from the cache, since only the properties
+    /// of the proxy's own interface are cached.
+    pub fn get_interface_property<'i, I, T>(&self, interface: I, property_name: &str) -> Result<T>
+    where
+        I: TryInto<InterfaceName<'i>>,
+        I::Error: Into<Error>,
+        T: TryFrom<OwnedValue>,
+        T::Error: Into<Error>,
+    {
+        block_on(
+            self.inner()
+                .get_interface_property(interface, property_name),
+        )
+    }
+
+    /// Set the property `property_name` of another interface of the object.
+    ///
+    /// See [`Proxy::get_interface_property`] for details.
+    pub fn set_interface_property<'i, 't, I, T>(
+        &self,
+        interface: I,
+        property_name: &str,
+        value: T,
+    ) -> fdo::Result<()>
+    where
+        I: TryInto<InterfaceName<'i>>,
+        I::Error: Into<Error>,
+        T: Into<Value<'t>> + 't,
+    {
+        block_on(
+            self.inner()
+                .set_interface_property(interface, property_name, value),
+        )
+    }
+
     /// Call a method and return the reply.
     ///
     /// Typically, you would want to use [`call`] method instead. Use this method if you need to
@@ -255,6 +308,24 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().call_with_flags(method_name, flags, body))
     }
 
+    /// Call a method and return the reply body, retrying on transient failures.
+    ///
+    /// See [`crate::Proxy::call_with_retry`] for details.
+    pub fn call_with_retry<'m, M, B, R>(
+        &self,
+        method_name: M,
+        body: &B,
+        policy: &RetryPolicy,
+    ) -> Result<R>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        R: serde::de::DeserializeOwned + zvariant::Type,
+    {
+        block_on(self.inner().call_with_retry(method_name, body, policy))
+    }
+
     /// Call a method without expecting a reply
     ///
     /// This sets the `NoReplyExpected` flag on the calling message and does not wait for a reply.
@@ -267,6 +338,28 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().call_noreply(method_name, body))
     }
 
+    /// Call a method and return an iterator over its replies.
+    ///
+    /// See [`crate::Proxy::call_method_streaming`] for details.
+    pub fn call_method_streaming<'m, M, B, F>(
+        &self,
+        method_name: M,
+        body: &B,
+        is_final: F,
+    ) -> Result<ReplyIterator>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        F: FnMut(&Message) -> bool + Send + Sync + 'static,
+    {
+        block_on(
+            self.inner()
+                .call_method_streaming(method_name, body, is_final),
+        )
+        .map(ReplyIterator)
+    }
+
     /// Create a stream for signal named `signal_name`.
     ///
     /// # Errors
@@ -317,6 +410,13 @@ impl<'a> Proxy<'a> {
             .map(SignalIterator)
     }
 
+    /// The current owner of the proxy destination.
+    ///
+    /// See [`crate::Proxy::destination_owner`] for details.
+    pub fn destination_owner(&self) -> Result<Option<OwnedUniqueName>> {
+        block_on(self.inner().destination_owner())
+    }
+
     /// Get an iterator to receive owner changed events.
     ///
     /// If the proxy destination is a unique name, the stream will be notified of the peer
@@ -343,6 +443,13 @@ impl<'a> Proxy<'a> {
         block_on(self.inner().receive_owner_changed()).map(OwnerChangedIterator)
     }
 
+    /// Close the proxy, removing the match rules of its subscriptions.
+    ///
+    /// See [`crate::Proxy::close`] for details.
+    pub fn close(self) -> Result<()> {
+        block_on(self.into_inner().close())
+    }
+
     /// Get a reference to the underlying async Proxy.
     pub fn inner(&self) -> &crate::Proxy<'a> {
         self.azync.as_ref().expect("Inner proxy is `None`")
@@ -388,6 +495,34 @@ impl<'a> SignalIterator<'a> {
     pub fn name(&self) -> Option<&MemberName<'a>> {
         self.0.as_ref().expect("`SignalStream` is `None`").name()
     }
+
+    /// Wait for the first signal for which `predicate` returns `true`.
+    ///
+    /// See [`crate::proxy::SignalStream::wait_for`] for details.
+    pub fn wait_for<F>(mut self, predicate: F) -> Option<Arc<Message>>
+    where
+        F: FnMut(&Arc<Message>) -> bool,
+    {
+        let azync = self.0.take().expect("`SignalStream` is `None`");
+
+        block_on(azync.wait_for(predicate))
+    }
+
+    /// Wait for the first signal for which `predicate` returns `true`, for up to `timeout`.
+    ///
+    /// See [`crate::proxy::SignalStream::wait_for_timeout`] for details.
+    pub fn wait_for_timeout<F>(
+        mut self,
+        timeout: std::time::Duration,
+        predicate: F,
+    ) -> Option<Arc<Message>>
+    where
+        F: FnMut(&Arc<Message>) -> bool,
+    {
+        let azync = self.0.take().expect("`SignalStream` is `None`");
+
+        block_on(azync.wait_for_timeout(timeout, predicate))
+    }
 }
 
 assert_impl_all!(SignalIterator<'_>: Send, Sync, Unpin);
@@ -410,6 +545,22 @@ impl std::ops::Drop for SignalIterator<'_> {
     }
 }
 
+/// An [`std::iter::Iterator`] implementation that yields the replies to a single method call.
+///
+/// Use [`Proxy::call_method_streaming`] to create an instance of this type.
+#[derive(Debug)]
+pub struct ReplyIterator(crate::proxy::ReplyStream);
+
+assert_impl_all!(ReplyIterator: Send, Sync, Unpin);
+
+impl std::iter::Iterator for ReplyIterator {
+    type Item = Result<Arc<Message>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        block_on(self.0.next())
+    }
+}
+
 /// An [`std::iter::Iterator`] implementation that yields property change notifications.
 ///
 /// Use [`Proxy::receive_property_changed`] to create an instance of this type.
@@ -483,6 +634,43 @@ impl<'a> std::iter::Iterator for OwnerChangedIterator<'a> {
     }
 }
 
+/// An [`std::iter::Iterator`] implementation that yields the elements of an array returned by a
+/// method.
+///
+/// This is the blocking equivalent of [`crate::proxy::ArrayStream`].
+#[derive(Debug)]
+pub struct ArrayIterator<T>(crate::proxy::ArrayStream<T>);
+
+assert_impl_all!(ArrayIterator<()>: Send, Sync, Unpin);
+
+impl<T> ArrayIterator<T>
+where
+    T: serde::de::DeserializeOwned + zvariant::Type,
+{
+    /// Create an iterator over the elements of the array in the body of `reply`.
+    ///
+    /// Returns an error if the body of `reply` isn't a single array of `T`.
+    pub fn new(reply: Arc<Message>) -> Result<Self> {
+        crate::proxy::ArrayStream::new(reply).map(Self)
+    }
+
+    /// The message the elements are deserialized from.
+    pub fn message(&self) -> &Arc<Message> {
+        self.0.message()
+    }
+}
+
+impl<T> std::iter::Iterator for ArrayIterator<T>
+where
+    T: serde::de::DeserializeOwned + zvariant::Type,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_element()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,8 @@
 #[cfg(not(feature = "tokio"))]
 use async_io::Async;
 use event_listener::Event;
+#[cfg(not(feature = "tokio"))]
+use futures_util::io::{AsyncRead, AsyncWrite};
 use static_assertions::assert_impl_all;
 #[cfg(not(feature = "tokio"))]
 use std::net::TcpStream;
@@ -9,8 +11,11 @@ use std::os::unix::net::UnixStream;
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     sync::Arc,
+    time::Duration,
 };
 #[cfg(feature = "tokio")]
+use tokio::io::{AsyncRead, AsyncWrite};
+#[cfg(feature = "tokio")]
 use tokio::net::TcpStream;
 #[cfg(all(unix, feature = "tokio"))]
 use tokio::net::UnixStream;
@@ -26,6 +31,7 @@ use zvariant::{ObjectPath, Str};
 use crate::{
     address::{self, Address},
     async_lock::RwLock,
+    message,
     names::{InterfaceName, UniqueName, WellKnownName},
     object_server::Interface,
     Connection, Error, Executor, Guid, Result,
@@ -33,7 +39,8 @@ use crate::{
 
 use super::{
     handshake::{AuthMechanism, Authenticated},
-    raw::Socket,
+    raw::{Socket, StreamSocket},
+    ReplyOrdering, ReplyRouting, SendQueueFullBehavior,
 };
 
 const DEFAULT_MAX_QUEUED: usize = 64;
@@ -61,6 +68,14 @@ type Interfaces<'a> =
 pub struct Builder<'a> {
     target: Target,
     max_queued: Option<usize>,
+    send_queue_limit: Option<usize>,
+    method_timeout: Option<Duration>,
+    #[derivative(Debug = "ignore")]
+    reply_routing: Option<ReplyRouting>,
+    reply_ordering: ReplyOrdering,
+    max_concurrent_calls: Option<usize>,
+    keepalive: Option<(Duration, Duration)>,
+    send_queue_full_behavior: SendQueueFullBehavior,
     guid: Option<&'a Guid>,
     p2p: bool,
     internal_executor: bool,
@@ -82,10 +97,20 @@ impl<'a> Builder<'a> {
     }
 
     /// Create a builder for the system-wide message bus connection.
+    ///
+    /// See [`Address::system`] for how the address of the bus is determined.
     pub fn system() -> Result<Self> {
         Ok(Self::new(Target::Address(Address::system()?)))
     }
 
+    /// Create a builder for the connection to the message bus that activated the current
+    /// process.
+    ///
+    /// See [`Address::starter_bus`] for how the bus is determined.
+    pub fn starter_bus() -> Result<Self> {
+        Ok(Self::new(Target::Address(Address::starter_bus()?)))
+    }
+
     /// Create a builder for connection that will use the given [D-Bus bus address].
     ///
     /// # Example
@@ -163,6 +188,31 @@ impl<'a> Builder<'a> {
         Self::new(Target::Socket(Box::new(socket)))
     }
 
+    /// Create a builder for connection that will use the given byte stream.
+    ///
+    /// This allows running D-Bus over any reliable and ordered byte stream, such as a TLS stream
+    /// or an SSH channel, that is not otherwise supported. The messages are written and flushed
+    /// one at a time.
+    ///
+    /// If the default `async-io` feature is disabled, this method will expect a stream
+    /// implementing the [`tokio::io::AsyncRead`] and [`tokio::io::AsyncWrite`] traits, instead of
+    /// the ones from [`futures-io`].
+    ///
+    /// **Note:** File descriptors can not be passed over such a stream, so sending a message with
+    /// any will fail. Since the peer credentials are not available either, the `EXTERNAL`
+    /// authentication mechanism can't be used to authenticate clients; a server will typically
+    /// need to use the `ANONYMOUS` mechanism (see [`Builder::auth_mechanisms`]).
+    ///
+    /// [`tokio::io::AsyncRead`]: https://docs.rs/tokio/latest/tokio/io/trait.AsyncRead.html
+    /// [`tokio::io::AsyncWrite`]: https://docs.rs/tokio/latest/tokio/io/trait.AsyncWrite.html
+    /// [`futures-io`]: https://docs.rs/futures-io
+    pub fn stream<S>(stream: S) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + Sync + 'static,
+    {
+        Self::new(Target::Socket(Box::new(StreamSocket::new(stream))))
+    }
+
     /// Specify the mechanisms to use during authentication.
     pub fn auth_mechanisms(mut self, auth_mechanisms: &[AuthMechanism]) -> Self {
         self.auth_mechanisms = Some(VecDeque::from(auth_mechanisms.to_vec()));
@@ -249,6 +299,256 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Limit the number of outgoing messages waiting to be written to the socket.
+    ///
+    /// By default, there is no limit and if the peer is slow to read, the outgoing messages keep
+    /// piling up in memory. Once the limit is reached, sending a message (either directly, through
+    /// the [`Sink`] implementation or indirectly, e.g. by calling a method) either waits for the
+    /// queue to drain or fails with [`Error::SendQueueFull`], depending on the
+    /// [behavior](Builder::send_queue_full_behavior) chosen, which defaults to
+    /// [`SendQueueFullBehavior::Wait`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # use zbus::connection::{Builder, SendQueueFullBehavior};
+    /// # use zbus::block_on;
+    /// #
+    /// # block_on(async {
+    /// let conn = Builder::session()?
+    ///     .send_queue_limit(16)
+    ///     .send_queue_full_behavior(SendQueueFullBehavior::Fail)
+    ///     .build()
+    ///     .await?;
+    /// assert_eq!(conn.send_queue_limit(), Some(16));
+    ///
+    /// #     Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// #
+    /// // Do something useful with `conn`..
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    ///
+    /// [`Sink`]: futures_sink::Sink
+    pub fn send_queue_limit(mut self, limit: usize) -> Self {
+        self.send_queue_limit = Some(limit);
+
+        self
+    }
+
+    /// Set what sending a message does when the [outgoing queue limit] is reached.
+    ///
+    /// [outgoing queue limit]: Builder::send_queue_limit
+    pub fn send_queue_full_behavior(mut self, behavior: SendQueueFullBehavior) -> Self {
+        self.send_queue_full_behavior = behavior;
+
+        self
+    }
+
+    /// Limit how long to wait for the reply to a method call.
+    ///
+    /// By default, there is no limit on the connection side and method calls wait until the peer
+    /// replies. Although a bus typically replies with an `org.freedesktop.DBus.Error.NoReply` error
+    /// after a while (25 seconds by default for the reference implementation), p2p connections
+    /// can wait forever. Once the timeout elapses, the call fails with [`Error::Timeout`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::{error::Error, time::Duration};
+    /// # use zbus::connection::Builder;
+    /// # use zbus::block_on;
+    /// #
+    /// # block_on(async {
+    /// let conn = Builder::session()?
+    ///     .method_timeout(Duration::from_secs(5))
+    ///     .build()
+    ///     .await?;
+    /// assert_eq!(conn.method_timeout(), Some(Duration::from_secs(5)));
+    ///
+    /// #     Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// #
+    /// // Do something useful with `conn`..
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    pub fn method_timeout(mut self, timeout: Duration) -> Self {
+        self.method_timeout = Some(timeout);
+
+        self
+    }
+
+    /// Set a policy for the addressing of the replies sent by the connection.
+    ///
+    /// By default, a reply is sent from the unique name of the connection (if any) and its
+    /// destination mirrors the sender of the method call it's replying to. This suits the usual
+    /// case of a peer directly calling a service but not the setups where calls are forwarded, e.g
+    /// by a proxy or a router that expects the replies to go through it.
+    ///
+    /// `routing` is called with the header of the method call and a message builder for the reply,
+    /// already set up with the default addressing, and returns the builder to send the reply
+    /// with. It applies to all the replies sent through [`Connection::reply`],
+    /// [`Connection::reply_error`] and [`Connection::reply_dbus_error`], and therefore to the
+    /// replies of the [`ObjectServer`](crate::ObjectServer) as well. If it returns an error, the
+    /// reply isn't sent and the error is returned to the caller of these methods.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # use zbus::connection::Builder;
+    /// # use zbus::block_on;
+    /// #
+    /// # block_on(async {
+    /// // Send all the replies to the router the calls come from.
+    /// let conn = Builder::session()?
+    ///     .reply_routing(|_call, reply| reply.destination("org.example.Router"))
+    ///     .build()
+    ///     .await?;
+    ///
+    /// // Do something useful with `conn`..
+    /// # drop(conn);
+    /// #     Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    pub fn reply_routing<F>(mut self, routing: F) -> Self
+    where
+        F: for<'b> Fn(&message::Header<'_>, message::Builder<'b>) -> Result<message::Builder<'b>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.reply_routing = Some(Arc::new(routing));
+
+        self
+    }
+
+    /// Set in which order the object server replies to method calls.
+    ///
+    /// By default ([`ReplyOrdering::Completion`]), each method call is dispatched in its own task as
+    /// soon as it's received and replied to as soon as it's handled. This maximizes concurrency but
+    /// a call to a slow `async` method can be replied to after the calls that followed it, even if
+    /// they come from the same peer. Peers assuming the replies come in the order of their calls
+    /// break in that case.
+    ///
+    /// With [`ReplyOrdering::PerSender`], the method calls of each sender are dispatched one after
+    /// the other, in the order they were received, so their replies are in that order too. The
+    /// calls of different senders are still dispatched concurrently. The price is that a slow call
+    /// delays all the subsequent calls of the same sender, and that a method which waits for
+    /// another call from the peer that called it never completes. Up to [`Builder::max_queued`]
+    /// calls of a sender wait for the dispatch of its earlier ones: the calls it makes beyond that
+    /// are replied to with an `org.freedesktop.DBus.Error.LimitsExceeded` error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # use zbus::connection::{Builder, ReplyOrdering};
+    /// # use zbus::block_on;
+    /// #
+    /// # block_on(async {
+    /// let conn = Builder::session()?
+    ///     .reply_ordering(ReplyOrdering::PerSender)
+    ///     .build()
+    ///     .await?;
+    /// assert_eq!(conn.reply_ordering(), ReplyOrdering::PerSender);
+    ///
+    /// #     Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// #
+    /// // Do something useful with `conn`..
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    pub fn reply_ordering(mut self, ordering: ReplyOrdering) -> Self {
+        self.reply_ordering = ordering;
+
+        self
+    }
+
+    /// Limit the number of method calls the object server handles concurrently.
+    ///
+    /// By default, there is no limit and each method call is dispatched as soon as it's received.
+    /// Once `limit` handlers are running, the calls received in the meantime wait in a queue, to be
+    /// dispatched in the order they were received as the running ones complete. That queue holds
+    /// up to [`Builder::max_queued`] calls: the calls received while it's full are replied to with
+    /// an `org.freedesktop.DBus.Error.LimitsExceeded` error, so a peer flooding the connection with
+    /// slow calls can't make it use more memory. The other incoming messages are still received
+    /// meanwhile, so the handlers can themselves make method calls.
+    ///
+    /// # Panics
+    ///
+    /// If `limit` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # use zbus::connection::Builder;
+    /// # use zbus::block_on;
+    /// #
+    /// # block_on(async {
+    /// let conn = Builder::session()?
+    ///     .max_concurrent_calls(4)
+    ///     .build()
+    ///     .await?;
+    /// assert_eq!(conn.max_concurrent_calls(), Some(4));
+    ///
+    /// #     Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// #
+    /// // Do something useful with `conn`..
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    pub fn max_concurrent_calls(mut self, limit: usize) -> Self {
+        assert!(
+            limit > 0,
+            "the concurrent method calls limit must be at least 1"
+        );
+        self.max_concurrent_calls = Some(limit);
+
+        self
+    }
+
+    /// Periodically check that the peer is still there.
+    ///
+    /// Every `interval`, an `org.freedesktop.DBus.Peer.Ping` call is sent to the peer (or the bus,
+    /// for bus connections). If no reply arrives within `timeout`, the peer is considered gone and
+    /// the connection is closed, just as if the socket was disconnected: the pending method calls
+    /// fail and the message streams end after yielding the error. Any reply, including an error
+    /// one, counts as a sign of life.
+    ///
+    /// The keepalive is off by default. It's only sensible for p2p connections or connections to
+    /// a remote bus (e.g over TCP), where an idle connection can be silently dropped by a NAT or a
+    /// proxy along the way. Note that the peer needs to reply to `Ping` calls, which a zbus peer
+    /// does once its [`ObjectServer`](crate::ObjectServer) is set up.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::{error::Error, time::Duration};
+    /// # use zbus::connection::Builder;
+    /// # use zbus::block_on;
+    /// #
+    /// # block_on(async {
+    /// let conn = Builder::session()?
+    ///     .keepalive(Duration::from_secs(30), Duration::from_secs(5))
+    ///     .build()
+    ///     .await?;
+    ///
+    /// // Do something useful with `conn`..
+    /// # drop(conn);
+    /// #     Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    pub fn keepalive(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.keepalive = Some((interval, timeout));
+
+        self
+    }
+
     /// Enable or disable the internal executor thread.
     ///
     /// The thread is enabled by default.
@@ -398,6 +698,12 @@ impl<'a> Builder<'a> {
 
         let mut conn = Connection::new(auth, !self.p2p, executor).await?;
         conn.set_max_queued(self.max_queued.unwrap_or(DEFAULT_MAX_QUEUED));
+        conn.set_send_queue_limit(self.send_queue_limit);
+        conn.set_send_queue_full_behavior(self.send_queue_full_behavior);
+        conn.set_method_timeout(self.method_timeout);
+        conn.set_reply_routing(self.reply_routing);
+        conn.set_reply_ordering(self.reply_ordering);
+        conn.set_max_concurrent_calls(self.max_concurrent_calls);
         if let Some(unique_name) = self.unique_name {
             conn.set_unique_name(unique_name)?;
         }
@@ -432,6 +738,10 @@ impl<'a> Builder<'a> {
             conn.request_name(name).await?;
         }
 
+        if let Some((interval, timeout)) = self.keepalive {
+            conn.start_keepalive(interval, timeout);
+        }
+
         Ok(conn)
     }
 
@@ -440,6 +750,13 @@ impl<'a> Builder<'a> {
             target,
             p2p: false,
             max_queued: None,
+            send_queue_limit: None,
+            method_timeout: None,
+            reply_routing: None,
+            reply_ordering: ReplyOrdering::default(),
+            max_concurrent_calls: None,
+            keepalive: None,
+            send_queue_full_behavior: SendQueueFullBehavior::default(),
             guid: None,
             internal_executor: true,
             interfaces: HashMap::new(),
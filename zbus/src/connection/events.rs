@@ -0,0 +1,120 @@
+use futures_core::stream;
+use futures_util::stream::FusedStream;
+use static_assertions::assert_impl_all;
+use std::{
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tracing::debug;
+use zbus_names::{BusName, OwnedBusName};
+
+use crate::{message::Type, Connection, Error, MatchRule, MessageStream, Result};
+
+/// An event in the lifecycle of a [`Connection`], as yielded by [`ConnectionEvents`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ConnectionEvent {
+    /// The connection is established. This is always the first event, unless the connection is
+    /// already closed when the stream is first polled.
+    Connected,
+    /// The connection was closed, because of the given error. This is always the last event.
+    Disconnected(Error),
+    /// The bus gave the given name to the connection.
+    NameAcquired(OwnedBusName),
+    /// The connection lost the given name.
+    NameLost(OwnedBusName),
+}
+
+/// A [`stream::Stream`] implementation that yields the [`ConnectionEvent`]s of a connection.
+///
+/// Use [`Connection::events`] to create an instance of this type.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ConnectionEvents {
+    conn: Connection,
+    connected: bool,
+    // `None` once the connection is closed.
+    stream: Option<MessageStream>,
+}
+
+assert_impl_all!(ConnectionEvents: Send, Sync, Unpin);
+
+impl ConnectionEvents {
+    pub(crate) async fn new(conn: &Connection) -> Result<Self> {
+        // The bus sends the name signals to their owner only, so (unlike `NameOwnerChanged`) to us
+        // but no one else.
+        let mut rule = MatchRule::builder()
+            .msg_type(Type::Signal)
+            .sender("org.freedesktop.DBus")?
+            .interface("org.freedesktop.DBus")?
+            .path("/org/freedesktop/DBus")?;
+        if let Some(unique_name) = conn.unique_name() {
+            rule = rule.destination(unique_name.as_ref())?;
+        }
+        let stream = MessageStream::for_match_rule(rule.build(), conn, None).await?;
+
+        Ok(Self {
+            conn: conn.clone(),
+            connected: false,
+            stream: Some(stream),
+        })
+    }
+}
+
+impl stream::Stream for ConnectionEvents {
+    type Item = ConnectionEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if !this.connected {
+            this.connected = true;
+
+            // Only `Disconnected` is left to report if the connection is already gone.
+            if this.conn.socket_error().is_none() {
+                return Poll::Ready(Some(ConnectionEvent::Connected));
+            }
+        }
+
+        while let Some(stream) = &mut this.stream {
+            let msg = match futures_core::ready!(Pin::new(stream).poll_next(cx)) {
+                Some(Ok(msg)) => msg,
+                Some(Err(e)) => {
+                    this.stream = None;
+
+                    return Poll::Ready(Some(ConnectionEvent::Disconnected(e)));
+                }
+                None => {
+                    this.stream = None;
+                    // The error is normally received first, unless the stream was created as the
+                    // socket reader task was stopping.
+                    let e = this.conn.socket_error().unwrap_or_else(|| {
+                        let e = io::Error::new(io::ErrorKind::BrokenPipe, "connection closed");
+
+                        Error::InputOutput(Arc::new(e))
+                    });
+
+                    return Poll::Ready(Some(ConnectionEvent::Disconnected(e)));
+                }
+            };
+            let event = match msg.member().as_ref().map(|m| m.as_str()) {
+                Some("NameAcquired") => ConnectionEvent::NameAcquired,
+                Some("NameLost") => ConnectionEvent::NameLost,
+                _ => continue,
+            };
+            match msg.body::<BusName<'_>>() {
+                Ok(name) => return Poll::Ready(Some(event(name.into()))),
+                Err(e) => debug!("Invalid name signal from the bus: {}", e),
+            }
+        }
+
+        Poll::Ready(None)
+    }
+}
+
+impl FusedStream for ConnectionEvents {
+    fn is_terminated(&self) -> bool {
+        self.connected && self.stream.is_none()
+    }
+}
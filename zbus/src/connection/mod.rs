@@ -6,7 +6,7 @@ use once_cell::sync::OnceCell;
 use ordered_stream::{OrderedFuture, OrderedStream, PollResult};
 use static_assertions::assert_impl_all;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     io::{self, ErrorKind},
     num::NonZeroU32,
     ops::Deref,
@@ -17,9 +17,15 @@ use std::{
         Arc, Weak,
     },
     task::{Context, Poll},
+    time::Duration,
+};
+use tracing::{
+    debug, debug_span, field, info_span, instrument, trace, trace_span, warn, Instrument, Span,
+};
+use zbus_names::{
+    BusName, ErrorName, InterfaceName, MemberName, OwnedBusName, OwnedMemberName, OwnedUniqueName,
+    OwnedWellKnownName, WellKnownName,
 };
-use tracing::{debug, info_span, instrument, trace, trace_span, warn, Instrument};
-use zbus_names::{BusName, ErrorName, InterfaceName, MemberName, OwnedUniqueName, WellKnownName};
 use zvariant::ObjectPath;
 
 use futures_core::{ready, Future};
@@ -27,11 +33,12 @@ use futures_sink::Sink;
 use futures_util::{sink::SinkExt, StreamExt};
 
 use crate::{
+    abstractions::timer::sleep,
     async_lock::Mutex,
     blocking,
-    fdo::{self, ConnectionCredentials, RequestNameFlags, RequestNameReply},
+    fdo::{self, ConnectionCredentials, ReleaseNameReply, RequestNameFlags, RequestNameReply},
     message::{self, Flags, Message, Type},
-    proxy::CacheProperties,
+    proxy::{CacheProperties, RetryPolicy},
     DBusError, Error, Executor, Guid, MatchRule, MessageStream, ObjectServer, OwnedMatchRule,
     Result, Task,
 };
@@ -46,14 +53,19 @@ pub use raw::Socket;
 mod socket_reader;
 use socket_reader::SocketReader;
 
+mod events;
+pub use events::{ConnectionEvent, ConnectionEvents};
+
 pub(crate) mod handshake;
 use handshake::Authenticated;
 
 const DEFAULT_MAX_QUEUED: usize = 64;
 const DEFAULT_MAX_METHOD_RETURN_QUEUED: usize = 8;
+const MAX_CACHED_CREDENTIALS: usize = 128;
 
 /// Inner state shared by Connection and WeakConnection
-#[derive(Debug)]
+#[derive(derivative::Derivative)]
+#[derivative(Debug)]
 pub(crate) struct ConnectionInner {
     server_guid: Guid,
     #[cfg(unix)]
@@ -62,11 +74,24 @@ pub(crate) struct ConnectionInner {
     unique_name: OnceCell<OwnedUniqueName>,
     registered_names: Mutex<HashMap<WellKnownName<'static>, NameStatus>>,
 
+    // The credentials of the peers on the bus, as queried by `caller_credentials`. Unique names
+    // are never reused by the bus, so the entries can't get out of date.
+    caller_credentials: sync::Mutex<HashMap<OwnedUniqueName, ConnectionCredentials>>,
+
     raw_conn: Arc<sync::Mutex<RawConnection<Box<dyn Socket>>>>,
 
     // Serial number for next outgoing message
     serial: AtomicU32,
 
+    method_timeout: sync::Mutex<Option<Duration>>,
+
+    #[derivative(Debug = "ignore")]
+    reply_routing: sync::Mutex<Option<ReplyRouting>>,
+
+    reply_ordering: sync::Mutex<ReplyOrdering>,
+
+    max_concurrent_calls: sync::Mutex<Option<usize>>,
+
     // Our executor
     executor: Executor<'static>,
 
@@ -74,9 +99,15 @@ pub(crate) struct ConnectionInner {
     #[allow(unused)]
     socket_reader_task: OnceCell<Task<()>>,
 
+    // Keepalive task
+    #[allow(unused)]
+    keepalive_task: OnceCell<Task<()>>,
+
     pub(crate) msg_receiver: InactiveReceiver<Result<Arc<Message>>>,
     pub(crate) method_return_receiver: InactiveReceiver<Result<Arc<Message>>>,
     msg_senders: Arc<Mutex<HashMap<Option<OwnedMatchRule>, MsgBroadcaster>>>,
+    reply_senders: ReplySenders,
+    socket_error: SocketError,
 
     subscriptions: Mutex<Subscriptions>,
 
@@ -84,10 +115,154 @@ pub(crate) struct ConnectionInner {
     object_server_dispatch_task: OnceCell<Task<()>>,
 }
 
+/// A policy adjusting the headers of the replies sent by a connection.
+///
+/// See [`Builder::reply_routing`] for details.
+pub(crate) type ReplyRouting = Arc<
+    dyn for<'b> Fn(&message::Header<'_>, message::Builder<'b>) -> Result<message::Builder<'b>>
+        + Send
+        + Sync,
+>;
+
+// Method calls queued per sender, see `ReplyOrdering::PerSender`. A sender has an entry as long
+// as one of its calls is being dispatched or waiting for a slot, and up to `max_queued` calls
+// queued after it.
+type PendingCalls = Arc<sync::Mutex<HashMap<Option<OwnedUniqueName>, VecDeque<Arc<Message>>>>>;
+
+// A method call to dispatch, with its sender in `ReplyOrdering::PerSender` mode.
+type Call = (Arc<Message>, Option<Option<OwnedUniqueName>>);
+
+// The method calls being dispatched and those waiting for a free slot, see
+// `Builder::max_concurrent_calls`.
+#[derive(Debug, Default)]
+struct DispatchSlots(sync::Mutex<Slots>);
+
+#[derive(Debug, Default)]
+struct Slots {
+    running: usize,
+    waiting: VecDeque<Call>,
+}
+
+// What to do with an incoming method call, see `DispatchSlots::admit`.
+enum Admission {
+    Dispatch(Call),
+    Queued,
+    Full(Call),
+}
+
+impl DispatchSlots {
+    // Take a slot to dispatch `call`, or an earlier call waiting for one. If fewer than `limit`
+    // calls are being dispatched, `call` waits for a slot unless `max_waiting` calls already do.
+    fn admit(&self, call: Call, limit: Option<usize>, max_waiting: usize) -> Admission {
+        let mut slots = self.0.lock().expect("poisoned lock");
+        if limit.map_or(true, |limit| slots.running < limit) {
+            slots.running += 1;
+            // Only if the limit was raised since the waiting calls were queued.
+            let call = match slots.waiting.pop_front() {
+                Some(first) => {
+                    slots.waiting.push_back(call);
+
+                    first
+                }
+                None => call,
+            };
+
+            Admission::Dispatch(call)
+        } else if slots.waiting.len() < max_waiting {
+            slots.waiting.push_back(call);
+
+            Admission::Queued
+        } else {
+            Admission::Full(call)
+        }
+    }
+
+    // Hand the slot of a completed dispatch over to the next waiting call, if any and the slot is
+    // still within `limit`, or free it.
+    fn next(&self, limit: Option<usize>) -> Option<Call> {
+        let mut slots = self.0.lock().expect("poisoned lock");
+        if limit.map_or(true, |limit| slots.running <= limit) {
+            if let Some(call) = slots.waiting.pop_front() {
+                return Some(call);
+            }
+        }
+        slots.running -= 1;
+
+        None
+    }
+}
+
+// Reply to `call` that the object server has too many method calls to handle already.
+async fn reply_limits_exceeded(conn: &Connection, call: &Message) {
+    let hdr = match call.header() {
+        Ok(hdr) => hdr,
+        Err(e) => {
+            warn!("Failed to parse header: {}", e);
+
+            return;
+        }
+    };
+    if hdr.primary().flags().contains(Flags::NoReplyExpected) {
+        return;
+    }
+    let err = fdo::Error::LimitsExceeded(String::from("Too many pending method calls"));
+    if let Err(e) = conn.reply_dbus_error(&hdr, err).await {
+        debug!("Failed to reply to `{}`: {}", call, e);
+    }
+}
+
 type Subscriptions = HashMap<OwnedMatchRule, (u64, InactiveReceiver<Result<Arc<Message>>>)>;
 
 pub(crate) type MsgBroadcaster = Broadcaster<Result<Arc<Message>>>;
 
+// The channels of the replies to the calls of `Proxy::call_method_streaming`, by call serial.
+pub(crate) type ReplySenders = Arc<sync::Mutex<HashMap<NonZeroU32, MsgBroadcaster>>>;
+
+// The error that stopped the socket reader task, once it did.
+pub(crate) type SocketError = Arc<sync::Mutex<Option<Error>>>;
+
+/// The channel of the replies to a single method call, unregistered on drop.
+#[derive(Debug)]
+pub(crate) struct ReplyChannel {
+    pub(crate) stream: MessageStream,
+    pub(crate) serial: NonZeroU32,
+    senders: ReplySenders,
+}
+
+impl Drop for ReplyChannel {
+    fn drop(&mut self) {
+        self.senders
+            .lock()
+            .expect("poisoned lock")
+            .remove(&self.serial);
+    }
+}
+
+/// What sending a message does when the outgoing queue of a [`Connection`] is full.
+///
+/// See [`Builder::send_queue_limit`] for details.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SendQueueFullBehavior {
+    /// Wait until there is space in the queue again.
+    #[default]
+    Wait,
+    /// Fail immediately with [`Error::SendQueueFull`].
+    Fail,
+}
+
+/// In which order the [`ObjectServer`] of a [`Connection`] replies to method calls.
+///
+/// See [`Builder::reply_ordering`] for details.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReplyOrdering {
+    /// Dispatch all method calls concurrently and reply to each as soon as it's handled.
+    #[default]
+    Completion,
+    /// Dispatch the method calls of each sender one at a time, so they're replied to in the order
+    /// they were received.
+    PerSender,
+}
+
 /// A D-Bus connection.
 ///
 /// A connection to a D-Bus bus, or a direct peer.
@@ -228,6 +403,25 @@ assert_impl_all!(Connection: Send, Sync, Unpin);
 pub(crate) struct PendingMethodCall {
     stream: Option<MessageStream>,
     serial: NonZeroU32,
+    /// The span of the call, entered while waiting for the reply.
+    span: Span,
+    timeout: Option<CallTimeout>,
+}
+
+/// The deadline of a pending method call, along with the details reported on expiry.
+struct CallTimeout {
+    timer: Pin<Box<dyn Future<Output = ()> + Send + Sync>>,
+    destination: Option<OwnedBusName>,
+    member: OwnedMemberName,
+}
+
+impl std::fmt::Debug for CallTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallTimeout")
+            .field("destination", &self.destination)
+            .field("member", &self.member)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Future for PendingMethodCall {
@@ -254,6 +448,7 @@ impl OrderedFuture for PendingMethodCall {
         before: Option<&Self::Ordering>,
     ) -> Poll<Option<(Self::Ordering, Self::Output)>> {
         let this = self.get_mut();
+        let _entered = this.span.enter();
         if let Some(stream) = &mut this.stream {
             loop {
                 match Pin::new(&mut *stream).poll_next_before(cx, before) {
@@ -269,6 +464,7 @@ impl OrderedFuture for PendingMethodCall {
                             Type::MethodReturn => Ok(msg),
                             _ => continue,
                         };
+                        trace!("Received reply");
                         this.stream = None;
                         return Poll::Ready(Some((ordering, res)));
                     }
@@ -285,7 +481,27 @@ impl OrderedFuture for PendingMethodCall {
                     Poll::Ready(PollResult::Terminated) => {
                         return Poll::Ready(None);
                     }
-                    Poll::Pending => return Poll::Pending,
+                    Poll::Pending => {
+                        let expired = match &mut this.timeout {
+                            Some(timeout) => timeout.timer.as_mut().poll(cx).is_ready(),
+                            None => false,
+                        };
+                        if !expired {
+                            return Poll::Pending;
+                        }
+                        let CallTimeout {
+                            destination,
+                            member,
+                            ..
+                        } = this.timeout.take().expect("no timeout");
+                        trace!("Timed out waiting for the reply");
+                        this.stream = None;
+
+                        return Poll::Ready(Some((
+                            message::Sequence::LAST,
+                            Err(Error::Timeout(destination, member)),
+                        )));
+                    }
                 }
             }
         }
@@ -348,6 +564,53 @@ impl Connection {
         .await
     }
 
+    /// Send a pre-built method call message and wait for the reply.
+    ///
+    /// This is a low-level alternative to [`Connection::call_method`], for when the message is
+    /// built by hand, e.g using [`message::Builder`]. Same as with [`Connection::send_message`],
+    /// the serial number of `msg` is assigned by the connection and then used to match the reply.
+    /// The [method timeout] of the connection applies as well.
+    ///
+    /// On successful reply, an `Ok(Message)` is returned. D-Bus error replies are returned as
+    /// [`Error::MethodError`].
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidField`] is returned if `msg` is not a method call or if it has the
+    /// `NoReplyExpected` flag set, since no reply would ever be received then.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use zbus::{message, Connection};
+    ///
+    /// let conn = Connection::session().await?;
+    /// let msg = message::Builder::method_call("/org/freedesktop/DBus", "GetId")?
+    ///     .destination("org.freedesktop.DBus")?
+    ///     .interface("org.freedesktop.DBus")?
+    ///     .build(&())?;
+    /// let reply = conn.call(msg).await?;
+    /// let id: String = reply.body()?;
+    /// # drop(id);
+    /// # Ok::<_, zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// [method timeout]: crate::connection::Builder::method_timeout
+    pub async fn call(&self, msg: Message) -> Result<Arc<Message>> {
+        if msg.message_type() != Type::MethodCall
+            || msg
+                .primary_header()
+                .flags()
+                .contains(Flags::NoReplyExpected)
+        {
+            return Err(Error::InvalidField);
+        }
+
+        self.send_method_call(msg).await?.expect("no reply").await
+    }
+
     /// Send a method call.
     ///
     /// Send the given message, which must be a method call, over the connection and return an
@@ -367,6 +630,33 @@ impl Connection {
         flags: BitFlags<Flags>,
         body: &B,
     ) -> Result<Option<PendingMethodCall>>
+    where
+        D: TryInto<BusName<'d>>,
+        P: TryInto<ObjectPath<'p>>,
+        I: TryInto<InterfaceName<'i>>,
+        M: TryInto<MemberName<'m>>,
+        D::Error: Into<Error>,
+        P::Error: Into<Error>,
+        I::Error: Into<Error>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+    {
+        let msg =
+            self.method_call_message(destination, path, interface, method_name, flags, body)?;
+
+        self.send_method_call(msg).await
+    }
+
+    /// Build a method call message, with this connection as the sender.
+    pub(crate) fn method_call_message<'d, 'p, 'i, 'm, D, P, I, M, B>(
+        &self,
+        destination: Option<D>,
+        path: P,
+        interface: Option<I>,
+        method_name: M,
+        flags: BitFlags<Flags>,
+        body: &B,
+    ) -> Result<Message>
     where
         D: TryInto<BusName<'d>>,
         P: TryInto<ObjectPath<'p>>,
@@ -391,7 +681,71 @@ impl Connection {
         for flag in flags {
             builder = builder.with_flags(flag)?;
         }
-        let msg = builder.build(body)?;
+
+        builder.build(body)
+    }
+
+    /// Send the method call `msg` and return the channel of the replies to it.
+    ///
+    /// The channel is dedicated to the call, so that a stream kept around (and not polled) doesn't
+    /// hold up the replies to other calls, and it's registered before `msg` is sent, so no reply
+    /// can be missed. Like any [`MessageStream`], it can hold up to [`Connection::max_queued`]
+    /// replies.
+    pub(crate) async fn send_method_call_streaming(
+        &self,
+        mut msg: Message,
+    ) -> Result<ReplyChannel> {
+        let serial = self.assign_serial_num(&mut msg)?;
+        let (sender, receiver) = broadcast(self.max_queued());
+        let senders = self.inner.reply_senders.clone();
+        senders
+            .lock()
+            .expect("poisoned lock")
+            .insert(serial, sender);
+        let channel = ReplyChannel {
+            stream: MessageStream::for_subscription_channel(
+                receiver,
+                // This is a lie but we only use the stream internally so it's fine.
+                None, self,
+            ),
+            serial,
+            senders,
+        };
+
+        trace!("Sending message: {:?}", msg);
+        (&mut &*self).send(msg).await?;
+        trace!("Sent message with serial: {}", serial);
+
+        Ok(channel)
+    }
+
+    /// Send the method call `msg` and return an object that allows the reply to be retrieved.
+    ///
+    /// Unless `msg` has the `NoReplyExpected` flag set, the return value is guaranteed to be
+    /// `Ok(Some(_))`, if there was no error encountered.
+    async fn send_method_call(&self, msg: Message) -> Result<Option<PendingMethodCall>> {
+        let flags = msg.primary_header().flags();
+        let timeout = match self.method_timeout() {
+            Some(duration) => {
+                let hdr = msg.header()?;
+                Some(CallTimeout {
+                    timer: Box::pin(sleep(duration)),
+                    destination: hdr.destination()?.map(|d| d.to_owned().into()),
+                    member: hdr.member()?.ok_or(Error::MissingField)?.to_owned().into(),
+                })
+            }
+            None => None,
+        };
+        let span = {
+            let hdr = msg.header()?;
+            debug_span!(
+                "call_method",
+                serial = field::Empty,
+                destination = hdr.destination()?.map(|d| d.as_str()),
+                interface = hdr.interface()?.map(|i| i.as_str()),
+                member = hdr.member()?.map(|m| m.as_str()),
+            )
+        };
 
         let msg_receiver = self.inner.method_return_receiver.activate_cloned();
         let stream = Some(MessageStream::for_subscription_channel(
@@ -400,11 +754,17 @@ impl Connection {
             None,
             self,
         ));
-        let serial = self.send_message(msg).await?;
+        let serial = self.send_message(msg).instrument(span.clone()).await?;
+        span.record("serial", serial.get());
         if flags.contains(Flags::NoReplyExpected) {
             Ok(None)
         } else {
-            Ok(Some(PendingMethodCall { stream, serial }))
+            Ok(Some(PendingMethodCall {
+                stream,
+                serial,
+                span,
+                timeout,
+            }))
         }
     }
 
@@ -442,6 +802,84 @@ impl Connection {
         self.send_message(m).await.map(|_| ())
     }
 
+    /// Receive all the signals matching `rule`, as raw messages.
+    ///
+    /// Unlike the streams of the proxies, which are specific to a signal of an interface, the rule
+    /// doesn't need to specify an interface or a member. This is useful for debugging or for
+    /// generic event routers, e.g to receive all the signals emitted by a peer or from a path.
+    ///
+    /// If `rule` doesn't specify a message type, it's restricted to signals. It's an error if it
+    /// specifies any other message type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # zbus::block_on(async {
+    /// use futures_util::stream::TryStreamExt;
+    /// use zbus::{Connection, MatchRule};
+    ///
+    /// let conn = Connection::session().await?;
+    /// let rule = MatchRule::builder()
+    ///     .sender("org.freedesktop.DBus")?
+    ///     .path("/org/freedesktop/DBus")?
+    ///     .build();
+    /// let mut stream = conn.receive_all_signals(rule).await?;
+    ///
+    /// conn.request_name("org.freedesktop.zbus.ReceiveAllSignalsDoc").await?;
+    /// let signal = stream.try_next().await?.unwrap();
+    /// println!("Received `{}` signal", signal.member().unwrap());
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// # Overhead
+    ///
+    /// The broader the rule, the more signals the bus will route to this connection. Apart from
+    /// the bandwidth and the processing needed to (de)serialize all these messages, keep in mind
+    /// that the received messages are queued in the returned stream (see
+    /// [`MessageStream::max_queued`]) and that once the queue is full, the connection stops
+    /// reading incoming messages until the stream is polled again. A broad rule consumed slowly can
+    /// therefore delay all the other streams and method calls of the connection. Make the rule as
+    /// specific as possible and consume the stream promptly.
+    pub async fn receive_all_signals<R>(&self, rule: R) -> Result<MessageStream>
+    where
+        R: TryInto<OwnedMatchRule>,
+        R::Error: Into<Error>,
+    {
+        let mut rule = rule.try_into().map_err(Into::into)?.into_inner();
+        match rule.msg_type {
+            None => rule.msg_type = Some(Type::Signal),
+            Some(Type::Signal) => (),
+            Some(_) => return Err(Error::InvalidMatchRule),
+        }
+
+        MessageStream::for_match_rule(rule, self, None).await
+    }
+
+    /// The match rules this connection is currently subscribed to, sorted.
+    ///
+    /// This is a snapshot of the connection's own bookkeeping, not a query to the bus: a rule is
+    /// listed for as long as a signal stream or a proxy of this connection uses it, no matter how
+    /// many of them do. On a bus connection, the signal rules listed are the ones registered with
+    /// the bus (through `AddMatch`). A rule whose stream was just dropped may still be listed
+    /// briefly, since its removal happens in the background.
+    ///
+    /// This is mostly useful for debugging, e.g to find out why signals are (not) received or
+    /// whether subscriptions leak.
+    pub async fn active_match_rules(&self) -> Vec<String> {
+        let mut rules: Vec<_> = self
+            .inner
+            .subscriptions
+            .lock()
+            .await
+            .keys()
+            .map(|rule| rule.to_string())
+            .collect();
+        rules.sort();
+
+        rules
+    }
+
     /// Reply to a message.
     ///
     /// Given an existing message (likely a method call), send a reply back to the caller with the
@@ -453,6 +891,7 @@ impl Connection {
         B: serde::ser::Serialize + zvariant::DynamicType,
     {
         let m = Message::method_reply(self.unique_name(), call, body)?;
+        let m = self.route_reply(&call.header()?, m)?;
         self.send_message(m).await
     }
 
@@ -474,6 +913,7 @@ impl Connection {
         E::Error: Into<Error>,
     {
         let m = Message::method_error(self.unique_name(), call, error_name, body)?;
+        let m = self.route_reply(&call.header()?, m)?;
         self.send_message(m).await
     }
 
@@ -488,8 +928,35 @@ impl Connection {
         call: &zbus::message::Header<'_>,
         err: impl DBusError,
     ) -> Result<NonZeroU32> {
-        let m = err.create_reply(call);
-        self.send_message(m?).await
+        let m = self.route_reply(call, err.create_reply(call)?)?;
+        self.send_message(m).await
+    }
+
+    /// Apply the reply routing policy of the connection, if any, to the `reply` to `call`.
+    fn route_reply(&self, call: &message::Header<'_>, reply: Message) -> Result<Message> {
+        let routing = match &*self.inner.reply_routing.lock().expect("poisoned lock") {
+            Some(routing) => routing.clone(),
+            None => return Ok(reply),
+        };
+        let builder = routing(call, message::Builder::from(reply.header()?))?;
+        // The builder strips the STRUCT delimiters of the signature it's given, while the one in
+        // the header already lacks them.
+        let signature = match reply.body_signature() {
+            Ok(signature) if !signature.is_empty() => format!("({signature})"),
+            Ok(_) | Err(Error::NoBodySignature) => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        // SAFETY: The body, signature and fds come from a valid message we just built, so they
+        // are in the native byte order.
+        unsafe {
+            builder.build_raw_body(
+                reply.body_as_bytes()?,
+                signature.as_str(),
+                #[cfg(unix)]
+                reply.fds(),
+            )
+        }
     }
 
     /// Register a well-known name for this connection.
@@ -612,6 +1079,56 @@ impl Connection {
         well_known_name: W,
         flags: BitFlags<RequestNameFlags>,
     ) -> Result<RequestNameReply>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        match self.request_name_full(well_known_name, flags).await? {
+            RequestNameReply::Exists => Err(Error::NameTaken),
+            reply => Ok(reply),
+        }
+    }
+
+    /// Register a well-known name for this connection, returning the reply of the bus as is.
+    ///
+    /// This is the same as [`Connection::request_name_with_flags`], except that the name already
+    /// being owned by another peer is not considered an error: [`RequestNameReply::Exists`] is
+    /// returned instead of [`Error::NameTaken`]. This is convenient when you want to react to all
+    /// the possible outcomes of the request in a single `match`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # zbus::block_on(async {
+    /// use zbus::{Connection, fdo::{RequestNameFlags, RequestNameReply}};
+    ///
+    /// let name = "org.freedesktop.zbus.RequestNameFullTest";
+    /// let conn1 = Connection::session().await?;
+    /// conn1.request_name(name).await?;
+    ///
+    /// let conn2 = Connection::session().await?;
+    /// match conn2
+    ///     .request_name_full(name, RequestNameFlags::DoNotQueue.into())
+    ///     .await?
+    /// {
+    ///     RequestNameReply::PrimaryOwner | RequestNameReply::AlreadyOwner => {
+    ///         println!("We own the name")
+    ///     }
+    ///     RequestNameReply::InQueue => println!("Waiting for the name"),
+    ///     RequestNameReply::Exists => println!("Someone else owns the name"),
+    /// }
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// # Caveats
+    ///
+    /// Same as that of [`Connection::request_name_with_flags`].
+    pub async fn request_name_full<'w, W>(
+        &self,
+        well_known_name: W,
+        flags: BitFlags<RequestNameFlags>,
+    ) -> Result<RequestNameReply>
     where
         W: TryInto<WellKnownName<'w>>,
         W::Error: Into<Error>,
@@ -742,7 +1259,7 @@ impl Connection {
 
                 NameStatus::Owner(task)
             }
-            RequestNameReply::Exists => return Err(Error::NameTaken),
+            RequestNameReply::Exists => return Ok(reply),
         };
 
         names.insert(well_known_name.to_owned(), status);
@@ -750,71 +1267,322 @@ impl Connection {
         Ok(reply)
     }
 
-    /// Deregister a previously registered well-known name for this service on the bus.
+    /// Receive the `NameLost` signals for a well-known name owned by this connection.
     ///
-    /// Use this method to deregister a well-known name, registered through
-    /// [`Connection::request_name`].
+    /// Unlike [`fdo::DBusProxy::receive_name_lost`], only the signals for `well_known_name` are
+    /// received. This is typically used to hand a name over to a replacement instance of a service,
+    /// without any downtime:
     ///
-    /// Unless an error is encountered, returns `Ok(true)` if name was previously registered with
-    /// the bus through `self` and it has now been successfully deregistered, `Ok(false)` if name
-    /// was not previously registered or already deregistered.
-    pub async fn release_name<'w, W>(&self, well_known_name: W) -> Result<bool>
+    /// 1. The running instance creates this stream and requests the name with the
+    ///    [`RequestNameFlags::AllowReplacement`] flag.
+    /// 2. The new instance requests the same name with the [`RequestNameFlags::ReplaceExisting`]
+    ///    flag. The bus immediately makes it the primary owner, so it gets all the new method calls
+    ///    sent to the name.
+    /// 3. The old instance receives the `NameLost` signal, finishes the work already in progress
+    ///    (replies to calls sent to its unique name still reach the callers) and exits.
+    ///
+    /// See the `name-handoff` example for a complete service doing this.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # zbus::block_on(async {
+    /// use futures_util::stream::StreamExt;
+    /// use zbus::{fdo::RequestNameFlags, Connection};
+    ///
+    /// let name = "org.freedesktop.zbus.NameHandoffTest";
+    /// let old = Connection::session().await?;
+    /// let mut lost_stream = old.receive_name_lost(name).await?;
+    /// old.request_name_with_flags(name, RequestNameFlags::AllowReplacement.into())
+    ///     .await?;
+    ///
+    /// let new = Connection::session().await?;
+    /// new.request_name_with_flags(name, RequestNameFlags::ReplaceExisting.into())
+    ///     .await?;
+    ///
+    /// let lost = lost_stream.next().await.unwrap();
+    /// assert_eq!(lost.args()?.name, name);
+    /// // Finish the in-flight work here and exit.
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// # Caveats
+    ///
+    /// The stream must be created before the name can be lost, so ideally before requesting the
+    /// name. Only the bus emits this signal, so on peer-to-peer connections the stream never
+    /// yields anything.
+    pub async fn receive_name_lost<'w, W>(
+        &self,
+        well_known_name: W,
+    ) -> Result<fdo::NameLostStream<'static>>
     where
         W: TryInto<WellKnownName<'w>>,
         W::Error: Into<Error>,
     {
-        let well_known_name: WellKnownName<'w> = well_known_name.try_into().map_err(Into::into)?;
-        let mut names = self.inner.registered_names.lock().await;
-        // FIXME: Should be possible to avoid cloning/allocation here
-        if names.remove(&well_known_name.to_owned()).is_none() {
-            return Ok(false);
-        };
-
-        if !self.is_bus() {
-            return Ok(true);
-        }
+        let well_known_name = well_known_name.try_into().map_err(Into::into)?;
 
         fdo::DBusProxy::builder(self)
             .cache_properties(CacheProperties::No)
             .build()
             .await?
-            .release_name(well_known_name)
+            .receive_name_lost_with_args(&[(0, well_known_name.as_str())])
             .await
-            .map(|_| true)
-            .map_err(Into::into)
     }
 
-    /// Checks if `self` is a connection to a message bus.
+    /// Create a stream of the lifecycle events of the connection.
     ///
-    /// This will return `false` for p2p connections.
-    pub fn is_bus(&self) -> bool {
-        self.inner.bus_conn
-    }
-
-    /// Assigns a serial number to `msg` that is unique to this connection.
+    /// The stream first yields [`ConnectionEvent::Connected`], then the
+    /// [`ConnectionEvent::NameAcquired`] and [`ConnectionEvent::NameLost`] events as the bus gives
+    /// and takes (well-known) names, and ends with a [`ConnectionEvent::Disconnected`] event once
+    /// the connection is closed, e.g by the peer or after a failed keepalive ping. This is meant
+    /// for supervising connections, to report their state or to reconnect.
     ///
-    /// This method can fail if `msg` is corrupted.
-    pub fn assign_serial_num(&self, msg: &mut Message) -> Result<NonZeroU32> {
-        let serial = self
-            .next_serial()
-            .try_into()
-            .map_err(|_| Error::InvalidSerial)?;
-        msg.set_serial_num(serial)?;
-
-        Ok(serial)
-    }
-
-    /// The unique name of the connection, if set/applicable.
+    /// # Caveats
     ///
-    /// The unique name is assigned by the message bus or set manually using
-    /// [`Connection::set_unique_name`].
-    pub fn unique_name(&self) -> Option<&OwnedUniqueName> {
-        self.inner.unique_name.get()
-    }
-
-    /// Sets the unique name of the connection (if not already set).
+    /// Only the events happening after the creation of the stream are reported: the unique name,
+    /// notably, is acquired before the connection is even returned. Only the bus emits the name
+    /// signals, so on peer-to-peer connections the stream only yields the `Connected` and
+    /// `Disconnected` events.
     ///
-    /// # Panics
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use futures_util::stream::StreamExt;
+    /// use zbus::{connection::ConnectionEvent, Connection};
+    ///
+    /// let connection = Connection::session().await?;
+    /// let mut events = connection.events().await?;
+    /// connection.request_name("org.zbus.EventsExample").await?;
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         ConnectionEvent::Disconnected(e) => println!("Disconnected: {e}"),
+    ///         event => println!("{event:?}"),
+    ///     }
+    /// }
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn events(&self) -> Result<ConnectionEvents> {
+        ConnectionEvents::new(self).await
+    }
+
+    /// Register a well-known name for this connection, retrying on transient failures.
+    ///
+    /// This is the same as [`Connection::request_name_full`], except that:
+    ///
+    /// * The request is retried according to `retry`, if it fails with one of the errors of the
+    ///   policy (e.g because the bus is still starting up). Note that the default [`RetryPolicy`] is
+    ///   tailored for method calls to services, so you'll likely want to give it the errors you
+    ///   expect from the bus instead.
+    /// * If `queue_timeout` is given and the connection ends up in the queue of the name, this
+    ///   waits until it becomes the primary owner, for up to `queue_timeout`. This is typically
+    ///   useful when a previous instance of the service is still shutting down.
+    ///
+    /// The final ownership state is returned: [`RequestNameReply::PrimaryOwner`] if the name was
+    /// acquired while waiting in the queue, [`RequestNameReply::InQueue`] if `queue_timeout`
+    /// elapsed before that (the connection remains in the queue then) and otherwise, the reply to
+    /// the request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # zbus::block_on(async {
+    /// use std::time::Duration;
+    /// use zbus::{fdo::{RequestNameFlags, RequestNameReply}, proxy::RetryPolicy, Connection};
+    ///
+    /// let conn = Connection::session().await?;
+    /// let retry = RetryPolicy::new()
+    ///     .errors([])
+    ///     .add_error("org.freedesktop.DBus.Error.LimitsExceeded")?;
+    /// let reply = conn
+    ///     .request_name_with_retry(
+    ///         "org.freedesktop.zbus.RequestNameWithRetryDocTest",
+    ///         RequestNameFlags::ReplaceExisting.into(),
+    ///         &retry,
+    ///         Some(Duration::from_secs(5)),
+    ///     )
+    ///     .await?;
+    /// assert_eq!(reply, RequestNameReply::PrimaryOwner);
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// [`RetryPolicy`]: crate::proxy::RetryPolicy
+    pub async fn request_name_with_retry<'w, W>(
+        &self,
+        well_known_name: W,
+        flags: BitFlags<RequestNameFlags>,
+        retry: &RetryPolicy,
+        queue_timeout: Option<Duration>,
+    ) -> Result<RequestNameReply>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        let well_known_name = well_known_name.try_into().map_err(Into::into)?;
+        // Like in `request_name_full`, the stream must be created before the request so the
+        // signal isn't missed.
+        let acquired_stream = match queue_timeout {
+            Some(_) if self.is_bus() && !flags.contains(RequestNameFlags::DoNotQueue) => Some(
+                fdo::DBusProxy::builder(self)
+                    .cache_properties(CacheProperties::No)
+                    .build()
+                    .await?
+                    .receive_name_acquired_with_args(&[(0, well_known_name.as_str())])
+                    .await?,
+            ),
+            _ => None,
+        };
+        let reply = retry
+            .retry(|| self.request_name_full(well_known_name.clone(), flags))
+            .await?;
+
+        match (reply, acquired_stream, queue_timeout) {
+            (RequestNameReply::InQueue, Some(stream), Some(timeout)) => {
+                let acquired = stream
+                    .wait_for_timeout(timeout, |signal| {
+                        signal
+                            .args()
+                            .map_or(false, |args| args.name == well_known_name)
+                    })
+                    .await;
+
+                match acquired {
+                    Some(_) => Ok(RequestNameReply::PrimaryOwner),
+                    None => Ok(RequestNameReply::InQueue),
+                }
+            }
+            (reply, _, _) => Ok(reply),
+        }
+    }
+
+    /// Deregister a previously registered well-known name for this service on the bus.
+    ///
+    /// Use this method to deregister a well-known name, registered through
+    /// [`Connection::request_name`].
+    ///
+    /// Unless an error is encountered, returns `Ok(true)` if name was previously registered with
+    /// the bus through `self` and it has now been successfully deregistered, `Ok(false)` if name
+    /// was not previously registered or already deregistered.
+    pub async fn release_name<'w, W>(&self, well_known_name: W) -> Result<bool>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        let well_known_name: WellKnownName<'w> = well_known_name.try_into().map_err(Into::into)?;
+        let mut names = self.inner.registered_names.lock().await;
+        // FIXME: Should be possible to avoid cloning/allocation here
+        if names.remove(&well_known_name.to_owned()).is_none() {
+            return Ok(false);
+        };
+
+        if !self.is_bus() {
+            return Ok(true);
+        }
+
+        fdo::DBusProxy::builder(self)
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?
+            .release_name(well_known_name)
+            .await
+            .map(|_| true)
+            .map_err(Into::into)
+    }
+
+    /// Deregister a well-known name for this connection, returning the reply of the bus as is.
+    ///
+    /// Unlike [`Connection::release_name`], the bus is asked to release the name even if it wasn't
+    /// registered through `self`, so this will also inform you if the name is owned by someone
+    /// else ([`ReleaseNameReply::NotOwner`]) or not owned at all
+    /// ([`ReleaseNameReply::NonExistent`]).
+    ///
+    /// For p2p connections, [`ReleaseNameReply::Released`] is returned if the name was registered
+    /// through `self`, [`ReleaseNameReply::NonExistent`] otherwise.
+    pub async fn release_name_full<'w, W>(&self, well_known_name: W) -> Result<ReleaseNameReply>
+    where
+        W: TryInto<WellKnownName<'w>>,
+        W::Error: Into<Error>,
+    {
+        let well_known_name: WellKnownName<'w> = well_known_name.try_into().map_err(Into::into)?;
+        let mut names = self.inner.registered_names.lock().await;
+        let registered = names.remove(&well_known_name.to_owned()).is_some();
+
+        if !self.is_bus() {
+            return Ok(if registered {
+                ReleaseNameReply::Released
+            } else {
+                ReleaseNameReply::NonExistent
+            });
+        }
+
+        fdo::DBusProxy::builder(self)
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?
+            .release_name(well_known_name)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// The well-known names currently owned by this connection.
+    ///
+    /// These are the names successfully acquired through [`Connection::request_name`] and friends
+    /// (including queued requests that got the name since) and not released through
+    /// [`Connection::release_name`] since. Names requested with the
+    /// [`RequestNameFlags::AllowReplacement`] flag are removed once the `NameLost` signal for them is
+    /// received. Names acquired without this crate's API (e.g with a direct call to
+    /// [`fdo::DBusProxy::request_name`]) are not tracked.
+    ///
+    /// The names are returned in no particular order.
+    pub async fn owned_names(&self) -> Vec<OwnedWellKnownName> {
+        self.inner
+            .registered_names
+            .lock()
+            .await
+            .iter()
+            .filter(|(_, status)| matches!(status, NameStatus::Owner(_)))
+            .map(|(name, _)| name.clone().into())
+            .collect()
+    }
+
+    /// Checks if `self` is a connection to a message bus.
+    ///
+    /// This will return `false` for p2p connections.
+    pub fn is_bus(&self) -> bool {
+        self.inner.bus_conn
+    }
+
+    /// Assigns a serial number to `msg` that is unique to this connection.
+    ///
+    /// This method can fail if `msg` is corrupted, or with [`Error::InvalidSerial`] if its serial
+    /// was set through [`message::Builder::serial`].
+    pub fn assign_serial_num(&self, msg: &mut Message) -> Result<NonZeroU32> {
+        if msg.fixed_serial {
+            return Err(Error::InvalidSerial);
+        }
+        let serial = self
+            .next_serial()
+            .try_into()
+            .map_err(|_| Error::InvalidSerial)?;
+        msg.set_serial_num(serial)?;
+
+        Ok(serial)
+    }
+
+    /// The unique name of the connection, if set/applicable.
+    ///
+    /// The unique name is assigned by the message bus or set manually using
+    /// [`Connection::set_unique_name`].
+    pub fn unique_name(&self) -> Option<&OwnedUniqueName> {
+        self.inner.unique_name.get()
+    }
+
+    /// Sets the unique name of the connection (if not already set).
+    ///
+    /// # Panics
     ///
     /// This method panics if the unique name is already set. It will always panic if the connection
     /// is to a message bus as it's the bus that assigns peers their unique names. This is mainly
@@ -843,6 +1611,100 @@ impl Connection {
         self.inner.msg_receiver.clone().set_capacity(max);
     }
 
+    /// The maximum number of messages waiting to be written to the socket, if any.
+    pub fn send_queue_limit(&self) -> Option<usize> {
+        self.inner
+            .raw_conn
+            .lock()
+            .expect("poisoned lock")
+            .out_msgs_limit()
+    }
+
+    /// Set the maximum number of messages waiting to be written to the socket.
+    ///
+    /// See [`Builder::send_queue_limit`] for details.
+    pub fn set_send_queue_limit(&mut self, limit: Option<usize>) {
+        self.inner
+            .raw_conn
+            .lock()
+            .expect("poisoned lock")
+            .set_out_msgs_limit(limit);
+    }
+
+    /// What sending a message does when the outgoing queue is full.
+    pub fn send_queue_full_behavior(&self) -> SendQueueFullBehavior {
+        self.inner
+            .raw_conn
+            .lock()
+            .expect("poisoned lock")
+            .out_msgs_full_behavior()
+    }
+
+    /// Set what sending a message does when the outgoing queue is full.
+    pub fn set_send_queue_full_behavior(&mut self, behavior: SendQueueFullBehavior) {
+        self.inner
+            .raw_conn
+            .lock()
+            .expect("poisoned lock")
+            .set_out_msgs_full_behavior(behavior);
+    }
+
+    /// How long to wait for the reply to a method call, if there is a limit.
+    pub fn method_timeout(&self) -> Option<Duration> {
+        *self.inner.method_timeout.lock().expect("poisoned lock")
+    }
+
+    /// Set how long to wait for the reply to a method call.
+    ///
+    /// See [`Builder::method_timeout`] for details.
+    pub fn set_method_timeout(&mut self, timeout: Option<Duration>) {
+        *self.inner.method_timeout.lock().expect("poisoned lock") = timeout;
+    }
+
+    pub(crate) fn set_reply_routing(&mut self, routing: Option<ReplyRouting>) {
+        *self.inner.reply_routing.lock().expect("poisoned lock") = routing;
+    }
+
+    /// In which order the object server replies to method calls.
+    pub fn reply_ordering(&self) -> ReplyOrdering {
+        *self.inner.reply_ordering.lock().expect("poisoned lock")
+    }
+
+    /// Set in which order the object server replies to method calls.
+    ///
+    /// See [`Builder::reply_ordering`] for details.
+    pub fn set_reply_ordering(&mut self, ordering: ReplyOrdering) {
+        *self.inner.reply_ordering.lock().expect("poisoned lock") = ordering;
+    }
+
+    /// The maximum number of method calls the object server handles concurrently, if any.
+    pub fn max_concurrent_calls(&self) -> Option<usize> {
+        *self
+            .inner
+            .max_concurrent_calls
+            .lock()
+            .expect("poisoned lock")
+    }
+
+    /// Set the maximum number of method calls the object server handles concurrently.
+    ///
+    /// See [`Builder::max_concurrent_calls`] for details.
+    ///
+    /// # Panics
+    ///
+    /// If `limit` is `Some(0)`.
+    pub fn set_max_concurrent_calls(&mut self, limit: Option<usize>) {
+        assert!(
+            limit != Some(0),
+            "the concurrent method calls limit must be at least 1"
+        );
+        *self
+            .inner
+            .max_concurrent_calls
+            .lock()
+            .expect("poisoned lock") = limit;
+    }
+
     /// The server's GUID.
     pub fn server_guid(&self) -> &str {
         self.inner.server_guid.as_str()
@@ -988,6 +1850,11 @@ impl Connection {
                         started_event.notify(1);
                     }
 
+                    // The calls waiting for the dispatch of an earlier call from the same sender,
+                    // in `ReplyOrdering::PerSender` mode.
+                    let pending_calls: PendingCalls = Default::default();
+                    let dispatch_slots = Arc::new(DispatchSlots::default());
+
                     trace!("waiting for incoming method call messages..");
                     while let Some(msg) = stream.next().await.and_then(|m| {
                         if let Err(e) = &m {
@@ -1023,27 +1890,104 @@ impl Connection {
                                     continue;
                                 }
                             }
-                            let member = match msg.member() {
-                                Some(member) => member,
+                            let task_name = match msg.member() {
+                                Some(member) => format!("`{member}` method dispatcher"),
                                 None => {
                                     warn!("Got a method call with no `MEMBER` field: {}", msg);
 
                                     continue;
                                 }
                             };
-                            trace!("Got `{}`. Will spawn a task for dispatch..", msg);
+                            let sender = match conn.reply_ordering() {
+                                ReplyOrdering::Completion => None,
+                                ReplyOrdering::PerSender => {
+                                    let sender = hdr.sender().ok().flatten().map(|s| s.to_owned().into());
+                                    // Whether the call is queued, if the sender has calls being dispatched.
+                                    let queued = {
+                                        let mut pending = pending_calls.lock().expect("poisoned lock");
+                                        match pending.get_mut(&sender) {
+                                            Some(queue) if queue.len() < conn.max_queued() => {
+                                                trace!("Queued `{}` after the calls of the same sender", msg);
+                                                queue.push_back(msg.clone());
+
+                                                Some(true)
+                                            }
+                                            Some(_) => Some(false),
+                                            None => {
+                                                pending.insert(sender.clone(), VecDeque::new());
+
+                                                None
+                                            }
+                                        }
+                                    };
+                                    match queued {
+                                        Some(true) => continue,
+                                        Some(false) => {
+                                            debug!("Too many pending method calls from the sender, rejecting `{}`", msg);
+                                            reply_limits_exceeded(&conn, &msg).await;
+
+                                            continue;
+                                        }
+                                        None => Some(sender),
+                                    }
+                                }
+                            };
+                            // The calls over the limit wait in a queue of their own, rather than in
+                            // the stream, so that the other messages (e.g the replies the method
+                            // handlers wait for) are still read.
+                            let call = match dispatch_slots.admit(
+                                (msg, sender),
+                                conn.max_concurrent_calls(),
+                                conn.max_queued(),
+                            ) {
+                                Admission::Dispatch(call) => call,
+                                Admission::Queued => continue,
+                                Admission::Full((msg, sender)) => {
+                                    debug!("Too many pending method calls, rejecting `{}`", msg);
+                                    if let Some(sender) = sender {
+                                        // Nothing else of this sender is queued, or it would be.
+                                        pending_calls.lock().expect("poisoned lock").remove(&sender);
+                                    }
+                                    reply_limits_exceeded(&conn, &msg).await;
+
+                                    continue;
+                                }
+                            };
+                            trace!("Got `{}`. Will spawn a task for dispatch..", call.0);
                             let executor = conn.inner.executor.clone();
-                            let task_name = format!("`{member}` method dispatcher");
+                            let dispatch_slots = dispatch_slots.clone();
+                            let pending_calls = pending_calls.clone();
                             executor
                                 .spawn(
                                     async move {
-                                        trace!("spawned a task to dispatch `{}`.", msg);
+                                        trace!("spawned a task to dispatch `{}`.", call.0);
                                         let server = conn.object_server();
-                                        if let Err(e) = server.dispatch_message(&msg).await {
-                                            debug!(
-                                                "Error dispatching message. Message: {:?}, error: {:?}",
-                                                msg, e
-                                            );
+                                        let (mut msg, mut sender) = call;
+                                        loop {
+                                            if let Err(e) = server.dispatch_message(&msg).await {
+                                                debug!(
+                                                    "Error dispatching message. Message: {:?}, error: {:?}",
+                                                    msg, e
+                                                );
+                                            }
+                                            // The next call of the same sender goes first.
+                                            if let Some(sender) = &sender {
+                                                let mut pending = pending_calls.lock().expect("poisoned lock");
+                                                match pending.get_mut(sender).and_then(VecDeque::pop_front) {
+                                                    Some(next) => {
+                                                        msg = next;
+
+                                                        continue;
+                                                    }
+                                                    None => {
+                                                        pending.remove(sender);
+                                                    }
+                                                }
+                                            }
+                                            match dispatch_slots.next(conn.max_concurrent_calls()) {
+                                                Some(next) => (msg, sender) = next,
+                                                None => break,
+                                            }
                                         }
                                     }
                                     .instrument(trace_span!("{}", task_name)),
@@ -1063,6 +2007,21 @@ impl Connection {
         });
     }
 
+    /// Whether the socket of the connection was closed, or failed.
+    pub(crate) async fn is_closed(&self) -> bool {
+        // The socket reader task clears the senders when it stops.
+        self.inner.msg_senders.lock().await.is_empty()
+    }
+
+    /// The error that stopped the socket reader task, if it did.
+    pub(crate) fn socket_error(&self) -> Option<Error> {
+        self.inner
+            .socket_error
+            .lock()
+            .expect("poisoned lock")
+            .clone()
+    }
+
     pub(crate) async fn add_match(
         &self,
         rule: OwnedMatchRule,
@@ -1070,12 +2029,14 @@ impl Connection {
     ) -> Result<Receiver<Result<Arc<Message>>>> {
         use std::collections::hash_map::Entry;
 
-        if self.inner.msg_senders.lock().await.is_empty() {
+        if self.is_closed().await {
             // This only happens if socket reader task has errored out.
-            return Err(Error::InputOutput(Arc::new(io::Error::new(
-                io::ErrorKind::BrokenPipe,
-                "Socket reader task has errored out",
-            ))));
+            return Err(self.socket_error().unwrap_or_else(|| {
+                Error::InputOutput(Arc::new(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "Socket reader task has errored out",
+                )))
+            }));
         }
 
         let mut subscriptions = self.inner.subscriptions.lock().await;
@@ -1085,20 +2046,32 @@ impl Connection {
                 let max_queued = max_queued.unwrap_or(DEFAULT_MAX_QUEUED);
                 let (sender, mut receiver) = broadcast(max_queued);
                 receiver.set_await_active(false);
-                if self.is_bus() && msg_type == Type::Signal {
-                    fdo::DBusProxy::builder(self)
-                        .cache_properties(CacheProperties::No)
-                        .build()
-                        .await?
-                        .add_match_rule(e.key().inner().clone())
-                        .await?;
-                }
-                e.insert((1, receiver.clone().deactivate()));
+                // Register the sender before adding the match rule to the bus, so that the
+                // messages matching the rule and arriving right after the bus acknowledges it are
+                // not missed.
                 self.inner
                     .msg_senders
                     .lock()
                     .await
-                    .insert(Some(rule), sender);
+                    .insert(Some(rule.clone()), sender);
+                if self.is_bus() && msg_type == Type::Signal {
+                    let res = async {
+                        fdo::DBusProxy::builder(self)
+                            .cache_properties(CacheProperties::No)
+                            .build()
+                            .await?
+                            .add_match_rule(e.key().inner().clone())
+                            .await
+                            .map_err(Error::from)
+                    }
+                    .await;
+                    if let Err(err) = res {
+                        self.inner.msg_senders.lock().await.remove(&Some(rule));
+
+                        return Err(err);
+                    }
+                }
+                e.insert((1, receiver.clone().deactivate()));
 
                 Ok(receiver)
             }
@@ -1217,16 +2190,24 @@ impl Connection {
                 cap_unix_fd,
                 bus_conn: bus_connection,
                 serial: AtomicU32::new(1),
+                method_timeout: sync::Mutex::new(None),
+                reply_routing: sync::Mutex::new(None),
+                reply_ordering: sync::Mutex::new(ReplyOrdering::default()),
+                max_concurrent_calls: sync::Mutex::new(None),
                 unique_name: OnceCell::new(),
                 subscriptions,
                 object_server: OnceCell::new(),
                 object_server_dispatch_task: OnceCell::new(),
                 executor,
                 socket_reader_task: OnceCell::new(),
+                keepalive_task: OnceCell::new(),
                 msg_senders,
+                reply_senders: Default::default(),
+                socket_error: Default::default(),
                 msg_receiver,
                 method_return_receiver,
                 registered_names: Mutex::new(HashMap::new()),
+                caller_credentials: sync::Mutex::new(HashMap::new()),
             }),
         };
 
@@ -1243,10 +2224,49 @@ impl Connection {
     }
 
     /// Create a `Connection` to the system-wide message bus.
+    ///
+    /// The `DBUS_SYSTEM_BUS_ADDRESS` environment variable is honored, if set. Otherwise, the default
+    /// system bus socket is used. See [`crate::Address::system`] for details.
     pub async fn system() -> Result<Self> {
         Builder::system()?.build().await
     }
 
+    /// Create a `Connection` to the message bus that activated the current process.
+    ///
+    /// This is how a service started through [D-Bus activation] should connect back to the bus
+    /// that started it, rather than guessing between [`Connection::session`] and
+    /// [`Connection::system`]. See [`crate::Address::starter_bus`] for how the bus is determined.
+    ///
+    /// [D-Bus activation]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-bus-starting-services
+    pub async fn for_starter_bus() -> Result<Self> {
+        Builder::starter_bus()?.build().await
+    }
+
+    /// Create a `Connection` to the message bus at the given [D-Bus address].
+    ///
+    /// This is a shorthand for [`Builder::address`], for buses at non-standard locations (test
+    /// setups and containers, for example). The address is validated before connecting and an
+    /// [`Error::Address`] describes the faulty part of it, if any.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// let connection = zbus::Connection::for_address("unix:path=/run/my-test-bus").await?;
+    /// println!("Connected as {}", connection.unique_name().unwrap());
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// [D-Bus address]: https://dbus.freedesktop.org/doc/dbus-specification.html#addresses
+    pub async fn for_address<A>(address: A) -> Result<Self>
+    where
+        A: TryInto<crate::Address>,
+        A::Error: Into<Error>,
+    {
+        Builder::address(address)?.build().await
+    }
+
     /// Returns a listener, notified on various connection activity.
     ///
     /// This function is meant for the caller to implement idle or timeout on inactivity.
@@ -1287,16 +2307,150 @@ impl Connection {
         })
     }
 
+    /// Returns the credentials of the sender of `msg`.
+    ///
+    /// On a bus connection, they are queried from the bus with
+    /// [`fdo::DBusProxy::get_connection_credentials`], and cached per sender so that only the first
+    /// message of each peer costs a round trip. On a p2p connection, these are the
+    /// [peer credentials](Connection::peer_credentials) of the socket.
+    ///
+    /// This is mostly useful for authorizing method calls, which is why it's also available to
+    /// `dbus_interface` methods through the `#[zbus(credentials)]` argument attribute.
+    pub async fn caller_credentials(&self, msg: &Message) -> Result<ConnectionCredentials> {
+        if !self.is_bus() {
+            return self.peer_credentials().await.map_err(Into::into);
+        }
+
+        let sender: OwnedUniqueName = msg
+            .header()?
+            .sender()?
+            .ok_or(Error::MissingField)?
+            .to_owned()
+            .into();
+        if let Some(creds) = self
+            .inner
+            .caller_credentials
+            .lock()
+            .expect("poisoned lock")
+            .get(&sender)
+        {
+            return Ok(creds.clone());
+        }
+
+        let creds = fdo::DBusProxy::builder(self)
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?
+            .get_connection_credentials(sender.as_ref().into())
+            .await?;
+        let mut cache = self.inner.caller_credentials.lock().expect("poisoned lock");
+        if cache.len() >= MAX_CACHED_CREDENTIALS {
+            // The peers that left are never removed, so don't keep too many around.
+            cache.clear();
+        }
+        cache.insert(sender, creds.clone());
+
+        Ok(creds)
+    }
+
     pub(crate) fn init_socket_reader(&self) {
         let inner = &self.inner;
         inner
             .socket_reader_task
             .set(
-                SocketReader::new(inner.raw_conn.clone(), inner.msg_senders.clone())
-                    .spawn(&inner.executor),
+                SocketReader::new(
+                    inner.raw_conn.clone(),
+                    inner.msg_senders.clone(),
+                    inner.reply_senders.clone(),
+                    inner.socket_error.clone(),
+                )
+                .spawn(&inner.executor),
             )
             .expect("Attempted to set `socket_reader_task` twice");
     }
+
+    // Ping the peer every `interval` and close the connection if it doesn't reply in `timeout`.
+    pub(crate) fn start_keepalive(&self, interval: Duration, timeout: Duration) {
+        use futures_util::future::{select, Either};
+
+        let weak_conn = WeakConnection::from(self);
+        let (destination, path) = if self.inner.bus_conn {
+            (Some("org.freedesktop.DBus"), "/org/freedesktop/DBus")
+        } else {
+            (None, "/")
+        };
+        let task = self.inner.executor.spawn(
+            async move {
+                loop {
+                    sleep(interval).await;
+                    let conn = match weak_conn.upgrade() {
+                        Some(conn) => conn,
+                        None => {
+                            trace!("Connection is gone, stopping keepalive task");
+
+                            return;
+                        }
+                    };
+                    let ping = conn.call_method(
+                        destination,
+                        path,
+                        Some("org.freedesktop.DBus.Peer"),
+                        "Ping",
+                        &(),
+                    );
+                    let err = match select(Box::pin(ping), Box::pin(sleep(timeout))).await {
+                        Either::Left((Ok(_), _))
+                        | Either::Left((Err(Error::MethodError(..)), _)) => continue,
+                        Either::Left((Err(e), _)) => {
+                            debug!("Keepalive ping failed: {}", e);
+
+                            e
+                        }
+                        Either::Right(_) => {
+                            debug!("No reply to keepalive ping, disconnecting");
+
+                            Error::InputOutput(Arc::new(io::Error::new(
+                                ErrorKind::TimedOut,
+                                "no reply to keepalive ping",
+                            )))
+                        }
+                    };
+                    if let Err(e) = conn.inner.raw_conn.lock().expect("poisoned lock").close() {
+                        debug!("Failed to close the connection: {}", e);
+                    }
+                    // Not all sockets can be closed from here so end the streams as the socket
+                    // reader does on errors, whether it notices the closing or not.
+                    let mut senders = conn.inner.msg_senders.lock().await;
+                    for sender in senders.values() {
+                        if let Err(e) = sender.broadcast(Err(err.clone())).await {
+                            trace!("Error broadcasting keepalive error: {:?}", e);
+                        }
+                    }
+                    senders.clear();
+                    let reply_senders: Vec<_> = conn
+                        .inner
+                        .reply_senders
+                        .lock()
+                        .expect("poisoned lock")
+                        .drain()
+                        .map(|(_, sender)| sender)
+                        .collect();
+                    for sender in reply_senders {
+                        if let Err(e) = sender.broadcast(Err(err.clone())).await {
+                            trace!("Error broadcasting keepalive error: {:?}", e);
+                        }
+                    }
+
+                    return;
+                }
+            },
+            "keepalive",
+        );
+        self.inner
+            .keepalive_task
+            .set(task)
+            .expect("Attempted to set `keepalive_task` twice");
+    }
 }
 
 impl<T> Sink<T> for Connection
@@ -1328,9 +2482,25 @@ where
 {
     type Error = Error;
 
-    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
-        // TODO: We should have a max queue length in raw::Socket for outgoing messages.
-        Poll::Ready(Ok(()))
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let mut raw_conn = self.inner.raw_conn.lock().expect("poisoned lock");
+        if !raw_conn.is_out_msgs_full() {
+            return Poll::Ready(Ok(()));
+        }
+
+        // Try to make some room by writing out the queued messages.
+        if let Poll::Ready(res) = raw_conn.flush(cx) {
+            return Poll::Ready(res);
+        }
+        if !raw_conn.is_out_msgs_full() {
+            return Poll::Ready(Ok(()));
+        }
+
+        match raw_conn.out_msgs_full_behavior() {
+            // `flush` will wake us up once the socket is writable again.
+            SendQueueFullBehavior::Wait => Poll::Pending,
+            SendQueueFullBehavior::Fail => Poll::Ready(Err(Error::SendQueueFull)),
+        }
     }
 
     fn start_send(self: Pin<&mut Self>, msg: T) -> Result<()> {
@@ -1527,6 +2697,74 @@ mod tests {
         futures_util::try_join!(server_conn_builder.build(), client_conn_builder.build())
     }
 
+    #[test]
+    #[timeout(15000)]
+    fn stream_p2p() {
+        crate::utils::block_on(test_stream_p2p()).unwrap();
+    }
+
+    async fn test_stream_p2p() -> Result<()> {
+        let (server1, client1) = stream_p2p_pipe().await?;
+        let (server2, client2) = stream_p2p_pipe().await?;
+
+        test_p2p(server1, client1, server2, client2).await
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn stream_close() {
+        crate::utils::block_on(test_stream_close()).unwrap();
+    }
+
+    async fn test_stream_close() -> Result<()> {
+        use futures_util::{SinkExt, StreamExt};
+
+        let (server, client) = stream_p2p_pipe().await?;
+        let mut events = server.events().await?;
+        assert!(matches!(
+            events.next().await,
+            Some(ConnectionEvent::Connected)
+        ));
+
+        // Closing the connection shuts the stream down, which the peer sees.
+        SinkExt::<Arc<Message>>::close(&mut &client).await?;
+        assert!(matches!(
+            events.next().await,
+            Some(ConnectionEvent::Disconnected(_))
+        ));
+
+        Ok(())
+    }
+
+    async fn stream_p2p_pipe() -> Result<(Connection, Connection)> {
+        let guid = Guid::generate();
+
+        #[cfg(not(feature = "tokio"))]
+        let (p0, p1) = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = listener.local_addr().unwrap();
+            let p1 = std::net::TcpStream::connect(addr).unwrap();
+            let p0 = listener.incoming().next().unwrap().unwrap();
+
+            (
+                async_io::Async::new(p0).unwrap(),
+                async_io::Async::new(p1).unwrap(),
+            )
+        };
+
+        #[cfg(feature = "tokio")]
+        let (p0, p1) = tokio::io::duplex(4096);
+
+        futures_util::try_join!(
+            Builder::stream(p0)
+                .server(&guid)
+                .p2p()
+                .auth_mechanisms(&[AuthMechanism::Anonymous])
+                .build(),
+            Builder::stream(p1).p2p().build(),
+        )
+    }
+
     #[cfg(unix)]
     #[test]
     #[timeout(15000)]
@@ -1657,6 +2895,51 @@ mod tests {
         .expect("Unable to connect to session bus");
     }
 
+    #[test]
+    #[timeout(15000)]
+    fn for_address() {
+        crate::utils::block_on(test_for_address()).unwrap();
+    }
+
+    async fn test_for_address() -> Result<()> {
+        // The `unix:` address of the session bus.
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            let address = crate::Address::session()?.to_string();
+            let conn = Connection::for_address(address.as_str()).await?;
+            assert!(conn.unique_name().is_some());
+        }
+
+        // Nothing listens on the port anymore.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+        Connection::for_address(format!("tcp:host=127.0.0.1,port={port}").as_str())
+            .await
+            .unwrap_err();
+
+        // Malformed and unsupported addresses are rejected before connecting.
+        for (address, error) in [
+            ("tcp:host=127.0.0.1,port=forty", "invalid tcp `port`: forty"),
+            (
+                "unix:path=/tmp/dbus-test,dir=/tmp",
+                "unix: address is invalid, it needs exactly one of `path`, `abstract`, `dir` or \
+                 `tmpdir`",
+            ),
+            (
+                "unixexec:path=/usr/bin/dbus-daemon",
+                "unsupported transport 'unixexec': spawning the peer process is not supported",
+            ),
+        ] {
+            match Connection::for_address(address).await {
+                Err(Error::Address(e)) => assert_eq!(e, error),
+                r => panic!("unexpected result for `{address}`: {r:?}"),
+            }
+        }
+
+        Ok(())
+    }
+
     #[test]
     #[timeout(15000)]
     fn disconnect_on_drop() {
@@ -1776,4 +3059,449 @@ mod tests {
         )
         .map(|_| ())
     }
+
+    #[test]
+    #[timeout(15000)]
+    #[cfg(all(unix, not(feature = "tokio")))]
+    fn send_queue_limit() {
+        crate::utils::block_on(test_send_queue_limit()).unwrap();
+    }
+
+    #[cfg(all(unix, not(feature = "tokio")))]
+    async fn test_send_queue_limit() -> Result<()> {
+        use async_io::Async;
+        use futures_util::FutureExt;
+        use std::{
+            os::unix::{io::RawFd, net::UnixStream},
+            sync::atomic::AtomicBool,
+        };
+
+        // A socket that doesn't write anything while stalled, like one with a peer that's too slow
+        // to read. It doesn't wake up the writer on being unstalled so the test must poll again.
+        #[derive(Debug)]
+        struct StallableSocket {
+            socket: Async<UnixStream>,
+            stalled: Arc<AtomicBool>,
+        }
+
+        impl Socket for StallableSocket {
+            fn poll_recvmsg(
+                &mut self,
+                cx: &mut Context<'_>,
+                buf: &mut [u8],
+            ) -> Poll<io::Result<(usize, Vec<crate::OwnedFd>)>> {
+                self.socket.poll_recvmsg(cx, buf)
+            }
+
+            fn poll_sendmsg(
+                &mut self,
+                cx: &mut Context<'_>,
+                buffer: &[u8],
+                fds: &[RawFd],
+            ) -> Poll<io::Result<usize>> {
+                if self.stalled.load(SeqCst) {
+                    return Poll::Pending;
+                }
+
+                self.socket.poll_sendmsg(cx, buffer, fds)
+            }
+
+            fn close(&self) -> io::Result<()> {
+                self.socket.close()
+            }
+        }
+
+        let guid = Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let stalled = Arc::new(AtomicBool::new(false));
+        let socket = StallableSocket {
+            socket: Async::new(p1)?,
+            stalled: stalled.clone(),
+        };
+        let (mut client, server) = futures_util::try_join!(
+            Builder::socket(socket)
+                .p2p()
+                .send_queue_limit(1)
+                .send_queue_full_behavior(SendQueueFullBehavior::Fail)
+                .build(),
+            Builder::unix_stream(p0).server(&guid).p2p().build(),
+        )?;
+        assert_eq!(client.send_queue_limit(), Some(1));
+        let mut stream = MessageStream::from(&server);
+        let emit = |client: &Connection, n: u32| {
+            let client = client.clone();
+            async move {
+                client
+                    .emit_signal(None::<()>, "/org/zbus/test", "org.zbus.Test", "Ping", &n)
+                    .await
+            }
+            .boxed()
+        };
+
+        stalled.store(true, SeqCst);
+        // The first message fills up the queue and waits to be written out.
+        let mut first = emit(&client, 1);
+        assert!((&mut first).now_or_never().is_none());
+        // So the next one fails immediately..
+        let res = emit(&client, 2).now_or_never();
+        assert!(matches!(res, Some(Err(Error::SendQueueFull))));
+        // ..or waits for room in the queue.
+        client.set_send_queue_full_behavior(SendQueueFullBehavior::Wait);
+        let mut third = emit(&client, 3);
+        assert!((&mut third).now_or_never().is_none());
+
+        stalled.store(false, SeqCst);
+        futures_util::try_join!(first, third)?;
+        for expected in [1u32, 3] {
+            let msg = stream.try_next().await?.unwrap();
+            assert_eq!(msg.body::<u32>()?, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    #[cfg(unix)]
+    fn keepalive() {
+        crate::utils::block_on(test_keepalive()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_keepalive() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        struct Dummy;
+
+        #[crate::dbus_interface(name = "org.zbus.Dummy")]
+        impl Dummy {}
+
+        let guid = Guid::generate();
+        let client = |stream| {
+            Builder::unix_stream(stream)
+                .p2p()
+                .keepalive(Duration::from_millis(50), Duration::from_millis(200))
+                .build()
+        };
+
+        // A peer replying to the pings keeps the connection alive.
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (conn, _server) = futures_util::try_join!(
+            client(p1),
+            Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .serve_at("/org/zbus/Dummy", Dummy)?
+                .build(),
+        )?;
+        sleep(Duration::from_millis(500)).await;
+        conn.call_method(
+            None::<()>,
+            "/",
+            Some("org.freedesktop.DBus.Peer"),
+            "Ping",
+            &(),
+        )
+        .await?;
+
+        // One that doesn't is considered gone.
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (conn, _server) = futures_util::try_join!(
+            client(p1),
+            Builder::unix_stream(p0).server(&guid).p2p().build(),
+        )?;
+        let mut stream = MessageStream::from(&conn);
+        assert!(stream.next().await.unwrap().is_err());
+        assert!(stream.next().await.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn call_prebuilt_message() {
+        crate::utils::block_on(test_call_prebuilt_message()).unwrap();
+    }
+
+    async fn test_call_prebuilt_message() -> Result<()> {
+        let conn = Connection::session().await?;
+
+        let msg = message::Builder::method_call("/org/freedesktop/DBus", "GetId")?
+            .destination("org.freedesktop.DBus")?
+            .interface("org.freedesktop.DBus")?
+            .build(&())?;
+        let reply = conn.call(msg).await?;
+        assert_eq!(
+            reply.body::<Guid>()?,
+            DBusProxy::new(&conn).await?.get_id().await?
+        );
+
+        let msg = message::Builder::method_call("/org/freedesktop/DBus", "DoesNotExist")?
+            .destination("org.freedesktop.DBus")?
+            .interface("org.freedesktop.DBus")?
+            .build(&())?;
+        assert!(matches!(
+            conn.call(msg).await.unwrap_err(),
+            Error::MethodError(..)
+        ));
+
+        // Only method calls expecting a reply are accepted.
+        let msg =
+            message::Builder::signal("/org/zbus/Test", "org.zbus.Test", "Signal")?.build(&())?;
+        assert_eq!(conn.call(msg).await.unwrap_err(), Error::InvalidField);
+        let msg = message::Builder::method_call("/org/freedesktop/DBus", "GetId")?
+            .destination("org.freedesktop.DBus")?
+            .with_flags(Flags::NoReplyExpected)?
+            .build(&())?;
+        assert_eq!(conn.call(msg).await.unwrap_err(), Error::InvalidField);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn send_fixed_serial() {
+        crate::utils::block_on(test_send_fixed_serial()).unwrap();
+    }
+
+    async fn test_send_fixed_serial() -> Result<()> {
+        use futures_util::{SinkExt, StreamExt};
+
+        let (server, client) = tcp_p2p_pipe().await?;
+        let mut stream = MessageStream::from(&server);
+        let msg = || {
+            message::Builder::signal("/org/zbus/Test", "org.zbus.Test", "Signal")?
+                .serial(42)?
+                .build(&())
+        };
+
+        // The connection won't renumber the message, but it can be sent as is.
+        assert_eq!(
+            client.send_message(msg()?).await.unwrap_err(),
+            Error::InvalidSerial
+        );
+        (&mut &client).send(msg()?).await?;
+        let received = stream.next().await.unwrap()?;
+        assert_eq!(received.primary_header().serial_num().unwrap().get(), 42);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn request_name_with_retry() {
+        crate::utils::block_on(test_request_name_with_retry()).unwrap();
+    }
+
+    async fn test_request_name_with_retry() -> Result<()> {
+        use crate::{fdo::RequestNameFlags, proxy::RetryPolicy};
+
+        let name = "org.freedesktop.zbus.RequestNameWithRetryTest";
+        let retry = RetryPolicy::new();
+        let conn1 = Connection::session().await?;
+        conn1.request_name(name).await?;
+
+        // Still queued once the timeout elapses.
+        let conn2 = Connection::session().await?;
+        let reply = conn2
+            .request_name_with_retry(
+                name,
+                BitFlags::empty(),
+                &retry,
+                Some(Duration::from_millis(50)),
+            )
+            .await?;
+        assert_eq!(reply, fdo::RequestNameReply::InQueue);
+
+        // Acquired once the current owner goes away.
+        let conn3 = Connection::session().await?;
+        let release = async {
+            sleep(Duration::from_millis(100)).await;
+            conn1.release_name(name).await?;
+            conn2.release_name(name).await
+        };
+        let (reply, _) = futures_util::try_join!(
+            conn3.request_name_with_retry(
+                name,
+                BitFlags::empty(),
+                &retry,
+                Some(Duration::from_secs(5)),
+            ),
+            release,
+        )?;
+        assert_eq!(reply, fdo::RequestNameReply::PrimaryOwner);
+
+        // Without queueing, the reply is returned as is.
+        let reply = conn1
+            .request_name_with_retry(name, RequestNameFlags::DoNotQueue.into(), &retry, None)
+            .await?;
+        assert_eq!(reply, fdo::RequestNameReply::Exists);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn receive_name_lost() {
+        crate::utils::block_on(test_receive_name_lost()).unwrap();
+    }
+
+    async fn test_receive_name_lost() -> Result<()> {
+        use crate::fdo::RequestNameFlags;
+        use futures_util::StreamExt;
+
+        let name1 = "org.freedesktop.zbus.ReceiveNameLostTest1";
+        let name2 = "org.freedesktop.zbus.ReceiveNameLostTest2";
+        let old = Connection::session().await?;
+        let mut lost_stream = old.receive_name_lost(name2).await?;
+        for name in [name1, name2] {
+            old.request_name_with_flags(name, RequestNameFlags::AllowReplacement.into())
+                .await?;
+        }
+
+        // Only the signal for the given name is received.
+        let new = Connection::session().await?;
+        for name in [name1, name2] {
+            let reply = new
+                .request_name_with_flags(name, RequestNameFlags::ReplaceExisting.into())
+                .await?;
+            assert_eq!(reply, fdo::RequestNameReply::PrimaryOwner);
+        }
+        let lost = lost_stream.next().await.unwrap();
+        assert_eq!(lost.args()?.name, name2);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn owned_names() {
+        crate::utils::block_on(test_owned_names()).unwrap();
+    }
+
+    async fn test_owned_names() -> Result<()> {
+        use crate::fdo::RequestNameFlags;
+
+        let name1 = "org.freedesktop.zbus.OwnedNamesTest1";
+        let name2 = "org.freedesktop.zbus.OwnedNamesTest2";
+        let conn = Connection::session().await?;
+        assert!(conn.owned_names().await.is_empty());
+        conn.request_name(name1).await?;
+        conn.request_name_with_flags(name2, RequestNameFlags::AllowReplacement.into())
+            .await?;
+        let mut names = conn.owned_names().await;
+        names.sort();
+        assert_eq!(names, [name1, name2]);
+
+        assert!(conn.release_name(name1).await?);
+        assert_eq!(conn.owned_names().await, [name2]);
+
+        // Names lost to another peer are removed.
+        let other = Connection::session().await?;
+        other
+            .request_name_with_flags(name2, RequestNameFlags::ReplaceExisting.into())
+            .await?;
+        while !conn.owned_names().await.is_empty() {
+            sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(other.owned_names().await, [name2]);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn events() {
+        crate::utils::block_on(test_events()).unwrap();
+    }
+
+    async fn test_events() -> Result<()> {
+        use futures_util::{stream::FusedStream, StreamExt};
+
+        let name = "org.freedesktop.zbus.ConnectionEventsTest";
+        let conn = Connection::session().await?;
+        let mut events = conn.events().await?;
+        assert!(matches!(
+            events.next().await,
+            Some(ConnectionEvent::Connected)
+        ));
+        conn.request_name(name).await?;
+        match events.next().await {
+            Some(ConnectionEvent::NameAcquired(acquired)) => assert_eq!(acquired, name),
+            event => panic!("unexpected event: {event:?}"),
+        }
+        conn.release_name(name).await?;
+        match events.next().await {
+            Some(ConnectionEvent::NameLost(lost)) => assert_eq!(lost, name),
+            event => panic!("unexpected event: {event:?}"),
+        }
+
+        // The stream ends once the peer closes the connection.
+        let (server, client) = tcp_p2p_pipe().await?;
+        let mut events = client.events().await?;
+        assert!(matches!(
+            events.next().await,
+            Some(ConnectionEvent::Connected)
+        ));
+        drop(server);
+        // With the error that stopped the connection.
+        match events.next().await {
+            Some(ConnectionEvent::Disconnected(Error::InputOutput(e))) => {
+                assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof)
+            }
+            event => panic!("unexpected event: {event:?}"),
+        }
+        assert!(events.next().await.is_none());
+        assert!(events.is_terminated());
+
+        // No `Connected` event if the connection is gone by the time the stream is polled.
+        let (server, client) = tcp_p2p_pipe().await?;
+        let mut events = client.events().await?;
+        drop(server);
+        while !client.is_closed().await {
+            sleep(Duration::from_millis(10)).await;
+        }
+        assert!(matches!(
+            events.next().await,
+            Some(ConnectionEvent::Disconnected(Error::InputOutput(_)))
+        ));
+        assert!(events.next().await.is_none());
+        // And the streams created afterwards fail with the same error.
+        match client.events().await {
+            Err(Error::InputOutput(e)) => assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof),
+            r => panic!("unexpected result: {r:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn signal_context_has_subscribers() {
+        crate::utils::block_on(test_signal_context_has_subscribers()).unwrap();
+    }
+
+    async fn test_signal_context_has_subscribers() -> Result<()> {
+        use crate::SignalContext;
+
+        let (server, client) = tcp_p2p_pipe().await?;
+        let ctxt = SignalContext::new(&server, "/org/zbus/Test")?;
+        assert!(ctxt.has_subscribers().await);
+
+        // Nobody is left to receive the signals once the peer disconnects.
+        drop(client);
+        while ctxt.has_subscribers().await {
+            sleep(Duration::from_millis(10)).await;
+        }
+
+        // On the bus, the subscribers are unknown.
+        let conn = Connection::session().await?;
+        let ctxt = SignalContext::new(&conn, "/org/zbus/Test")?;
+        assert!(ctxt.has_subscribers().await);
+
+        Ok(())
+    }
 }
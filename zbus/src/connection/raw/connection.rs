@@ -10,6 +10,7 @@ use event_listener::{Event, EventListener};
 #[cfg(unix)]
 use crate::OwnedFd;
 use crate::{
+    connection::SendQueueFullBehavior,
     message::{
         header::{MAX_MESSAGE_SIZE, MIN_MESSAGE_SIZE},
         Message, PrimaryHeader,
@@ -41,6 +42,8 @@ pub struct Connection<S> {
     raw_in_pos: usize,
     out_pos: usize,
     out_msgs: VecDeque<Arc<Message>>,
+    out_msgs_limit: Option<usize>,
+    out_msgs_full_behavior: SendQueueFullBehavior,
     prev_seq: u64,
 }
 
@@ -55,6 +58,8 @@ impl<S: Socket> Connection<S> {
             raw_in_fds: vec![],
             out_pos: 0,
             out_msgs: VecDeque::new(),
+            out_msgs_limit: None,
+            out_msgs_full_behavior: SendQueueFullBehavior::default(),
             prev_seq: 0,
         }
     }
@@ -96,6 +101,36 @@ impl<S: Socket> Connection<S> {
         self.out_msgs.push_back(msg);
     }
 
+    /// The maximum number of messages in the outgoing buffer, if any.
+    pub fn out_msgs_limit(&self) -> Option<usize> {
+        self.out_msgs_limit
+    }
+
+    /// Set the maximum number of messages in the outgoing buffer.
+    ///
+    /// The limit is not enforced by `enqueue_message`. It's up to the caller to check
+    /// `is_out_msgs_full` before enqueuing a message.
+    pub fn set_out_msgs_limit(&mut self, limit: Option<usize>) {
+        self.out_msgs_limit = limit;
+    }
+
+    /// What to do when the outgoing buffer has reached its limit.
+    pub fn out_msgs_full_behavior(&self) -> SendQueueFullBehavior {
+        self.out_msgs_full_behavior
+    }
+
+    /// Set what to do when the outgoing buffer has reached its limit.
+    pub fn set_out_msgs_full_behavior(&mut self, behavior: SendQueueFullBehavior) {
+        self.out_msgs_full_behavior = behavior;
+    }
+
+    /// If the outgoing buffer has reached its limit.
+    pub fn is_out_msgs_full(&self) -> bool {
+        self.out_msgs_limit
+            .map(|limit| self.out_msgs.len() >= limit)
+            .unwrap_or(false)
+    }
+
     /// Attempt to read a message from the socket
     ///
     /// This methods will read from the socket until either a full D-Bus message is
@@ -3,3 +3,4 @@ mod socket;
 
 pub use connection::Connection;
 pub use socket::Socket;
+pub(crate) use socket::StreamSocket;
@@ -2,12 +2,13 @@
 use async_io::Async;
 #[cfg(not(feature = "tokio"))]
 use futures_core::ready;
+use futures_util::task::AtomicWaker;
 #[cfg(unix)]
 use std::io::{IoSlice, IoSliceMut};
-#[cfg(feature = "tokio")]
 use std::pin::Pin;
 use std::{
     io,
+    sync::atomic::{AtomicBool, Ordering},
     task::{Context, Poll},
 };
 #[cfg(not(feature = "tokio"))]
@@ -701,3 +702,225 @@ impl Socket for tokio_vsock::VsockStream {
         self.shutdown(std::net::Shutdown::Both)
     }
 }
+
+/// A [`Socket`] over any byte stream, for `connection::Builder::stream`.
+///
+/// The writes are flushed right away, since the messages are sent as a whole and the stream may
+/// be buffered (e.g if it's encrypted).
+///
+/// Shutting down the stream needs `&mut self`, so [`Socket::close`] only marks the socket as
+/// closed and wakes up the reader, which shuts the stream down and drops it on its next read.
+pub(crate) struct StreamSocket<S> {
+    // `None` once shut down.
+    stream: Option<S>,
+    // The length of the last write, if it still needs to be flushed.
+    unflushed: usize,
+    closed: AtomicBool,
+    reader: AtomicWaker,
+}
+
+impl<S> StreamSocket<S> {
+    pub(crate) fn new(stream: S) -> Self {
+        Self {
+            stream: Some(stream),
+            unflushed: 0,
+            closed: AtomicBool::new(false),
+            reader: AtomicWaker::new(),
+        }
+    }
+
+    fn mark_closed(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.reader.wake();
+    }
+
+    fn stream(&mut self) -> Pin<&mut S>
+    where
+        S: Unpin,
+    {
+        Pin::new(self.stream.as_mut().expect("stream already shut down"))
+    }
+}
+
+impl<S> std::fmt::Debug for StreamSocket<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamSocket").finish_non_exhaustive()
+    }
+}
+
+#[cfg(not(feature = "tokio"))]
+impl<S> StreamSocket<S>
+where
+    S: futures_util::io::AsyncWrite + Unpin,
+{
+    // Shut the stream down and drop it if the socket was closed. Returns whether it was closed.
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<bool>> {
+        if !self.closed.load(Ordering::SeqCst) {
+            return Poll::Ready(Ok(false));
+        }
+        if let Some(stream) = &mut self.stream {
+            ready!(Pin::new(stream).poll_close(cx))?;
+            self.stream = None;
+        }
+
+        Poll::Ready(Ok(true))
+    }
+}
+
+#[cfg(not(feature = "tokio"))]
+impl<S> Socket for StreamSocket<S>
+where
+    S: futures_util::io::AsyncRead + futures_util::io::AsyncWrite + Unpin + Send + Sync,
+{
+    fn can_pass_unix_fd(&self) -> bool {
+        false
+    }
+
+    fn poll_recvmsg(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> PollRecvmsg {
+        self.reader.register(cx.waker());
+        if ready!(self.poll_shutdown(cx))? {
+            #[cfg(unix)]
+            return Poll::Ready(Ok((0, vec![])));
+            #[cfg(not(unix))]
+            return Poll::Ready(Ok(0));
+        }
+
+        self.stream().poll_read(cx, buf).map(|res| {
+            res.map(|len| {
+                #[cfg(unix)]
+                let ret = (len, vec![]);
+                #[cfg(not(unix))]
+                let ret = len;
+
+                ret
+            })
+        })
+    }
+
+    fn poll_sendmsg(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        #[cfg(unix)] fds: &[RawFd],
+    ) -> Poll<io::Result<usize>> {
+        #[cfg(unix)]
+        if !fds.is_empty() {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "fds cannot be sent with a byte stream",
+            )));
+        }
+
+        if ready!(self.poll_shutdown(cx))? {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "socket closed",
+            )));
+        }
+
+        if self.unflushed == 0 {
+            self.unflushed = ready!(self.stream().poll_write(cx, buf))?;
+        }
+        ready!(self.stream().poll_flush(cx))?;
+
+        Poll::Ready(Ok(std::mem::take(&mut self.unflushed)))
+    }
+
+    fn close(&self) -> io::Result<()> {
+        self.mark_closed();
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<S> StreamSocket<S>
+where
+    S: tokio::io::AsyncWrite + Unpin,
+{
+    // Shut the stream down and drop it if the socket was closed. Returns whether it was closed.
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<bool>> {
+        use futures_core::ready;
+
+        if !self.closed.load(Ordering::SeqCst) {
+            return Poll::Ready(Ok(false));
+        }
+        if let Some(stream) = &mut self.stream {
+            ready!(Pin::new(stream).poll_shutdown(cx))?;
+            self.stream = None;
+        }
+
+        Poll::Ready(Ok(true))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<S> Socket for StreamSocket<S>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + Sync,
+{
+    fn can_pass_unix_fd(&self) -> bool {
+        false
+    }
+
+    fn poll_recvmsg(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> PollRecvmsg {
+        use futures_core::ready;
+        use tokio::io::ReadBuf;
+
+        self.reader.register(cx.waker());
+        if ready!(self.poll_shutdown(cx))? {
+            #[cfg(unix)]
+            return Poll::Ready(Ok((0, vec![])));
+            #[cfg(not(unix))]
+            return Poll::Ready(Ok(0));
+        }
+
+        let mut read_buf = ReadBuf::new(buf);
+        self.stream().poll_read(cx, &mut read_buf).map(|res| {
+            res.map(|_| {
+                let ret = read_buf.filled().len();
+                #[cfg(unix)]
+                let ret = (ret, vec![]);
+
+                ret
+            })
+        })
+    }
+
+    fn poll_sendmsg(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        #[cfg(unix)] fds: &[RawFd],
+    ) -> Poll<io::Result<usize>> {
+        use futures_core::ready;
+
+        #[cfg(unix)]
+        if !fds.is_empty() {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "fds cannot be sent with a byte stream",
+            )));
+        }
+
+        if ready!(self.poll_shutdown(cx))? {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "socket closed",
+            )));
+        }
+
+        if self.unflushed == 0 {
+            self.unflushed = ready!(self.stream().poll_write(cx, buf))?;
+        }
+        ready!(self.stream().poll_flush(cx))?;
+
+        Poll::Ready(Ok(std::mem::take(&mut self.unflushed)))
+    }
+
+    fn close(&self) -> io::Result<()> {
+        self.mark_closed();
+
+        Ok(())
+    }
+}
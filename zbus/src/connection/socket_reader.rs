@@ -6,7 +6,11 @@ use std::{
 use futures_util::future::poll_fn;
 use tracing::{debug, instrument, trace};
 
-use crate::{async_lock::Mutex, connection::MsgBroadcaster, Executor, OwnedMatchRule, Task};
+use crate::{
+    async_lock::Mutex,
+    connection::{MsgBroadcaster, ReplySenders, SocketError},
+    Executor, OwnedMatchRule, Task,
+};
 
 use super::raw::{Connection as RawConnection, Socket};
 
@@ -14,14 +18,23 @@ use super::raw::{Connection as RawConnection, Socket};
 pub(crate) struct SocketReader {
     raw_conn: Arc<sync::Mutex<RawConnection<Box<dyn Socket>>>>,
     senders: Arc<Mutex<HashMap<Option<OwnedMatchRule>, MsgBroadcaster>>>,
+    reply_senders: ReplySenders,
+    socket_error: SocketError,
 }
 
 impl SocketReader {
     pub fn new(
         raw_conn: Arc<sync::Mutex<RawConnection<Box<dyn Socket>>>>,
         senders: Arc<Mutex<HashMap<Option<OwnedMatchRule>, MsgBroadcaster>>>,
+        reply_senders: ReplySenders,
+        socket_error: SocketError,
     ) -> Self {
-        Self { raw_conn, senders }
+        Self {
+            raw_conn,
+            senders,
+            reply_senders,
+            socket_error,
+        }
     }
 
     pub fn spawn(self, executor: &Executor<'_>) -> Task<()> {
@@ -43,7 +56,11 @@ impl SocketReader {
             };
             match &msg {
                 Ok(msg) => trace!("Message received on the socket: {:?}", msg),
-                Err(e) => trace!("Error reading from the socket: {:?}", e),
+                Err(e) => {
+                    trace!("Error reading from the socket: {:?}", e);
+                    // Kept for the streams created or ending after it's broadcast.
+                    *self.socket_error.lock().expect("poisoned lock") = Some(e.clone());
+                }
             };
 
             let mut senders = self.senders.lock().await;
@@ -78,6 +95,25 @@ impl SocketReader {
             }
             trace!("Broadcasted to all streams: {:?}", msg);
 
+            // Replies also go to the channel of their call, if it has one. All these channels get
+            // the errors.
+            let reply_senders: Vec<_> = {
+                let mut reply_senders = self.reply_senders.lock().expect("poisoned lock");
+                match &msg {
+                    Ok(msg) => msg
+                        .reply_serial()
+                        .and_then(|serial| reply_senders.get(&serial).cloned())
+                        .into_iter()
+                        .collect(),
+                    Err(_) => reply_senders.drain().map(|(_, sender)| sender).collect(),
+                }
+            };
+            for sender in reply_senders {
+                if let Err(e) = sender.broadcast(msg.clone()).await {
+                    trace!("Error broadcasting message to a reply stream: {:?}", e);
+                }
+            }
+
             if msg.is_err() {
                 senders.clear();
                 trace!("Socket reading task stopped");
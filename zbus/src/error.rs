@@ -2,7 +2,7 @@
 use quick_xml::de::DeError;
 use static_assertions::assert_impl_all;
 use std::{convert::Infallible, error, fmt, io, sync::Arc};
-use zbus_names::{Error as NamesError, OwnedErrorName};
+use zbus_names::{Error as NamesError, OwnedBusName, OwnedErrorName, OwnedMemberName};
 use zvariant::Error as VariantError;
 
 use crate::{
@@ -63,8 +63,17 @@ pub enum Error {
     Failure(String),
     /// A required parameter was missing.
     MissingParameter(&'static str),
-    /// Serial number in the message header is 0 (which is invalid).
+    /// Serial number in the message header is 0 (which is invalid), or it was set through
+    /// [`crate::message::Builder::serial`] on a message the connection would renumber.
     InvalidSerial,
+    /// The outgoing message queue of the connection is full.
+    SendQueueFull,
+    /// No reply to a method call was received before the [method timeout] elapsed.
+    ///
+    /// Contains the destination (if any) and the member of the call.
+    ///
+    /// [method timeout]: crate::connection::Builder::method_timeout
+    Timeout(Option<OwnedBusName>, OwnedMemberName),
 }
 
 assert_impl_all!(Error: Send, Sync, Unpin);
@@ -82,6 +91,8 @@ impl PartialEq for Error {
             (Self::MissingField, Self::MissingField) => true,
             (Self::InvalidGUID, Self::InvalidGUID) => true,
             (Self::InvalidSerial, Self::InvalidSerial) => true,
+            (Self::SendQueueFull, Self::SendQueueFull) => true,
+            (Self::Timeout(d1, m1), Self::Timeout(d2, m2)) => d1 == d2 && m1 == m2,
             (Self::Unsupported, Self::Unsupported) => true,
             (Self::FDO(s), Self::FDO(o)) => s == o,
             (Self::NoBodySignature, Self::NoBodySignature) => true,
@@ -125,6 +136,8 @@ impl error::Error for Error {
             Error::Failure(_) => None,
             Error::MissingParameter(_) => None,
             Error::InvalidSerial => None,
+            Error::SendQueueFull => None,
+            Error::Timeout(_, _) => None,
         }
     }
 }
@@ -161,7 +174,17 @@ impl fmt::Display for Error {
             Error::MissingParameter(p) => {
                 write!(f, "Parameter `{}` was not specified but it is required", p)
             }
-            Error::InvalidSerial => write!(f, "Serial number in the message header is 0"),
+            Error::InvalidSerial => {
+                write!(
+                    f,
+                    "Serial number in the message header is 0 or can't be assigned"
+                )
+            }
+            Error::SendQueueFull => write!(f, "Outgoing message queue is full"),
+            Error::Timeout(Some(destination), member) => {
+                write!(f, "Method call `{member}` to `{destination}` timed out")
+            }
+            Error::Timeout(None, member) => write!(f, "Method call `{member}` timed out"),
         }
     }
 }
@@ -194,6 +217,10 @@ impl Clone for Error {
             Error::Failure(e) => Error::Failure(e.clone()),
             Error::MissingParameter(p) => Error::MissingParameter(p),
             Error::InvalidSerial => Error::InvalidSerial,
+            Error::SendQueueFull => Error::SendQueueFull,
+            Error::Timeout(destination, member) => {
+                Error::Timeout(destination.clone(), member.clone())
+            }
         }
     }
 }
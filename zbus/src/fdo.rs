@@ -17,8 +17,10 @@ use zvariant::{
 };
 
 use crate::{
-    dbus_interface, dbus_proxy, message::Header, object_server::SignalContext, DBusError, Guid,
-    ObjectServer,
+    dbus_interface, dbus_proxy,
+    message::{Header, Message},
+    object_server::SignalContext,
+    DBusError, Guid, ObjectServer,
 };
 
 #[rustfmt::skip]
@@ -110,6 +112,87 @@ macro_rules! gen_properties_proxy {
 gen_properties_proxy!(true, false);
 assert_impl_all!(PropertiesProxy<'_>: Send, Sync, Unpin);
 
+impl PropertiesChanged {
+    /// Retrieve the signal arguments, as owned values with named fields.
+    pub fn owned_args(&self) -> crate::Result<OwnedPropertiesChangedArgs> {
+        OwnedPropertiesChangedArgs::try_from(&**self)
+    }
+}
+
+/// The owned arguments of an `org.freedesktop.DBus.Properties.PropertiesChanged` signal.
+///
+/// Unlike [`PropertiesChangedArgs`], this doesn't borrow from the message, so it can be kept
+/// around or sent elsewhere. Since the signal is the same for all interfaces, this can be used to
+/// watch the properties of any object, without a generated proxy for its interfaces:
+///
+/// ```no_run
+/// # use futures_util::stream::StreamExt;
+/// # use zbus::{fdo::PropertiesProxy, Connection};
+/// #
+/// # zbus::block_on(async {
+/// let conn = Connection::session().await?;
+/// let proxy = PropertiesProxy::builder(&conn)
+///     .destination("org.freedesktop.zbus.Example")?
+///     .path("/org/freedesktop/zbus/Example")?
+///     .build()
+///     .await?;
+/// let mut stream = proxy.receive_properties_changed().await?;
+/// while let Some(signal) = stream.next().await {
+///     let args = signal.owned_args()?;
+///     for (name, value) in &args.changed {
+///         println!("{}.{name} changed to {value:?}", args.interface);
+///     }
+///     for name in &args.invalidated {
+///         println!("{}.{name} changed", args.interface);
+///     }
+/// }
+/// # Ok::<_, zbus::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedPropertiesChangedArgs {
+    /// The interface of the properties.
+    pub interface: OwnedInterfaceName,
+    /// The properties that changed, with their new value.
+    pub changed: HashMap<String, OwnedValue>,
+    /// The properties that changed but whose new value isn't included in the signal.
+    pub invalidated: Vec<String>,
+}
+
+assert_impl_all!(OwnedPropertiesChangedArgs: Send, Sync, Unpin);
+
+impl TryFrom<&Message> for OwnedPropertiesChangedArgs {
+    type Error = crate::Error;
+
+    fn try_from(message: &Message) -> crate::Result<Self> {
+        let (interface, changed, invalidated) = message.body()?;
+
+        Ok(Self {
+            interface,
+            changed,
+            invalidated,
+        })
+    }
+}
+
+impl From<PropertiesChangedArgs<'_>> for OwnedPropertiesChangedArgs {
+    fn from(args: PropertiesChangedArgs<'_>) -> Self {
+        Self {
+            interface: args.interface_name.into(),
+            changed: args
+                .changed_properties
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.into()))
+                .collect(),
+            invalidated: args
+                .invalidated_properties
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
 /// Server-side implementation for the `org.freedesktop.DBus.Properties` interface.
 /// This interface is implemented automatically for any object registered to the
 /// [ObjectServer](crate::ObjectServer).
@@ -287,6 +370,7 @@ pub struct ObjectManager;
 
 #[dbus_interface(name = "org.freedesktop.DBus.ObjectManager")]
 impl ObjectManager {
+    #[dbus_interface(out_args("objpath_interfaces_and_properties"))]
     async fn get_managed_objects(
         &self,
         #[zbus(object_server)] server: &ObjectServer,
@@ -459,6 +543,105 @@ macro_rules! gen_stats_proxy {
 gen_stats_proxy!(true, false);
 assert_impl_all!(StatsProxy<'_>: Send, Sync, Unpin);
 
+/// The type returned by the [`ContainersProxy::get_instance_info`] method.
+///
+/// The elements are the credentials of the connection that created the container instance, the
+/// container type, the container name and the metadata the instance was created with.
+pub type ContainerInstanceInfo = (
+    ConnectionCredentials,
+    String,
+    String,
+    HashMap<String, OwnedValue>,
+);
+
+#[rustfmt::skip]
+macro_rules! gen_containers_proxy {
+    ($gen_async:literal, $gen_blocking:literal) => {
+        /// Proxy for the `org.freedesktop.DBus.Containers1` interface.
+        ///
+        /// This interface lets sandboxing frameworks (app containers) create additional bus
+        /// servers for their sandboxed apps and lets services identify the container instance
+        /// their peers belong to. It's optional and not all message bus implementations provide
+        /// it, in which case the calls fail with an [`Error::UnknownInterface`] or
+        /// [`Error::UnknownMethod`] error.
+        ///
+        /// See [the spec] for details.
+        ///
+        /// [`Error::UnknownInterface`]: crate::fdo::Error::UnknownInterface
+        /// [`Error::UnknownMethod`]: crate::fdo::Error::UnknownMethod
+        /// [the spec]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-bus-containers
+        #[dbus_proxy(
+            interface = "org.freedesktop.DBus.Containers1",
+            default_service = "org.freedesktop.DBus",
+            default_path = "/org/freedesktop/DBus",
+            gen_async = $gen_async,
+            gen_blocking = $gen_blocking,
+        )]
+        trait Containers {
+            /// Creates a container instance and a new server for it to connect to.
+            ///
+            /// # Arguments
+            ///
+            /// * `container_type` - A reversed domain name identifying the container technology,
+            ///   e.g `org.flatpak`.
+            /// * `container_name` - An identifier for the app in the container, e.g its app ID.
+            /// * `metadata` - Arbitrary metadata to associate with the container instance.
+            /// * `named_arguments` - Additional arguments. See the
+            ///   [`supported_arguments`] property for the ones understood by the message bus.
+            ///
+            /// Returns the object path of the container instance, the path of the new server's
+            /// socket (as a NUL-terminated byte string) and the D-Bus address to connect to it.
+            ///
+            /// [`supported_arguments`]: #method.supported_arguments
+            fn add_server(
+                &self,
+                container_type: &str,
+                container_name: &str,
+                metadata: HashMap<&str, Value<'_>>,
+                named_arguments: HashMap<&str, Value<'_>>,
+            ) -> Result<(OwnedObjectPath, Vec<u8>, String)>;
+
+            /// Stops the server of the container instance and disconnects its connections.
+            fn stop_instance(&self, container: ObjectPath<'_>) -> Result<()>;
+
+            /// Stops the server of the container instance but keeps its connections.
+            fn stop_listening(&self, container: ObjectPath<'_>) -> Result<()>;
+
+            /// Returns the container instance the connection of `bus_name` belongs to, along with
+            /// the information on it, as returned by [`get_instance_info`].
+            ///
+            /// [`get_instance_info`]: #method.get_instance_info
+            fn get_connection_instance(
+                &self,
+                bus_name: BusName<'_>,
+            ) -> Result<(
+                OwnedObjectPath,
+                ConnectionCredentials,
+                String,
+                String,
+                HashMap<String, OwnedValue>,
+            )>;
+
+            /// Returns information on the given container instance.
+            fn get_instance_info(&self, container: ObjectPath<'_>) -> Result<ContainerInstanceInfo>;
+
+            /// Emitted when a container instance is gone, after its server has stopped listening
+            /// and all its connections have been closed.
+            #[dbus_proxy(signal)]
+            fn instance_removed(&self, container: ObjectPath<'_>);
+
+            /// The named arguments understood by [`add_server`].
+            ///
+            /// [`add_server`]: #method.add_server
+            #[dbus_proxy(property)]
+            fn supported_arguments(&self) -> Result<Vec<String>>;
+        }
+    };
+}
+
+gen_containers_proxy!(true, false);
+assert_impl_all!(ContainersProxy<'_>: Send, Sync, Unpin);
+
 /// The flags used by the bus [`request_name`] method.
 ///
 /// [`request_name`]: struct.DBusProxy.html#method.request_name
@@ -551,6 +734,20 @@ pub enum ReleaseNameReply {
 
 assert_impl_all!(ReleaseNameReply: Send, Sync, Unpin);
 
+/// The return code of the [`start_service_by_name`] method.
+///
+/// [`start_service_by_name`]: struct.DBusProxy.html#method.start_service_by_name
+#[repr(u32)]
+#[derive(Deserialize_repr, Serialize_repr, Type, Debug, PartialEq, Eq)]
+pub enum StartServiceReply {
+    /// The service was successfully started.
+    Success = 0x01,
+    /// A connection already owns the given name.
+    AlreadyRunning = 0x02,
+}
+
+assert_impl_all!(StartServiceReply: Send, Sync, Unpin);
+
 /// Credentials of a process connected to a bus server.
 ///
 /// If unable to determine certain credentials (for instance, because the process is not on the same
@@ -560,7 +757,7 @@ assert_impl_all!(ReleaseNameReply: Send, Sync, Unpin);
 ///
 /// **Note**: unknown keys, in particular those with "." that are not from the specification, will
 /// be ignored. Use your own implementation or contribute your keys here, or in the specification.
-#[derive(Debug, Default, DeserializeDict, PartialEq, Eq, SerializeDict, Type)]
+#[derive(Debug, Default, Clone, DeserializeDict, PartialEq, Eq, SerializeDict, Type)]
 #[zvariant(signature = "a{sv}")]
 pub struct ConnectionCredentials {
     #[zvariant(rename = "UnixUserID")]
@@ -745,6 +942,9 @@ macro_rules! gen_dbus_proxy {
             fn list_names(&self) -> Result<Vec<OwnedBusName>>;
 
             /// List the connections currently queued for a bus name.
+            ///
+            /// The primary owner of the name comes first, followed by the other connections in
+            /// the order they'd become the owner.
             fn list_queued_owners(&self, name: WellKnownName<'_>) -> Result<Vec<OwnedUniqueName>>;
 
             /// Checks if the specified name exists (currently has an owner).
@@ -769,9 +969,20 @@ macro_rules! gen_dbus_proxy {
 
             /// Tries to launch the executable associated with a name (service
             /// activation), as an explicit request.
-            fn start_service_by_name(&self, name: WellKnownName<'_>, flags: u32) -> Result<u32>;
+            ///
+            /// The `flags` are currently not used and should be `0`.
+            fn start_service_by_name(
+                &self,
+                name: WellKnownName<'_>,
+                flags: u32,
+            ) -> Result<StartServiceReply>;
 
-            /// This method adds to or modifies that environment when activating services.
+            /// Adds to or modifies the environment of the services activated by the bus.
+            ///
+            /// The `environment` entries are variable names mapped to their values. They only
+            /// affect services activated after the call, not the running ones. This is only
+            /// meaningful on the session bus: the system bus refuses the call since its services
+            /// are shared by all users.
             fn update_activation_environment(&self, environment: HashMap<&str, &str>)
                 -> Result<()>;
 
@@ -1092,4 +1303,192 @@ mod tests {
                 dbg!(v)
             });
     }
+
+    #[test]
+    #[timeout(15000)]
+    fn containers() {
+        crate::block_on(test_containers()).unwrap();
+    }
+
+    async fn test_containers() -> fdo::Result<()> {
+        let conn = crate::Connection::session().await?;
+        let dbus = fdo::DBusProxy::new(&conn).await?;
+        let proxy = fdo::ContainersProxy::new(&conn).await?;
+
+        let unique_name = conn.unique_name().unwrap();
+        let res = proxy.get_connection_instance(unique_name.into()).await;
+        let containers_iface = "org.freedesktop.DBus.Containers1";
+        if dbus
+            .interfaces()
+            .await?
+            .iter()
+            .any(|i| i.as_str() == containers_iface)
+        {
+            proxy.supported_arguments().await?;
+            // We're not in a container.
+            assert!(res.is_err());
+        } else {
+            assert!(
+                matches!(
+                    res,
+                    Err(fdo::Error::UnknownInterface(_)) | Err(fdo::Error::UnknownMethod(_))
+                ),
+                "{res:?}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn start_service_by_name() {
+        crate::block_on(test_start_service_by_name()).unwrap();
+    }
+
+    async fn test_start_service_by_name() -> fdo::Result<()> {
+        let conn = crate::Connection::session().await?;
+        let proxy = fdo::DBusProxy::new(&conn).await?;
+
+        // A name that is not activatable can't be started.
+        let name = WellKnownName::try_from("org.freedesktop.zbus.NotActivatable").unwrap();
+        assert!(!proxy
+            .list_activatable_names()
+            .await?
+            .iter()
+            .any(|n| n.as_str() == name.as_str()));
+        let res = proxy.start_service_by_name(name, 0).await;
+        assert!(matches!(res, Err(fdo::Error::ServiceUnknown(_))), "{res:?}");
+
+        let mut call = Message::method(
+            None::<()>,
+            Some("org.freedesktop.DBus"),
+            "/org/freedesktop/DBus",
+            Some("org.freedesktop.DBus"),
+            "StartServiceByName",
+            &("org.freedesktop.zbus.Activatable", 0u32),
+        )?;
+        call.set_serial_num(1.try_into().unwrap())?;
+        let reply = Message::method_reply(None::<()>, &call, &2u32)?;
+        assert_eq!(
+            reply.body::<fdo::StartServiceReply>()?,
+            fdo::StartServiceReply::AlreadyRunning
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn update_activation_environment() {
+        crate::block_on(test_update_activation_environment()).unwrap();
+    }
+
+    async fn test_update_activation_environment() -> fdo::Result<()> {
+        use std::collections::HashMap;
+        use zvariant::Type;
+
+        assert_eq!(HashMap::<&str, &str>::signature(), "a{ss}");
+
+        let conn = crate::Connection::session().await?;
+        let proxy = fdo::DBusProxy::new(&conn).await?;
+        let environment: HashMap<String, String> = [
+            ("ZBUS_TEST_ACTIVATION_ENV", "1"),
+            ("ZBUS_TEST_ACTIVATION_EMPTY", ""),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        proxy
+            .update_activation_environment(
+                environment
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn owned_properties_changed() {
+        crate::block_on(test_owned_properties_changed()).unwrap();
+    }
+
+    async fn test_owned_properties_changed() -> Result<(), Error> {
+        use crate::SignalContext;
+        use std::collections::HashMap;
+        use zvariant::{OwnedValue, Value};
+
+        let service = crate::Connection::session().await?;
+        let client = crate::Connection::session().await?;
+        let proxy = fdo::PropertiesProxy::builder(&client)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/freedesktop/zbus/OwnedPropertiesChanged")?
+            .build()
+            .await?;
+        let mut stream = proxy.receive_properties_changed().await?;
+
+        let ctxt = SignalContext::new(&service, "/org/freedesktop/zbus/OwnedPropertiesChanged")?;
+        let count = Value::from(7u32);
+        let changed = HashMap::from([("Count", &count)]);
+        fdo::Properties::properties_changed(
+            &ctxt,
+            "org.freedesktop.zbus.Counter".try_into()?,
+            &changed,
+            &["Label"],
+        )
+        .await?;
+
+        let signal = stream.next().await.unwrap();
+        let args = signal.owned_args()?;
+        assert_eq!(args.interface, "org.freedesktop.zbus.Counter");
+        assert_eq!(
+            args.changed,
+            HashMap::from([("Count".to_string(), OwnedValue::from(7u32))])
+        );
+        assert_eq!(args.invalidated, vec!["Label".to_string()]);
+        assert_eq!(fdo::OwnedPropertiesChangedArgs::from(signal.args()?), args);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn queued_owners() {
+        crate::block_on(test_queued_owners()).unwrap();
+    }
+
+    async fn test_queued_owners() -> Result<(), Error> {
+        use crate::Connection;
+        use enumflags2::BitFlags;
+
+        let name = WellKnownName::try_from("org.freedesktop.zbus.QueuedOwnersTest")?;
+        let conn1 = Connection::session().await?;
+        conn1.request_name(&name).await?;
+        let conn2 = Connection::session().await?;
+        let reply = conn2
+            .request_name_with_flags(&name, BitFlags::empty())
+            .await?;
+        assert_eq!(reply, fdo::RequestNameReply::InQueue);
+
+        // The primary owner comes first.
+        let proxy = fdo::DBusProxy::new(&conn1).await?;
+        let owners = proxy.list_queued_owners(name.clone()).await?;
+        assert_eq!(
+            owners,
+            vec![
+                conn1.unique_name().unwrap().clone(),
+                conn2.unique_name().unwrap().clone()
+            ]
+        );
+
+        // All the connections are on the same bus.
+        let id = proxy.get_id().await?;
+        assert_eq!(fdo::DBusProxy::new(&conn2).await?.get_id().await?, id);
+
+        Ok(())
+    }
 }
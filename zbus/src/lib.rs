@@ -185,7 +185,9 @@ pub mod blocking;
 #[cfg(feature = "xml")]
 pub mod xml;
 
-pub use zbus_macros::{dbus_interface, dbus_proxy, DBusError};
+#[cfg(feature = "xml")]
+pub use zbus_macros::{assert_proxy_matches_interface, proxy_from_xml};
+pub use zbus_macros::{dbus_interface, dbus_proxy, interface, proxy, DBusError};
 
 // Required for the macros to function within this crate.
 extern crate self as zbus;
@@ -199,6 +201,11 @@ pub mod export {
     pub use ordered_stream;
     pub use serde;
     pub use static_assertions;
+
+    #[cfg(not(feature = "tokio"))]
+    pub use async_lock::RwLock;
+    #[cfg(feature = "tokio")]
+    pub use tokio::sync::RwLock;
 }
 
 pub use zbus_names as names;
@@ -932,6 +939,633 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[timeout(15000)]
+    fn cached_properties_allow_list() {
+        block_on(test_cached_properties_allow_list()).unwrap();
+    }
+
+    async fn test_cached_properties_allow_list() -> Result<()> {
+        // Same as above, but selecting the cached properties instead.
+        #[derive(Default)]
+        struct ServiceAllowListTest(bool);
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.AllowListTest")]
+        impl ServiceAllowListTest {
+            #[dbus_interface(property)]
+            fn stable(&self) -> bool {
+                self.0
+            }
+            #[dbus_interface(property)]
+            fn volatile(&self) -> bool {
+                self.0
+            }
+            async fn set_inner_to_true(&mut self) -> zbus::fdo::Result<()> {
+                self.0 = true;
+                Ok(())
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.AllowListTest",
+            default_service = "org.freedesktop.zbus.AllowListTest",
+            default_path = "/org/freedesktop/zbus/AllowListTest",
+            cached_properties("Stable")
+        )]
+        trait AllowListTest {
+            #[dbus_proxy(property)]
+            fn stable(&self) -> zbus::Result<bool>;
+
+            #[dbus_proxy(property)]
+            fn volatile(&self) -> zbus::Result<bool>;
+
+            fn set_inner_to_true(&self) -> zbus::Result<()>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at(
+                "/org/freedesktop/zbus/AllowListTest",
+                ServiceAllowListTest(false),
+            )?
+            .build()
+            .await?;
+        let dest = service.unique_name().unwrap();
+
+        let client_conn = crate::Connection::session().await?;
+        let client = AllowListTestProxy::builder(&client_conn)
+            .destination(dest)?
+            .build()
+            .await?;
+        assert!(!client.stable().await?);
+        assert!(!client.volatile().await?);
+        assert!(client.cached_stable()?.is_some());
+        assert!(client.cached_volatile()?.is_none());
+
+        client.set_inner_to_true().await?;
+        assert!(!client.stable().await?);
+        assert!(client.volatile().await?);
+
+        // Nothing is cached if caching is disabled, allowed or not.
+        let client = AllowListTestProxy::builder(&client_conn)
+            .destination(dest)?
+            .cache_properties(crate::proxy::CacheProperties::No)
+            .build()
+            .await?;
+        assert!(client.stable().await?);
+        assert!(client.cached_stable()?.is_none());
+
+        // The lists of the builder can be used without the macro too.
+        let client = AllowListTestProxy::builder(&client_conn)
+            .destination(dest)?
+            .cached_properties(&["Volatile"])
+            .build()
+            .await?;
+        assert!(client.volatile().await?);
+        assert!(client.cached_stable()?.is_none());
+        assert!(client.cached_volatile()?.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn batched_properties_changed() {
+        block_on(test_batched_properties_changed()).unwrap();
+    }
+
+    async fn test_batched_properties_changed() -> Result<()> {
+        use futures_util::StreamExt;
+
+        struct Batched {
+            first: u32,
+            second: String,
+        }
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.BatchedTest")]
+        impl Batched {
+            async fn update(
+                &mut self,
+                #[zbus(signal_context)] ctxt: SignalContext<'_>,
+            ) -> zbus::fdo::Result<()> {
+                self.first += 1;
+                self.second.push('!');
+
+                ctxt.properties_changed("org.freedesktop.zbus.BatchedTest")?
+                    .changed("First", self.first)
+                    .changed("Second", &*self.second)
+                    .invalidated("Third")
+                    .emit()
+                    .await?;
+
+                Ok(())
+            }
+
+            #[dbus_interface(property)]
+            fn first(&self) -> u32 {
+                self.first
+            }
+
+            #[dbus_interface(property)]
+            fn second(&self) -> &str {
+                &self.second
+            }
+
+            #[dbus_interface(property)]
+            fn third(&self) -> u32 {
+                self.first * 3
+            }
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at(
+                "/org/freedesktop/zbus/BatchedTest",
+                Batched {
+                    first: 0,
+                    second: String::from("hello"),
+                },
+            )?
+            .build()
+            .await?;
+
+        let client_conn = crate::Connection::session().await?;
+        let props = crate::fdo::PropertiesProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .path("/org/freedesktop/zbus/BatchedTest")?
+            .build()
+            .await?;
+        let mut changes = props.receive_properties_changed().await?;
+
+        client_conn
+            .call_method(
+                Some(service.unique_name().unwrap()),
+                "/org/freedesktop/zbus/BatchedTest",
+                Some("org.freedesktop.zbus.BatchedTest"),
+                "Update",
+                &(),
+            )
+            .await?;
+
+        // All the changes are carried by the same signal.
+        let signal = changes.next().await.unwrap();
+        let args = signal.args()?;
+        assert_eq!(args.interface_name(), "org.freedesktop.zbus.BatchedTest");
+        let changed = args.changed_properties();
+        assert_eq!(changed.len(), 2);
+        assert_eq!(changed["First"], zvariant::Value::from(1u32));
+        assert_eq!(changed["Second"], zvariant::Value::from("hello!"));
+        assert_eq!(args.invalidated_properties(), &["Third"]);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn property_on_other_interface() {
+        block_on(test_property_on_other_interface()).unwrap();
+    }
+
+    async fn test_property_on_other_interface() -> Result<()> {
+        struct Main;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.OtherInterfaceTest.Main")]
+        impl Main {
+            fn ping(&self) {}
+        }
+
+        struct Extra(String);
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.OtherInterfaceTest.Extra")]
+        impl Extra {
+            #[dbus_interface(property)]
+            fn label(&self) -> &str {
+                &self.0
+            }
+
+            #[dbus_interface(property)]
+            fn set_label(&mut self, label: String) {
+                self.0 = label;
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.OtherInterfaceTest.Main",
+            default_path = "/org/freedesktop/zbus/OtherInterfaceTest"
+        )]
+        trait Main {
+            fn ping(&self) -> zbus::Result<()>;
+
+            #[dbus_proxy(property(interface = "org.freedesktop.zbus.OtherInterfaceTest.Extra"))]
+            fn label(&self) -> zbus::Result<String>;
+
+            #[dbus_proxy(property(interface = "org.freedesktop.zbus.OtherInterfaceTest.Extra"))]
+            fn set_label(&self, label: &str) -> zbus::Result<()>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/OtherInterfaceTest", Main)?
+            .serve_at(
+                "/org/freedesktop/zbus/OtherInterfaceTest",
+                Extra(String::from("initial")),
+            )?
+            .build()
+            .await?;
+
+        let client_conn = crate::Connection::session().await?;
+        let client = MainProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+
+        client.ping().await?;
+        assert_eq!(client.label().await?, "initial");
+        client.set_label("updated").await?;
+        assert_eq!(client.label().await?, "updated");
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn array_stream() {
+        block_on(test_array_stream()).unwrap();
+    }
+
+    async fn test_array_stream() -> Result<()> {
+        use futures_util::TryStreamExt;
+
+        struct Inventory;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.ArrayStreamTest")]
+        impl Inventory {
+            fn items(&self, count: u64) -> Vec<(String, u64)> {
+                (0..count).map(|i| (format!("item{i}"), i)).collect()
+            }
+
+            fn names(&self) -> Vec<&str> {
+                vec!["a", "bc", "def"]
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.ArrayStreamTest",
+            default_path = "/org/freedesktop/zbus/ArrayStreamTest"
+        )]
+        trait Inventory {
+            #[dbus_proxy(stream)]
+            fn items(&self, count: u64) -> zbus::Result<Vec<(String, u64)>>;
+
+            #[dbus_proxy(stream)]
+            fn names(&self) -> zbus::fdo::Result<Vec<String>>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/ArrayStreamTest", Inventory)?
+            .build()
+            .await?;
+
+        let client_conn = crate::Connection::session().await?;
+        let client = InventoryProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+
+        for count in [0, 1, 5] {
+            let items: Vec<_> = client.items_stream(count).await?.try_collect().await?;
+            assert_eq!(items, client.items(count).await?);
+        }
+        let names: Vec<_> = client.names_stream().await?.try_collect().await?;
+        assert_eq!(names, ["a", "bc", "def"]);
+
+        let reply = client.items_stream(3).await?.message().clone();
+        let items = crate::blocking::proxy::ArrayIterator::<(String, u64)>::new(reply.clone())?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(items, client.items(3).await?);
+        // The element type must match the signature of the reply.
+        crate::proxy::ArrayStream::<String>::new(reply).unwrap_err();
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn iter_args() {
+        block_on(test_iter_args()).unwrap();
+    }
+
+    async fn test_iter_args() -> Result<()> {
+        struct Summer;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.IterArgsTest")]
+        impl Summer {
+            fn sum(&self, values: Vec<u32>, labels: Vec<String>) -> (u32, String) {
+                (values.iter().sum(), labels.join(","))
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.IterArgsTest",
+            default_path = "/org/freedesktop/zbus/IterArgsTest"
+        )]
+        trait Summer {
+            #[dbus_proxy(iter_args)]
+            fn sum(&self, values: &[u32], labels: &[String]) -> zbus::Result<(u32, String)>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/IterArgsTest", Summer)?
+            .build()
+            .await?;
+
+        let client_conn = crate::Connection::session().await?;
+        let client = SummerProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+
+        let labels = ["a", "b", "c"].map(String::from);
+        let (sum, joined) = client
+            .sum((1..=4).map(|i| i * 10), labels.iter().cloned())
+            .await?;
+        assert_eq!(sum, 100);
+        assert_eq!(joined, "a,b,c");
+        // Collections work too and so do empty iterators.
+        assert_eq!(
+            client.sum(vec![1, 2], std::iter::empty()).await?,
+            (3, String::new())
+        );
+        assert_eq!(
+            client.sum([5u32; 3], Some(String::from("x"))).await?,
+            (15, String::from("x"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn interfaceless_method_call() {
+        block_on(test_interfaceless_method_call()).unwrap();
+    }
+
+    async fn test_interfaceless_method_call() -> Result<()> {
+        struct First;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.InterfacelessTest.First")]
+        impl First {
+            fn first(&self, #[zbus(header)] header: crate::message::Header<'_>) -> bool {
+                header.interface().unwrap().is_none()
+            }
+
+            fn common(&self) {}
+        }
+
+        struct Second;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.InterfacelessTest.Second")]
+        impl Second {
+            fn common(&self) {}
+        }
+
+        let path = "/org/freedesktop/zbus/InterfacelessTest";
+        let service = crate::connection::Builder::session()?
+            .serve_at(path, First)?
+            .serve_at(path, Second)?
+            .build()
+            .await?;
+        let dest = service.unique_name().unwrap();
+
+        let client = crate::Connection::session().await?;
+        let reply = client
+            .call_method(Some(dest), path, None::<&str>, "First", &())
+            .await?;
+        assert!(reply.body::<bool>()?);
+        // Standard interfaces are looked up as well.
+        client
+            .call_method(Some(dest), path, None::<&str>, "Ping", &())
+            .await?;
+
+        let e = client
+            .call_method(Some(dest), path, None::<&str>, "Common", &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            crate::fdo::Error::from(e),
+            crate::fdo::Error::Failed(msg) if msg.starts_with("Ambiguous method 'Common'")
+        ));
+        let e = client
+            .call_method(Some(dest), path, None::<&str>, "Missing", &())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            crate::fdo::Error::from(e),
+            crate::fdo::Error::UnknownMethod(_)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn object_server_at_prefix() {
+        block_on(test_object_server_at_prefix()).unwrap();
+    }
+
+    async fn test_object_server_at_prefix() -> Result<()> {
+        struct PrefixTest(&'static str);
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.PrefixTest")]
+        impl PrefixTest {
+            fn whoami(&self, #[zbus(header)] header: crate::message::Header<'_>) -> String {
+                format!("{} {}", self.0, header.path().unwrap().unwrap())
+            }
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/Prefix/exact", PrefixTest("exact"))?
+            .build()
+            .await?;
+        let server = service.object_server();
+        assert!(
+            server
+                .at_prefix("/org/freedesktop/zbus/Prefix", PrefixTest("prefix"))
+                .await?
+        );
+        assert!(
+            server
+                .at_prefix("/org/freedesktop/zbus/Prefix/nested", PrefixTest("nested"))
+                .await?
+        );
+        assert!(
+            !server
+                .at_prefix("/org/freedesktop/zbus/Prefix", PrefixTest("again"))
+                .await?
+        );
+
+        let dest = service.unique_name().unwrap();
+        let client_conn = crate::Connection::session().await?;
+        let whoami = |path: &'static str| {
+            let client_conn = client_conn.clone();
+            async move {
+                let proxy =
+                    crate::Proxy::new(&client_conn, dest, path, "org.freedesktop.zbus.PrefixTest")
+                        .await?;
+                proxy.call::<_, _, String>("Whoami", &()).await
+            }
+        };
+
+        assert_eq!(
+            whoami("/org/freedesktop/zbus/Prefix").await?,
+            "prefix /org/freedesktop/zbus/Prefix"
+        );
+        assert_eq!(
+            whoami("/org/freedesktop/zbus/Prefix/dynamic/path").await?,
+            "prefix /org/freedesktop/zbus/Prefix/dynamic/path"
+        );
+        assert_eq!(
+            whoami("/org/freedesktop/zbus/Prefix/exact").await?,
+            "exact /org/freedesktop/zbus/Prefix/exact"
+        );
+        assert_eq!(
+            whoami("/org/freedesktop/zbus/Prefix/exact/child").await?,
+            "prefix /org/freedesktop/zbus/Prefix/exact/child"
+        );
+        assert_eq!(
+            whoami("/org/freedesktop/zbus/Prefix/nested/child").await?,
+            "nested /org/freedesktop/zbus/Prefix/nested/child"
+        );
+        assert!(whoami("/org/freedesktop/zbus/Other").await.is_err());
+
+        // Removing a prefix registration falls back to the next closest prefix, if any.
+        assert!(
+            server
+                .remove_prefix::<PrefixTest, _>("/org/freedesktop/zbus/Prefix/nested")
+                .await?
+        );
+        assert_eq!(
+            whoami("/org/freedesktop/zbus/Prefix/nested/child").await?,
+            "prefix /org/freedesktop/zbus/Prefix/nested/child"
+        );
+        assert!(matches!(
+            server
+                .remove_prefix::<PrefixTest, _>("/org/freedesktop/zbus/Prefix/nested")
+                .await,
+            Err(crate::Error::InterfaceNotFound)
+        ));
+        // The exact registrations are kept, and so is the node they live under.
+        assert!(
+            !server
+                .remove_prefix::<PrefixTest, _>("/org/freedesktop/zbus/Prefix")
+                .await?
+        );
+        assert!(whoami("/org/freedesktop/zbus/Prefix/dynamic/path")
+            .await
+            .is_err());
+        assert_eq!(
+            whoami("/org/freedesktop/zbus/Prefix/exact").await?,
+            "exact /org/freedesktop/zbus/Prefix/exact"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn object_server_replace() {
+        block_on(test_object_server_replace()).unwrap();
+    }
+
+    async fn test_object_server_replace() -> Result<()> {
+        #[derive(Debug, PartialEq)]
+        struct ReplaceTest(u32);
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.ReplaceTest")]
+        impl ReplaceTest {
+            fn version(&self) -> u32 {
+                self.0
+            }
+        }
+
+        let path = "/org/freedesktop/zbus/Replace";
+        let service = crate::connection::Builder::session()?
+            .serve_at(path, ReplaceTest(1))?
+            .build()
+            .await?;
+        let server = service.object_server();
+        let iface_ref = server.interface::<_, ReplaceTest>(path).await?;
+
+        let client_conn = crate::Connection::session().await?;
+        let proxy = crate::Proxy::new(
+            &client_conn,
+            service.unique_name().unwrap(),
+            path,
+            "org.freedesktop.zbus.ReplaceTest",
+        )
+        .await?;
+        assert_eq!(proxy.call::<_, _, u32>("Version", &()).await?, 1);
+
+        assert_eq!(server.replace(path, ReplaceTest(2)).await?, ReplaceTest(1));
+        assert_eq!(proxy.call::<_, _, u32>("Version", &()).await?, 2);
+        assert_eq!(*iface_ref.get().await, ReplaceTest(2));
+
+        assert!(matches!(
+            server
+                .replace("/org/freedesktop/zbus/Replace/None", ReplaceTest(3))
+                .await,
+            Err(crate::Error::InterfaceNotFound)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn object_server_next_call() {
+        block_on(test_object_server_next_call()).unwrap();
+    }
+
+    async fn test_object_server_next_call() -> Result<()> {
+        struct NextCallTest;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.NextCallTest")]
+        impl NextCallTest {
+            fn answer(&self) -> u32 {
+                1
+            }
+        }
+
+        let path = "/org/freedesktop/zbus/NextCallTest";
+        let service = crate::connection::Builder::session()?
+            .serve_at(path, NextCallTest)?
+            .build()
+            .await?;
+        let iface_ref = service
+            .object_server()
+            .interface::<_, NextCallTest>(path)
+            .await?;
+
+        let client_conn = crate::Connection::session().await?;
+        let proxy = crate::Proxy::new(
+            &client_conn,
+            service.unique_name().unwrap(),
+            path,
+            "org.freedesktop.zbus.NextCallTest",
+        )
+        .await?;
+
+        let intercepted = async {
+            let call = iface_ref.next_call("Answer").await?;
+            assert_eq!(call.member().unwrap(), "Answer");
+            service.reply(&call, &42u32).await?;
+
+            Ok::<_, crate::Error>(())
+        };
+        let (intercepted, answer) =
+            futures_util::future::join(intercepted, proxy.call::<_, _, u32>("Answer", &())).await;
+        intercepted?;
+        assert_eq!(answer?, 42);
+
+        // Normal dispatch resumes afterwards.
+        assert_eq!(proxy.call::<_, _, u32>("Answer", &()).await?, 1);
+
+        // Only one task can wait for calls to the same method.
+        let mut first = Box::pin(iface_ref.next_call("Answer"));
+        assert!(futures_util::poll!(first.as_mut()).is_pending());
+        assert!(iface_ref.next_call("Answer").await.is_err());
+        drop(first);
+        assert_eq!(proxy.call::<_, _, u32>("Answer", &()).await?, 1);
+
+        Ok(())
+    }
+
     #[test]
     #[timeout(15000)]
     fn issue_260() {
@@ -1107,4 +1741,2065 @@ mod tests {
             event.notify(1);
         }
     }
+
+    #[test]
+    #[timeout(15000)]
+    fn request_name_full() {
+        block_on(test_request_name_full()).unwrap();
+    }
+
+    async fn test_request_name_full() -> Result<()> {
+        use crate::fdo::ReleaseNameReply;
+
+        let name = "org.freedesktop.zbus.RequestNameFull";
+        let conn1 = Connection::session().await?;
+        let conn2 = Connection::session().await?;
+
+        let reply = conn1
+            .request_name_full(name, RequestNameFlags::DoNotQueue.into())
+            .await?;
+        assert_eq!(reply, RequestNameReply::PrimaryOwner);
+        let reply = conn1.request_name_full(name, BitFlags::empty()).await?;
+        assert_eq!(reply, RequestNameReply::AlreadyOwner);
+
+        // Being refused the name is not an error.
+        let reply = conn2
+            .request_name_full(name, RequestNameFlags::DoNotQueue.into())
+            .await?;
+        assert_eq!(reply, RequestNameReply::Exists);
+        // ..unless it's requested through the less detailed API.
+        assert!(matches!(
+            conn2
+                .request_name_with_flags(name, RequestNameFlags::DoNotQueue.into())
+                .await,
+            Err(crate::Error::NameTaken)
+        ));
+        let reply = conn2.request_name_full(name, BitFlags::empty()).await?;
+        assert_eq!(reply, RequestNameReply::InQueue);
+
+        assert_eq!(
+            conn1.release_name_full(name).await?,
+            ReleaseNameReply::Released
+        );
+        assert_eq!(
+            conn1.release_name_full(name).await?,
+            ReleaseNameReply::NotOwner
+        );
+        assert_eq!(
+            conn2.release_name_full(name).await?,
+            ReleaseNameReply::Released
+        );
+        assert_eq!(
+            conn2.release_name_full(name).await?,
+            ReleaseNameReply::NonExistent
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn borrowed_replies() {
+        block_on(test_borrowed_replies()).unwrap();
+    }
+
+    async fn test_borrowed_replies() -> Result<()> {
+        use std::borrow::Cow;
+
+        struct Library;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.BorrowedRepliesTest")]
+        impl Library {
+            fn title(&self) -> &str {
+                "The Hitchhiker's Guide to the Galaxy"
+            }
+
+            fn authors(&self) -> Vec<&str> {
+                vec!["Douglas Adams"]
+            }
+
+            fn cover(&self) -> Vec<u8> {
+                vec![4, 2]
+            }
+
+            fn pages(&self) -> u32 {
+                224
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.BorrowedRepliesTest",
+            default_path = "/org/freedesktop/zbus/BorrowedRepliesTest",
+            borrowed_replies
+        )]
+        trait Library {
+            fn title(&self) -> zbus::Result<Cow<'_, str>>;
+
+            fn authors(&self) -> zbus::fdo::Result<Vec<&str>>;
+
+            fn cover(&self) -> zbus::Result<Cow<'_, [u8]>>;
+
+            // Owned return types are not affected.
+            fn pages(&self) -> zbus::Result<u32>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/BorrowedRepliesTest", Library)?
+            .build()
+            .await?;
+
+        let client_conn = crate::Connection::session().await?;
+        let client = LibraryProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+
+        let reply: TitleReply = client.title().await?;
+        let title = reply.body()?;
+        assert!(matches!(title, Cow::Borrowed(_)));
+        assert_eq!(title, "The Hitchhiker's Guide to the Galaxy");
+        assert_eq!(reply.message().body_signature()?, "s");
+
+        let reply = client.authors().await?;
+        assert_eq!(reply.body()?, ["Douglas Adams"]);
+
+        let reply = client.cover().await?;
+        let cover = reply.body()?;
+        assert!(matches!(cover, Cow::Borrowed(_)));
+        assert_eq!(&*cover, [4, 2]);
+
+        assert_eq!(client.pages().await?, 224);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn receive_all_signals() {
+        block_on(test_receive_all_signals()).unwrap();
+    }
+
+    async fn test_receive_all_signals() -> Result<()> {
+        use futures_util::TryStreamExt;
+
+        let service = Connection::session().await?;
+        let client = Connection::session().await?;
+        let rule = crate::MatchRule::builder()
+            .sender(service.unique_name().unwrap())?
+            .path("/org/freedesktop/zbus/AllSignals")?
+            .build();
+        let mut stream = client.receive_all_signals(rule).await?;
+        assert_eq!(
+            stream.match_rule().unwrap().msg_type(),
+            Some(crate::message::Type::Signal)
+        );
+
+        service
+            .emit_signal(
+                None::<()>,
+                "/org/freedesktop/zbus/Elsewhere",
+                "org.freedesktop.zbus.AllSignals1",
+                "Ignored",
+                &(),
+            )
+            .await?;
+        for (iface, member) in [
+            ("org.freedesktop.zbus.AllSignals1", "First"),
+            ("org.freedesktop.zbus.AllSignals2", "Second"),
+        ] {
+            service
+                .emit_signal(
+                    None::<()>,
+                    "/org/freedesktop/zbus/AllSignals",
+                    iface,
+                    member,
+                    &(),
+                )
+                .await?;
+        }
+        for (iface, member) in [
+            ("org.freedesktop.zbus.AllSignals1", "First"),
+            ("org.freedesktop.zbus.AllSignals2", "Second"),
+        ] {
+            let msg = stream.try_next().await?.unwrap();
+            assert_eq!(msg.interface().unwrap(), iface);
+            assert_eq!(msg.member().unwrap(), member);
+        }
+
+        let rule = crate::MatchRule::builder()
+            .msg_type(crate::message::Type::MethodCall)
+            .build();
+        assert!(matches!(
+            client.receive_all_signals(rule).await,
+            Err(crate::Error::InvalidMatchRule)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn active_match_rules() {
+        block_on(test_active_match_rules()).unwrap();
+    }
+
+    async fn test_active_match_rules() -> Result<()> {
+        use crate::AsyncDrop;
+
+        let conn = Connection::session().await?;
+        let before = conn.active_match_rules().await;
+        let rule = crate::MatchRule::builder()
+            .msg_type(crate::message::Type::Signal)
+            .interface("org.freedesktop.zbus.ActiveMatchRules")?
+            .build();
+        let rule_str = rule.to_string();
+        assert!(!before.contains(&rule_str));
+
+        // A rule is only listed once, however many streams use it.
+        let first = crate::MessageStream::for_match_rule(rule.clone(), &conn, None).await?;
+        let second = crate::MessageStream::for_match_rule(rule, &conn, None).await?;
+        let rules = conn.active_match_rules().await;
+        assert_eq!(rules.iter().filter(|r| **r == rule_str).count(), 1);
+        assert_eq!(rules.len(), before.len() + 1);
+
+        first.async_drop().await;
+        assert!(conn.active_match_rules().await.contains(&rule_str));
+        second.async_drop().await;
+        assert_eq!(conn.active_match_rules().await, before);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn relay_signal() {
+        block_on(test_relay_signal()).unwrap();
+    }
+
+    async fn test_relay_signal() -> Result<()> {
+        use futures_util::TryStreamExt;
+
+        let service = Connection::session().await?;
+        let relay = Connection::session().await?;
+        let client = Connection::session().await?;
+
+        let rule = crate::MatchRule::builder()
+            .sender(service.unique_name().unwrap())?
+            .build();
+        let mut relay_stream = relay.receive_all_signals(rule).await?;
+        let rule = crate::MatchRule::builder()
+            .sender(relay.unique_name().unwrap())?
+            .path("/org/freedesktop/zbus/Relayed")?
+            .build();
+        let mut client_stream = client.receive_all_signals(rule).await?;
+
+        service
+            .emit_signal(
+                None::<()>,
+                "/org/freedesktop/zbus/Relay",
+                "org.freedesktop.zbus.Relay",
+                "Changed",
+                &("relayed", 42u32),
+            )
+            .await?;
+        let signal = relay_stream.try_next().await?.unwrap();
+        let ctxt = SignalContext::new(&relay, "/org/freedesktop/zbus/Relayed")?;
+        ctxt.relay(&signal).await?;
+
+        let relayed = client_stream.try_next().await?.unwrap();
+        assert_eq!(relayed.interface().unwrap(), "org.freedesktop.zbus.Relay");
+        assert_eq!(relayed.member().unwrap(), "Changed");
+        assert_eq!(relayed.body_signature()?, "su");
+        assert_eq!(relayed.body::<(&str, u32)>()?, ("relayed", 42));
+
+        // A body serialized by hand.
+        let body = crate::message::Builder::signal("/", "org.freedesktop.zbus.Relay", "Raw")?
+            .build(&(7u32,))?;
+        unsafe {
+            ctxt.emit_raw(
+                "org.freedesktop.zbus.Relay",
+                "Raw",
+                body.body_as_bytes()?,
+                "u",
+                #[cfg(unix)]
+                vec![],
+            )
+            .await?;
+        }
+        let relayed = client_stream.try_next().await?.unwrap();
+        assert_eq!(relayed.member().unwrap(), "Raw");
+        assert_eq!(relayed.body::<u32>()?, 7);
+
+        let call = Message::method(None::<()>, None::<()>, "/", None::<()>, "Relay", &())?;
+        assert!(matches!(
+            ctxt.relay(&call).await,
+            Err(crate::Error::InvalidField)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn reply_routing() {
+        block_on(test_reply_routing()).unwrap();
+    }
+
+    async fn test_reply_routing() -> Result<()> {
+        use futures_util::TryStreamExt;
+
+        struct Service;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.ReplyRouting")]
+        impl Service {
+            fn direct(&self) -> u32 {
+                1
+            }
+
+            fn routed(&self) -> ((u32, u32), (u32, u32)) {
+                ((1, 2), (3, 4))
+            }
+
+            fn routed_error(&self) -> crate::fdo::Result<()> {
+                Err(crate::fdo::Error::Failed("routed".into()))
+            }
+        }
+
+        let router = Connection::session().await?;
+        let router_name = router.unique_name().unwrap().clone();
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/ReplyRouting", Service)?
+            .reply_routing(move |call, reply| match call.member()? {
+                Some(member) if member.starts_with("Routed") => {
+                    reply.destination(router_name.clone().into_inner())
+                }
+                _ => Ok(reply),
+            })
+            .build()
+            .await?;
+        let mut router_stream = crate::MessageStream::from(&router);
+
+        let client = Connection::session().await?;
+        // By default, replies go back to the caller.
+        let reply = client
+            .call_method(
+                service.unique_name(),
+                "/org/freedesktop/zbus/ReplyRouting",
+                Some("org.freedesktop.zbus.ReplyRouting"),
+                "Direct",
+                &(),
+            )
+            .await?;
+        assert_eq!(reply.body::<u32>()?, 1);
+
+        for method in ["Routed", "RoutedError"] {
+            let call =
+                crate::message::Builder::method_call("/org/freedesktop/zbus/ReplyRouting", method)?
+                    .interface("org.freedesktop.zbus.ReplyRouting")?
+                    .destination(service.unique_name().unwrap())?
+                    .build(&())?;
+            let serial = client.send_message(call).await?;
+
+            let reply = loop {
+                let msg = router_stream.try_next().await?.unwrap();
+                if msg.reply_serial() == Some(serial) {
+                    break msg;
+                }
+            };
+            assert_eq!(
+                reply.header()?.sender()?.unwrap(),
+                service.unique_name().unwrap()
+            );
+            if method == "Routed" {
+                assert_eq!(reply.message_type(), crate::message::Type::MethodReturn);
+                assert_eq!(reply.body_signature()?, "(uu)(uu)");
+                assert_eq!(reply.body::<((u32, u32), (u32, u32))>()?, ((1, 2), (3, 4)));
+            } else {
+                assert_eq!(reply.message_type(), crate::message::Type::Error);
+                assert_eq!(
+                    reply.header()?.error_name()?.unwrap(),
+                    "org.freedesktop.DBus.Error.Failed"
+                );
+                assert_eq!(reply.body::<&str>()?, "routed");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn object_server_introspect() {
+        block_on(test_object_server_introspect()).unwrap();
+    }
+
+    async fn test_object_server_introspect() -> Result<()> {
+        struct Iface;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.ServerIntrospect")]
+        impl Iface {
+            fn ping(&self, count: u32) -> u32 {
+                count
+            }
+
+            #[dbus_interface(property)]
+            fn level(&self) -> u8 {
+                1
+            }
+        }
+
+        let service = Connection::session().await?;
+        service
+            .object_server()
+            .at("/org/freedesktop/zbus/ServerIntrospect/child", Iface)
+            .await?;
+        let client = Connection::session().await?;
+
+        for path in [
+            "/",
+            "/org/freedesktop/zbus/ServerIntrospect",
+            "/org/freedesktop/zbus/ServerIntrospect/child",
+        ] {
+            let local = service.object_server().introspect(path).await?;
+            let remote = crate::fdo::IntrospectableProxy::builder(&client)
+                .destination(service.unique_name().unwrap())?
+                .path(path)?
+                .build()
+                .await?
+                .introspect()
+                .await?;
+            assert_eq!(local, remote);
+        }
+        let xml = service
+            .object_server()
+            .introspect("/org/freedesktop/zbus/ServerIntrospect/child")
+            .await?;
+        assert!(xml.contains(r#"<interface name="org.freedesktop.zbus.ServerIntrospect">"#));
+
+        match service.object_server().introspect("/does/not/exist").await {
+            Err(crate::Error::FDO(e)) => {
+                assert!(matches!(*e, crate::fdo::Error::UnknownObject(_)))
+            }
+            res => panic!("unexpected result: {res:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn per_sender_reply_ordering() {
+        block_on(test_per_sender_reply_ordering()).unwrap();
+    }
+
+    async fn test_per_sender_reply_ordering() -> Result<()> {
+        use crate::connection::ReplyOrdering;
+        use futures_util::TryStreamExt;
+
+        struct Slow;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.ReplyOrdering")]
+        impl Slow {
+            async fn delayed(&self, millis: u64) -> u64 {
+                crate::abstractions::timer::sleep(std::time::Duration::from_millis(millis)).await;
+
+                millis
+            }
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/ReplyOrdering", Slow)?
+            .reply_ordering(ReplyOrdering::PerSender)
+            .build()
+            .await?;
+        assert_eq!(service.reply_ordering(), ReplyOrdering::PerSender);
+
+        let client = Connection::session().await?;
+        assert_eq!(client.reply_ordering(), ReplyOrdering::Completion);
+        let mut stream = crate::MessageStream::from(&client);
+
+        // The later calls are faster, so their replies would come first if dispatched concurrently.
+        let delays = [400u64, 300, 200, 100, 0];
+        let mut serials = vec![];
+        for millis in delays {
+            let call = crate::message::Builder::method_call(
+                "/org/freedesktop/zbus/ReplyOrdering",
+                "Delayed",
+            )?
+            .interface("org.freedesktop.zbus.ReplyOrdering")?
+            .destination(service.unique_name().unwrap())?
+            .build(&millis)?;
+            serials.push(client.send_message(call).await?);
+        }
+
+        let mut replies = vec![];
+        while replies.len() < delays.len() {
+            let msg = stream.try_next().await?.unwrap();
+            if let Some(serial) = msg.reply_serial() {
+                if serials.contains(&serial) {
+                    replies.push((serial, msg.body::<u64>()?));
+                }
+            }
+        }
+        let expected: Vec<_> = serials.into_iter().zip(delays).collect();
+        assert_eq!(replies, expected);
+
+        // Only up to `max_queued` calls of a sender wait for its earlier ones.
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/ReplyOrdering", Slow)?
+            .reply_ordering(ReplyOrdering::PerSender)
+            .max_queued(2)
+            .build()
+            .await?;
+        let mut serials = vec![];
+        for millis in [200u64, 0, 0, 0] {
+            let call = crate::message::Builder::method_call(
+                "/org/freedesktop/zbus/ReplyOrdering",
+                "Delayed",
+            )?
+            .interface("org.freedesktop.zbus.ReplyOrdering")?
+            .destination(service.unique_name().unwrap())?
+            .build(&millis)?;
+            serials.push(client.send_message(call).await?);
+        }
+        let mut replies = vec![];
+        while replies.len() < serials.len() {
+            let msg = stream.try_next().await?.unwrap();
+            if let Some(serial) = msg.reply_serial() {
+                if serials.contains(&serial) {
+                    let error_name = msg.header()?.error_name()?.map(|e| e.to_string());
+                    replies.push((serial, error_name));
+                }
+            }
+        }
+        // The rejected call is replied to right away.
+        let limits_exceeded = Some(String::from("org.freedesktop.DBus.Error.LimitsExceeded"));
+        let expected = vec![
+            (serials[3], limits_exceeded),
+            (serials[0], None),
+            (serials[1], None),
+            (serials[2], None),
+        ];
+        assert_eq!(replies, expected);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn raw_reply() {
+        block_on(test_raw_reply()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_raw_reply() -> Result<()> {
+        use std::os::unix::io::IntoRawFd;
+        use zvariant::OwnedFd;
+
+        struct Opener;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.RawReplyTest")]
+        impl Opener {
+            fn open(&self, label: &str) -> (String, OwnedFd) {
+                let file = File::open("/dev/null").unwrap();
+
+                (label.to_uppercase(), unsafe {
+                    OwnedFd::from_raw_fd(file.into_raw_fd())
+                })
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.RawReplyTest",
+            default_path = "/org/freedesktop/zbus/RawReplyTest"
+        )]
+        trait Opener {
+            #[dbus_proxy(raw_reply)]
+            fn open(&self, label: &str) -> zbus::fdo::Result<(String, OwnedFd)>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/RawReplyTest", Opener)?
+            .build()
+            .await?;
+
+        let client_conn = crate::Connection::session().await?;
+        let client = OpenerProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+
+        let reply = client.open_raw("file").await?;
+        assert_eq!(
+            reply.header()?.sender()?.unwrap(),
+            service.unique_name().unwrap()
+        );
+        assert_eq!(reply.body_signature()?, "sh");
+        let (label, fd) = reply.body::<(String, Fd)>()?;
+        assert_eq!(label, "FILE");
+        let fds = reply.take_fds();
+        assert_eq!(fds.len(), 1);
+        assert_eq!(fds[0].as_raw_fd(), fd.as_raw_fd());
+        let file = unsafe { File::from_raw_fd(fds.into_iter().next().unwrap().into_raw_fd()) };
+        file.metadata().unwrap();
+
+        // The typed method is still generated.
+        assert_eq!(client.open("typed").await?.0, "TYPED");
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn unknown_object_handler() {
+        block_on(test_unknown_object_handler()).unwrap();
+    }
+
+    async fn test_unknown_object_handler() -> Result<()> {
+        use crate::{fdo, object_server::UnknownObjectAction, Error};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Item;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.LazyItem")]
+        impl Item {
+            fn name(&self) -> &str {
+                "lazy"
+            }
+
+            fn missing_child(&self) -> fdo::Result<()> {
+                Err(fdo::Error::UnknownObject("No such child".into()))
+            }
+        }
+
+        let service = Connection::session().await?;
+        let handled = Arc::new(AtomicUsize::new(0));
+        let handler_handled = handled.clone();
+        service
+            .object_server()
+            .set_unknown_object_handler(move |conn, msg| {
+                handler_handled.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    let path = msg.path().unwrap();
+                    match path.as_str() {
+                        "/org/freedesktop/zbus/Lazy" => {
+                            conn.object_server()
+                                .at(path, Item)
+                                .await
+                                .map_err(|e| fdo::Error::Failed(e.to_string()))?;
+
+                            Ok(UnknownObjectAction::Retry)
+                        }
+                        "/org/freedesktop/zbus/Replied" => {
+                            conn.reply(&msg, &"replied")
+                                .await
+                                .map_err(|e| fdo::Error::Failed(e.to_string()))?;
+
+                            Ok(UnknownObjectAction::Replied)
+                        }
+                        // Claims to register something but doesn't.
+                        "/org/freedesktop/zbus/Liar" => Ok(UnknownObjectAction::Retry),
+                        _ => Err(fdo::Error::AccessDenied(format!("No access to '{path}'"))),
+                    }
+                }
+            });
+        let client = Connection::session().await?;
+        let call = |path: &'static str| {
+            let client = client.clone();
+            let destination = service.unique_name().unwrap().to_owned();
+            async move {
+                let reply = client
+                    .call_method(
+                        Some(destination),
+                        path,
+                        Some("org.freedesktop.zbus.LazyItem"),
+                        "Name",
+                        &(),
+                    )
+                    .await?;
+
+                reply.body::<String>()
+            }
+        };
+
+        let error_name = |err: Error| match err {
+            Error::MethodError(name, _, _) => name.to_string(),
+            e => panic!("unexpected error: {e}"),
+        };
+
+        assert_eq!(call("/org/freedesktop/zbus/Lazy").await?, "lazy");
+        assert!(service
+            .object_server()
+            .interface::<_, Item>("/org/freedesktop/zbus/Lazy")
+            .await
+            .is_ok());
+        assert_eq!(call("/org/freedesktop/zbus/Replied").await?, "replied");
+        assert_eq!(
+            error_name(call("/org/freedesktop/zbus/Liar").await.unwrap_err()),
+            "org.freedesktop.DBus.Error.UnknownObject"
+        );
+        assert_eq!(
+            error_name(call("/org/freedesktop/zbus/Other").await.unwrap_err()),
+            "org.freedesktop.DBus.Error.AccessDenied"
+        );
+
+        // Unknown interfaces of existing objects are left alone.
+        let err = client
+            .call_method(
+                service.unique_name(),
+                "/org/freedesktop/zbus/Lazy",
+                Some("org.freedesktop.zbus.Unknown"),
+                "Name",
+                &(),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error_name(err),
+            "org.freedesktop.DBus.Error.UnknownInterface"
+        );
+
+        // Nor are the `UnknownObject` errors of the methods of existing objects.
+        let handled_before = handled.load(Ordering::SeqCst);
+        let err = client
+            .call_method(
+                service.unique_name(),
+                "/org/freedesktop/zbus/Lazy",
+                Some("org.freedesktop.zbus.LazyItem"),
+                "MissingChild",
+                &(),
+            )
+            .await
+            .unwrap_err();
+        match err {
+            Error::MethodError(name, Some(detail), _) => {
+                assert_eq!(name, "org.freedesktop.DBus.Error.UnknownObject");
+                assert_eq!(detail, "No such child");
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+        assert_eq!(handled.load(Ordering::SeqCst), handled_before);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn struct_return() {
+        block_on(test_struct_return()).unwrap();
+    }
+
+    async fn test_struct_return() -> Result<()> {
+        use serde::Deserialize;
+        use zvariant::Type;
+
+        struct Pairs;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.StructReturn")]
+        impl Pairs {
+            fn pair_output(&self) -> ((u32, String),) {
+                ((42, "answer".into()),)
+            }
+
+            fn many_output(&self) -> (u32, String) {
+                (7, "seven".into())
+            }
+        }
+
+        #[derive(Debug, PartialEq, Deserialize, Type)]
+        struct Pair {
+            count: u32,
+            label: String,
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.StructReturn",
+            default_path = "/org/freedesktop/zbus/StructReturn"
+        )]
+        trait Pairs {
+            // A single `(us)` output argument.
+            fn pair_output(&self) -> zbus::Result<Pair>;
+
+            // `u` and `s` output arguments.
+            fn many_output(&self) -> zbus::Result<Pair>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/StructReturn", Pairs)?
+            .build()
+            .await?;
+        let client = Connection::session().await?;
+        let proxy = PairsProxy::builder(&client)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+
+        assert_eq!(
+            proxy.pair_output().await?,
+            Pair {
+                count: 42,
+                label: "answer".into(),
+            }
+        );
+        assert_eq!(
+            proxy.many_output().await?,
+            Pair {
+                count: 7,
+                label: "seven".into(),
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    #[cfg(unix)]
+    fn caller_credentials() {
+        block_on(test_caller_credentials()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_caller_credentials() -> zbus::Result<()> {
+        use crate::{connection, dbus_interface, dbus_proxy, fdo::ConnectionCredentials};
+
+        struct WhoamiService;
+
+        #[dbus_interface(name = "org.freedesktop.zbus.Whoami")]
+        impl WhoamiService {
+            fn whoami(&self, #[zbus(credentials)] creds: ConnectionCredentials) -> (u32, u32) {
+                (creds.unix_user_id().unwrap(), creds.process_id().unwrap())
+            }
+        }
+
+        #[dbus_proxy(
+            interface = "org.freedesktop.zbus.Whoami",
+            default_service = "org.freedesktop.zbus.WhoamiService",
+            default_path = "/org/freedesktop/zbus/Whoami"
+        )]
+        trait Whoami {
+            fn whoami(&self) -> zbus::Result<(u32, u32)>;
+        }
+
+        let _service = connection::Builder::session()?
+            .name("org.freedesktop.zbus.WhoamiService")?
+            .serve_at("/org/freedesktop/zbus/Whoami", WhoamiService)?
+            .build()
+            .await?;
+        let client = Connection::session().await?;
+        let proxy = WhoamiProxy::new(&client).await?;
+
+        let expected = (nix::unistd::getuid().as_raw(), std::process::id());
+        assert_eq!(proxy.whoami().await?, expected);
+        // The second time, the credentials come from the cache.
+        assert_eq!(proxy.whoami().await?, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn managed_properties() {
+        block_on(test_managed_properties()).unwrap();
+    }
+
+    async fn test_managed_properties() -> zbus::Result<()> {
+        use crate::{connection, dbus_interface, fdo};
+        use futures_util::StreamExt;
+
+        struct Sensor;
+
+        #[dbus_interface(name = "org.freedesktop.zbus.Sensor", managed_properties("Name"))]
+        impl Sensor {
+            #[dbus_interface(property)]
+            fn name(&self) -> &str {
+                "thermometer"
+            }
+
+            // Imagine this is expensive to read.
+            #[dbus_interface(property)]
+            fn reading(&self) -> f64 {
+                21.5
+            }
+        }
+
+        let service = connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/Sensors", fdo::ObjectManager)?
+            .build()
+            .await?;
+        let client = Connection::session().await?;
+        let manager = fdo::ObjectManagerProxy::builder(&client)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/freedesktop/zbus/Sensors")?
+            .build()
+            .await?;
+        let mut added = manager.receive_interfaces_added().await?;
+
+        service
+            .object_server()
+            .at("/org/freedesktop/zbus/Sensors/0", Sensor)
+            .await?;
+        let signal = added.next().await.unwrap();
+        let args = signal.args()?;
+        let props = &args.interfaces_and_properties()["org.freedesktop.zbus.Sensor"];
+        assert_eq!(props.keys().collect::<Vec<_>>(), [&"Name"]);
+
+        let objects = manager.get_managed_objects().await?;
+        let path = "/org/freedesktop/zbus/Sensors/0".try_into()?;
+        let iface: crate::names::OwnedInterfaceName = "org.freedesktop.zbus.Sensor".try_into()?;
+        let props = &objects[&path][&iface];
+        assert_eq!(props.keys().collect::<Vec<_>>(), ["Name"]);
+
+        // All the properties are still available through the `Properties` interface.
+        let props = fdo::PropertiesProxy::builder(&client)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .path("/org/freedesktop/zbus/Sensors/0")?
+            .build()
+            .await?
+            .get_all(iface.as_ref())
+            .await?;
+        assert_eq!(props.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn wait_for_signal() {
+        block_on(test_wait_for_signal()).unwrap();
+    }
+
+    async fn test_wait_for_signal() -> zbus::Result<()> {
+        use crate::dbus_proxy;
+        use std::time::Duration;
+
+        #[dbus_proxy(
+            interface = "org.freedesktop.zbus.Clock",
+            default_path = "/org/freedesktop/zbus/Clock"
+        )]
+        trait Clock {
+            #[dbus_proxy(signal)]
+            fn tick(&self, count: u32) -> zbus::Result<()>;
+        }
+
+        let service = Connection::session().await?;
+        let client = Connection::session().await?;
+        let proxy = ClockProxy::builder(&client)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .build()
+            .await?;
+        let ticks = proxy.receive_tick().await?;
+
+        for count in 0..5u32 {
+            service
+                .emit_signal(
+                    None::<()>,
+                    "/org/freedesktop/zbus/Clock",
+                    "org.freedesktop.zbus.Clock",
+                    "Tick",
+                    &count,
+                )
+                .await?;
+        }
+        let tick = ticks
+            .wait_for(|tick| tick.args().map_or(false, |args| args.count == 3))
+            .await
+            .unwrap();
+        assert_eq!(tick.args()?.count, 3);
+
+        let ticks = proxy.receive_tick().await?;
+        let tick = ticks
+            .wait_for_timeout(Duration::from_millis(100), |_| true)
+            .await;
+        assert!(tick.is_none());
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn fd_method_return() {
+        block_on(test_fd_method_return()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_fd_method_return() -> Result<()> {
+        use std::{
+            io::{Read, Write},
+            os::unix::{io::IntoRawFd, net::UnixStream},
+        };
+        use zvariant::OwnedFd;
+
+        // Returns the read ends of two socket pairs, to which `first` and `second` were written.
+        struct Streams;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.FdReturnTest")]
+        impl Streams {
+            fn open(&self, first: &str, second: &str) -> (OwnedFd, u32, OwnedFd) {
+                let open = |data: &str| {
+                    let (mut writer, reader) = UnixStream::pair().unwrap();
+                    writer.write_all(data.as_bytes()).unwrap();
+
+                    unsafe { OwnedFd::from_raw_fd(reader.into_raw_fd()) }
+                };
+
+                (open(first), 2, open(second))
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.FdReturnTest",
+            default_path = "/org/freedesktop/zbus/FdReturnTest"
+        )]
+        trait Streams {
+            fn open(&self, first: &str, second: &str) -> zbus::Result<(OwnedFd, u32, OwnedFd)>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/FdReturnTest", Streams)?
+            .build()
+            .await?;
+        let client_conn = crate::Connection::session().await?;
+        let client = StreamsProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+
+        // The fds are still open after the reply is gone, and are in the order of the body.
+        let (first, count, second) = client.open("first", "second").await?;
+        assert_eq!(count, 2);
+        for (fd, expected) in [(first, "first"), (second, "second")] {
+            let mut stream = unsafe { UnixStream::from_raw_fd(fd.into_raw_fd()) };
+            let mut data = String::new();
+            stream.read_to_string(&mut data).unwrap();
+            assert_eq!(data, expected);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    #[timeout(15000)]
+    fn set_introspection() {
+        block_on(test_set_introspection()).unwrap();
+    }
+
+    #[cfg(feature = "xml")]
+    async fn test_set_introspection() -> Result<()> {
+        use crate::{fdo, Error};
+
+        struct Annotated;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.Annotated")]
+        impl Annotated {
+            fn ping(&self) {}
+        }
+
+        let path = "/org/freedesktop/zbus/Annotated";
+        let service = crate::connection::Builder::session()?
+            .serve_at(path, Annotated)?
+            .build()
+            .await?;
+        let client_conn = crate::Connection::session().await?;
+        let proxy = fdo::IntrospectableProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .path(path)?
+            .build()
+            .await?;
+        let object_server = service.object_server();
+        let generated = proxy.introspect().await?;
+
+        let xml = r#"<node>
+  <interface name="org.freedesktop.zbus.Annotated">
+    <method name="Ping">
+      <annotation name="org.freedesktop.DBus.Method.NoReply" value="true"/>
+    </method>
+  </interface>
+</node>"#;
+        object_server
+            .set_introspection(path, Some(xml.to_string()))
+            .await?;
+        assert_eq!(proxy.introspect().await?, xml);
+        // The parent's XML is still generated.
+        assert_ne!(object_server.introspect("/org/freedesktop").await?, xml);
+
+        // Invalid XML is rejected, keeping the previous one.
+        let res = object_server
+            .set_introspection(path, Some("<node><interface".to_string()))
+            .await;
+        assert!(matches!(res, Err(Error::QuickXml(_))));
+        assert_eq!(proxy.introspect().await?, xml);
+        let res = object_server
+            .set_introspection("/org/freedesktop/zbus/Unknown", Some(xml.to_string()))
+            .await;
+        assert!(matches!(res, Err(Error::FDO(e)) if matches!(*e, fdo::Error::UnknownObject(_))));
+
+        object_server.set_introspection(path, None).await?;
+        assert_eq!(proxy.introspect().await?, generated);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn object_server_at_many() {
+        block_on(test_object_server_at_many()).unwrap();
+    }
+
+    async fn test_object_server_at_many() -> Result<()> {
+        use crate::{fdo, Error};
+
+        struct Bulk(u32);
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.Bulk")]
+        impl Bulk {
+            #[dbus_interface(property)]
+            fn id(&self) -> u32 {
+                self.0
+            }
+        }
+
+        let root = "/org/freedesktop/zbus/Bulk";
+        let service = crate::Connection::session().await?;
+        let object_server = service.object_server();
+        object_server.at(root, fdo::ObjectManager).await?;
+        object_server.at(format!("{root}/0"), Bulk(0)).await?;
+
+        // Already registered interfaces are skipped.
+        let added = object_server
+            .at_many((0..4).map(|i| (format!("{root}/{i}"), Bulk(i * 10))))
+            .await?;
+        assert_eq!(added, 3);
+
+        let client_conn = crate::Connection::session().await?;
+        let proxy = fdo::ObjectManagerProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .path(root)?
+            .build()
+            .await?;
+        let objects = proxy.get_managed_objects().await?;
+        assert_eq!(objects.len(), 4);
+        let id = |i: u32| {
+            let path = OwnedObjectPath::try_from(format!("{root}/{i}")).unwrap();
+            u32::try_from(objects[&path]["org.freedesktop.zbus.Bulk"]["Id"].clone()).unwrap()
+        };
+        assert_eq!(id(0), 0);
+        assert_eq!(id(2), 20);
+
+        // Nothing gets registered if any of the paths is invalid.
+        let res = object_server
+            .at_many([
+                (format!("{root}/4"), Bulk(4)),
+                ("invalid".to_string(), Bulk(5)),
+            ])
+            .await;
+        match res {
+            Err(Error::Failure(e)) => assert!(e.contains("entry 1"), "{e}"),
+            res => panic!("unexpected result: {res:?}"),
+        }
+        assert_eq!(proxy.get_managed_objects().await?.len(), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn borrowed_str_array_body() {
+        block_on(test_borrowed_str_array_body()).unwrap();
+    }
+
+    async fn test_borrowed_str_array_body() -> Result<()> {
+        struct Strings;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.Strings")]
+        impl Strings {
+            fn strings(&self) -> Vec<String> {
+                (0..1000).map(|i| format!("string {i}")).collect()
+            }
+        }
+
+        let path = "/org/freedesktop/zbus/Strings";
+        let service = crate::connection::Builder::session()?
+            .serve_at(path, Strings)?
+            .build()
+            .await?;
+        let client_conn = crate::Connection::session().await?;
+        let reply = client_conn
+            .call_method(
+                service.unique_name(),
+                path,
+                Some("org.freedesktop.zbus.Strings"),
+                "Strings",
+                &(),
+            )
+            .await?;
+
+        // The strings are borrowed from the message instead of being allocated.
+        let strings: Vec<&str> = reply.body()?;
+        assert_eq!(strings.len(), 1000);
+        assert_eq!(strings[999], "string 999");
+        let range = reply.as_bytes().as_ptr_range();
+        assert!(strings.iter().all(|s| range.contains(&s.as_ptr())));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    #[timeout(15000)]
+    fn proxy_verify_on_build() {
+        block_on(test_proxy_verify_on_build()).unwrap();
+    }
+
+    #[cfg(feature = "xml")]
+    async fn test_proxy_verify_on_build() -> Result<()> {
+        use crate::Error;
+
+        struct Verified;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.Verified")]
+        impl Verified {
+            fn ping(&self, n: u32) -> u32 {
+                n
+            }
+
+            #[dbus_interface(property)]
+            fn version(&self) -> u32 {
+                1
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.Verified",
+            default_path = "/org/freedesktop/zbus/Verified",
+            verify_on_build
+        )]
+        trait Verified {
+            fn ping(&self, n: u32) -> crate::Result<u32>;
+
+            #[dbus_proxy(property)]
+            fn version(&self) -> crate::Result<u32>;
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.Verified",
+            default_path = "/org/freedesktop/zbus/Verified",
+            verify_on_build
+        )]
+        trait VerifiedNewer {
+            fn ping(&self, n: u32) -> crate::Result<u32>;
+
+            fn reset(&self) -> crate::Result<()>;
+
+            #[dbus_proxy(property)]
+            fn version(&self) -> crate::Result<String>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/Verified", Verified)?
+            .build()
+            .await?;
+        let client_conn = crate::Connection::session().await?;
+        let destination = service.unique_name().unwrap();
+
+        let proxy = VerifiedProxy::builder(&client_conn)
+            .destination(destination)?
+            .build()
+            .await?;
+        assert_eq!(proxy.ping(7).await?, 7);
+
+        // All the mismatches are reported.
+        let res = VerifiedNewerProxy::builder(&client_conn)
+            .destination(destination)?
+            .build()
+            .await;
+        match res {
+            Err(Error::Failure(e)) => {
+                assert!(e.contains("method `Reset` is missing"), "{e}");
+                assert!(e.contains("property `Version` is of type `u`"), "{e}");
+            }
+            res => panic!("unexpected result: {res:?}"),
+        }
+
+        // Wrong path.
+        let res = VerifiedProxy::builder(&client_conn)
+            .destination(destination)?
+            .path("/org/freedesktop/zbus")?
+            .build()
+            .await;
+        match res {
+            Err(Error::Failure(e)) => assert!(
+                e.contains("interface `org.freedesktop.zbus.Verified` is missing"),
+                "{e}"
+            ),
+            res => panic!("unexpected result: {res:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn unit_args() {
+        block_on(test_unit_args()).unwrap();
+    }
+
+    async fn test_unit_args() -> Result<()> {
+        struct Units;
+
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.Units")]
+        impl Units {
+            fn mixed(&self, _unit: (), n: u32) -> ((), u32, ((),)) {
+                ((), n, ((),))
+            }
+
+            fn nothing(&self, _unit: ((),)) -> ((),) {
+                ((),)
+            }
+
+            #[dbus_interface(signal)]
+            async fn units(ctxt: &SignalContext<'_>, unit: (), n: u32) -> Result<()>;
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.Units",
+            default_path = "/org/freedesktop/zbus/Units"
+        )]
+        trait Units {
+            fn mixed(&self, unit: (), n: u32) -> crate::Result<((), u32, ((),))>;
+
+            fn nothing(&self, unit: ((),)) -> crate::Result<((),)>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/Units", Units)?
+            .build()
+            .await?;
+        let client_conn = crate::Connection::session().await?;
+        let proxy = UnitsProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+
+        // Units are not sent over the bus, whatever their nesting.
+        assert_eq!(proxy.mixed((), 7).await?, ((), 7, ((),)));
+        assert_eq!(proxy.nothing(((),)).await?, ((),));
+        let reply = client_conn
+            .call_method(
+                service.unique_name(),
+                "/org/freedesktop/zbus/Units",
+                Some("org.freedesktop.zbus.Units"),
+                "Mixed",
+                &7u32,
+            )
+            .await?;
+        assert_eq!(reply.body_signature()?, "u");
+
+        // ..nor are they part of the introspection.
+        let xml = service
+            .object_server()
+            .introspect("/org/freedesktop/zbus/Units")
+            .await?;
+        assert!(!xml.contains("type=\"\""), "{xml}");
+        assert!(
+            xml.contains(r#"<arg name="n" type="u" direction="in"/>"#),
+            "{xml}"
+        );
+        assert!(xml.contains(r#"<arg name="n" type="u"/>"#), "{xml}");
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn property_type_mismatch() {
+        block_on(test_property_type_mismatch()).unwrap();
+    }
+
+    async fn test_property_type_mismatch() -> Result<()> {
+        use crate::Error;
+
+        struct Versioned;
+
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.Versioned")]
+        impl Versioned {
+            #[dbus_interface(property)]
+            fn version(&self) -> u32 {
+                1
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.Versioned",
+            default_path = "/org/freedesktop/zbus/Versioned"
+        )]
+        trait Versioned {
+            #[dbus_proxy(property)]
+            fn version(&self) -> crate::Result<String>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/Versioned", Versioned)?
+            .build()
+            .await?;
+        let client_conn = crate::Connection::session().await?;
+        let expected = "Signature mismatch: got `u`, expected `s` for property `Version`";
+
+        // The typed getters keep failing with the conversion error..
+        let proxy = VersionedProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+        assert!(matches!(
+            proxy.version().await.unwrap_err(),
+            Error::Variant(zvariant::Error::IncorrectType)
+        ));
+        assert!(matches!(
+            proxy.cached_version().unwrap_err(),
+            Error::Variant(zvariant::Error::IncorrectType)
+        ));
+
+        // ..while the checked getter names the property and the signatures, from the cache..
+        match proxy.get_property_checked::<String>("Version").await {
+            Err(e @ Error::Variant(zvariant::Error::SignatureMismatch(..))) => {
+                assert_eq!(e.to_string(), expected)
+            }
+            res => panic!("unexpected result: {res:?}"),
+        }
+
+        // ..and from the peer.
+        let proxy = VersionedProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .cache_properties(crate::CacheProperties::No)
+            .build()
+            .await?;
+        let e = proxy
+            .get_property_checked::<String>("Version")
+            .await
+            .unwrap_err();
+        assert_eq!(e.to_string(), expected);
+        assert_eq!(proxy.get_property_checked::<u32>("Version").await?, 1);
+        assert_eq!(proxy.get_property::<u32>("Version").await?, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn interface_versions() {
+        block_on(test_interface_versions()).unwrap();
+    }
+
+    async fn test_interface_versions() -> Result<()> {
+        struct Greeter {
+            greeting: String,
+        }
+
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.VersionedGreeter", versions("1", "2"))]
+        impl Greeter {
+            fn greet(&self, name: &str) -> String {
+                format!("{}, {name}!", self.greeting)
+            }
+
+            #[dbus_interface(versions("2"))]
+            fn set_greeting(&mut self, greeting: String) {
+                self.greeting = greeting;
+            }
+
+            #[dbus_interface(property)]
+            fn greeting(&self) -> String {
+                self.greeting.clone()
+            }
+
+            #[dbus_interface(signal, versions("2"))]
+            async fn greeted(ctxt: &SignalContext<'_>, name: &str) -> Result<()>;
+        }
+
+        let path = "/org/freedesktop/zbus/VersionedGreeter";
+        let greeter = GreeterV1::from(Greeter {
+            greeting: String::from("Hello"),
+        });
+        let shared = greeter.0.clone();
+        let service = crate::connection::Builder::session()?
+            .serve_at(path, greeter)?
+            .serve_at(path, GreeterV2(shared))?
+            .build()
+            .await?;
+        let client_conn = crate::Connection::session().await?;
+        let proxy = |version: &'static str| {
+            crate::proxy::Builder::<crate::Proxy<'_>>::new_bare(&client_conn)
+                .destination(service.unique_name().unwrap().to_owned())
+                .and_then(|b| b.path(path))
+                .and_then(|b| {
+                    b.interface(format!("org.freedesktop.zbus.VersionedGreeter{version}"))
+                })
+                .map(|b| b.cache_properties(crate::proxy::CacheProperties::No))
+        };
+        let v1 = proxy("1")?.build().await?;
+        let v2 = proxy("2")?.build().await?;
+
+        // The members common to both versions are served by both, by the same instance.
+        assert_eq!(
+            v1.call::<_, _, String>("Greet", &"Maria").await?,
+            "Hello, Maria!"
+        );
+        v2.call::<_, _, ()>("SetGreeting", &"Hi").await?;
+        assert_eq!(
+            v2.call::<_, _, String>("Greet", &"Maria").await?,
+            "Hi, Maria!"
+        );
+        assert_eq!(
+            v1.call::<_, _, String>("Greet", &"Maria").await?,
+            "Hi, Maria!"
+        );
+        assert_eq!(v1.get_property::<String>("Greeting").await?, "Hi");
+        assert_eq!(v2.get_property::<String>("Greeting").await?, "Hi");
+
+        // The others only by the versions they're declared for.
+        match v1.call::<_, _, ()>("SetGreeting", &"Hey").await {
+            Err(crate::Error::MethodError(name, _, _)) => {
+                assert_eq!(name, "org.freedesktop.DBus.Error.UnknownMethod")
+            }
+            r => panic!("unexpected reply: {r:?}"),
+        }
+        let xml = service.object_server().introspect(path).await?;
+        let v2_xml = &xml[xml
+            .find(r#"<interface name="org.freedesktop.zbus.VersionedGreeter2">"#)
+            .unwrap()..];
+        let v2_xml = &v2_xml[..v2_xml.find("</interface>").unwrap()];
+        assert!(v2_xml.contains(r#"<signal name="Greeted">"#), "{xml}");
+        assert_eq!(
+            xml.matches(r#"<signal name="Greeted">"#).count(),
+            1,
+            "{xml}"
+        );
+        assert_eq!(xml.matches(r#"<method name="Greet">"#).count(), 2, "{xml}");
+
+        // Signals are emitted through the wrapper of their version.
+        let mut greeted = v2.receive_signal("Greeted").await?;
+        let iface_ref = service
+            .object_server()
+            .interface::<_, GreeterV2>(path)
+            .await?;
+        assert_eq!(iface_ref.get().await.0.read().await.greeting, "Hi");
+        GreeterV2::greeted(iface_ref.signal_context(), "Maria").await?;
+        let signal = futures_util::StreamExt::next(&mut greeted).await.unwrap();
+        assert_eq!(signal.body::<&str>()?, "Maria");
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn raw_property_getter() {
+        block_on(test_raw_property_getter()).unwrap();
+    }
+
+    async fn test_raw_property_getter() -> Result<()> {
+        struct Thermometer {
+            celsius: f64,
+        }
+
+        // A newer version of the service, giving the temperature with a unit.
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.Thermometer")]
+        impl Thermometer {
+            #[dbus_interface(property)]
+            fn temperature(&self) -> (f64, String) {
+                (self.celsius, String::from("°C"))
+            }
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.Thermometer",
+            default_path = "/org/freedesktop/zbus/Thermometer",
+            gen_blocking = false
+        )]
+        trait Thermometer {
+            #[dbus_proxy(property(raw))]
+            fn temperature(&self) -> Result<f64>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at(
+                "/org/freedesktop/zbus/Thermometer",
+                Thermometer { celsius: 21.5 },
+            )?
+            .build()
+            .await?;
+        let client_conn = crate::Connection::session().await?;
+        let proxy = ThermometerProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .cache_properties(crate::proxy::CacheProperties::No)
+            .build()
+            .await?;
+
+        // The typed getter can't cope with the new type, but the raw one can.
+        proxy.temperature().await.unwrap_err();
+        let (value, unit) = <(f64, String)>::try_from(proxy.temperature_raw().await?)?;
+        assert_eq!(value, 21.5);
+        assert_eq!(unit, "°C");
+
+        // The raw getter doesn't use the cache.
+        let proxy = ThermometerProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap().to_owned())?
+            .cache_properties(crate::proxy::CacheProperties::Yes)
+            .build()
+            .await?;
+        proxy.temperature().await.unwrap_err();
+        service
+            .object_server()
+            .interface::<_, Thermometer>("/org/freedesktop/zbus/Thermometer")
+            .await?
+            .get_mut()
+            .await
+            .celsius = 22.0;
+        let (value, _) = <(f64, String)>::try_from(proxy.temperature_raw().await?)?;
+        assert_eq!(value, 22.0);
+        let cached = <(f64, String)>::try_from(
+            proxy
+                .inner()
+                .cached_property_raw("Temperature")
+                .unwrap()
+                .clone(),
+        )?;
+        assert_eq!(cached.0, 21.5);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn reply_with_headers() {
+        block_on(test_reply_with_headers()).unwrap();
+    }
+
+    async fn test_reply_with_headers() -> Result<()> {
+        struct Adder;
+
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.WithHeadersTest")]
+        impl Adder {
+            fn add(&self, a: u32, b: u32) -> u32 {
+                a + b
+            }
+
+            fn reset(&self) {}
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.WithHeadersTest",
+            default_path = "/org/freedesktop/zbus/WithHeadersTest"
+        )]
+        trait Adder {
+            #[dbus_proxy(with_headers)]
+            fn add(&self, a: u32, b: u32) -> zbus::fdo::Result<u32>;
+
+            #[dbus_proxy(with_headers)]
+            fn reset(&self) -> zbus::Result<()>;
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/WithHeadersTest", Adder)?
+            .build()
+            .await?;
+        let client_conn = crate::Connection::session().await?;
+        let client = AdderProxy::builder(&client_conn)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+
+        let (sum, meta) = client.add_with_headers(2, 3).await?;
+        assert_eq!(sum, 5);
+        assert_eq!(meta.sender(), service.unique_name());
+        assert!(meta.serial().is_some());
+        let (sum, next_meta) = client.add_with_headers(4, 3).await?;
+        assert_eq!(sum, 7);
+        // Each reply is for its own call.
+        assert!(next_meta.reply_serial() > meta.reply_serial());
+
+        let ((), meta) = client.reset_with_headers().await?;
+        assert_eq!(meta.sender(), service.unique_name());
+
+        // The typed method is still generated.
+        assert_eq!(client.add(1, 1).await?, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn max_concurrent_calls() {
+        block_on(test_max_concurrent_calls()).unwrap();
+    }
+
+    async fn test_max_concurrent_calls() -> Result<()> {
+        use crate::abstractions::timer::sleep;
+        use event_listener::Event;
+        use futures_util::TryStreamExt;
+        use std::{
+            sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+            time::Duration,
+        };
+
+        #[derive(Default)]
+        struct State {
+            running: AtomicUsize,
+            max_running: AtomicUsize,
+            released: AtomicBool,
+            release: Event,
+        }
+
+        struct Blocking(Arc<State>);
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.MaxConcurrentCalls")]
+        impl Blocking {
+            async fn block(&self) {
+                let state = &self.0;
+                let running = state.running.fetch_add(1, Ordering::SeqCst) + 1;
+                state.max_running.fetch_max(running, Ordering::SeqCst);
+                while !state.released.load(Ordering::SeqCst) {
+                    let listener = state.release.listen();
+                    if state.released.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    listener.await;
+                }
+                state.running.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        let state = Arc::new(State::default());
+        let service = crate::connection::Builder::session()?
+            .serve_at(
+                "/org/freedesktop/zbus/MaxConcurrentCalls",
+                Blocking(state.clone()),
+            )?
+            .max_concurrent_calls(2)
+            .build()
+            .await?;
+        assert_eq!(service.max_concurrent_calls(), Some(2));
+
+        let client = Connection::session().await?;
+        assert_eq!(client.max_concurrent_calls(), None);
+        let mut stream = crate::MessageStream::from(&client);
+
+        let mut serials = vec![];
+        for _ in 0..4 {
+            let call = crate::message::Builder::method_call(
+                "/org/freedesktop/zbus/MaxConcurrentCalls",
+                "Block",
+            )?
+            .interface("org.freedesktop.zbus.MaxConcurrentCalls")?
+            .destination(service.unique_name().unwrap())?
+            .build(&())?;
+            serials.push(client.send_message(call).await?);
+        }
+
+        // Give all the calls the time to reach the service, only 2 of them are dispatched.
+        while state.running.load(Ordering::SeqCst) < 2 {
+            sleep(Duration::from_millis(10)).await;
+        }
+        sleep(Duration::from_millis(200)).await;
+        assert_eq!(state.running.load(Ordering::SeqCst), 2);
+
+        // The queued calls are dispatched once the blocked ones complete.
+        state.released.store(true, Ordering::SeqCst);
+        state.release.notify(usize::MAX);
+        let mut replies = 0;
+        while replies < serials.len() {
+            let msg = stream.try_next().await?.unwrap();
+            if msg.reply_serial().map_or(false, |s| serials.contains(&s)) {
+                assert_eq!(msg.message_type(), crate::message::Type::MethodReturn);
+                replies += 1;
+            }
+        }
+        assert_eq!(state.max_running.load(Ordering::SeqCst), 2);
+        assert_eq!(state.running.load(Ordering::SeqCst), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn max_concurrent_calls_saturated() {
+        block_on(test_max_concurrent_calls_saturated()).unwrap();
+    }
+
+    async fn test_max_concurrent_calls_saturated() -> Result<()> {
+        use crate::abstractions::timer::sleep;
+        use event_listener::Event;
+        use futures_util::TryStreamExt;
+        use std::{
+            sync::atomic::{AtomicBool, Ordering},
+            time::Duration,
+        };
+
+        #[derive(Default)]
+        struct State {
+            relaying: AtomicBool,
+            released: AtomicBool,
+            release: Event,
+        }
+
+        struct Relay(Arc<State>);
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.SaturatedCalls")]
+        impl Relay {
+            // Holds the only slot until released, then calls the bus.
+            async fn relay(&self, #[zbus(connection)] conn: &Connection) -> String {
+                let state = &self.0;
+                state.relaying.store(true, Ordering::SeqCst);
+                while !state.released.load(Ordering::SeqCst) {
+                    let listener = state.release.listen();
+                    if state.released.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    listener.await;
+                }
+
+                crate::fdo::DBusProxy::new(conn)
+                    .await
+                    .unwrap()
+                    .get_id()
+                    .await
+                    .unwrap()
+                    .to_string()
+            }
+
+            fn noop(&self) {}
+        }
+
+        let state = Arc::new(State::default());
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/SaturatedCalls", Relay(state.clone()))?
+            .max_concurrent_calls(1)
+            .build()
+            .await?;
+        let client = Connection::session().await?;
+        let mut stream = crate::MessageStream::from(&client);
+        let call = |member| {
+            crate::message::Builder::method_call("/org/freedesktop/zbus/SaturatedCalls", member)?
+                .interface("org.freedesktop.zbus.SaturatedCalls")?
+                .destination(service.unique_name().unwrap())?
+                .build(&())
+        };
+
+        let relay = client.send_message(call("Relay")?).await?;
+        while !state.relaying.load(Ordering::SeqCst) {
+            sleep(Duration::from_millis(10)).await;
+        }
+        // More calls than fit in any queue of the service.
+        let mut noops = vec![];
+        for _ in 0..service.max_queued() * 2 {
+            noops.push(client.send_message(call("Noop")?).await?);
+        }
+        sleep(Duration::from_millis(200)).await;
+
+        // The relayed call gets its reply and so all the calls get theirs.
+        state.released.store(true, Ordering::SeqCst);
+        state.release.notify(usize::MAX);
+        let (mut relayed, mut returns, mut rejected) = (false, 0, 0);
+        while !relayed || returns + rejected < noops.len() {
+            let msg = stream.try_next().await?.unwrap();
+            let serial = match msg.reply_serial() {
+                Some(serial) => serial,
+                None => continue,
+            };
+            if serial == relay {
+                assert_eq!(msg.message_type(), crate::message::Type::MethodReturn);
+                relayed = true;
+            } else if noops.contains(&serial) {
+                match msg.message_type() {
+                    crate::message::Type::MethodReturn => returns += 1,
+                    _ => {
+                        let name = msg.header()?.error_name()?.unwrap().to_string();
+                        assert_eq!(name, "org.freedesktop.DBus.Error.LimitsExceeded");
+                        rejected += 1;
+                    }
+                }
+            }
+        }
+        // The calls over the limit wait, up to the capacity of the queue.
+        assert_eq!(returns, service.max_queued());
+        assert_eq!(rejected, service.max_queued());
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn object_server_paths() {
+        block_on(test_object_server_paths()).unwrap();
+    }
+
+    async fn test_object_server_paths() -> Result<()> {
+        use crate::object_server::Interface;
+        use futures_util::future::join;
+
+        struct First;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.Paths.First")]
+        impl First {}
+
+        struct Second;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.Paths.Second")]
+        impl Second {}
+
+        let conn = Connection::session().await?;
+        let object_server = conn.object_server();
+        assert!(object_server.paths().await.is_empty());
+
+        object_server.at("/org/zbus/a", First).await?;
+        object_server.at("/org/zbus/a", Second).await?;
+        object_server.at("/org/zbus/a/b/c", First).await?;
+        object_server.at_prefix("/org/zbus/p", Second).await?;
+        let paths = |paths: Vec<OwnedObjectPath>| {
+            paths
+                .into_iter()
+                .map(|p| p.as_str().to_owned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            paths(object_server.paths().await),
+            ["/org/zbus/a", "/org/zbus/a/b/c", "/org/zbus/p"],
+        );
+        assert_eq!(
+            object_server.interfaces_at("/org/zbus/a").await?,
+            [First::name(), Second::name()],
+        );
+        assert_eq!(
+            object_server.interfaces_at("/org/zbus/p").await?,
+            [Second::name()],
+        );
+        // Neither the intermediate nodes nor the paths under a prefix have interfaces of their own.
+        assert!(object_server
+            .interfaces_at("/org/zbus/a/b")
+            .await?
+            .is_empty());
+        assert!(object_server
+            .interfaces_at("/org/zbus/p/q")
+            .await?
+            .is_empty());
+        assert!(object_server.interfaces_at("/nope").await?.is_empty());
+
+        object_server.remove::<First, _>("/org/zbus/a/b/c").await?;
+        object_server.remove::<Second, _>("/org/zbus/a").await?;
+        assert_eq!(
+            paths(object_server.paths().await),
+            ["/org/zbus/a", "/org/zbus/p"],
+        );
+        assert_eq!(
+            object_server.interfaces_at("/org/zbus/a").await?,
+            [First::name()],
+        );
+
+        // Each snapshot taken while objects are registered has all the objects registered before it.
+        let expected: Vec<_> = (0..10).map(|i| format!("/org/zbus/n/{i}")).collect();
+        let register = async {
+            for path in &expected {
+                object_server.at(path.as_str(), First).await?;
+            }
+
+            Ok::<_, crate::Error>(())
+        };
+        let list = async {
+            let mut snapshots = vec![];
+            for _ in 0..20 {
+                snapshots.push(paths(object_server.paths().await));
+                crate::abstractions::timer::sleep(std::time::Duration::from_millis(1)).await;
+            }
+
+            snapshots
+        };
+        let (registered, snapshots) = join(register, list).await;
+        registered?;
+        for snapshot in snapshots {
+            let registered: Vec<_> = snapshot
+                .iter()
+                .filter(|p| p.starts_with("/org/zbus/n/"))
+                .collect();
+            assert_eq!(
+                registered,
+                expected[..registered.len()].iter().collect::<Vec<_>>()
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn variant_signal() {
+        block_on(test_variant_signal()).unwrap();
+    }
+
+    async fn test_variant_signal() -> Result<()> {
+        use crate::object_server::SignalContext;
+        use futures_util::StreamExt;
+        use zvariant::{OwnedValue, Value};
+
+        struct Settings;
+        #[crate::dbus_interface(name = "org.freedesktop.zbus.VariantSignal")]
+        impl Settings {
+            #[dbus_interface(signal)]
+            async fn changed(ctxt: &SignalContext<'_>, key: &str, value: Value<'_>) -> Result<()>;
+        }
+
+        #[crate::dbus_proxy(
+            interface = "org.freedesktop.zbus.VariantSignal",
+            default_path = "/org/freedesktop/zbus/VariantSignal"
+        )]
+        trait Settings {
+            #[dbus_proxy(signal)]
+            fn changed(&self, key: &str, value: OwnedValue) -> Result<()>;
+        }
+
+        mod borrowed {
+            use zvariant::Value;
+
+            // The same signal, but with the variant borrowed from the message.
+            #[crate::dbus_proxy(
+                interface = "org.freedesktop.zbus.VariantSignal",
+                default_path = "/org/freedesktop/zbus/VariantSignal"
+            )]
+            trait Settings {
+                #[dbus_proxy(signal)]
+                fn changed(&self, key: &str, value: Value<'_>) -> Result<()>;
+            }
+        }
+
+        let service = crate::connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/VariantSignal", Settings)?
+            .build()
+            .await?;
+        let client = Connection::session().await?;
+        let proxy = SettingsProxy::builder(&client)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+        let borrowed_proxy = borrowed::SettingsProxy::builder(&client)
+            .destination(service.unique_name().unwrap())?
+            .build()
+            .await?;
+        let mut changed = proxy.receive_changed().await?;
+        let mut changed_borrowed = borrowed_proxy.receive_changed().await?;
+
+        // The variant holds a value of a different type in each emission.
+        let ctxt = SignalContext::new(&service, "/org/freedesktop/zbus/VariantSignal")?;
+        Settings::changed(&ctxt, "volume", Value::from(11u32)).await?;
+        Settings::changed(&ctxt, "name", Value::from("zbus")).await?;
+        Settings::changed(&ctxt, "muted", Value::from(true)).await?;
+
+        let mut values = vec![];
+        for _ in 0..3 {
+            let signal = changed.next().await.unwrap();
+            let args = signal.args()?;
+            values.push((args.key().to_string(), args.value().clone()));
+        }
+        assert_eq!(values[0].0, "volume");
+        assert_eq!(u32::try_from(values[0].1.clone())?, 11);
+        assert_eq!(values[1].0, "name");
+        assert_eq!(String::try_from(values[1].1.clone())?, "zbus");
+        assert_eq!(values[2].0, "muted");
+        assert!(bool::try_from(values[2].1.clone())?);
+
+        for expected in [Value::from(11u32), Value::from("zbus"), Value::from(true)] {
+            let signal = changed_borrowed.next().await.unwrap();
+            assert_eq!(signal.args()?.value(), &expected);
+
+            // The signal type can also be built from any message carrying it.
+            let msg: &Arc<crate::message::Message> = signal.as_ref();
+            let signal = Changed::from_message(msg.clone()).unwrap();
+            assert_eq!(signal.args()?.value(), &OwnedValue::from(expected));
+        }
+
+        Ok(())
+    }
 }
@@ -266,7 +266,7 @@ impl<'m> MatchRule<'m> {
             };
             match path_spec {
                 PathSpec::Path(path) if path != &msg_path => return Ok(false),
-                PathSpec::PathNamespace(path_ns) if !msg_path.starts_with(path_ns.as_str()) => {
+                PathSpec::PathNamespace(path_ns) if !msg_path.is_descendant_of(path_ns) => {
                     return Ok(false);
                 }
                 PathSpec::Path(_) | PathSpec::PathNamespace(_) => (),
@@ -558,3 +558,32 @@ impl PartialEq<MatchRule<'_>> for OwnedMatchRule {
         self.0 == *other
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::MatchRule;
+    use crate::message::Builder;
+
+    #[test]
+    fn path_namespace() {
+        let signal = |path: &str| {
+            Builder::signal(path, "org.zbus.Test", "Test")
+                .unwrap()
+                .build(&())
+                .unwrap()
+        };
+        let rule = MatchRule::try_from("type='signal',path_namespace='/a/b'").unwrap();
+
+        assert!(rule.matches(&signal("/a/b")).unwrap());
+        assert!(rule.matches(&signal("/a/b/c")).unwrap());
+        // Only whole path elements match.
+        assert!(!rule.matches(&signal("/a/bc")).unwrap());
+        assert!(!rule.matches(&signal("/a")).unwrap());
+
+        // The root namespace matches all paths.
+        let rule = MatchRule::try_from("type='signal',path_namespace='/'").unwrap();
+        assert!(rule.matches(&signal("/a/bc")).unwrap());
+    }
+}
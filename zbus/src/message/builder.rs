@@ -33,9 +33,35 @@ macro_rules! dbus_context {
 }
 
 /// A builder for [`Message`]
+///
+/// Start with the constructor for the type of message you want ([`Builder::method_call`],
+/// [`Builder::signal`], [`Builder::method_return`] or [`Builder::error`]), set the header fields
+/// and flags as needed and finally call [`Builder::build`] with the body.
+///
+/// # Example
+///
+/// Build a method call that must not trigger the activation of the destination service:
+///
+/// ```
+/// use zbus::message::{Builder, Flags};
+///
+/// let msg = Builder::method_call("/org/freedesktop/DBus", "GetNameOwner")?
+///     .destination("org.freedesktop.DBus")?
+///     .interface("org.freedesktop.DBus")?
+///     .with_flags(Flags::NoAutoStart)?
+///     .build(&"org.freedesktop.zbus.Example")?;
+///
+/// let header = msg.header()?;
+/// assert_eq!(header.destination()?.unwrap(), "org.freedesktop.DBus");
+/// assert_eq!(header.interface()?.unwrap(), "org.freedesktop.DBus");
+/// assert_eq!(header.member()?.unwrap(), "GetNameOwner");
+/// assert!(header.primary().flags().contains(Flags::NoAutoStart));
+/// # Ok::<(), zbus::Error>(())
+/// ```
 #[derive(Debug, Clone)]
 pub struct Builder<'a> {
     header: Header<'a>,
+    fixed_serial: bool,
 }
 
 impl<'a> Builder<'a> {
@@ -43,10 +69,24 @@ impl<'a> Builder<'a> {
         let primary = PrimaryHeader::new(msg_type, 0);
         let fields = Fields::new();
         let header = Header::new(primary, fields);
-        Self { header }
+        Self {
+            header,
+            fixed_serial: false,
+        }
     }
 
     /// Create a message of type [`Type::MethodCall`].
+    ///
+    /// The object path and the method name are required. Use [`Builder::destination`] and
+    /// [`Builder::interface`] to set the destination service and the interface of the method, and
+    /// [`Builder::with_flags`] to request no reply ([`Flags::NoReplyExpected`]) or to prevent the
+    /// bus from starting the destination service ([`Flags::NoAutoStart`]).
+    ///
+    /// The interface is optional for method calls: if it's not set, the receiver looks the method
+    /// up by its name, among all the interfaces of the object. The [`ObjectServer`] replies with
+    /// an error if more than one of them has a method with that name.
+    ///
+    /// [`ObjectServer`]: crate::ObjectServer
     pub fn method_call<'p: 'a, 'm: 'a, P, M>(path: P, method_name: M) -> Result<Self>
     where
         P: TryInto<ObjectPath<'p>>,
@@ -87,6 +127,29 @@ impl<'a> Builder<'a> {
         Self::new(Type::Error).error_name(name)?.reply_to(reply_to)
     }
 
+    /// Set the serial number of the message.
+    ///
+    /// The serial number is normally assigned by the connection when sending the message. Setting
+    /// it up-front makes the message bytes reproducible, e.g to compare them against a known
+    /// encoding in tests.
+    ///
+    /// Since the connection would otherwise renumber it, such a message can not be sent with
+    /// [`Connection::send_message`] (nor any method building on it), which fails with
+    /// [`Error::InvalidSerial`]. It can only be sent through the [`Sink`] implementation of the
+    /// connection, and it's then up to the caller to keep the serials unique on the connection.
+    ///
+    /// The function will return an error if `serial` is 0 (which is invalid).
+    ///
+    /// [`Connection::send_message`]: crate::Connection::send_message
+    /// [`Sink`]: https://docs.rs/futures/0.3.17/futures/sink/trait.Sink.html
+    pub fn serial(mut self, serial: u32) -> Result<Self> {
+        let serial = serial.try_into().map_err(|_| Error::InvalidSerial)?;
+        self.header.primary_mut().set_serial_num(serial);
+        self.fixed_serial = true;
+
+        Ok(self)
+    }
+
     /// Add flags to the message.
     ///
     /// See [`Flags`] documentation for the meaning of the flags.
@@ -189,6 +252,9 @@ impl<'a> Builder<'a> {
     ///
     /// You may pass `()` as the body if the message has no body.
     ///
+    /// On Unix, any file descriptors in the body (as [`zvariant::Fd`]) are attached to the message
+    /// and the `UNIX_FDS` header field is set accordingly.
+    ///
     /// The caller is currently required to ensure that the resulting message contains the headers
     /// as compliant with the [specification]. Additional checks may be added to this builder over
     /// time as needed.
@@ -278,6 +344,7 @@ impl<'a> Builder<'a> {
         WriteFunc: FnOnce(&mut Cursor<&mut Vec<u8>>) -> Result<BuildGenericResult>,
     {
         let ctxt = dbus_context!(0);
+        let fixed_serial = self.fixed_serial;
         let mut header = self.header;
 
         if !signature.is_empty() {
@@ -332,6 +399,7 @@ impl<'a> Builder<'a> {
             #[cfg(unix)]
             fds: Arc::new(RwLock::new(Fds::Raw(fds))),
             recv_seq: Sequence::default(),
+            fixed_serial,
         })
     }
 }
@@ -343,14 +411,20 @@ impl<'m> From<Header<'m>> for Builder<'m> {
         fields.remove(FieldCode::Signature);
         fields.remove(FieldCode::UnixFDs);
 
-        Self { header }
+        Self {
+            header,
+            fixed_serial: false,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Builder;
-    use crate::Error;
+    use crate::{
+        message::{Flags, Type},
+        Error,
+    };
     use test_log::test;
 
     #[test]
@@ -371,4 +445,63 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn serial() -> Result<(), Error> {
+        let build = || {
+            Builder::signal("/", "test.test", "test")?
+                .serial(42)?
+                .build(&7u32)
+        };
+        let message = build()?;
+        assert_eq!(message.primary_header().serial_num().unwrap().get(), 42);
+        // The same message is encoded the same way.
+        assert_eq!(message.as_bytes(), build()?.as_bytes());
+
+        assert!(matches!(
+            Builder::signal("/", "test.test", "test")?.serial(0),
+            Err(Error::InvalidSerial)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn method_call() -> Result<(), Error> {
+        let message = Builder::method_call("/org/freedesktop/zbus/Test", "Echo")?
+            .sender(":1.42")?
+            .destination("org.freedesktop.zbus.Test")?
+            .interface("org.freedesktop.zbus.Test")?
+            .with_flags(Flags::NoReplyExpected)?
+            .with_flags(Flags::NoAutoStart)?
+            .build(&("hello", 42u32))?;
+
+        let header = message.header()?;
+        assert_eq!(header.message_type()?, Type::MethodCall);
+        assert_eq!(header.path()?.unwrap(), "/org/freedesktop/zbus/Test");
+        assert_eq!(header.member()?.unwrap(), "Echo");
+        assert_eq!(header.sender()?.unwrap(), ":1.42");
+        assert_eq!(header.destination()?.unwrap(), "org.freedesktop.zbus.Test");
+        assert_eq!(header.interface()?.unwrap(), "org.freedesktop.zbus.Test");
+        assert_eq!(
+            header.primary().flags(),
+            Flags::NoReplyExpected | Flags::NoAutoStart
+        );
+        assert_eq!(header.signature()?.unwrap(), "su");
+        let (s, n): (String, u32) = message.body()?;
+        assert_eq!(s, "hello");
+        assert_eq!(n, 42);
+
+        // The interface is optional.
+        let message = Builder::method_call("/org/freedesktop/zbus/Test", "Echo")?.build(&())?;
+        assert_eq!(message.header()?.interface()?, None);
+
+        // The no-reply flag only makes sense for method calls.
+        assert!(matches!(
+            Builder::signal("/", "test.test", "test")?.with_flags(Flags::NoReplyExpected),
+            Err(Error::InvalidField)
+        ));
+
+        Ok(())
+    }
 }
@@ -87,6 +87,8 @@ pub struct Message {
     #[cfg(unix)]
     pub(crate) fds: Arc<RwLock<Fds>>,
     pub(crate) recv_seq: Sequence,
+    // Whether the serial was set by the builder, which the connection must not override.
+    pub(crate) fixed_serial: bool,
 }
 
 assert_impl_all!(Message: Send, Sync, Unpin);
@@ -184,6 +186,8 @@ impl Message {
 
     /// Create a message of type [`Type::MethodError`].
     ///
+    /// Unlike [`Message::error_reply`], this doesn't check that `call` is a method call.
+    ///
     /// [`Type::MethodError`]: enum.Type.html#variant.MethodError
     pub fn method_error<'s, 'e, S, E, B>(
         sender: Option<S>,
@@ -205,6 +209,27 @@ impl Message {
         b.build(body)
     }
 
+    /// Create an error reply to this method call.
+    ///
+    /// The reply is addressed to the sender of the call and refers to it through its serial number,
+    /// so that the caller can match the two. `body` is typically the error message, as a string.
+    ///
+    /// This is the same as [`Message::method_error`] without a sender, except that it fails with
+    /// [`Error::InvalidField`] if this message isn't a method call. It also fails with
+    /// [`Error::MissingField`] if it has no serial number, as it wasn't sent yet.
+    pub fn error_reply<'e, E, B>(&self, name: E, body: &B) -> Result<Self>
+    where
+        E: TryInto<ErrorName<'e>>,
+        E::Error: Into<Error>,
+        B: serde::ser::Serialize + DynamicType,
+    {
+        if self.message_type() != Type::MethodCall {
+            return Err(Error::InvalidField);
+        }
+
+        Self::method_error(None::<UniqueName<'_>>, self, name, body)
+    }
+
     /// Create a message from bytes.
     ///
     /// The `fds` parameter is only available on unix. It specifies the file descriptors that
@@ -255,6 +280,7 @@ impl Message {
             #[cfg(unix)]
             fds,
             recv_seq: Sequence { recv_seq },
+            fixed_serial: false,
         })
     }
 
@@ -587,7 +613,7 @@ mod tests {
 
     #[cfg(unix)]
     use super::Fds;
-    use super::Message;
+    use super::{Message, Type};
     use crate::Error;
 
     #[test]
@@ -633,4 +659,45 @@ mod tests {
         .unwrap();
         assert_eq!(e.to_string(), "Error org.freedesktop.zbus.Error: kaboom!");
     }
+
+    #[test]
+    fn error_reply() {
+        let mut call = Message::method(
+            Some(":1.72"),
+            Some(":1.42"),
+            "/org/freedesktop/zbus/Test",
+            Some("org.freedesktop.zbus.Test"),
+            "Do",
+            &(),
+        )
+        .unwrap();
+        // The call must have been sent, to have a serial number.
+        assert_eq!(
+            call.error_reply("org.freedesktop.zbus.Error", &"kaboom!")
+                .unwrap_err(),
+            Error::MissingField
+        );
+        call.set_serial_num(7.try_into().unwrap()).unwrap();
+
+        let reply = call
+            .error_reply("org.freedesktop.zbus.Error", &"kaboom!")
+            .unwrap();
+        let header = reply.header().unwrap();
+        assert_eq!(header.message_type().unwrap(), Type::Error);
+        assert_eq!(reply.reply_serial(), call.primary_header().serial_num());
+        assert_eq!(header.destination().unwrap().unwrap(), ":1.72");
+        assert_eq!(
+            header.error_name().unwrap().unwrap(),
+            "org.freedesktop.zbus.Error"
+        );
+        assert_eq!(reply.body::<&str>().unwrap(), "kaboom!");
+
+        // Only method calls can be replied to.
+        assert_eq!(
+            reply
+                .error_reply("org.freedesktop.zbus.Error", &"kaboom!")
+                .unwrap_err(),
+            Error::InvalidField
+        );
+    }
 }
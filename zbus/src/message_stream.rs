@@ -49,6 +49,10 @@ impl MessageStream {
     /// Having said that, stream created by this method can still very useful as it allows you to
     /// avoid needless task wakeups and simplify your stream consuming code.
     ///
+    /// The returned future only resolves once the bus has acknowledged the match rule, and the
+    /// stream is set up to receive the matching messages before that, so none of the signals
+    /// emitted after the acknowledgement are missed.
+    ///
     /// You can optionally also request the capacity of the underlying message queue through
     /// `max_queued`. If specified, the capacity is guaranteed to be at least `max_queued`. If not
     /// specified, the default of 64 is assumed. The capacity can also be changed later through
@@ -74,6 +74,18 @@ pub trait Interface: Any + Send + Sync {
     /// Return all the properties.
     async fn get_all(&self) -> HashMap<String, OwnedValue>;
 
+    /// Return the properties advertised through the `org.freedesktop.DBus.ObjectManager`
+    /// interface, in its `InterfacesAdded` signal and `GetManagedObjects` method.
+    ///
+    /// The default implementation returns all the properties, like `get_all`. Use the
+    /// `managed_properties` attribute of [`dbus_interface`] to only advertise some of them (e.g
+    /// the cheap ones, since these are read for all the objects at once).
+    ///
+    /// [`dbus_interface`]: attr.dbus_interface.html
+    async fn get_all_managed(&self) -> HashMap<String, OwnedValue> {
+        self.get_all().await
+    }
+
     /// Set a property value.
     ///
     /// Return [`DispatchResult::NotFound`] if the property doesn't exist, or
@@ -126,6 +138,16 @@ pub trait Interface: Any + Send + Sync {
         name: MemberName<'call>,
     ) -> DispatchResult<'call>;
 
+    /// Whether the interface has a method named `name`.
+    ///
+    /// This is used to dispatch the method calls that don't specify an interface: such calls are
+    /// delivered to the only interface of the object having a method with the called name. The
+    /// default implementation returns `false`, i-e the interface never handles such calls.
+    fn has_method(&self, name: &str) -> bool {
+        let _ = name;
+        false
+    }
+
     /// Write introspection XML to the writer, with the given indentation level.
     fn introspect_to_writer(&self, writer: &mut dyn Write, level: usize);
 }
@@ -1,22 +1,25 @@
 //! The object server API.
 
+use async_broadcast::{broadcast, Sender};
 use event_listener::{Event, EventListener};
+use futures_core::Future;
 use serde::Serialize;
 use std::{
     collections::{hash_map::Entry, HashMap, VecDeque},
     fmt::Write,
     marker::PhantomData,
     ops::{Deref, DerefMut},
-    sync::Arc,
+    pin::Pin,
+    sync::{self, Arc},
 };
-use tracing::{debug, instrument, trace};
+use tracing::{debug, debug_span, instrument, trace, Instrument};
 
 use static_assertions::assert_impl_all;
-use zbus_names::InterfaceName;
+use zbus_names::{InterfaceName, MemberName, OwnedInterfaceName, OwnedMemberName};
 use zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Signature, Type, Value};
 
 use crate::{
-    async_lock::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+    async_lock::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard},
     connection::WeakConnection,
     fdo,
     fdo::{Introspectable, ManagedObjects, ObjectManager, Peer, Properties},
@@ -29,6 +32,8 @@ pub use interface::{DispatchResult, Interface};
 
 mod signal_context;
 pub use signal_context::SignalContext;
+mod properties_changed;
+pub use properties_changed::PropertiesChangedBuilder;
 
 /// Opaque structure that derefs to an `Interface` type.
 pub struct InterfaceDeref<'d, I> {
@@ -168,6 +173,22 @@ where
     pub fn signal_context(&self) -> &SignalContext<'static> {
         &self.ctxt
     }
+
+    /// Wait for the next call to the method `member` of this interface.
+    ///
+    /// See [`ObjectServer::next_call`] for details.
+    pub async fn next_call<'m, M>(&self, member: M) -> Result<Message>
+    where
+        I: Interface,
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+    {
+        self.ctxt
+            .connection()
+            .object_server()
+            .next_call::<I, _, _>(self.ctxt.path(), member)
+            .await
+    }
 }
 
 impl<I> Clone for InterfaceRef<I> {
@@ -187,6 +208,11 @@ pub(crate) struct Node {
     children: HashMap<String, Node>,
     #[derivative(Debug = "ignore")]
     interfaces: HashMap<InterfaceName<'static>, Arc<RwLock<dyn Interface>>>,
+    // Interfaces registered (through `ObjectServer::at_prefix`) for this path and all paths below.
+    #[derivative(Debug = "ignore")]
+    prefix_interfaces: HashMap<InterfaceName<'static>, Arc<RwLock<dyn Interface>>>,
+    // The XML set through `ObjectServer::set_introspection`, replacing the generated one.
+    introspection: Option<String>,
 }
 
 impl Node {
@@ -266,17 +292,113 @@ impl Node {
         self.interfaces.get(&interface_name).cloned()
     }
 
+    // Get the interface that handles calls to `path`: either the one registered at `path` itself
+    // or the one registered for the closest prefix of `path`.
+    //
+    // Returns `None` if neither a node at `path` nor a prefix interface exists and `Some(None)` if
+    // the node exists but doesn't have the interface.
+    fn dispatch_interface_lock(
+        &self,
+        path: &ObjectPath<'_>,
+        interface_name: &InterfaceName<'_>,
+    ) -> Option<Option<Arc<RwLock<dyn Interface>>>> {
+        let mut node = self;
+        let mut prefix_iface = node.prefix_interfaces.get(interface_name);
+
+        for i in path.split('/').skip(1) {
+            if i.is_empty() {
+                continue;
+            }
+            match node.children.get(i) {
+                Some(n) => node = n,
+                None => return prefix_iface.map(|iface| Some(iface.clone())),
+            }
+            if let Some(iface) = node.prefix_interfaces.get(interface_name) {
+                prefix_iface = Some(iface);
+            }
+        }
+
+        Some(
+            node.interfaces
+                .get(interface_name)
+                .or(prefix_iface)
+                .cloned(),
+        )
+    }
+
+    // Get all the interfaces that handle calls to `path`, i-e the ones registered at `path` itself
+    // and the ones registered for any prefix of `path`, unless overridden by a closer one.
+    //
+    // Returns `None` if neither a node at `path` nor a prefix interface exists.
+    fn dispatch_interfaces(
+        &self,
+        path: &ObjectPath<'_>,
+    ) -> Option<HashMap<InterfaceName<'static>, Arc<RwLock<dyn Interface>>>> {
+        let mut node = self;
+        let mut ifaces = node.prefix_interfaces.clone();
+
+        for i in path.split('/').skip(1) {
+            if i.is_empty() {
+                continue;
+            }
+            match node.children.get(i) {
+                Some(n) => node = n,
+                None => return (!ifaces.is_empty()).then_some(ifaces),
+            }
+            ifaces.extend(
+                node.prefix_interfaces
+                    .iter()
+                    .map(|(name, iface)| (name.clone(), iface.clone())),
+            );
+        }
+        ifaces.extend(
+            node.interfaces
+                .iter()
+                .map(|(name, iface)| (name.clone(), iface.clone())),
+        );
+
+        Some(ifaces)
+    }
+
     fn remove_interface(&mut self, interface_name: InterfaceName<'static>) -> bool {
         self.interfaces.remove(&interface_name).is_some()
     }
 
     fn is_empty(&self) -> bool {
-        !self.interfaces.keys().any(|k| {
-            *k != Peer::name()
-                && *k != Introspectable::name()
-                && *k != Properties::name()
-                && *k != ObjectManager::name()
-        })
+        self.prefix_interfaces.is_empty()
+            && !self.interfaces.keys().any(|k| {
+                *k != Peer::name()
+                    && *k != Introspectable::name()
+                    && *k != Properties::name()
+                    && *k != ObjectManager::name()
+            })
+    }
+
+    // The interfaces registered at this path, either directly or as a prefix, leaving out the
+    // standard interfaces that every node implements.
+    fn registered_interfaces(&self) -> impl Iterator<Item = &InterfaceName<'static>> {
+        self.interfaces
+            .keys()
+            .filter(|k| {
+                **k != Peer::name() && **k != Introspectable::name() && **k != Properties::name()
+            })
+            .chain(self.prefix_interfaces.keys())
+    }
+
+    // Remove the node at `path`, below this one. The root node itself can't be removed.
+    fn remove_descendant(&mut self, path: &ObjectPath<'_>) -> bool {
+        let mut path_parts = path.rsplit('/').filter(|i| !i.is_empty());
+        let last_part = match path_parts.next() {
+            Some(last_part) => last_part,
+            None => return false,
+        };
+        let ppath = ObjectPath::from_string_unchecked(
+            path_parts.fold(String::new(), |a, p| format!("/{p}{a}")),
+        );
+        self.get_child_mut(&ppath, false)
+            .0
+            .unwrap()
+            .remove_node(last_part)
     }
 
     fn remove_node(&mut self, node: &str) -> bool {
@@ -334,6 +456,9 @@ impl Node {
     }
 
     pub(crate) async fn introspect(&self) -> String {
+        if let Some(xml) = &self.introspection {
+            return xml.clone();
+        }
         let mut xml = String::with_capacity(1024);
 
         self.introspect_to_writer(&mut xml).await;
@@ -355,7 +480,7 @@ impl Node {
                     && *n != &Properties::name()
                     && *n != &ObjectManager::name()
             }) {
-                let props = node.get_properties(iface_name.clone()).await;
+                let props = node.get_managed_properties(iface_name.clone()).await;
                 interfaces.insert(iface_name.clone().into(), props);
             }
             managed_objects.insert(node.path.clone(), interfaces);
@@ -365,7 +490,7 @@ impl Node {
         managed_objects
     }
 
-    async fn get_properties(
+    async fn get_managed_properties(
         &self,
         interface_name: InterfaceName<'_>,
     ) -> HashMap<String, OwnedValue> {
@@ -373,7 +498,7 @@ impl Node {
             .expect("Interface was added but not found")
             .read()
             .await
-            .get_all()
+            .get_all_managed()
             .await
     }
 }
@@ -435,10 +560,43 @@ impl Node {
 /// # })?;
 /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
 /// ```
-#[derive(Debug)]
+#[derive(derivative::Derivative)]
+#[derivative(Debug)]
 pub struct ObjectServer {
     conn: WeakConnection,
     root: RwLock<Node>,
+    intercepted_calls: Mutex<HashMap<InterceptedCall, Sender<Message>>>,
+    #[derivative(Debug = "ignore")]
+    unknown_object_handler: sync::RwLock<Option<UnknownObjectHandler>>,
+}
+
+type InterceptedCall = (OwnedObjectPath, OwnedInterfaceName, OwnedMemberName);
+
+type UnknownObjectHandler = Arc<
+    dyn Fn(
+            Connection,
+            Message,
+        ) -> Pin<Box<dyn Future<Output = fdo::Result<UnknownObjectAction>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// What the object server does with a method call to an unknown object, as decided by the handler
+/// set through [`ObjectServer::set_unknown_object_handler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownObjectAction {
+    /// The handler replied to the call itself.
+    Replied,
+    /// The handler registered an object for the called path, so the call is dispatched again.
+    Retry,
+}
+
+// The outcome of an attempt to dispatch a method call.
+enum DispatchOutcome {
+    // The call was handled by an interface, with the given result.
+    Dispatched(Result<()>),
+    // There is no object at the path of the call.
+    NoObject,
 }
 
 assert_impl_all!(ObjectServer: Send, Sync, Unpin);
@@ -449,6 +607,8 @@ impl ObjectServer {
         Self {
             conn: conn.into(),
             root: RwLock::new(Node::new("/".try_into().expect("zvariant bug"))),
+            intercepted_calls: Mutex::new(HashMap::new()),
+            unknown_object_handler: sync::RwLock::new(None),
         }
     }
 
@@ -474,6 +634,38 @@ impl ObjectServer {
             .await
     }
 
+    /// Register a D-Bus [`Interface`] for all paths under a given prefix.
+    ///
+    /// Method calls on `iface` to `prefix` or any path under it will be dispatched to `iface`,
+    /// unless the same interface is also registered at the exact path through [`ObjectServer::at`],
+    /// in which case that registration takes precedence. If multiple prefixes of a path have the
+    /// interface registered, the longest (i-e closest) one is used. The handler can find out the
+    /// concrete object path that was called through the message header (see the `header` argument
+    /// attribute of [`crate::dbus_interface`]).
+    ///
+    /// Only the given interface is dispatched this way. The standard interfaces (e.g
+    /// `org.freedesktop.DBus.Introspectable`) are only provided for paths that actually have an
+    /// object registered.
+    ///
+    /// If the interface is already registered for this prefix, returns false.
+    pub async fn at_prefix<'p, P, I>(&self, prefix: P, iface: I) -> Result<bool>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let prefix = prefix.try_into().map_err(Into::into)?;
+        let mut root = self.root().write().await;
+        let node = root.get_child_mut(&prefix, true).0.unwrap();
+
+        match node.prefix_interfaces.entry(I::name()) {
+            Entry::Vacant(e) => e.insert(Arc::new(RwLock::new(iface))),
+            Entry::Occupied(_) => return Ok(false),
+        };
+
+        Ok(true)
+    }
+
     /// Same as `at` but expects an interface already in `Arc<RwLock<dyn Interface>>` form.
     // FIXME: Better name?
     pub(crate) async fn at_ready<'node, 'p, P, F>(
@@ -497,38 +689,106 @@ impl ObjectServer {
         let node = node.unwrap();
         let added = node.at(name.clone(), iface_creator);
         if added {
-            if name == ObjectManager::name() {
-                // Just added an object manager. Need to signal all managed objects under it.
-                let ctxt = SignalContext::new(&self.connection(), path)?;
-                let objects = node.get_managed_objects().await;
-                for (path, owned_interfaces) in objects {
-                    let interfaces = owned_interfaces
-                        .iter()
-                        .map(|(i, props)| {
-                            let props = props
-                                .iter()
-                                .map(|(k, v)| (k.as_str(), Value::from(v)))
-                                .collect();
-                            (i.into(), props)
-                        })
-                        .collect();
-                    ObjectManager::interfaces_added(&ctxt, &path, &interfaces).await?;
-                }
-            } else if let Some(manager_path) = manager_path {
-                let ctxt = SignalContext::new(&self.connection(), manager_path.clone())?;
-                let mut interfaces = HashMap::new();
-                let owned_props = node.get_properties(name.clone()).await;
-                let props = owned_props
+            self.interface_added(node, &path, manager_path, name)
+                .await?;
+        }
+
+        Ok(added)
+    }
+
+    /// Register many D-Bus [`Interface`]s of the same type at once.
+    ///
+    /// This is the same as calling [`ObjectServer::at`] for each `(path, iface)` pair of
+    /// `objects`, but the object tree is only locked once for all of them, which is a lot cheaper
+    /// when registering many objects at startup.
+    ///
+    /// All paths are validated before anything gets registered. Interfaces that already exist at
+    /// their path are skipped, as with `at`. Returns the number of interfaces that were added.
+    ///
+    /// # Errors
+    ///
+    /// If an entry fails, either because of an invalid path or because the
+    /// `org.freedesktop.DBus.ObjectManager.InterfacesAdded` signal for it couldn't be emitted, an
+    /// [`Error::Failure`] mentioning the index and path of the entry is returned. The entries
+    /// before it are registered in the latter case.
+    pub async fn at_many<'p, P, I, O>(&self, objects: O) -> Result<usize>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+        O: IntoIterator<Item = (P, I)>,
+    {
+        let objects = objects
+            .into_iter()
+            .enumerate()
+            .map(|(n, (path, iface))| match path.try_into() {
+                Ok(path) => Ok((path, iface)),
+                Err(e) => Err(Error::Failure(format!(
+                    "invalid path for entry {n}: {}",
+                    e.into()
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut root = self.root().write().await;
+        let mut added = 0;
+        for (n, (path, iface)) in objects.into_iter().enumerate() {
+            let (node, manager_path) = root.get_child_mut(&path, true);
+            let node = node.unwrap();
+            if !node.at(I::name(), move || Arc::new(RwLock::new(iface))) {
+                continue;
+            }
+            self.interface_added(node, &path, manager_path, I::name())
+                .await
+                .map_err(|e| {
+                    Error::Failure(format!("failed to register entry {n} at `{path}`: {e}"))
+                })?;
+            added += 1;
+        }
+
+        Ok(added)
+    }
+
+    // Emit the `InterfacesAdded` signals for the interface `name` just added to `node`, if it's
+    // managed by an object manager.
+    async fn interface_added(
+        &self,
+        node: &Node,
+        path: &ObjectPath<'_>,
+        manager_path: Option<ObjectPath<'_>>,
+        name: InterfaceName<'static>,
+    ) -> Result<()> {
+        if name == ObjectManager::name() {
+            // Just added an object manager. Need to signal all managed objects under it.
+            let ctxt = SignalContext::new(&self.connection(), path.clone())?;
+            let objects = node.get_managed_objects().await;
+            for (path, owned_interfaces) in objects {
+                let interfaces = owned_interfaces
                     .iter()
-                    .map(|(k, v)| (k.as_str(), Value::from(v)))
+                    .map(|(i, props)| {
+                        let props = props
+                            .iter()
+                            .map(|(k, v)| (k.as_str(), Value::from(v)))
+                            .collect();
+                        (i.into(), props)
+                    })
                     .collect();
-                interfaces.insert(name, props);
-
                 ObjectManager::interfaces_added(&ctxt, &path, &interfaces).await?;
             }
+        } else if let Some(manager_path) = manager_path {
+            let ctxt = SignalContext::new(&self.connection(), manager_path.clone())?;
+            let mut interfaces = HashMap::new();
+            let owned_props = node.get_managed_properties(name.clone()).await;
+            let props = owned_props
+                .iter()
+                .map(|(k, v)| (k.as_str(), Value::from(v)))
+                .collect();
+            interfaces.insert(name, props);
+
+            ObjectManager::interfaces_added(&ctxt, path, &interfaces).await?;
         }
 
-        Ok(added)
+        Ok(())
     }
 
     /// Unregister a D-Bus [`Interface`] at a given path.
@@ -553,20 +813,71 @@ impl ObjectServer {
             ObjectManager::interfaces_removed(&ctxt, &path, &[I::name()]).await?;
         }
         if node.is_empty() {
-            let mut path_parts = path.rsplit('/').filter(|i| !i.is_empty());
-            let last_part = path_parts.next().unwrap();
-            let ppath = ObjectPath::from_string_unchecked(
-                path_parts.fold(String::new(), |a, p| format!("/{p}{a}")),
-            );
-            root.get_child_mut(&ppath, false)
-                .0
-                .unwrap()
-                .remove_node(last_part);
-            return Ok(true);
+            return Ok(root.remove_descendant(&path));
         }
         Ok(false)
     }
 
+    /// Unregister a D-Bus [`Interface`] registered for a path prefix through
+    /// [`ObjectServer::at_prefix`].
+    ///
+    /// Registrations of the interface at the exact paths under `prefix`, or for other prefixes,
+    /// are kept. If there are no more interfaces left at that path, nor objects under it, destroys
+    /// the object as well. Returns whether the object was destroyed.
+    pub async fn remove_prefix<'p, I, P>(&self, prefix: P) -> Result<bool>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let prefix = prefix.try_into().map_err(Into::into)?;
+        let mut root = self.root.write().await;
+        let node = root
+            .get_child_mut(&prefix, false)
+            .0
+            .ok_or(Error::InterfaceNotFound)?;
+        if node.prefix_interfaces.remove(&I::name()).is_none() {
+            return Err(Error::InterfaceNotFound);
+        }
+        // The objects registered under the prefix are kept.
+        if node.is_empty() && node.children.is_empty() {
+            return Ok(root.remove_descendant(&prefix));
+        }
+        Ok(false)
+    }
+
+    /// Replace the instance of the D-Bus [`Interface`] registered at a given path.
+    ///
+    /// The registration is kept as is and only the implementation behind it is swapped, so clients
+    /// never see the object or the interface disappear in between. The swap waits for the method
+    /// calls and property accesses being handled by the old instance to complete. All those
+    /// dispatched after it are handled by the new instance. No call ever sees both instances.
+    /// Existing [`InterfaceRef`]s to the interface refer to the new instance afterwards.
+    ///
+    /// No signals are emitted by this method. If the properties of `iface` differ from those of the
+    /// old instance, it's up to you to emit the relevant `PropertiesChanged` signals.
+    ///
+    /// Since it waits for in-flight calls, this method must not be called from a method or property
+    /// of the same interface instance, or it would wait forever.
+    ///
+    /// Returns the old instance.
+    ///
+    /// # Errors
+    ///
+    /// If the interface is not registered at the given path, `Error::InterfaceNotFound` error is
+    /// returned.
+    pub async fn replace<'p, P, I>(&self, path: P, iface: I) -> Result<I>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let iface_ref = self.interface::<_, I>(path).await?;
+        let mut old = iface_ref.get_mut().await;
+
+        Ok(std::mem::replace(&mut *old, iface))
+    }
+
     /// Get the interface at the given path.
     ///
     /// # Errors
@@ -641,38 +952,366 @@ impl ObjectServer {
         })
     }
 
-    #[instrument(skip(self, connection))]
+    /// Get the introspection XML of the object at the given path.
+    ///
+    /// This is the same XML that peers receive when calling the `Introspect` method of the
+    /// `org.freedesktop.DBus.Introspectable` interface on `path`, but generated locally, without a
+    /// round trip through the bus.
+    ///
+    /// # Errors
+    ///
+    /// If no object is registered at `path` or under it, [`Error::FDO`] with
+    /// [`fdo::Error::UnknownObject`] is returned, just as peers would get.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # use zbus::{Connection, dbus_interface};
+    /// #
+    /// struct MyIface;
+    ///
+    /// #[dbus_interface(name = "org.myiface.MyIface")]
+    /// impl MyIface {
+    ///     fn ping(&self) {}
+    /// }
+    ///
+    /// # async_io::block_on(async {
+    /// let connection = Connection::session().await?;
+    /// connection.object_server().at("/org/zbus/path", MyIface).await?;
+    ///
+    /// let xml = connection.object_server().introspect("/org/zbus/path").await?;
+    /// assert!(xml.contains(r#"<interface name="org.myiface.MyIface">"#));
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// # })?;
+    /// #
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    pub async fn introspect<'p, P>(&self, path: P) -> Result<String>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let path = path.try_into().map_err(Into::into)?;
+        let root = self.root().read().await;
+        let node = root.get_child(&path).ok_or_else(|| {
+            Error::FDO(Box::new(fdo::Error::UnknownObject(format!(
+                "Unknown object '{path}'"
+            ))))
+        })?;
+
+        Ok(node.introspect().await)
+    }
+
+    /// The paths of all the objects registered on the server, in sorted order.
+    ///
+    /// A path is listed if at least one interface is registered at it, through
+    /// [`ObjectServer::at`] or [`ObjectServer::at_prefix`]. The intermediate nodes, which only
+    /// exist to reach the objects below them, are not. The list is a snapshot: it's taken at once,
+    /// while no object is being added or removed, but it can be outdated as soon as it's returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # use zbus::{Connection, dbus_interface};
+    /// #
+    /// struct MyIface;
+    ///
+    /// #[dbus_interface(name = "org.myiface.MyIface")]
+    /// impl MyIface {
+    ///     fn ping(&self) {}
+    /// }
+    ///
+    /// # zbus::block_on(async {
+    /// let connection = Connection::session().await?;
+    /// let object_server = connection.object_server();
+    /// object_server.at("/org/zbus/a", MyIface).await?;
+    /// object_server.at("/org/zbus/b", MyIface).await?;
+    ///
+    /// let paths = object_server.paths().await;
+    /// let paths: Vec<_> = paths.iter().map(|p| p.as_str()).collect();
+    /// assert_eq!(paths, ["/org/zbus/a", "/org/zbus/b"]);
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// # })?;
+    /// #
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    pub async fn paths(&self) -> Vec<OwnedObjectPath> {
+        let root = self.root.read().await;
+        let mut paths = vec![];
+        let mut node_list = vec![&*root];
+        while let Some(node) = node_list.pop() {
+            if node.registered_interfaces().next().is_some() {
+                paths.push(node.path.clone());
+            }
+            node_list.extend(node.children.values());
+        }
+        paths.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+        paths
+    }
+
+    /// The names of the interfaces registered at the given path, in sorted order.
+    ///
+    /// This includes the interfaces registered through [`ObjectServer::at_prefix`] at `path`
+    /// itself, but not those of the prefixes above it. The standard `org.freedesktop.DBus.Peer`,
+    /// `org.freedesktop.DBus.Introspectable` and `org.freedesktop.DBus.Properties` interfaces,
+    /// which all objects implement, are left out. An empty list is returned if no object is
+    /// registered at `path`.
+    ///
+    /// Like [`ObjectServer::paths`], the list is a snapshot of the registrations.
+    pub async fn interfaces_at<'p, P>(&self, path: P) -> Result<Vec<InterfaceName<'static>>>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let path = path.try_into().map_err(Into::into)?;
+        let root = self.root.read().await;
+        let mut names: Vec<_> = root
+            .get_child(&path)
+            .map(|node| node.registered_interfaces().cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// Override the introspection XML of the object at the given path.
+    ///
+    /// From then on, `xml` is returned as is to the peers calling the `Introspect` method of the
+    /// `org.freedesktop.DBus.Introspectable` interface on `path` (as well as by
+    /// [`ObjectServer::introspect`]), e.g to add annotations that the generated XML lacks. Note
+    /// that it **replaces** the generated XML, rather than being merged into it, so it must
+    /// describe all the interfaces and child nodes of the object itself. The XML of the parent
+    /// objects, which includes that of their children, is not affected.
+    ///
+    /// Pass `None` to go back to the generated XML. The override is also dropped along with the
+    /// object, when its last interface is removed.
+    ///
+    /// This method is only available with the `xml` feature, since `xml` is parsed to check its
+    /// validity.
+    ///
+    /// # Errors
+    ///
+    /// If `xml` is not a valid introspection document, [`Error::QuickXml`] is returned. If no
+    /// object is registered at `path` or under it, [`Error::FDO`] with
+    /// [`fdo::Error::UnknownObject`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # use zbus::{Connection, dbus_interface};
+    /// #
+    /// struct MyIface;
+    ///
+    /// #[dbus_interface(name = "org.myiface.MyIface")]
+    /// impl MyIface {
+    ///     fn ping(&self) {}
+    /// }
+    ///
+    /// # zbus::block_on(async {
+    /// let connection = Connection::session().await?;
+    /// let object_server = connection.object_server();
+    /// object_server.at("/org/zbus/path", MyIface).await?;
+    ///
+    /// let xml = r#"<node>
+    ///   <interface name="org.myiface.MyIface">
+    ///     <method name="Ping">
+    ///       <annotation name="org.freedesktop.DBus.Method.NoReply" value="true"/>
+    ///     </method>
+    ///   </interface>
+    /// </node>"#;
+    /// object_server
+    ///     .set_introspection("/org/zbus/path", Some(xml.to_string()))
+    ///     .await?;
+    /// assert_eq!(object_server.introspect("/org/zbus/path").await?, xml);
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// # })?;
+    /// #
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    #[cfg(feature = "xml")]
+    pub async fn set_introspection<'p, P>(&self, path: P, xml: Option<String>) -> Result<()>
+    where
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+    {
+        let path = path.try_into().map_err(Into::into)?;
+        if let Some(xml) = &xml {
+            crate::xml::Node::from_reader(xml.as_bytes())?;
+        }
+        let mut root = self.root().write().await;
+        let node = root.get_child_mut(&path, false).0.ok_or_else(|| {
+            Error::FDO(Box::new(fdo::Error::UnknownObject(format!(
+                "Unknown object '{path}'"
+            ))))
+        })?;
+        node.introspection = xml;
+
+        Ok(())
+    }
+
+    /// Wait for the next call to the method `member` of the interface `I` at the given path.
+    ///
+    /// Instead of being dispatched to the interface as usual, the next such call is handed over as
+    /// is and it's up to the caller to reply to it (e.g using [`Connection::reply`]). This is mostly
+    /// useful for tests and simple request-driven services. Other methods of the interface are
+    /// dispatched normally.
+    ///
+    /// Interception and normal dispatch are mutually exclusive per method: while this method is
+    /// being awaited, the interface implementation will not see the calls to `member`, and only
+    /// one call is intercepted for each invocation of this method. Once the returned future is
+    /// resolved or dropped, normal dispatch resumes.
+    ///
+    /// # Errors
+    ///
+    /// If another task is already waiting for calls to the same method, [`Error::Failure`] is
+    /// returned.
+    pub async fn next_call<'p, 'm, I, P, M>(&self, path: P, member: M) -> Result<Message>
+    where
+        I: Interface,
+        P: TryInto<ObjectPath<'p>>,
+        P::Error: Into<Error>,
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+    {
+        let path = path.try_into().map_err(Into::into)?;
+        let member = member.try_into().map_err(Into::into)?;
+        let key = (path.into(), I::name().into(), member.into());
+
+        let (sender, mut receiver) = broadcast(1);
+        match self.intercepted_calls.lock().await.entry(key) {
+            Entry::Occupied(e) if !e.get().is_closed() => {
+                let (path, iface, member) = e.key();
+                return Err(Error::Failure(format!(
+                    "Calls to `{iface}.{member}` at `{path}` are already being awaited"
+                )));
+            }
+            Entry::Occupied(mut e) => {
+                e.insert(sender);
+            }
+            Entry::Vacant(e) => {
+                e.insert(sender);
+            }
+        }
+
+        receiver
+            .recv()
+            .await
+            .map_err(|e| Error::Failure(e.to_string()))
+    }
+
+    /// Handle the method calls to paths with no object registered.
+    ///
+    /// By default, these calls are replied to with an [`fdo::Error::UnknownObject`] error. Once a
+    /// handler is set, it's called with the connection and the method call instead, and decides
+    /// what to do with it:
+    ///
+    /// * register an object for the path (e.g lazily, for objects too many or too expensive to be
+    ///   registered upfront) and return [`UnknownObjectAction::Retry`] for the call to be
+    ///   dispatched to it. There is only one retry: if the object still isn't found, the default
+    ///   error is returned.
+    /// * reply by itself (e.g through [`Connection::reply`]) and return
+    ///   [`UnknownObjectAction::Replied`].
+    /// * return an error, to be replied to the call. That's how the default error is kept, for
+    ///   the paths the handler isn't interested in.
+    ///
+    /// Only the calls to paths without any object are concerned. The calls to an unknown interface
+    /// or method of an existing object are replied to with the usual errors. Setting a handler
+    /// replaces the previous one, if any.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// use zbus::{dbus_interface, fdo, object_server::UnknownObjectAction, Connection};
+    ///
+    /// struct Item;
+    ///
+    /// #[dbus_interface(name = "org.myiface.Item")]
+    /// impl Item {
+    ///     fn name(&self) -> &str {
+    ///         "item"
+    ///     }
+    /// }
+    ///
+    /// # zbus::block_on(async {
+    /// let connection = Connection::session().await?;
+    /// connection
+    ///     .object_server()
+    ///     .set_unknown_object_handler(|conn, msg| async move {
+    ///         // Only register the items on demand.
+    ///         let path = msg.path().expect("method calls have a path");
+    ///         if !path.as_str().starts_with("/org/myiface/items/") {
+    ///             return Err(fdo::Error::UnknownObject(format!("Unknown object '{path}'")));
+    ///         }
+    ///         conn.object_server()
+    ///             .at(path, Item)
+    ///             .await
+    ///             .map_err(|e| fdo::Error::Failed(e.to_string()))?;
+    ///
+    ///         Ok(UnknownObjectAction::Retry)
+    ///     });
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// # })?;
+    /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+    /// ```
+    pub fn set_unknown_object_handler<F, Fut>(&self, handler: F)
+    where
+        F: Fn(Connection, Message) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = fdo::Result<UnknownObjectAction>> + Send + 'static,
+    {
+        let handler: UnknownObjectHandler = Arc::new(move |conn, msg| Box::pin(handler(conn, msg)));
+        *self.unknown_object_handler.write().expect("poisoned lock") = Some(handler);
+    }
+
+    // Hand the message over to the task waiting in `next_call` for it, if any.
+    async fn intercept_method_call(&self, msg: &Message) -> bool {
+        let key = match (msg.path(), msg.interface(), msg.member()) {
+            (Some(path), Some(iface), Some(member)) => (path.into(), iface.into(), member.into()),
+            _ => return false,
+        };
+        let sender = match self.intercepted_calls.lock().await.remove(&key) {
+            Some(sender) => sender,
+            None => return false,
+        };
+
+        sender.try_broadcast(msg.clone()).is_ok()
+    }
+
+    #[instrument(skip(self, connection, msg))]
     async fn dispatch_method_call_try(
         &self,
         connection: &Connection,
         msg: &Message,
-    ) -> fdo::Result<Result<()>> {
+    ) -> fdo::Result<DispatchOutcome> {
         let path = msg
             .path()
             .ok_or_else(|| fdo::Error::Failed("Missing object path".into()))?;
-        let iface_name = msg
-            .interface()
-            // TODO: In the absence of an INTERFACE field, if two or more interfaces on the same
-            // object have a method with the same name, it is undefined which of those
-            // methods will be invoked. Implementations may choose to either return an
-            // error, or deliver the message as though it had an arbitrary one of those
-            // interfaces.
-            .ok_or_else(|| fdo::Error::Failed("Missing interface".into()))?;
         let member = msg
             .member()
             .ok_or_else(|| fdo::Error::Failed("Missing member".into()))?;
 
         // Ensure the root lock isn't held while dispatching the message. That
         // way, the object server can be mutated during that time.
-        let iface = {
-            let root = self.root.read().await;
-            let node = root
-                .get_child(&path)
-                .ok_or_else(|| fdo::Error::UnknownObject(format!("Unknown object '{path}'")))?;
-
-            node.interface_lock(iface_name.as_ref()).ok_or_else(|| {
-                fdo::Error::UnknownInterface(format!("Unknown interface '{iface_name}'"))
-            })?
+        let (iface_name, iface) = match msg.interface() {
+            Some(iface_name) => {
+                let root = self.root.read().await;
+                let iface = match root.dispatch_interface_lock(&path, &iface_name) {
+                    Some(iface) => iface.ok_or_else(|| {
+                        fdo::Error::UnknownInterface(format!("Unknown interface '{iface_name}'"))
+                    })?,
+                    None => return Ok(DispatchOutcome::NoObject),
+                };
+
+                (iface_name, iface)
+            }
+            None => match self.method_interface(&path, &member).await? {
+                Some(found) => found,
+                None => return Ok(DispatchOutcome::NoObject),
+            },
         };
 
         trace!("acquiring read lock on interface `{}`", iface_name);
@@ -685,7 +1324,7 @@ impl ObjectServer {
                 )));
             }
             DispatchResult::Async(f) => {
-                return Ok(f.await);
+                return Ok(DispatchOutcome::Dispatched(f.await));
             }
             DispatchResult::RequiresMut => {}
         }
@@ -697,7 +1336,7 @@ impl ObjectServer {
             DispatchResult::NotFound => {}
             DispatchResult::RequiresMut => {}
             DispatchResult::Async(f) => {
-                return Ok(f.await);
+                return Ok(DispatchOutcome::Dispatched(f.await));
             }
         }
         drop(write_lock);
@@ -706,17 +1345,71 @@ impl ObjectServer {
         )))
     }
 
-    #[instrument(skip(self, connection))]
+    // Find the only interface at `path` that has a method named `member`, for method calls that
+    // don't specify the interface. Returns `None` if there is no object at `path`.
+    async fn method_interface(
+        &self,
+        path: &ObjectPath<'_>,
+        member: &MemberName<'_>,
+    ) -> fdo::Result<Option<(InterfaceName<'static>, Arc<RwLock<dyn Interface>>)>> {
+        let ifaces = match self.root.read().await.dispatch_interfaces(path) {
+            Some(ifaces) => ifaces,
+            None => return Ok(None),
+        };
+
+        let mut found = None;
+        for (name, iface) in ifaces {
+            if !iface.read().await.has_method(member) {
+                continue;
+            }
+            if let Some((other, _)) = &found {
+                return Err(fdo::Error::Failed(format!(
+                    "Ambiguous method '{member}': implemented by both '{other}' and '{name}' \
+                     interfaces, the interface must be specified"
+                )));
+            }
+            found = Some((name, iface));
+        }
+
+        found
+            .map(Some)
+            .ok_or_else(|| fdo::Error::UnknownMethod(format!("Unknown method '{member}'")))
+    }
+
+    #[instrument(skip(self, connection, msg))]
     async fn dispatch_method_call(&self, connection: &Connection, msg: &Message) -> Result<()> {
-        match self.dispatch_method_call_try(connection, msg).await {
-            Err(e) => {
-                let hdr = msg.header()?;
-                debug!("Returning error: {}", e);
-                connection.reply_dbus_error(&hdr, e).await?;
-                Ok(())
+        let mut res = self.dispatch_method_call_try(connection, msg).await;
+        if let Ok(DispatchOutcome::NoObject) = res {
+            let handler = self
+                .unknown_object_handler
+                .read()
+                .expect("poisoned lock")
+                .clone();
+            if let Some(handler) = handler {
+                res = match handler(connection.clone(), msg.clone()).await {
+                    Ok(UnknownObjectAction::Replied) => return Ok(()),
+                    Ok(UnknownObjectAction::Retry) => {
+                        trace!("Retrying the dispatch of: {}", msg);
+                        self.dispatch_method_call_try(connection, msg).await
+                    }
+                    Err(e) => Err(e),
+                };
             }
-            Ok(r) => r,
         }
+
+        let e = match res {
+            Ok(DispatchOutcome::Dispatched(r)) => return r,
+            Ok(DispatchOutcome::NoObject) => {
+                let path = msg.path().expect("dispatched a call without a path");
+                fdo::Error::UnknownObject(format!("Unknown object '{path}'"))
+            }
+            Err(e) => e,
+        };
+        let hdr = msg.header()?;
+        debug!("Returning error: {}", e);
+        connection.reply_dbus_error(&hdr, e).await?;
+
+        Ok(())
     }
 
     /// Dispatch an incoming message to a registered interface.
@@ -731,13 +1424,34 @@ impl ObjectServer {
     ///   the caller through the associated server connection.
     ///
     /// Returns an error if the message is malformed, true if it's handled, false otherwise.
-    #[instrument(skip(self))]
     pub(crate) async fn dispatch_message(&self, msg: &Message) -> Result<bool> {
-        let conn = self.connection();
-        self.dispatch_method_call(&conn, msg).await?;
-        trace!("Handled: {}", msg);
+        let span = {
+            let hdr = msg.header()?;
+            debug_span!(
+                "dispatch_message",
+                serial = hdr.primary().serial_num().map(|s| s.get()),
+                sender = hdr.sender()?.map(|s| s.as_str()),
+                path = hdr.path()?.map(|p| p.as_str()),
+                interface = hdr.interface()?.map(|i| i.as_str()),
+                member = hdr.member()?.map(|m| m.as_str()),
+            )
+        };
 
-        Ok(true)
+        async move {
+            if self.intercept_method_call(msg).await {
+                trace!("Intercepted: {}", msg);
+
+                return Ok(true);
+            }
+
+            let conn = self.connection();
+            self.dispatch_method_call(&conn, msg).await?;
+            trace!("Handled: {}", msg);
+
+            Ok(true)
+        }
+        .instrument(span)
+        .await
     }
 
     pub(crate) fn connection(&self) -> Connection {
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use zbus_names::InterfaceName;
+use zvariant::Value;
+
+use crate::{fdo, object_server::SignalContext, Result};
+
+/// A builder for a `org.freedesktop.DBus.Properties.PropertiesChanged` signal reporting the
+/// changes of multiple properties at once.
+///
+/// Emitting a single signal when updating several properties together is not only cheaper than
+/// emitting one per property, it also ensures that the peers never see the object in an
+/// intermediate state. Use [`SignalContext::properties_changed`] to create one.
+///
+/// # Example
+///
+/// ```no_run
+/// # use zbus::{dbus_interface, SignalContext};
+/// struct Player {
+///     title: String,
+///     position: u64,
+/// }
+///
+/// #[dbus_interface(name = "org.myservice.Player")]
+/// impl Player {
+///     async fn play(
+///         &mut self,
+///         title: String,
+///         #[zbus(signal_context)] ctxt: SignalContext<'_>,
+///     ) -> zbus::fdo::Result<()> {
+///         self.title = title;
+///         self.position = 0;
+///
+///         ctxt.properties_changed("org.myservice.Player")?
+///             .changed("Title", &*self.title)
+///             .changed("Position", self.position)
+///             .emit()
+///             .await?;
+///
+///         Ok(())
+///     }
+///
+///     #[dbus_interface(property)]
+///     fn title(&self) -> &str {
+///         &self.title
+///     }
+///
+///     #[dbus_interface(property)]
+///     fn position(&self) -> u64 {
+///         self.position
+///     }
+/// }
+/// # let _ = zbus::blocking::connection::Builder::session()?.serve_at(
+/// #     "/org/myservice/Player",
+/// #     Player { title: String::new(), position: 0 },
+/// # )?;
+/// # Ok::<(), zbus::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct PropertiesChangedBuilder<'b> {
+    ctxt: &'b SignalContext<'b>,
+    interface: InterfaceName<'b>,
+    changed: Vec<(&'b str, Value<'b>)>,
+    invalidated: Vec<&'b str>,
+}
+
+impl<'b> PropertiesChangedBuilder<'b> {
+    pub(crate) fn new(ctxt: &'b SignalContext<'b>, interface: InterfaceName<'b>) -> Self {
+        Self {
+            ctxt,
+            interface,
+            changed: vec![],
+            invalidated: vec![],
+        }
+    }
+
+    /// Report the new `value` of the property `name`.
+    ///
+    /// This replaces any previous report for the same property.
+    #[must_use]
+    pub fn changed<V>(mut self, name: &'b str, value: V) -> Self
+    where
+        V: Into<Value<'b>>,
+    {
+        self.remove(name);
+        self.changed.push((name, value.into()));
+
+        self
+    }
+
+    /// Report that the property `name` changed, without giving its new value.
+    ///
+    /// This replaces any previous report for the same property.
+    #[must_use]
+    pub fn invalidated(mut self, name: &'b str) -> Self {
+        self.remove(name);
+        self.invalidated.push(name);
+
+        self
+    }
+
+    /// Whether no property change has been reported yet.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.invalidated.is_empty()
+    }
+
+    /// Emit the signal, with all the reported changes.
+    ///
+    /// Nothing is emitted if no change was reported.
+    pub async fn emit(self) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let changed: HashMap<&str, &Value<'_>> = self
+            .changed
+            .iter()
+            .map(|(name, value)| (*name, value))
+            .collect();
+
+        fdo::Properties::properties_changed(self.ctxt, self.interface, &changed, &self.invalidated)
+            .await
+    }
+
+    fn remove(&mut self, name: &str) {
+        self.changed.retain(|(n, _)| *n != name);
+        self.invalidated.retain(|n| *n != name);
+    }
+}
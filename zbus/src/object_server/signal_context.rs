@@ -1,6 +1,14 @@
-use zbus_names::BusName;
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
+use zbus_names::{BusName, InterfaceName, MemberName};
+use zvariant::Signature;
 
-use crate::{zvariant::ObjectPath, Connection, Error, Result};
+use crate::{
+    message::{self, Message, NATIVE_ENDIAN_SIG},
+    object_server::PropertiesChangedBuilder,
+    zvariant::ObjectPath,
+    Connection, Error, Result,
+};
 
 /// A signal emission context.
 ///
@@ -66,6 +74,137 @@ impl<'s> SignalContext<'s> {
         self.destination.as_ref()
     }
 
+    /// Whether the signals emitted with this context may be received by anyone.
+    ///
+    /// This allows skipping the construction of signals that are expensive to build, when nobody
+    /// would receive them anyway:
+    ///
+    /// ```no_run
+    /// # use zbus::{dbus_interface, SignalContext};
+    /// struct Sampler;
+    ///
+    /// #[dbus_interface(name = "org.zbus.Sampler")]
+    /// impl Sampler {
+    ///     #[dbus_interface(signal)]
+    ///     async fn samples(ctxt: &SignalContext<'_>, samples: Vec<f64>) -> zbus::Result<()>;
+    /// }
+    ///
+    /// # zbus::block_on(async {
+    /// # let connection = zbus::Connection::session().await?;
+    /// # let ctxt = SignalContext::new(&connection, "/org/zbus/Sampler")?;
+    /// if ctxt.has_subscribers().await {
+    ///     let samples = (0..1024).map(|i| f64::from(i).sin()).collect();
+    ///     Sampler::samples(&ctxt, samples).await?;
+    /// }
+    /// # Ok::<_, zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// On a peer-to-peer connection, the peer receives all the signals so this returns `true` as
+    /// long as the connection is open.
+    ///
+    /// **Note:** On a bus connection, the match rules of the other peers are only known to the
+    /// bus, which doesn't tell whether any of them matches a given signal. Hence this always
+    /// returns `true` there, unless the connection is closed.
+    pub async fn has_subscribers(&self) -> bool {
+        !self.conn.is_closed().await
+    }
+
+    /// Create a builder for a single `PropertiesChanged` signal reporting the changes of multiple
+    /// properties of `interface`.
+    ///
+    /// See [`PropertiesChangedBuilder`] for details.
+    pub fn properties_changed<'b, I>(&'b self, interface: I) -> Result<PropertiesChangedBuilder<'b>>
+    where
+        I: TryInto<InterfaceName<'b>>,
+        I::Error: Into<Error>,
+    {
+        let interface = interface.try_into().map_err(Into::into)?;
+
+        Ok(PropertiesChangedBuilder::new(self, interface))
+    }
+
+    /// Emit a signal with an already serialized body.
+    ///
+    /// This is meant for relays and bridges, which re-broadcast the body of the signals they
+    /// receive: `body` is sent as is, without deserializing and serializing it again. For the common
+    /// case of relaying a received signal message, use the safe [`SignalContext::relay`] instead.
+    ///
+    /// Only the validity of `signature` is checked. Since the context doesn't know the declaration
+    /// of the signals, it's up to the caller to ensure that `signature` is the one of `signal_name`.
+    ///
+    /// # Safety
+    ///
+    /// `body` must be the encoding of a value of type `signature`, in the D-Bus format and in the
+    /// native byte order, as otherwise an invalid message is sent. On Unix, `fds` are the file
+    /// descriptors the body refers to and must remain open until this call returns.
+    pub async unsafe fn emit_raw<'i, 'm, 'b, I, M, S>(
+        &self,
+        interface: I,
+        signal_name: M,
+        body: &[u8],
+        signature: S,
+        #[cfg(unix)] fds: Vec<RawFd>,
+    ) -> Result<()>
+    where
+        I: TryInto<InterfaceName<'i>>,
+        I::Error: Into<Error>,
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        S: TryInto<Signature<'b>>,
+        S::Error: Into<Error>,
+    {
+        let mut builder = message::Builder::signal(&self.path, interface, signal_name)?;
+        if let Some(sender) = self.conn.unique_name() {
+            builder = builder.sender(sender)?;
+        }
+        if let Some(destination) = &self.destination {
+            builder = builder.destination(destination)?;
+        }
+        let msg = builder.build_raw_body(
+            body,
+            signature,
+            #[cfg(unix)]
+            fds,
+        )?;
+
+        self.conn.send_message(msg).await.map(|_| ())
+    }
+
+    /// Re-emit the `signal` message received from another peer, from the associated object path.
+    ///
+    /// The interface, the member and the body of `signal` are used as is: the body isn't
+    /// deserialized and serialized again. Returns [`Error::InvalidField`] if `signal` isn't a
+    /// signal message and [`Error::IncorrectEndian`] if it's not in the native byte order.
+    pub async fn relay(&self, signal: &Message) -> Result<()> {
+        if signal.message_type() != message::Type::Signal {
+            return Err(Error::InvalidField);
+        }
+        if signal.primary_header().endian_sig() != NATIVE_ENDIAN_SIG {
+            return Err(Error::IncorrectEndian);
+        }
+        let interface = signal.interface().ok_or(Error::MissingField)?;
+        let member = signal.member().ok_or(Error::MissingField)?;
+        let signature = match signal.body_signature() {
+            Ok(signature) => signature,
+            Err(Error::NoBodySignature) => Signature::from_static_str_unchecked(""),
+            Err(e) => return Err(e),
+        };
+
+        // SAFETY: The body, signature and fds come from a valid message in the native byte order.
+        unsafe {
+            self.emit_raw(
+                interface,
+                member,
+                signal.body_as_bytes()?,
+                signature,
+                #[cfg(unix)]
+                signal.fds(),
+            )
+            .await
+        }
+    }
+
     /// Creates an owned clone of `self`.
     pub fn to_owned(&self) -> SignalContext<'static> {
         SignalContext {
@@ -0,0 +1,153 @@
+use futures_core::stream;
+use serde::de::DeserializeOwned;
+use static_assertions::assert_impl_all;
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use zvariant::{EncodingContext, Signature, Type};
+
+use crate::{message::Message, utils::padding_for_n_bytes, Error, Result};
+
+/// A [`stream::Stream`] implementation that yields the elements of an array returned by a method.
+///
+/// Instead of deserializing the whole array at once, the elements are deserialized one at a time,
+/// as the stream is polled. This avoids allocating a potentially large `Vec` when the elements only
+/// need to be processed one by one.
+///
+/// This is what the methods generated for the `stream` attribute of the [`dbus_proxy`] macro
+/// return, but it can be created from any method reply whose body is a single array of `T`.
+///
+/// # Example
+///
+/// ```
+/// # zbus::block_on(async {
+/// use futures_util::stream::StreamExt;
+/// use zbus::{proxy::ArrayStream, Connection, Proxy};
+///
+/// let connection = Connection::session().await?;
+/// let proxy = Proxy::new(
+///     &connection,
+///     "org.freedesktop.DBus",
+///     "/org/freedesktop/DBus",
+///     "org.freedesktop.DBus",
+/// )
+/// .await?;
+/// let reply = proxy.call_method("ListNames", &()).await?;
+/// let mut names = ArrayStream::<String>::new(reply)?;
+/// while let Some(name) = names.next().await {
+///     println!("{}", name?);
+/// }
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+///
+/// [`dbus_proxy`]: attr.dbus_proxy.html
+#[derive(Debug)]
+pub struct ArrayStream<T> {
+    reply: Arc<Message>,
+    /// The offset of the next element in the body.
+    pos: usize,
+    /// The offset of the end of the array in the body.
+    end: usize,
+    phantom: PhantomData<fn() -> T>,
+}
+
+assert_impl_all!(ArrayStream<()>: Send, Sync, Unpin);
+
+impl<T> ArrayStream<T>
+where
+    T: DeserializeOwned + Type,
+{
+    /// Create a stream for the elements of the array in the body of `reply`.
+    ///
+    /// Returns an error if the body of `reply` isn't a single array of `T`.
+    pub fn new(reply: Arc<Message>) -> Result<Self> {
+        let signature = reply.body_signature()?;
+        let elem_signature = T::signature();
+        let expected = format!("a{elem_signature}");
+        if signature != expected.as_str() {
+            return Err(Error::Variant(zvariant::Error::SignatureMismatch(
+                signature.to_owned(),
+                format!("`{expected}`"),
+            )));
+        }
+
+        let body = reply.body_as_bytes()?;
+        let (len, pos) = zvariant::from_slice::<_, u32>(body, ctxt(0))?;
+        // The elements are aligned, even if there is none.
+        let pos = pos + padding_for_n_bytes(pos, alignment(&elem_signature));
+        let end = pos + len as usize;
+        if end > body.len() {
+            return Err(Error::Variant(zvariant::Error::OutOfBounds));
+        }
+
+        Ok(Self {
+            reply,
+            pos,
+            end,
+            phantom: PhantomData,
+        })
+    }
+
+    /// The message the elements are deserialized from.
+    pub fn message(&self) -> &Arc<Message> {
+        &self.reply
+    }
+
+    /// Deserialize the next element, if any.
+    pub(crate) fn next_element(&mut self) -> Option<Result<T>> {
+        if self.pos >= self.end {
+            return None;
+        }
+        let bytes = match self.reply.body_as_bytes() {
+            Ok(body) => &body[self.pos..self.end],
+            Err(e) => return Some(Err(e)),
+        };
+        #[cfg(unix)]
+        let res = zvariant::from_slice_fds(bytes, Some(&self.reply.fds()), ctxt(self.pos));
+        #[cfg(not(unix))]
+        let res = zvariant::from_slice(bytes, ctxt(self.pos));
+
+        match res {
+            Ok((elem, len)) => {
+                self.pos += len;
+
+                Some(Ok(elem))
+            }
+            Err(e) => {
+                // There's no way to find the next element after a malformed one.
+                self.pos = self.end;
+
+                Some(Err(e.into()))
+            }
+        }
+    }
+}
+
+impl<T> stream::Stream for ArrayStream<T>
+where
+    T: DeserializeOwned + Type,
+{
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.get_mut().next_element())
+    }
+}
+
+fn ctxt(pos: usize) -> EncodingContext<byteorder::NativeEndian> {
+    EncodingContext::new_dbus(pos)
+}
+
+/// The alignment of values with the given `signature`, in the D-Bus format.
+fn alignment(signature: &Signature<'_>) -> usize {
+    match signature.as_bytes().first() {
+        Some(b'(' | b'{' | b'x' | b't' | b'd') => 8,
+        Some(b'n' | b'q') => 2,
+        Some(b'y' | b'g' | b'v') => 1,
+        _ => 4,
+    }
+}
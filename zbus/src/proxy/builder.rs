@@ -4,18 +4,32 @@ use static_assertions::assert_impl_all;
 use zbus_names::{BusName, InterfaceName};
 use zvariant::{ObjectPath, Str};
 
-use crate::{proxy::ProxyInner, Connection, Error, Proxy, Result};
+use crate::{
+    proxy::{CacheFilter, ProxyInner},
+    Connection, Error, Proxy, Result,
+};
 
 /// The properties caching mode.
+///
+/// When cached, the properties are all fetched at once with a `GetAll` call, then kept up to date
+/// through the `PropertiesChanged` signal.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum CacheProperties {
     /// Cache properties. The properties will be cached upfront as part of the proxy
     /// creation.
+    ///
+    /// Building the proxy then waits for the `GetAll` call to complete, which costs an extra
+    /// round trip to the service (and fails if the call does), but the first reads of the
+    /// properties are served from the cache right away. This suits latency-sensitive code reading
+    /// the properties soon after creating the proxy, e.g to fill a UI.
     Yes,
     /// Don't cache properties.
     No,
     /// Cache properties but only populate the cache on the first read of a property (default).
+    ///
+    /// Building the proxy doesn't wait for the service, but the first read of a property waits for
+    /// the `GetAll` call.
     #[default]
     Lazily,
 }
@@ -30,6 +44,12 @@ pub struct Builder<'a, T = ()> {
     proxy_type: PhantomData<T>,
     cache: CacheProperties,
     uncached_properties: Option<HashSet<Str<'a>>>,
+    cached_properties: Option<HashSet<Str<'a>>>,
+    auto_start: bool,
+    auto_start_properties: bool,
+    allow_interactive_auth: bool,
+    #[cfg(feature = "xml")]
+    verify: Option<String>,
 }
 
 impl<'a, T> Clone for Builder<'a, T> {
@@ -41,6 +61,12 @@ impl<'a, T> Clone for Builder<'a, T> {
             interface: self.interface.clone(),
             cache: self.cache,
             uncached_properties: self.uncached_properties.clone(),
+            cached_properties: self.cached_properties.clone(),
+            auto_start: self.auto_start,
+            auto_start_properties: self.auto_start_properties,
+            allow_interactive_auth: self.allow_interactive_auth,
+            #[cfg(feature = "xml")]
+            verify: self.verify.clone(),
             proxy_type: PhantomData,
         }
     }
@@ -59,6 +85,12 @@ impl<'a, T> Builder<'a, T> {
             interface: None,
             cache: CacheProperties::default(),
             uncached_properties: None,
+            cached_properties: None,
+            auto_start: true,
+            auto_start_properties: true,
+            allow_interactive_auth: false,
+            #[cfg(feature = "xml")]
+            verify: None,
             proxy_type: PhantomData,
         }
     }
@@ -96,6 +128,9 @@ impl<'a, T> Builder<'a, T> {
     }
 
     /// Set the properties caching mode.
+    ///
+    /// Use [`CacheProperties::Yes`] to populate the cache when building the proxy, at the cost of a
+    /// round trip to the service in [`Builder::build`].
     #[must_use]
     pub fn cache_properties(mut self, cache: CacheProperties) -> Self {
         self.cache = cache;
@@ -111,6 +146,76 @@ impl<'a, T> Builder<'a, T> {
         self
     }
 
+    /// Specify the only properties (by name) which should be cached.
+    ///
+    /// By default, all the properties are cached except the ones excluded with
+    /// [`Builder::uncached_properties`], which are still excluded if listed here as well. This is
+    /// useful to only cache the properties that are known to be stable, leaving the volatile ones to
+    /// always be fetched from the peer. This has no effect if caching is disabled altogether, with
+    /// [`CacheProperties::No`].
+    #[must_use]
+    pub fn cached_properties(mut self, properties: &[&'a str]) -> Self {
+        self.cached_properties
+            .replace(properties.iter().map(|p| Str::from(*p)).collect());
+
+        self
+    }
+
+    /// Set whether method calls may trigger the activation of the destination service.
+    ///
+    /// By default (`true`), the bus launches the destination service on a method call if it's
+    /// bus-activatable and not running yet. If set to `false`, the `NoAutoStart` flag is set on all
+    /// the method calls made through the proxy, so they fail with
+    /// [`crate::fdo::Error::ServiceUnknown`] instead. This is useful for health checks, for
+    /// example.
+    ///
+    /// Use the `no_autostart` attribute of [`dbus_proxy`](macro@crate::dbus_proxy) instead, if
+    /// you only need this for specific methods.
+    #[must_use]
+    pub fn auto_start(mut self, auto_start: bool) -> Self {
+        self.auto_start = auto_start;
+        self
+    }
+
+    /// Set whether property operations may trigger the activation of the destination service.
+    ///
+    /// This is the same as [`Builder::auto_start`] but for the `Get`, `Set` and `GetAll` calls
+    /// made by the proxy for its properties, including the ones populating the properties cache.
+    /// If set to `false` (the default is `true`), reading a property of a service that isn't
+    /// running fails instead of launching it.
+    #[must_use]
+    pub fn auto_start_properties(mut self, auto_start: bool) -> Self {
+        self.auto_start_properties = auto_start;
+        self
+    }
+
+    /// Set whether the destination may interactively prompt the user for authorization.
+    ///
+    /// If set to `true` (the default is `false`), the `AllowInteractiveAuth` flag is set on all the
+    /// method calls made through the proxy, including the ones to get and set its properties. This
+    /// lets services relying on PolicyKit (for example) ask the user for their credentials, instead
+    /// of denying the calls that need them.
+    ///
+    /// Use the `allow_interactive_auth` attribute of [`dbus_proxy`](macro@crate::dbus_proxy)
+    /// instead, if you only need this for specific methods.
+    #[must_use]
+    pub fn allow_interactive_auth(mut self, allow: bool) -> Self {
+        self.allow_interactive_auth = allow;
+        self
+    }
+
+    /// Check that the destination provides the interface as expected, when building the proxy.
+    ///
+    /// `expected` is the introspection XML of the interface, as the proxy expects it. This is
+    /// what the `verify_on_build` attribute of [`dbus_proxy`](macro@crate::dbus_proxy) uses.
+    #[cfg(feature = "xml")]
+    #[doc(hidden)]
+    #[must_use]
+    pub fn verify_introspection(mut self, expected: String) -> Self {
+        self.verify = Some(expected);
+        self
+    }
+
     pub(crate) fn build_internal(self) -> Result<Proxy<'a>> {
         let conn = self.conn;
         let destination = self
@@ -119,7 +224,13 @@ impl<'a, T> Builder<'a, T> {
         let path = self.path.ok_or(Error::MissingParameter("path"))?;
         let interface = self.interface.ok_or(Error::MissingParameter("interface"))?;
         let cache = self.cache;
-        let uncached_properties = self.uncached_properties.unwrap_or_default();
+        let cache_filter = CacheFilter {
+            allowed: self.cached_properties,
+            excluded: self.uncached_properties.unwrap_or_default(),
+        };
+        let auto_start = self.auto_start;
+        let auto_start_properties = self.auto_start_properties;
+        let allow_interactive_auth = self.allow_interactive_auth;
 
         Ok(Proxy {
             inner: Arc::new(ProxyInner::new(
@@ -128,7 +239,10 @@ impl<'a, T> Builder<'a, T> {
                 path,
                 interface,
                 cache,
-                uncached_properties,
+                cache_filter,
+                auto_start,
+                auto_start_properties,
+                allow_interactive_auth,
             )),
         })
     }
@@ -138,14 +252,23 @@ impl<'a, T> Builder<'a, T> {
     /// # Errors
     ///
     /// If the builder is lacking the necessary parameters to build a proxy,
-    /// [`Error::MissingParameter`] is returned.
+    /// [`Error::MissingParameter`] is returned. If the proxy was created with the
+    /// `verify_on_build` attribute of [`dbus_proxy`](macro@crate::dbus_proxy) and the destination
+    /// doesn't provide the interface as expected, [`Error::Failure`] is returned.
     pub async fn build(self) -> Result<T>
     where
         T: From<Proxy<'a>>,
     {
         let cache_upfront = self.cache == CacheProperties::Yes;
+        #[cfg(feature = "xml")]
+        let verify = self.verify.clone();
         let proxy = self.build_internal()?;
 
+        #[cfg(feature = "xml")]
+        if let Some(expected) = verify {
+            crate::xml::verify_proxy(&proxy, &expected).await?;
+        }
+
         if cache_upfront {
             proxy
                 .get_property_cache()
@@ -174,6 +297,12 @@ where
             ),
             cache: CacheProperties::default(),
             uncached_properties: None,
+            cached_properties: None,
+            auto_start: true,
+            auto_start_properties: true,
+            allow_interactive_auth: false,
+            #[cfg(feature = "xml")]
+            verify: None,
             proxy_type: PhantomData,
         }
     }
@@ -0,0 +1,72 @@
+use serde::ser::{Serialize, Serializer};
+use static_assertions::assert_impl_all;
+use zvariant::{Signature, Type};
+
+/// A method argument serialized as an array of the items of an iterator.
+///
+/// This allows passing the elements of an array argument without collecting them into a `Vec`
+/// first. The length of the array isn't needed upfront so any iterator will do but since the
+/// body of a message is serialized twice (once to compute its size and once to actually write
+/// it), the iterator is cloned for each pass. Hence, it should be cheap to clone, as most iterators
+/// over borrowed data or ranges are. If it isn't, collecting into a `Vec` is likely cheaper.
+///
+/// This is what the methods generated for the `iter_args` attribute of the [`dbus_proxy`] macro
+/// use for their array arguments.
+///
+/// # Example
+///
+/// ```
+/// use byteorder::LE;
+/// use zbus::{proxy::IterArg, zvariant::{to_bytes, EncodingContext, Type}};
+///
+/// let ctxt = EncodingContext::<LE>::new_dbus(0);
+/// let squares = IterArg::new((1..4u32).map(|i| i * i));
+/// assert_eq!(IterArg::<std::ops::Range<u32>>::signature(), "au");
+/// assert_eq!(to_bytes(ctxt, &squares)?, to_bytes(ctxt, &vec![1u32, 4, 9])?);
+/// # Ok::<(), zbus::Error>(())
+/// ```
+///
+/// [`dbus_proxy`]: attr.dbus_proxy.html
+#[derive(Debug, Clone)]
+pub struct IterArg<I>(I);
+
+assert_impl_all!(IterArg<std::ops::Range<u32>>: Send, Sync, Unpin);
+
+impl<I> IterArg<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: Serialize + Type,
+{
+    /// Create an argument for the items of `iter`.
+    pub fn new(iter: I) -> Self {
+        Self(iter)
+    }
+
+    /// Get back the wrapped iterator.
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
+impl<I> Serialize for IterArg<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: Serialize + Type,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.0.clone())
+    }
+}
+
+impl<I> Type for IterArg<I>
+where
+    I: IntoIterator,
+    I::Item: Type,
+{
+    fn signature() -> Signature<'static> {
+        Signature::from_string_unchecked(format!("a{}", I::Item::signature()))
+    }
+}
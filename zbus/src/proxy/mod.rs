@@ -12,22 +12,36 @@ use std::{
     future::Future,
     ops::Deref,
     pin::Pin,
-    sync::{Arc, RwLock, RwLockReadGuard},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock, RwLockReadGuard,
+    },
     task::{Context, Poll},
 };
 use tracing::{debug, info_span, instrument, trace, Instrument};
 
-use zbus_names::{BusName, InterfaceName, MemberName, UniqueName};
+use zbus_names::{BusName, InterfaceName, MemberName, OwnedUniqueName, UniqueName};
 use zvariant::{ObjectPath, OwnedValue, Str, Value};
 
 use crate::{
+    abstractions::timer::sleep,
     fdo::{self, IntrospectableProxy, NameOwnerChanged, PropertiesChangedStream, PropertiesProxy},
     message::{Flags, Message, Sequence, Type},
     AsyncDrop, Connection, Error, Executor, MatchRule, MessageStream, OwnedMatchRule, Result, Task,
 };
 
+mod array_stream;
+pub use array_stream::ArrayStream;
 mod builder;
 pub use builder::{Builder, CacheProperties, ProxyDefault};
+mod iter_arg;
+pub use iter_arg::IterArg;
+mod reply_meta;
+pub use reply_meta::ReplyMeta;
+mod reply_stream;
+pub use reply_stream::ReplyStream;
+mod retry;
+pub use retry::RetryPolicy;
 
 /// A client-side interface proxy.
 ///
@@ -91,9 +105,15 @@ pub(crate) struct ProxyInner<'a> {
 
     /// Cache of property values.
     property_cache: Option<OnceCell<(Arc<PropertiesCache>, Task<()>)>>,
-    /// Set of properties which do not get cached, by name.
+    /// The properties which get cached, by name.
     /// This overrides proxy-level caching behavior.
-    uncached_properties: HashSet<Str<'a>>,
+    cache_filter: CacheFilter<'a>,
+    /// Whether method calls may trigger the activation of the destination.
+    auto_start: bool,
+    /// Whether property operations may trigger the activation of the destination.
+    auto_start_properties: bool,
+    /// Whether the destination may interactively prompt the user for authorization.
+    allow_interactive_auth: bool,
 }
 
 impl Drop for ProxyInnerStatic {
@@ -200,6 +220,23 @@ where
     }
 }
 
+// Convert the value of the property `name` to `T`, naming the property if it's of another type.
+fn property_value<T>(name: &str, value: OwnedValue) -> Result<T>
+where
+    T: TryFrom<OwnedValue> + zvariant::Type,
+    T::Error: Into<zvariant::Error>,
+{
+    value.downcast().map_err(|e| match e {
+        zvariant::Error::SignatureMismatch(signature, expected) => {
+            Error::Variant(zvariant::Error::SignatureMismatch(
+                signature,
+                format!("{expected} for property `{name}`"),
+            ))
+        }
+        e => e.into(),
+    })
+}
+
 /// A [`stream::Stream`] implementation that yields property change notifications.
 ///
 /// Use [`Proxy::receive_property_changed`] to create an instance of this type.
@@ -249,6 +286,36 @@ where
 pub(crate) struct PropertiesCache {
     values: RwLock<HashMap<String, PropertyValue>>,
     caching_result: RwLock<CachingResult>,
+    /// Whether the cache should stop being updated.
+    stopped: AtomicBool,
+    stop: Event,
+}
+
+/// The properties a [`PropertiesCache`] keeps, by name.
+#[derive(Debug, Default)]
+pub(crate) struct CacheFilter<'a> {
+    /// If set, only these properties are cached.
+    pub(crate) allowed: Option<HashSet<Str<'a>>>,
+    /// These properties are never cached, even if allowed.
+    pub(crate) excluded: HashSet<Str<'a>>,
+}
+
+impl CacheFilter<'_> {
+    fn is_cached(&self, property_name: &str) -> bool {
+        let name = Str::from(property_name);
+
+        !self.excluded.contains(&name) && self.allowed.as_ref().map_or(true, |a| a.contains(&name))
+    }
+
+    fn to_owned(&self) -> CacheFilter<'static> {
+        CacheFilter {
+            allowed: self
+                .allowed
+                .as_ref()
+                .map(|a| a.iter().map(|s| s.to_owned()).collect()),
+            excluded: self.excluded.iter().map(|s| s.to_owned()).collect(),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -263,22 +330,22 @@ impl PropertiesCache {
         proxy: PropertiesProxy<'static>,
         interface: InterfaceName<'static>,
         executor: &Executor<'_>,
-        uncached_properties: HashSet<zvariant::Str<'static>>,
+        cache_filter: CacheFilter<'static>,
     ) -> (Arc<Self>, Task<()>) {
         let cache = Arc::new(PropertiesCache {
             values: Default::default(),
             caching_result: RwLock::new(CachingResult::Caching {
                 ready: Event::new(),
             }),
+            stopped: AtomicBool::new(false),
+            stop: Event::new(),
         });
 
         let cache_clone = cache.clone();
         let task_name = format!("{interface} proxy caching");
         let proxy_caching = async move {
-            let result = cache_clone
-                .init(proxy, interface, uncached_properties)
-                .await;
-            let (prop_changes, interface, uncached_properties) = {
+            let result = cache_clone.init(proxy, interface, cache_filter).await;
+            let (proxy, prop_changes, interface, cache_filter) = {
                 let mut caching_result = cache_clone.caching_result.write().expect("lock poisoned");
                 let ready = match &*caching_result {
                     CachingResult::Caching { ready } => ready,
@@ -287,11 +354,11 @@ impl PropertiesCache {
                     _ => unreachable!(),
                 };
                 match result {
-                    Ok((prop_changes, interface, uncached_properties)) => {
+                    Ok((proxy, prop_changes, interface, cache_filter)) => {
                         ready.notify(usize::MAX);
                         *caching_result = CachingResult::Cached { result: Ok(()) };
 
-                        (prop_changes, interface, uncached_properties)
+                        (proxy, prop_changes, interface, cache_filter)
                     }
                     Err(e) => {
                         ready.notify(usize::MAX);
@@ -303,11 +370,14 @@ impl PropertiesCache {
             };
 
             if let Err(e) = cache_clone
-                .keep_updated(prop_changes, interface, uncached_properties)
+                .keep_updated(prop_changes, interface, cache_filter)
                 .await
             {
                 debug!("Error keeping properties cache updated: {e}");
             }
+            if let Err(e) = proxy.into_inner().close().await {
+                debug!("Error closing the properties proxy: {e}");
+            }
         }
         .instrument(info_span!("{}", task_name));
         let task = executor.spawn(proxy_caching, &task_name);
@@ -320,11 +390,12 @@ impl PropertiesCache {
         &self,
         proxy: PropertiesProxy<'static>,
         interface: InterfaceName<'static>,
-        uncached_properties: HashSet<zvariant::Str<'static>>,
+        cache_filter: CacheFilter<'static>,
     ) -> Result<(
+        PropertiesProxy<'static>,
         PropertiesChangedStream<'static>,
         InterfaceName<'static>,
-        HashSet<zvariant::Str<'static>>,
+        CacheFilter<'static>,
     )> {
         use ordered_stream::OrderedStreamExt;
 
@@ -337,7 +408,7 @@ impl PropertiesCache {
                 proxy.path(),
                 Some(proxy.interface()),
                 "GetAll",
-                BitFlags::empty(),
+                proxy.inner().default_flags(),
                 &interface,
             )
             .await
@@ -352,7 +423,7 @@ impl PropertiesCache {
                 }
                 Some(Either::Right(populate)) => {
                     populate?.body().map(|values| {
-                        self.update_cache(&uncached_properties, &values, Vec::new(), &interface);
+                        self.update_cache(&cache_filter, &values, Vec::new(), &interface);
                     })?;
                     break;
                 }
@@ -365,7 +436,7 @@ impl PropertiesCache {
             if let Ok(args) = update.args() {
                 if args.interface_name == interface {
                     self.update_cache(
-                        &uncached_properties,
+                        &cache_filter,
                         &args.changed_properties,
                         args.invalidated_properties,
                         &interface,
@@ -378,7 +449,7 @@ impl PropertiesCache {
         // of directly to the stream.
         let prop_changes = join.into_inner().0.into_inner();
 
-        Ok((prop_changes, interface, uncached_properties))
+        Ok((proxy, prop_changes, interface, cache_filter))
     }
 
     // new() runs this in a task it spawns for keeping the cache in sync.
@@ -387,16 +458,25 @@ impl PropertiesCache {
         &self,
         mut prop_changes: PropertiesChangedStream<'static>,
         interface: InterfaceName<'static>,
-        uncached_properties: HashSet<zvariant::Str<'static>>,
+        cache_filter: CacheFilter<'static>,
     ) -> Result<()> {
-        use futures_util::StreamExt;
+        use futures_util::{future::select, StreamExt};
 
         trace!("Listening for property changes on {interface}...");
-        while let Some(update) = prop_changes.next().await {
+        loop {
+            let stop = self.stop.listen();
+            if self.stopped.load(Ordering::SeqCst) {
+                break;
+            }
+            let update = match select(prop_changes.next(), stop).await {
+                Either::Left((Some(update), _)) => update,
+                Either::Left((None, _)) => return Ok(()),
+                Either::Right(_) => break,
+            };
             if let Ok(args) = update.args() {
                 if args.interface_name == interface {
                     self.update_cache(
-                        &uncached_properties,
+                        &cache_filter,
                         &args.changed_properties,
                         args.invalidated_properties,
                         &interface,
@@ -404,13 +484,21 @@ impl PropertiesCache {
                 }
             }
         }
+        trace!("Stopped listening for property changes on {interface}");
+        prop_changes.async_drop().await;
 
         Ok(())
     }
 
+    /// Stop keeping the cache updated.
+    fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.stop.notify(usize::MAX);
+    }
+
     fn update_cache(
         &self,
-        uncached_properties: &HashSet<Str<'_>>,
+        cache_filter: &CacheFilter<'_>,
         changed: &HashMap<&str, Value<'_>>,
         invalidated: Vec<&str>,
         interface: &InterfaceName<'_>,
@@ -418,7 +506,7 @@ impl PropertiesCache {
         let mut values = self.values.write().expect("lock poisoned");
 
         for inval in invalidated {
-            if uncached_properties.contains(&Str::from(inval)) {
+            if !cache_filter.is_cached(inval) {
                 debug!(
                     "Ignoring invalidation of uncached property `{}.{}`",
                     interface, inval
@@ -434,7 +522,7 @@ impl PropertiesCache {
         }
 
         for (property_name, value) in changed {
-            if uncached_properties.contains(&Str::from(*property_name)) {
+            if !cache_filter.is_cached(property_name) {
                 debug!(
                     "Ignoring update of uncached property `{}.{}`",
                     interface, property_name
@@ -471,13 +559,17 @@ impl PropertiesCache {
 }
 
 impl<'a> ProxyInner<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         conn: Connection,
         destination: BusName<'a>,
         path: ObjectPath<'a>,
         interface: InterfaceName<'a>,
         cache: CacheProperties,
-        uncached_properties: HashSet<Str<'a>>,
+        cache_filter: CacheFilter<'a>,
+        auto_start: bool,
+        auto_start_properties: bool,
+        allow_interactive_auth: bool,
     ) -> Self {
         let property_cache = match cache {
             CacheProperties::Yes | CacheProperties::Lazily => Some(OnceCell::new()),
@@ -492,10 +584,30 @@ impl<'a> ProxyInner<'a> {
             path,
             interface,
             property_cache,
-            uncached_properties,
+            cache_filter,
+            auto_start,
+            auto_start_properties,
+            allow_interactive_auth,
         }
     }
 
+    /// Remove the match rules of our subscriptions and wait for the removal to complete.
+    async fn close(mut self) -> Result<()> {
+        if let Some((cache, task)) = self.property_cache.take().and_then(OnceCell::into_inner) {
+            cache.stop();
+            task.await;
+        }
+        if let Some(rule) = self
+            .inner_without_borrows
+            .dest_owner_change_match_rule
+            .take()
+        {
+            self.inner_without_borrows.conn.remove_match(rule).await?;
+        }
+
+        Ok(())
+    }
+
     /// Subscribe to the "NameOwnerChanged" signal on the bus for our destination.
     ///
     /// If the destination is a unique name, we will not subscribe to the signal.
@@ -646,6 +758,8 @@ impl<'a> Proxy<'a> {
             .unwrap()
             // does not have properties
             .cache_properties(CacheProperties::No)
+            .auto_start(self.inner.auto_start_properties)
+            .allow_interactive_auth(self.inner.allow_interactive_auth)
             .build_internal()
             .unwrap()
             .into()
@@ -661,6 +775,8 @@ impl<'a> Proxy<'a> {
             .unwrap()
             // does not have properties
             .cache_properties(CacheProperties::No)
+            .auto_start(self.inner.auto_start_properties)
+            .allow_interactive_auth(self.inner.allow_interactive_auth)
             .build_internal()
             .unwrap()
             .into()
@@ -678,15 +794,10 @@ impl<'a> Proxy<'a> {
         let (cache, _) = &cache.get_or_init(|| {
             let proxy = self.owned_properties_proxy();
             let interface = self.interface().to_owned();
-            let uncached_properties: HashSet<zvariant::Str<'static>> = self
-                .inner
-                .uncached_properties
-                .iter()
-                .map(|s| s.to_owned())
-                .collect();
+            let cache_filter = self.inner.cache_filter.to_owned();
             let executor = self.connection().executor();
 
-            PropertiesCache::new(proxy, interface, executor, uncached_properties)
+            PropertiesCache::new(proxy, interface, executor, cache_filter)
         });
 
         Some(cache)
@@ -784,6 +895,22 @@ impl<'a> Proxy<'a> {
         value.try_into().map_err(Into::into)
     }
 
+    /// Get the property `property_name`, checking its type.
+    ///
+    /// Same as [`Proxy::get_property`], except that if the value is not of type `T`, the returned
+    /// [`Error::Variant`] holds a [`zvariant::Error::SignatureMismatch`] naming the property and
+    /// the signatures of both the value and `T`, rather than a bare
+    /// [`zvariant::Error::IncorrectType`]. This requires `T` to implement [`zvariant::Type`].
+    pub async fn get_property_checked<T>(&self, property_name: &str) -> Result<T>
+    where
+        T: TryFrom<OwnedValue> + zvariant::Type,
+        T::Error: Into<zvariant::Error>,
+    {
+        let value = self.get_property::<OwnedValue>(property_name).await?;
+
+        property_value(property_name, value)
+    }
+
     /// Set the property `property_name`.
     ///
     /// Effectively, call the `Set` method of the `org.freedesktop.DBus.Properties` interface.
@@ -796,6 +923,52 @@ impl<'a> Proxy<'a> {
             .await
     }
 
+    /// Get the property `property_name` of another interface of the object.
+    ///
+    /// This is useful for objects that spread their properties over multiple interfaces. Unlike
+    /// [`Proxy::get_property`], the value is never taken from the cache, since only the properties
+    /// of the proxy's own interface are cached.
+    pub async fn get_interface_property<'i, I, T>(
+        &self,
+        interface: I,
+        property_name: &str,
+    ) -> Result<T>
+    where
+        I: TryInto<InterfaceName<'i>>,
+        I::Error: Into<Error>,
+        T: TryFrom<OwnedValue>,
+        T::Error: Into<Error>,
+    {
+        let interface = interface.try_into().map_err(Into::into)?;
+        let value = self
+            .properties_proxy()
+            .get(interface, property_name)
+            .await?;
+
+        value.try_into().map_err(Into::into)
+    }
+
+    /// Set the property `property_name` of another interface of the object.
+    ///
+    /// See [`Proxy::get_interface_property`] for details.
+    pub async fn set_interface_property<'i, 't, I, T>(
+        &self,
+        interface: I,
+        property_name: &str,
+        value: T,
+    ) -> fdo::Result<()>
+    where
+        I: TryInto<InterfaceName<'i>>,
+        I::Error: Into<Error>,
+        T: Into<Value<'t>> + 't,
+    {
+        let interface = interface.try_into().map_err(Into::into)?;
+
+        self.properties_proxy()
+            .set(interface, property_name, &value.into())
+            .await
+    }
+
     /// Call a method and return the reply.
     ///
     /// Typically, you would want to use [`call`] method instead. Use this method if you need to
@@ -812,13 +985,16 @@ impl<'a> Proxy<'a> {
         self.inner
             .inner_without_borrows
             .conn
-            .call_method(
+            .call_method_raw(
                 Some(&self.inner.destination),
                 self.inner.path.as_str(),
                 Some(&self.inner.interface),
                 method_name,
+                self.default_flags(),
                 body,
             )
+            .await?
+            .expect("no reply")
             .await
     }
 
@@ -860,7 +1036,7 @@ impl<'a> Proxy<'a> {
         B: serde::ser::Serialize + zvariant::DynamicType,
         R: serde::de::DeserializeOwned + zvariant::Type,
     {
-        let flags = flags.iter().map(Flags::from).collect::<BitFlags<_>>();
+        let flags = flags.iter().map(Flags::from).collect::<BitFlags<_>>() | self.default_flags();
         match self
             .inner
             .inner_without_borrows
@@ -880,6 +1056,44 @@ impl<'a> Proxy<'a> {
         }
     }
 
+    /// The message flags set on all method calls made through this proxy.
+    fn default_flags(&self) -> BitFlags<Flags> {
+        let mut flags = BitFlags::empty();
+        if !self.inner.auto_start {
+            flags |= Flags::NoAutoStart;
+        }
+        if self.inner.allow_interactive_auth {
+            flags |= Flags::AllowInteractiveAuth;
+        }
+
+        flags
+    }
+
+    /// Call a method and return the reply body, retrying on transient failures.
+    ///
+    /// This is the same as [`Proxy::call`], except that the call is retried if it fails with one
+    /// of the errors listed in the given `policy`, with an exponential backoff between the
+    /// attempts. This is typically useful when calling methods on services that are (re)starting
+    /// or that are activated lazily. See [`RetryPolicy`] for the errors retried by default.
+    ///
+    /// If all attempts fail, the error from the last attempt is returned.
+    pub async fn call_with_retry<'m, M, B, R>(
+        &self,
+        method_name: M,
+        body: &B,
+        policy: &RetryPolicy,
+    ) -> Result<R>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        R: serde::de::DeserializeOwned + zvariant::Type,
+    {
+        let method_name = method_name.try_into().map_err(Into::into)?;
+
+        policy.retry(|| self.call(method_name.as_ref(), body)).await
+    }
+
     /// Call a method without expecting a reply
     ///
     /// This sets the `NoReplyExpected` flag on the calling message and does not wait for a reply.
@@ -894,7 +1108,91 @@ impl<'a> Proxy<'a> {
         Ok(())
     }
 
+    /// Call a method and return a stream of its replies.
+    ///
+    /// A few services send more than one reply to a single call, e.g to report progress before
+    /// the final result. Unlike [`Proxy::call_method`], which returns the first reply, this method
+    /// yields all the replies to the call, until a terminal one:
+    ///
+    /// * An error reply, yielded as an [`Error::MethodError`].
+    /// * A method return for which `is_final` returns `true`. Pass `|_| true` for the usual single
+    ///   reply semantics.
+    ///
+    /// The stream also ends if the connection is closed. Since there is no telling how long the
+    /// replies will keep coming, the [method timeout] of the connection doesn't apply.
+    ///
+    /// The replies are queued for the stream alone, so not polling it doesn't hold up the other
+    /// calls. Like for any [`MessageStream`], up to [`Connection::max_queued`] replies are queued,
+    /// after which the reading of incoming messages waits for the stream to be polled.
+    ///
+    /// Note that sending more than one reply is not allowed by the D-Bus specification, so message
+    /// buses may not forward the replies after the first one. This is mostly useful on
+    /// peer-to-peer connections.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # zbus::block_on(async {
+    /// use futures_util::stream::TryStreamExt;
+    /// use zbus::{Connection, Proxy};
+    ///
+    /// # let connection = Connection::session().await?;
+    /// let proxy = Proxy::new(
+    ///     &connection,
+    ///     "org.zbus.Progress",
+    ///     "/org/zbus/Progress",
+    ///     "org.zbus.Progress",
+    /// )
+    /// .await?;
+    /// // The service replies with the progress (`u`) until it sends the result (`s`).
+    /// let mut replies = proxy
+    ///     .call_method_streaming("Work", &(), |reply| {
+    ///         reply.body_signature().map(|s| s == "s").unwrap_or(true)
+    ///     })
+    ///     .await?;
+    /// while let Some(reply) = replies.try_next().await? {
+    ///     match reply.body::<u32>() {
+    ///         Ok(progress) => println!("{progress}%"),
+    ///         Err(_) => println!("Done: {}", reply.body::<String>()?),
+    ///     }
+    /// }
+    /// # Ok::<(), zbus::Error>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// [method timeout]: crate::connection::Builder::method_timeout
+    pub async fn call_method_streaming<'m, M, B, F>(
+        &self,
+        method_name: M,
+        body: &B,
+        is_final: F,
+    ) -> Result<ReplyStream>
+    where
+        M: TryInto<MemberName<'m>>,
+        M::Error: Into<Error>,
+        B: serde::ser::Serialize + zvariant::DynamicType,
+        F: FnMut(&Message) -> bool + Send + Sync + 'static,
+    {
+        let conn = &self.inner.inner_without_borrows.conn;
+        let msg = conn.method_call_message(
+            Some(self.destination()),
+            self.path(),
+            Some(self.interface()),
+            method_name,
+            self.default_flags(),
+            body,
+        )?;
+        let channel = conn.send_method_call_streaming(msg).await?;
+
+        Ok(ReplyStream::new(channel, is_final))
+    }
+
     /// Create a stream for signal named `signal_name`.
+    ///
+    /// The returned future only resolves once the bus has acknowledged the underlying match rule,
+    /// so that all the signals emitted after that point are guaranteed to be received through the
+    /// stream. It's therefore safe to trigger the emission of the signal (e.g by calling a method)
+    /// right after this method returns.
     pub async fn receive_signal<'m, M>(&self, signal_name: M) -> Result<SignalStream<'m>>
     where
         M: TryInto<MemberName<'m>>,
@@ -968,6 +1266,27 @@ impl<'a> Proxy<'a> {
         }
     }
 
+    /// The current owner of the proxy destination.
+    ///
+    /// This queries the bus (using the `GetNameOwner` method) for the unique name of the peer
+    /// currently owning the destination name. Returns `None` if the name has no owner, i-e the
+    /// service is not running (or the peer is gone, in case the destination is a unique name).
+    ///
+    /// Unlike method calls on the proxy, this does **not** trigger activation of the destination
+    /// service, so it can be used to check if the service is running and fail fast otherwise.
+    pub async fn destination_owner(&self) -> Result<Option<OwnedUniqueName>> {
+        let dbus_proxy = fdo::DBusProxy::builder(self.connection())
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+
+        match dbus_proxy.get_name_owner(self.destination().as_ref()).await {
+            Ok(owner) => Ok(Some(owner)),
+            Err(fdo::Error::NameHasNoOwner(_)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Get a stream to receive destination owner changed events.
     ///
     /// If the proxy destination is a unique name, the stream will be notified of the peer
@@ -998,6 +1317,25 @@ impl<'a> Proxy<'a> {
             name: self.destination().clone(),
         })
     }
+
+    /// Close the proxy, removing the match rules of its subscriptions.
+    ///
+    /// The proxy subscribes to signals on the bus to keep track of the owner of its destination and
+    /// to keep its properties cache up to date. Dropping the proxy also removes these
+    /// subscriptions, but only on a best-effort basis in the background. Unlike that, this method
+    /// waits for the bus to confirm the removal, so you can rely on the related signals not being
+    /// delivered anymore once it returns.
+    ///
+    /// Since all clones of a proxy share the same subscriptions, they're only removed if this is
+    /// the last clone. Otherwise this is the same as dropping the proxy. Note that the streams
+    /// created from the proxy, e.g [`SignalStream`], have their own subscriptions, which you can
+    /// remove through [`AsyncDrop`].
+    pub async fn close(self) -> Result<()> {
+        match Arc::try_unwrap(self.inner) {
+            Ok(inner) => inner.close().await,
+            Err(_) => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -1100,6 +1438,63 @@ impl<'a> SignalStream<'a> {
         self.signal_name.as_ref()
     }
 
+    /// Wait for the first signal for which `predicate` returns `true`.
+    ///
+    /// The signals received before are discarded. Since the stream is consumed, the underlying
+    /// match rule is deregistered before returning.
+    ///
+    /// Returns `None` if the stream ends before a matching signal is received.
+    pub async fn wait_for<F>(mut self, mut predicate: F) -> Option<Arc<Message>>
+    where
+        F: FnMut(&Arc<Message>) -> bool,
+    {
+        use futures_util::StreamExt;
+
+        let mut found = None;
+        while let Some(msg) = self.next().await {
+            if predicate(&msg) {
+                found = Some(msg);
+
+                break;
+            }
+        }
+        self.async_drop().await;
+
+        found
+    }
+
+    /// Wait for the first signal for which `predicate` returns `true`, for up to `timeout`.
+    ///
+    /// Same as [`SignalStream::wait_for`], except that `None` is also returned if `timeout` elapses
+    /// before a matching signal is received.
+    pub async fn wait_for_timeout<F>(
+        mut self,
+        timeout: std::time::Duration,
+        mut predicate: F,
+    ) -> Option<Arc<Message>>
+    where
+        F: FnMut(&Arc<Message>) -> bool,
+    {
+        use futures_util::{future::select, StreamExt};
+
+        let next_match = async {
+            while let Some(msg) = self.next().await {
+                if predicate(&msg) {
+                    return Some(msg);
+                }
+            }
+
+            None
+        };
+        let found = match select(Box::pin(next_match), Box::pin(sleep(timeout))).await {
+            Either::Left((found, _)) => found,
+            Either::Right(_) => None,
+        };
+        self.async_drop().await;
+
+        found
+    }
+
     async fn new(
         proxy: Proxy<'_>,
         signal_name: Option<MemberName<'a>>,
@@ -1320,13 +1715,177 @@ impl<'a> From<crate::blocking::Proxy<'a>> for Proxy<'a> {
 mod tests {
     use super::*;
     use crate::{
-        connection, dbus_interface, dbus_proxy, object_server::SignalContext, utils::block_on,
-        AsyncDrop,
+        connection, dbus_interface, dbus_proxy, message::Header, object_server::SignalContext,
+        utils::block_on, AsyncDrop,
     };
-    use futures_util::StreamExt;
+    use futures_util::{stream::FusedStream, StreamExt};
     use ntest::timeout;
     use test_log::test;
 
+    #[test]
+    #[timeout(15000)]
+    fn call_with_retry() {
+        block_on(test_call_with_retry()).unwrap();
+    }
+
+    async fn test_call_with_retry() -> Result<()> {
+        use std::time::Duration;
+
+        let service_name = "org.freedesktop.zbus.async.ProxyCallWithRetryTest";
+        let conn = Connection::session().await?;
+        let proxy: Proxy<'_> = Builder::new_bare(&conn)
+            .destination(service_name)?
+            .path("/org/freedesktop/zbus/Test")?
+            .interface("org.freedesktop.DBus.Peer")?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+
+        // Nobody owns the name so we should get the last error after giving up.
+        let policy = RetryPolicy::default().timeout(Duration::from_millis(50));
+        match proxy
+            .call_with_retry::<_, _, ()>("Ping", &(), &policy)
+            .await
+        {
+            Err(Error::MethodError(name, _, _)) => assert!(
+                name == "org.freedesktop.DBus.Error.ServiceUnknown"
+                    || name == "org.freedesktop.DBus.Error.NameHasNoOwner"
+            ),
+            res => panic!("unexpected result: {res:?}"),
+        }
+
+        // Errors not covered by the policy, are not retried.
+        let policy = RetryPolicy::default().errors(vec![]);
+        assert!(proxy
+            .call_with_retry::<_, _, ()>("Ping", &(), &policy)
+            .await
+            .is_err());
+
+        // Now the service appears after a while, and the call should succeed.
+        let service = async {
+            crate::abstractions::timer::sleep(Duration::from_millis(100)).await;
+
+            // All objects implement the `Peer` interface, so any interface would do here.
+            connection::Builder::session()?
+                .name(service_name)?
+                .serve_at("/org/freedesktop/zbus/Test", fdo::ObjectManager)?
+                .build()
+                .await
+        };
+        let policy = RetryPolicy::default();
+        let (res, service_conn) = futures_util::future::join(
+            proxy.call_with_retry::<_, _, ()>("Ping", &(), &policy),
+            service,
+        )
+        .await;
+        let _service_conn = service_conn?;
+        res
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn call_timeout() {
+        block_on(test_call_timeout()).unwrap();
+    }
+
+    async fn test_call_timeout() -> Result<()> {
+        use futures_util::TryStreamExt;
+        use std::time::Duration;
+
+        // The object server of this connection is never started so nobody replies to the calls.
+        let service_conn = Connection::session().await?;
+        let service_name = service_conn.unique_name().unwrap().to_owned();
+        let calls = MessageStream::for_match_rule(
+            crate::MatchRule::builder()
+                .msg_type(crate::message::Type::MethodCall)
+                .member("Unanswered")?
+                .build(),
+            &service_conn,
+            None,
+        )
+        .await?;
+        let conn = connection::Builder::session()?
+            .method_timeout(Duration::from_millis(50))
+            .build()
+            .await?;
+        let proxy: Proxy<'_> = Builder::new_bare(&conn)
+            .destination(service_name.clone())?
+            .path("/org/freedesktop/zbus/Test")?
+            .interface("org.freedesktop.zbus.Test")?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+
+        match proxy.call::<_, _, ()>("Unanswered", &()).await {
+            Err(Error::Timeout(Some(destination), member)) => {
+                assert_eq!(destination.as_str(), service_name.as_str());
+                assert_eq!(member, "Unanswered");
+            }
+            res => panic!("unexpected result: {res:?}"),
+        }
+
+        // Timeouts are only retried if the policy says so.
+        let policy = RetryPolicy::default().timeout(Duration::from_millis(200));
+        let res = proxy
+            .call_with_retry::<_, _, ()>("Unanswered", &(), &policy)
+            .await;
+        assert!(matches!(res, Err(Error::Timeout(_, _))), "{res:?}");
+        let policy = policy.retry_timeouts(true);
+        let res = proxy
+            .call_with_retry::<_, _, ()>("Unanswered", &(), &policy)
+            .await;
+        assert!(matches!(res, Err(Error::Timeout(_, _))), "{res:?}");
+
+        // 1 call, then 1 call without retries and at least 2 calls with retries.
+        let received: Vec<_> = calls.take(4).try_collect().await?;
+        assert_eq!(received.len(), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn destination_owner() {
+        block_on(test_destination_owner()).unwrap();
+    }
+
+    async fn test_destination_owner() -> Result<()> {
+        let service_name = "org.freedesktop.zbus.async.ProxyDestinationOwnerTest";
+        let conn = Connection::session().await?;
+        let proxy: Proxy<'_> = Builder::new_bare(&conn)
+            .destination(service_name)?
+            .path("/does/not/matter")?
+            .interface("does.not.matter")?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+        assert_eq!(proxy.destination_owner().await?, None);
+
+        let service_conn = connection::Builder::session()?
+            .name(service_name)?
+            .build()
+            .await?;
+        assert_eq!(
+            proxy.destination_owner().await?.as_ref(),
+            service_conn.unique_name()
+        );
+
+        // Unique names work too.
+        let proxy: Proxy<'_> = Builder::new_bare(&conn)
+            .destination(conn.unique_name().unwrap().as_ref())?
+            .path("/does/not/matter")?
+            .interface("does.not.matter")?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+        assert_eq!(
+            proxy.destination_owner().await?.as_ref(),
+            conn.unique_name()
+        );
+
+        Ok(())
+    }
+
     #[test]
     #[timeout(15000)]
     fn signal() {
@@ -1391,6 +1950,308 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[timeout(15000)]
+    fn signal_right_after_subscription() {
+        block_on(test_signal_right_after_subscription()).unwrap();
+    }
+
+    async fn test_signal_right_after_subscription() -> Result<()> {
+        struct EarlySignal;
+
+        #[dbus_interface(name = "org.freedesktop.zbus.EarlySignal")]
+        impl EarlySignal {
+            async fn emit(
+                &self,
+                #[zbus(signal_context)] ctxt: SignalContext<'_>,
+            ) -> fdo::Result<()> {
+                Self::emitted(&ctxt).await.map_err(Into::into)
+            }
+
+            #[dbus_interface(signal)]
+            async fn emitted(ctxt: &SignalContext<'_>) -> Result<()>;
+        }
+
+        let service = connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/EarlySignal", EarlySignal)?
+            .build()
+            .await?;
+        let conn = Connection::session().await?;
+        let proxy: Proxy<'_> = Builder::new_bare(&conn)
+            .destination(service.unique_name().unwrap())?
+            .path("/org/freedesktop/zbus/EarlySignal")?
+            .interface("org.freedesktop.zbus.EarlySignal")?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+
+        // The `Emit` call is sent right after `AddMatch`, before the bus replies to it, so the
+        // signal arrives right after the `AddMatch` reply.
+        let (stream, emitted) = futures_util::join!(
+            proxy.receive_signal("Emitted"),
+            proxy.call::<_, _, ()>("Emit", &()),
+        );
+        emitted?;
+        let signal = stream?.next().await.unwrap();
+        assert_eq!(signal.member().unwrap(), "Emitted");
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn no_auto_start() {
+        block_on(test_no_auto_start()).unwrap();
+    }
+
+    async fn test_no_auto_start() -> Result<()> {
+        struct AutoStart;
+
+        #[dbus_interface(name = "org.freedesktop.zbus.AutoStart")]
+        impl AutoStart {
+            fn auto_start_allowed(&self, #[zbus(header)] header: Header<'_>) -> bool {
+                !header.primary().flags().contains(Flags::NoAutoStart)
+            }
+        }
+
+        let service = connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/AutoStart", AutoStart)?
+            .build()
+            .await?;
+        let conn = Connection::session().await?;
+        let builder: Builder<'_, Proxy<'_>> = Builder::new_bare(&conn)
+            .destination(service.unique_name().unwrap())?
+            .path("/org/freedesktop/zbus/AutoStart")?
+            .interface("org.freedesktop.zbus.AutoStart")?
+            .cache_properties(CacheProperties::No);
+
+        let proxy = builder.clone().build().await?;
+        assert!(proxy.call::<_, _, bool>("AutoStartAllowed", &()).await?);
+
+        let proxy = builder.auto_start(false).build().await?;
+        assert!(!proxy.call::<_, _, bool>("AutoStartAllowed", &()).await?);
+        let allowed: Option<bool> = proxy
+            .call_with_flags(
+                "AutoStartAllowed",
+                MethodFlags::AllowInteractiveAuth.into(),
+                &(),
+            )
+            .await?;
+        assert_eq!(allowed, Some(false));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn allow_interactive_auth() {
+        block_on(test_allow_interactive_auth()).unwrap();
+    }
+
+    async fn test_allow_interactive_auth() -> Result<()> {
+        struct InteractiveAuth;
+
+        #[dbus_interface(name = "org.freedesktop.zbus.InteractiveAuth")]
+        impl InteractiveAuth {
+            fn interactive_auth_allowed(&self, #[zbus(header)] header: Header<'_>) -> bool {
+                header
+                    .primary()
+                    .flags()
+                    .contains(Flags::AllowInteractiveAuth)
+            }
+        }
+
+        #[dbus_proxy(
+            interface = "org.freedesktop.zbus.InteractiveAuth",
+            default_path = "/org/freedesktop/zbus/InteractiveAuth"
+        )]
+        trait InteractiveAuth {
+            fn interactive_auth_allowed(&self) -> Result<bool>;
+
+            #[dbus_proxy(name = "InteractiveAuthAllowed", allow_interactive_auth)]
+            fn interactive_auth_allowed_with_auth(&self) -> Result<bool>;
+        }
+
+        let service = connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/InteractiveAuth", InteractiveAuth)?
+            .build()
+            .await?;
+        let conn = Connection::session().await?;
+        let builder =
+            InteractiveAuthProxy::builder(&conn).destination(service.unique_name().unwrap())?;
+
+        // Only set for the methods with the attribute by default.
+        let proxy = builder.clone().build().await?;
+        assert!(!proxy.interactive_auth_allowed().await?);
+        assert!(proxy.interactive_auth_allowed_with_auth().await?);
+
+        // Set for all the method calls when enabled on the proxy.
+        let proxy = builder.allow_interactive_auth(true).build().await?;
+        assert!(proxy.interactive_auth_allowed().await?);
+        assert!(proxy.interactive_auth_allowed_with_auth().await?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn no_auto_start_properties() {
+        block_on(test_no_auto_start_properties()).unwrap();
+    }
+
+    async fn test_no_auto_start_properties() -> Result<()> {
+        use futures_util::TryStreamExt;
+
+        // The member of the next property call and whether it allows auto-starting.
+        async fn next_call(calls: &mut MessageStream) -> Result<(String, bool)> {
+            let msg = calls.try_next().await?.unwrap();
+            let header = msg.header()?;
+            let member = header.member()?.unwrap().to_string();
+            let auto_start = !header.primary().flags().contains(Flags::NoAutoStart);
+
+            Ok((member, auto_start))
+        }
+
+        struct AutoStartProps(u32);
+
+        #[dbus_interface(name = "org.freedesktop.zbus.AutoStartProps")]
+        impl AutoStartProps {
+            #[dbus_interface(property)]
+            fn count(&self) -> u32 {
+                self.0
+            }
+
+            #[dbus_interface(property)]
+            fn set_count(&mut self, count: u32) {
+                self.0 = count;
+            }
+        }
+
+        let service = connection::Builder::session()?
+            .serve_at("/org/freedesktop/zbus/AutoStartProps", AutoStartProps(1))?
+            .build()
+            .await?;
+        let mut calls = MessageStream::for_match_rule(
+            crate::MatchRule::builder()
+                .msg_type(crate::message::Type::MethodCall)
+                .interface("org.freedesktop.DBus.Properties")?
+                .build(),
+            &service,
+            None,
+        )
+        .await?;
+
+        let conn = Connection::session().await?;
+        let builder: Builder<'_, Proxy<'_>> = Builder::new_bare(&conn)
+            .destination(service.unique_name().unwrap())?
+            .path("/org/freedesktop/zbus/AutoStartProps")?
+            .interface("org.freedesktop.zbus.AutoStartProps")?
+            .cache_properties(CacheProperties::No);
+
+        // Only method calls are affected by `auto_start`.
+        let proxy = builder.clone().auto_start(false).build().await?;
+        assert_eq!(proxy.get_property::<u32>("Count").await?, 1);
+        assert_eq!(next_call(&mut calls).await?, ("Get".to_string(), true));
+
+        let proxy = builder.clone().auto_start_properties(false).build().await?;
+        assert_eq!(proxy.get_property::<u32>("Count").await?, 1);
+        assert_eq!(next_call(&mut calls).await?, ("Get".to_string(), false));
+        proxy.set_property("Count", 2u32).await?;
+        assert_eq!(next_call(&mut calls).await?, ("Set".to_string(), false));
+
+        let proxy = builder
+            .auto_start_properties(false)
+            .cache_properties(CacheProperties::Yes)
+            .build()
+            .await?;
+        assert_eq!(proxy.cached_property::<u32>("Count")?, Some(2));
+        assert_eq!(next_call(&mut calls).await?, ("GetAll".to_string(), false));
+
+        Ok(())
+    }
+
+    #[test]
+    #[timeout(15000)]
+    fn close() {
+        block_on(test_close()).unwrap();
+    }
+
+    async fn test_close() -> Result<()> {
+        struct Counter(u32);
+
+        #[dbus_interface(name = "org.freedesktop.zbus.ProxyClose")]
+        impl Counter {
+            async fn bump(&mut self, #[zbus(signal_context)] ctxt: SignalContext<'_>) {
+                self.0 += 1;
+                self.count_changed(&ctxt).await.unwrap();
+            }
+
+            #[dbus_interface(property)]
+            fn count(&self) -> u32 {
+                self.0
+            }
+        }
+
+        // Read the messages received by `conn` until `replies` method returns, returning whether
+        // any signal was received in the meantime.
+        async fn signal_received(mut stream: MessageStream, mut replies: usize) -> bool {
+            let mut received = false;
+            while replies > 0 {
+                let msg = stream.next().await.unwrap().unwrap();
+                match msg.message_type() {
+                    Type::Signal => received = true,
+                    Type::MethodReturn => replies -= 1,
+                    _ => (),
+                }
+            }
+
+            received
+        }
+
+        let well_known = "org.freedesktop.zbus.ProxyClose";
+        let path = "/org/freedesktop/zbus/ProxyClose";
+        let service = connection::Builder::session()?
+            .name(well_known)?
+            .serve_at(path, Counter(0))?
+            .build()
+            .await?;
+        let service_name = service.unique_name().unwrap().to_owned();
+        let conn = Connection::session().await?;
+        let proxy: Proxy<'_> = Builder::new_bare(&conn)
+            .destination(well_known)?
+            .path(path)?
+            .interface(well_known)?
+            .cache_properties(CacheProperties::Yes)
+            .build()
+            .await?;
+        // Make the proxy track the owner of its destination.
+        proxy.receive_signal("Unknown").await?.async_drop().await;
+        assert_eq!(proxy.get_property::<u32>("Count").await?, 0);
+
+        let stream = MessageStream::from(&conn);
+        proxy.call_method("Bump", &()).await?;
+        assert!(signal_received(stream, 1).await);
+
+        proxy.close().await?;
+
+        // Neither the property changes nor the owner changes are delivered anymore.
+        let stream = MessageStream::from(&conn);
+        conn.call_method(Some(&service_name), path, Some(well_known), "Bump", &())
+            .await?;
+        service.release_name(well_known).await?;
+        conn.call_method(
+            Some("org.freedesktop.DBus"),
+            "/org/freedesktop/DBus",
+            Some("org.freedesktop.DBus"),
+            "GetId",
+            &(),
+        )
+        .await?;
+        assert!(!signal_received(stream, 2).await);
+
+        Ok(())
+    }
+
     #[test]
     #[timeout(15000)]
     fn signal_stream_deadlock() {
@@ -1503,4 +2364,103 @@ mod tests {
 
         Ok(())
     }
+
+    #[cfg(unix)]
+    #[test]
+    #[timeout(15000)]
+    fn call_method_streaming() {
+        block_on(test_call_method_streaming()).unwrap();
+    }
+
+    #[cfg(unix)]
+    async fn test_call_method_streaming() -> Result<()> {
+        #[cfg(not(feature = "tokio"))]
+        use std::os::unix::net::UnixStream;
+        #[cfg(feature = "tokio")]
+        use tokio::net::UnixStream;
+
+        let guid = crate::Guid::generate();
+        let (p0, p1) = UnixStream::pair().unwrap();
+        let (client, server) = futures_util::try_join!(
+            connection::Builder::unix_stream(p1).p2p().build(),
+            connection::Builder::unix_stream(p0)
+                .server(&guid)
+                .p2p()
+                .build(),
+        )?;
+
+        // The service sends some progress (`u`) replies before the final (`s`) one, or an error.
+        let mut server_stream = MessageStream::from(&server);
+        let server_fut = async move {
+            while let Some(call) = server_stream.next().await {
+                let call = call?;
+                let header = call.header()?;
+                if header.message_type()? != crate::message::Type::MethodCall {
+                    continue;
+                }
+                server.reply(&call, &25u32).await?;
+                server.reply(&call, &75u32).await?;
+                match header.member()?.map(|m| m.as_str()) {
+                    Some("Work") => server.reply(&call, &"done").await?,
+                    _ => {
+                        server
+                            .reply_error(&call, "org.freedesktop.zbus.Error", &"failed")
+                            .await?
+                    }
+                };
+            }
+
+            Ok::<_, Error>(())
+        };
+
+        let client_fut = async move {
+            let proxy: Proxy<'_> = Builder::new_bare(&client)
+                .destination("org.freedesktop.zbus.StreamingTest")?
+                .path("/org/freedesktop/zbus/StreamingTest")?
+                .interface("org.freedesktop.zbus.StreamingTest")?
+                .cache_properties(CacheProperties::No)
+                .build()
+                .await?;
+            let is_final = |msg: &Message| msg.body_signature().map_or(false, |s| s == "s");
+
+            let mut replies = proxy.call_method_streaming("Work", &(), is_final).await?;
+            assert_eq!(replies.next().await.unwrap()?.body::<u32>()?, 25);
+            assert_eq!(replies.next().await.unwrap()?.body::<u32>()?, 75);
+            assert_eq!(replies.next().await.unwrap()?.body::<&str>()?, "done");
+            assert!(replies.is_terminated());
+            assert!(replies.next().await.is_none());
+
+            // An idle stream doesn't hold up the replies to the other calls, even once more of
+            // them came than the method return queue can hold.
+            let mut idle = proxy.call_method_streaming("Work", &(), is_final).await?;
+            for _ in 0..10 {
+                let reply = proxy.call_method("Work", &()).await?;
+                assert_eq!(reply.body::<u32>()?, 25);
+            }
+            assert_eq!(idle.next().await.unwrap()?.body::<u32>()?, 25);
+            assert_eq!(idle.next().await.unwrap()?.body::<u32>()?, 75);
+            assert_eq!(idle.next().await.unwrap()?.body::<&str>()?, "done");
+
+            let mut replies = proxy.call_method_streaming("Fail", &(), is_final).await?;
+            assert_eq!(replies.next().await.unwrap()?.body::<u32>()?, 25);
+            assert_eq!(replies.next().await.unwrap()?.body::<u32>()?, 75);
+            match replies.next().await.unwrap() {
+                Err(Error::MethodError(name, Some(detail), _)) => {
+                    assert_eq!(name, "org.freedesktop.zbus.Error");
+                    assert_eq!(detail, "failed");
+                }
+                r => panic!("expected an error reply, got {r:?}"),
+            }
+            assert!(replies.next().await.is_none());
+
+            Ok::<_, Error>(())
+        };
+
+        futures_util::pin_mut!(server_fut);
+        futures_util::pin_mut!(client_fut);
+        match futures_util::future::select(server_fut, client_fut).await {
+            futures_util::future::Either::Left((res, _)) => res,
+            futures_util::future::Either::Right((res, _)) => res,
+        }
+    }
 }
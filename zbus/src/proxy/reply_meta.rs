@@ -0,0 +1,51 @@
+use static_assertions::assert_impl_all;
+use std::num::NonZeroU32;
+use zbus_names::OwnedUniqueName;
+
+use crate::{message::Message, Error, Result};
+
+/// The headers of a method reply, as returned along with its body by the `<method>_with_headers`
+/// methods that the `with_headers` attribute of [`dbus_proxy`] generates.
+///
+/// [`dbus_proxy`]: macro@crate::dbus_proxy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplyMeta {
+    sender: Option<OwnedUniqueName>,
+    serial: Option<NonZeroU32>,
+    reply_serial: Option<NonZeroU32>,
+}
+
+assert_impl_all!(ReplyMeta: Send, Sync, Unpin);
+
+impl ReplyMeta {
+    /// The unique name of the connection that sent the reply, if known.
+    ///
+    /// Only the messages routed through a bus are guaranteed to have a sender.
+    pub fn sender(&self) -> Option<&OwnedUniqueName> {
+        self.sender.as_ref()
+    }
+
+    /// The serial number of the reply.
+    pub fn serial(&self) -> Option<NonZeroU32> {
+        self.serial
+    }
+
+    /// The serial number of the method call the reply is for.
+    pub fn reply_serial(&self) -> Option<NonZeroU32> {
+        self.reply_serial
+    }
+}
+
+impl TryFrom<&Message> for ReplyMeta {
+    type Error = Error;
+
+    fn try_from(reply: &Message) -> Result<Self> {
+        let header = reply.header()?;
+
+        Ok(Self {
+            sender: header.sender()?.map(|s| s.to_owned().into()),
+            serial: header.primary().serial_num(),
+            reply_serial: header.reply_serial()?,
+        })
+    }
+}
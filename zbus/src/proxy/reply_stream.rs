@@ -0,0 +1,87 @@
+use futures_core::stream;
+use static_assertions::assert_impl_all;
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use crate::{
+    connection::ReplyChannel,
+    message::{Message, Type},
+    Result,
+};
+
+/// A [`stream::Stream`] implementation that yields the replies to a single method call.
+///
+/// Use [`Proxy::call_method_streaming`] to create an instance of this type.
+///
+/// [`Proxy::call_method_streaming`]: crate::Proxy::call_method_streaming
+#[derive(derivative::Derivative)]
+#[derivative(Debug)]
+pub struct ReplyStream {
+    // `None` once the final reply has been received.
+    channel: Option<ReplyChannel>,
+    #[derivative(Debug = "ignore")]
+    is_final: Box<dyn FnMut(&Message) -> bool + Send + Sync>,
+}
+
+assert_impl_all!(ReplyStream: Send, Sync, Unpin);
+
+impl ReplyStream {
+    pub(crate) fn new<F>(channel: ReplyChannel, is_final: F) -> Self
+    where
+        F: FnMut(&Message) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            channel: Some(channel),
+            is_final: Box::new(is_final),
+        }
+    }
+}
+
+impl stream::Stream for ReplyStream {
+    type Item = Result<Arc<Message>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while let Some(channel) = &mut this.channel {
+            let msg = match futures_core::ready!(Pin::new(&mut channel.stream).poll_next(cx)) {
+                Some(Ok(msg)) => msg,
+                Some(Err(e)) => {
+                    this.channel = None;
+
+                    return Poll::Ready(Some(Err(e)));
+                }
+                None => {
+                    this.channel = None;
+
+                    break;
+                }
+            };
+            match msg.message_type() {
+                Type::Error => {
+                    this.channel = None;
+
+                    return Poll::Ready(Some(Err(msg.into())));
+                }
+                Type::MethodReturn => {
+                    if (this.is_final)(&msg) {
+                        this.channel = None;
+                    }
+
+                    return Poll::Ready(Some(Ok(msg)));
+                }
+                _ => continue,
+            }
+        }
+
+        Poll::Ready(None)
+    }
+}
+
+impl stream::FusedStream for ReplyStream {
+    fn is_terminated(&self) -> bool {
+        self.channel.is_none()
+    }
+}
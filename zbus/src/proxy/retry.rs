@@ -0,0 +1,234 @@
+use static_assertions::assert_impl_all;
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+use tracing::debug;
+use zbus_names::{ErrorName, OwnedErrorName};
+
+use crate::{abstractions::timer::sleep, DBusError, Error};
+
+/// The policy for retrying method calls through [`Proxy::call_with_retry`].
+///
+/// By default, calls failing with the following errors are retried:
+///
+/// * `org.freedesktop.DBus.Error.NameHasNoOwner`
+/// * `org.freedesktop.DBus.Error.ServiceUnknown`
+///
+/// Both typically occur when the destination service is (re)starting or not yet activated. Calls
+/// that [timed out] are not retried by default, since the method may have been run by the service
+/// nevertheless, but you can opt in with [`RetryPolicy::retry_timeouts`]. The
+/// first retry happens after 10ms and the delay is doubled for each subsequent retry, up to a
+/// maximum of 1s. No retry is attempted if it would happen beyond the deadline of 5s (counting from
+/// the first attempt).
+///
+/// # Example
+///
+/// ```
+/// # zbus::block_on(async {
+/// use std::time::Duration;
+/// use zbus::{proxy::RetryPolicy, Connection, Proxy};
+///
+/// let connection = Connection::session().await?;
+/// let proxy = Proxy::new(
+///     &connection,
+///     "org.freedesktop.DBus",
+///     "/org/freedesktop/DBus",
+///     "org.freedesktop.DBus",
+/// )
+/// .await?;
+/// let policy = RetryPolicy::default()
+///     .add_error("org.freedesktop.DBus.Error.NoReply")?
+///     .retry_timeouts(true)
+///     .initial_delay(Duration::from_millis(50))
+///     .timeout(Duration::from_secs(30));
+/// let _id: String = proxy.call_with_retry("GetId", &(), &policy).await?;
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+///
+/// [`Proxy::call_with_retry`]: super::Proxy::call_with_retry
+/// [timed out]: crate::Error::Timeout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    errors: Vec<OwnedErrorName>,
+    timeouts: bool,
+    initial_delay: Duration,
+    max_delay: Duration,
+    timeout: Duration,
+}
+
+assert_impl_all!(RetryPolicy: Send, Sync, Unpin);
+
+impl RetryPolicy {
+    /// Create a new policy, with the default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the names of the errors to retry on, replacing the default ones.
+    #[must_use]
+    pub fn errors<I>(mut self, errors: I) -> Self
+    where
+        I: IntoIterator<Item = OwnedErrorName>,
+    {
+        self.errors = errors.into_iter().collect();
+
+        self
+    }
+
+    /// Add the name of an error to retry on.
+    pub fn add_error<'e, E>(mut self, error: E) -> crate::Result<Self>
+    where
+        E: TryInto<ErrorName<'e>>,
+        E::Error: Into<Error>,
+    {
+        let error = error.try_into().map_err(Into::into)?;
+        self.errors.push(error.into());
+
+        Ok(self)
+    }
+
+    /// Set whether to retry the calls that failed with [`Error::Timeout`].
+    ///
+    /// Only use this for methods that are safe to call more than once.
+    #[must_use]
+    pub fn retry_timeouts(mut self, retry: bool) -> Self {
+        self.timeouts = retry;
+
+        self
+    }
+
+    /// Set the delay before the first retry.
+    #[must_use]
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+
+        self
+    }
+
+    /// Set the maximum delay between two attempts.
+    #[must_use]
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+
+        self
+    }
+
+    /// Set the deadline, after which no more attempts are made.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+
+        self
+    }
+
+    /// Call `f` until it succeeds, fails with an error not covered by this policy or the deadline
+    /// is reached, in which case the last error is returned.
+    pub(crate) async fn retry<F, Fut, T>(&self, mut f: F) -> crate::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = crate::Result<T>>,
+    {
+        // `None` if the deadline is too far away to be represented, i.e there's none.
+        let deadline = Instant::now().checked_add(self.timeout);
+        let mut delay = self.initial_delay;
+
+        loop {
+            match f().await {
+                Err(e) if self.should_retry(&e) && before(deadline, delay) => {
+                    debug!("Call failed with `{}`, retrying in {:?}", e, delay);
+                    sleep(delay).await;
+                    delay = self.next_delay(delay);
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// The delay to wait after `delay`, for the next retry.
+    fn next_delay(&self, delay: Duration) -> Duration {
+        delay
+            .checked_mul(2)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// If the call that failed with `error` should be retried.
+    fn should_retry(&self, error: &Error) -> bool {
+        let name = match error {
+            Error::MethodError(name, _, _) => name.as_ref(),
+            Error::FDO(e) => e.name(),
+            Error::Timeout(_, _) => return self.timeouts,
+            _ => return false,
+        };
+
+        self.errors.iter().any(|e| **e == name)
+    }
+}
+
+/// Whether waiting for `delay` from now ends before `deadline`.
+fn before(deadline: Option<Instant>, delay: Duration) -> bool {
+    match (deadline, Instant::now().checked_add(delay)) {
+        (Some(deadline), Some(end)) => end < deadline,
+        (None, Some(_)) => true,
+        // Such a retry would never happen.
+        (_, None) => false,
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            errors: vec![
+                ErrorName::from_static_str_unchecked("org.freedesktop.DBus.Error.NameHasNoOwner")
+                    .into(),
+                ErrorName::from_static_str_unchecked("org.freedesktop.DBus.Error.ServiceUnknown")
+                    .into(),
+            ],
+            timeouts: false,
+            initial_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(1),
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetryPolicy;
+    use crate::{block_on, fdo, Error};
+    use std::time::Duration;
+    use test_log::test;
+
+    #[test]
+    fn huge_delays() {
+        // Doubling the delay doesn't overflow.
+        let policy = RetryPolicy::new().max_delay(Duration::MAX);
+        assert_eq!(policy.next_delay(Duration::MAX), Duration::MAX);
+        assert_eq!(
+            policy.next_delay(Duration::MAX / 2 + Duration::from_secs(1)),
+            Duration::MAX
+        );
+        let policy = RetryPolicy::new().max_delay(Duration::from_secs(1));
+        assert_eq!(policy.next_delay(Duration::MAX), Duration::from_secs(1));
+
+        // Nor does computing the deadline, and a retry that would happen after it isn't attempted.
+        let policy = RetryPolicy::new()
+            .initial_delay(Duration::MAX)
+            .max_delay(Duration::MAX)
+            .timeout(Duration::MAX);
+        let mut calls = 0;
+        let res: crate::Result<()> = block_on(policy.retry(|| {
+            calls += 1;
+
+            async {
+                Err(Error::FDO(Box::new(fdo::Error::ServiceUnknown(
+                    String::new(),
+                ))))
+            }
+        }));
+        assert!(matches!(res, Err(Error::FDO(_))));
+        assert_eq!(calls, 1);
+    }
+}
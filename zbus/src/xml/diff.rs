@@ -0,0 +1,219 @@
+use static_assertions::assert_impl_all;
+
+use super::{Interface, Method, Node, Property, Signal};
+use crate::names::InterfaceName;
+
+/// The differences between the interfaces of two [`Node`]s.
+///
+/// Use [`Node::diff`] to create one.
+#[derive(Debug, Clone)]
+pub struct NodeDiff<'n> {
+    added_interfaces: Vec<&'n Interface<'n>>,
+    removed_interfaces: Vec<&'n Interface<'n>>,
+    changed_interfaces: Vec<InterfaceDiff<'n>>,
+}
+
+assert_impl_all!(NodeDiff<'_>: Send, Sync, Unpin);
+
+impl<'n> NodeDiff<'n> {
+    pub(super) fn compare(old: &'n Node<'n>, new: &'n Node<'n>) -> Self {
+        let (added_interfaces, removed_interfaces, changed) =
+            diff_by_name(old.interfaces(), new.interfaces(), |a, b| {
+                a.name() == b.name()
+            });
+        let changed_interfaces = changed
+            .into_iter()
+            .map(|c| InterfaceDiff::compare(c.old, c.new))
+            .collect();
+
+        Self {
+            added_interfaces,
+            removed_interfaces,
+            changed_interfaces,
+        }
+    }
+
+    /// The interfaces only present in the newer node.
+    pub fn added_interfaces(&self) -> &[&'n Interface<'n>] {
+        &self.added_interfaces
+    }
+
+    /// The interfaces only present in the older node.
+    pub fn removed_interfaces(&self) -> &[&'n Interface<'n>] {
+        &self.removed_interfaces
+    }
+
+    /// The interfaces present in both nodes, that differ between them.
+    pub fn changed_interfaces(&self) -> &[InterfaceDiff<'n>] {
+        &self.changed_interfaces
+    }
+
+    /// Whether the interfaces of both nodes are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added_interfaces.is_empty()
+            && self.removed_interfaces.is_empty()
+            && self.changed_interfaces.is_empty()
+    }
+}
+
+/// The differences between two versions of an [`Interface`].
+#[derive(Debug, Clone)]
+pub struct InterfaceDiff<'n> {
+    old: &'n Interface<'n>,
+    new: &'n Interface<'n>,
+    methods: MembersDiff<'n, Method<'n>>,
+    properties: MembersDiff<'n, Property<'n>>,
+    signals: MembersDiff<'n, Signal<'n>>,
+}
+
+assert_impl_all!(InterfaceDiff<'_>: Send, Sync, Unpin);
+
+impl<'n> InterfaceDiff<'n> {
+    fn compare(old: &'n Interface<'n>, new: &'n Interface<'n>) -> Self {
+        Self {
+            old,
+            new,
+            methods: MembersDiff::compare(old.methods(), new.methods(), |a, b| {
+                a.name() == b.name()
+            }),
+            properties: MembersDiff::compare(old.properties(), new.properties(), |a, b| {
+                a.name() == b.name()
+            }),
+            signals: MembersDiff::compare(old.signals(), new.signals(), |a, b| {
+                a.name() == b.name()
+            }),
+        }
+    }
+
+    /// The interface name.
+    pub fn name(&self) -> InterfaceName<'_> {
+        self.old.name()
+    }
+
+    /// The older version of the interface.
+    pub fn before(&self) -> &'n Interface<'n> {
+        self.old
+    }
+
+    /// The newer version of the interface.
+    pub fn after(&self) -> &'n Interface<'n> {
+        self.new
+    }
+
+    /// The differences between the methods of both versions.
+    pub fn methods(&self) -> &MembersDiff<'n, Method<'n>> {
+        &self.methods
+    }
+
+    /// The differences between the properties of both versions.
+    pub fn properties(&self) -> &MembersDiff<'n, Property<'n>> {
+        &self.properties
+    }
+
+    /// The differences between the signals of both versions.
+    pub fn signals(&self) -> &MembersDiff<'n, Signal<'n>> {
+        &self.signals
+    }
+}
+
+/// The differences between the methods, properties or signals of two versions of an interface.
+#[derive(Debug, Clone)]
+pub struct MembersDiff<'n, T> {
+    added: Vec<&'n T>,
+    removed: Vec<&'n T>,
+    changed: Vec<Changed<'n, T>>,
+}
+
+assert_impl_all!(MembersDiff<'_, Method<'_>>: Send, Sync, Unpin);
+
+impl<'n, T> MembersDiff<'n, T>
+where
+    T: PartialEq,
+{
+    fn compare(old: &'n [T], new: &'n [T], same_name: fn(&T, &T) -> bool) -> Self {
+        let (added, removed, changed) = diff_by_name(old, new, same_name);
+
+        Self {
+            added,
+            removed,
+            changed,
+        }
+    }
+}
+
+impl<'n, T> MembersDiff<'n, T> {
+    /// The members only present in the newer version.
+    pub fn added(&self) -> &[&'n T] {
+        &self.added
+    }
+
+    /// The members only present in the older version.
+    pub fn removed(&self) -> &[&'n T] {
+        &self.removed
+    }
+
+    /// The members present in both versions, that differ between them.
+    pub fn changed(&self) -> &[Changed<'n, T>] {
+        &self.changed
+    }
+
+    /// Whether the members of both versions are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// An item present in both versions, that differs between them.
+#[derive(Debug)]
+pub struct Changed<'n, T> {
+    old: &'n T,
+    new: &'n T,
+}
+
+assert_impl_all!(Changed<'_, Method<'_>>: Send, Sync, Unpin);
+
+impl<'n, T> Changed<'n, T> {
+    /// The older version of the item.
+    pub fn before(&self) -> &'n T {
+        self.old
+    }
+
+    /// The newer version of the item.
+    pub fn after(&self) -> &'n T {
+        self.new
+    }
+}
+
+// Not derived since that would require `T: Clone`.
+impl<T> Clone for Changed<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            old: self.old,
+            new: self.new,
+        }
+    }
+}
+
+type ByName<'n, T> = (Vec<&'n T>, Vec<&'n T>, Vec<Changed<'n, T>>);
+
+/// Match the items of `old` and `new` by name, returning the added, removed and changed ones.
+fn diff_by_name<'n, T>(old: &'n [T], new: &'n [T], same_name: fn(&T, &T) -> bool) -> ByName<'n, T>
+where
+    T: PartialEq,
+{
+    let added = new
+        .iter()
+        .filter(|n| !old.iter().any(|o| same_name(o, n)))
+        .collect();
+    let mut removed = vec![];
+    let mut changed = vec![];
+    for o in old {
+        match new.iter().find(|n| same_name(o, n)) {
+            Some(n) if n != o => changed.push(Changed { old: o, new: n }),
+            Some(_) => (),
+            None => removed.push(o),
+        }
+    }
+
+    (added, removed, changed)
+}
@@ -12,6 +12,9 @@
 //! [Introspection format]: https://dbus.freedesktop.org/doc/dbus-specification.html#introspection-format
 //! [`org.freedesktop.DBus.Introspectable`]: https://dbus.freedesktop.org/doc/dbus-specification.html#standard-interfaces-introspectable
 
+mod diff;
+pub use diff::*;
+
 use quick_xml::{de::Deserializer, se::to_writer};
 use serde::{Deserialize, Serialize};
 use static_assertions::assert_impl_all;
@@ -316,6 +319,46 @@ impl<'a> Node<'a> {
     pub fn interfaces(&self) -> &[Interface<'a>] {
         &self.interfaces
     }
+
+    /// Compare the interfaces of `self` with the ones of `other`, a newer version of the node.
+    ///
+    /// Interfaces and their members are matched by name. Members that exist in both nodes but
+    /// aren't identical (e.g a method that gained an argument, a property of which the type changed
+    /// or even a signal with a renamed argument or a new annotation) are reported as changed.
+    ///
+    /// Only the interfaces of the nodes are compared, not the children nodes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use zbus::xml::Node;
+    ///
+    /// let old = Node::try_from(
+    ///     r#"<node><interface name="org.example.Player">
+    ///          <method name="Play"/>
+    ///          <property name="Volume" type="u" access="read"/>
+    ///        </interface></node>"#,
+    /// )?;
+    /// let new = Node::try_from(
+    ///     r#"<node><interface name="org.example.Player">
+    ///          <method name="Play"/>
+    ///          <method name="Pause"/>
+    ///          <property name="Volume" type="d" access="readwrite"/>
+    ///        </interface></node>"#,
+    /// )?;
+    ///
+    /// let diff = old.diff(&new);
+    /// let player = &diff.changed_interfaces()[0];
+    /// assert_eq!(player.name(), "org.example.Player");
+    /// assert_eq!(player.methods().added()[0].name(), "Pause");
+    /// let volume = &player.properties().changed()[0];
+    /// assert_eq!(volume.before().ty().signature(), "u");
+    /// assert_eq!(volume.after().ty().signature(), "d");
+    /// # Ok::<(), zbus::Error>(())
+    /// ```
+    pub fn diff<'n>(&'n self, other: &'n Node<'_>) -> NodeDiff<'n> {
+        NodeDiff::compare(self, other)
+    }
 }
 
 impl<'a> TryFrom<&'a str> for Node<'a> {
@@ -328,3 +371,160 @@ impl<'a> TryFrom<&'a str> for Node<'a> {
         Ok(Node::deserialize(&mut deserializer)?)
     }
 }
+
+/// Panics if the `proxy` and `interface` introspection XML of an interface don't agree.
+///
+/// Every member the proxy expects must be provided by the interface, with the same signature.
+/// Properties must also be readable if the proxy has a getter, and writable if it has a setter.
+/// The interface is free to provide more members than the proxy uses.
+///
+/// This is what the tests generated by [`assert_proxy_matches_interface`] call.
+///
+/// [`assert_proxy_matches_interface`]: crate::assert_proxy_matches_interface
+#[doc(hidden)]
+pub fn assert_proxy_matches_interface(proxy: &str, interface: &str) {
+    let proxy_xml = format!("<node>{proxy}</node>");
+    let proxy_node = Node::try_from(proxy_xml.as_str()).expect("invalid proxy introspection");
+    let iface_xml = format!("<node>{interface}</node>");
+    let iface_node = Node::try_from(iface_xml.as_str()).expect("invalid interface introspection");
+    let mismatches = mismatches(&proxy_node.interfaces()[0], &iface_node.interfaces()[0]);
+
+    assert!(
+        mismatches.is_empty(),
+        "the proxy doesn't match the interface:\n  {}",
+        mismatches.join("\n  ")
+    );
+}
+
+/// Check that the object `proxy` points to provides the interface it's for, as expected.
+///
+/// `expected` is the introspection XML of the interface, as the proxy expects it. The object is
+/// introspected and compared in the same way as [`assert_proxy_matches_interface`] does, except
+/// that an [`Error::Failure`] listing the mismatches is returned.
+///
+/// This is what proxies created with the `verify_on_build` attribute of
+/// [`dbus_proxy`](macro@crate::dbus_proxy) call when built.
+pub(crate) async fn verify_proxy(proxy: &crate::Proxy<'_>, expected: &str) -> crate::Result<()> {
+    let proxy_xml = format!("<node>{expected}</node>");
+    let proxy_node = Node::try_from(proxy_xml.as_str())?;
+    let expected = &proxy_node.interfaces()[0];
+    // Not using `Proxy::introspect`, since it builds a proxy itself.
+    let actual_xml: String = proxy
+        .connection()
+        .call_method(
+            Some(proxy.destination()),
+            proxy.path(),
+            Some("org.freedesktop.DBus.Introspectable"),
+            "Introspect",
+            &(),
+        )
+        .await?
+        .body()?;
+    let actual_node = Node::try_from(actual_xml.as_str())?;
+
+    let mismatches = match actual_node
+        .interfaces()
+        .iter()
+        .find(|i| i.name() == expected.name())
+    {
+        Some(actual) => mismatches(expected, actual),
+        None => vec![format!("interface `{}` is missing", expected.name())],
+    };
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+
+    Err(Error::Failure(format!(
+        "`{}` at `{}` doesn't match the proxy: {}",
+        proxy.destination(),
+        proxy.path(),
+        mismatches.join(", ")
+    )))
+}
+
+/// The ways in which the `iface` interface doesn't provide what the `proxy` expects.
+fn mismatches(proxy: &Interface<'_>, iface: &Interface<'_>) -> Vec<String> {
+    let mut mismatches = vec![];
+    if proxy.name() != iface.name() {
+        mismatches.push(format!(
+            "the proxy is for interface `{}`, not `{}`",
+            proxy.name(),
+            iface.name()
+        ));
+    }
+    for method in proxy.methods() {
+        match iface.methods().iter().find(|m| m.name() == method.name()) {
+            Some(m) => {
+                let expected = (
+                    args_signature(method.args(), false),
+                    args_signature(method.args(), true),
+                );
+                let actual = (
+                    args_signature(m.args(), false),
+                    args_signature(m.args(), true),
+                );
+                if expected != actual {
+                    mismatches.push(format!(
+                        "method `{}` takes `{}` and returns `{}`, not `{}` and `{}`",
+                        method.name(),
+                        actual.0,
+                        actual.1,
+                        expected.0,
+                        expected.1,
+                    ));
+                }
+            }
+            None => mismatches.push(format!("method `{}` is missing", method.name())),
+        }
+    }
+    for signal in proxy.signals() {
+        match iface.signals().iter().find(|s| s.name() == signal.name()) {
+            Some(s) => {
+                let expected = args_signature(signal.args(), false);
+                let actual = args_signature(s.args(), false);
+                if expected != actual {
+                    mismatches.push(format!(
+                        "signal `{}` has arguments `{actual}`, not `{expected}`",
+                        signal.name(),
+                    ));
+                }
+            }
+            None => mismatches.push(format!("signal `{}` is missing", signal.name())),
+        }
+    }
+    for property in proxy.properties() {
+        match iface
+            .properties()
+            .iter()
+            .find(|p| p.name() == property.name())
+        {
+            Some(p) => {
+                if p.ty() != property.ty() {
+                    mismatches.push(format!(
+                        "property `{}` is of type `{}`, not `{}`",
+                        property.name(),
+                        p.ty(),
+                        property.ty(),
+                    ));
+                }
+                if property.access().read() && !p.access().read() {
+                    mismatches.push(format!("property `{}` isn't readable", property.name()));
+                }
+                if property.access().write() && !p.access().write() {
+                    mismatches.push(format!("property `{}` isn't writable", property.name()));
+                }
+            }
+            None => mismatches.push(format!("property `{}` is missing", property.name())),
+        }
+    }
+
+    mismatches
+}
+
+/// The concatenated signatures of the input (or output, if `out`) arguments in `args`.
+fn args_signature(args: &[Arg<'_>], out: bool) -> String {
+    args.iter()
+        .filter(|arg| (arg.direction() == Some(ArgDirection::Out)) == out)
+        .map(|arg| arg.ty().signature().as_str())
+        .collect()
+}
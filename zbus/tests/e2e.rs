@@ -71,6 +71,8 @@ trait MyIface {
 
     fn test_hashmap_return(&self) -> zbus::Result<HashMap<String, String>>;
 
+    fn test_array_args(&self, ints: &[u32], strs: Vec<String>) -> zbus::Result<u32>;
+
     fn create_obj(&self, key: &str) -> zbus::Result<()>;
 
     fn destroy_obj(&self, key: &str) -> zbus::Result<()>;
@@ -253,6 +255,12 @@ impl MyIfaceImpl {
         Ok(map)
     }
 
+    #[instrument]
+    fn test_array_args(&self, ints: Vec<u32>, strs: Vec<String>) -> u32 {
+        debug!("`TestArrayArgs` called with {:?} and {:?}", ints, strs);
+        ints.iter().sum::<u32>() + strs.len() as u32
+    }
+
     #[instrument]
     async fn create_obj(&self, key: String) {
         debug!("`CreateObj` called.");
@@ -505,6 +513,11 @@ async fn my_iface_test(conn: Connection, event: Event) -> zbus::Result<u32> {
         .await?;
     check_hash_map(proxy.test_hashmap_return().await?);
     check_hash_map(proxy.hash_map().await?);
+    let ints = vec![1, 2, 3];
+    let strs = ["a".to_string(), "b".to_string()];
+    assert_eq!(proxy.test_array_args(&ints, &strs).await?, 8);
+    assert_eq!(proxy.test_array_args([4], vec![]).await?, 4);
+    assert_eq!(proxy.test_array_args(&ints[1..], &strs[..1]).await?, 6);
     proxy
         .set_address_data(IP4Adress {
             address: "localhost".to_string(),
@@ -574,6 +587,53 @@ async fn my_iface_test(conn: Connection, event: Event) -> zbus::Result<u32> {
         .build()
         .await?;
     debug!("Created: {:?}", obj_manager_proxy);
+
+    #[cfg(feature = "xml")]
+    {
+        let xml = zbus::fdo::IntrospectableProxy::builder(&conn)
+            .destination("org.freedesktop.MyService")?
+            .path("/zbus/test")?
+            .build()
+            .await?
+            .introspect()
+            .await?;
+        let node = zbus::xml::Node::from_reader(xml.as_bytes())?;
+        let ifaces = node.interfaces();
+        let iface = ifaces
+            .iter()
+            .find(|i| i.name() == "org.freedesktop.DBus.ObjectManager")
+            .unwrap();
+        let methods = iface.methods();
+        assert_eq!(methods.len(), 1);
+        let method = &methods[0];
+        assert_eq!(method.name(), "GetManagedObjects");
+        let args = method.args();
+        assert_eq!(args.len(), 1);
+        assert_eq!(args[0].name(), Some("objpath_interfaces_and_properties"));
+        assert_eq!(args[0].ty().signature(), "a{oa{sa{sv}}}");
+        let signals = iface.signals();
+        assert_eq!(signals.len(), 2);
+        let added = signals
+            .iter()
+            .find(|s| s.name() == "InterfacesAdded")
+            .unwrap();
+        let added_args: Vec<_> = added
+            .args()
+            .iter()
+            .map(|a| a.ty().signature().to_string())
+            .collect();
+        assert_eq!(added_args, ["o", "a{sa{sv}}"]);
+        let removed = signals
+            .iter()
+            .find(|s| s.name() == "InterfacesRemoved")
+            .unwrap();
+        let removed_args: Vec<_> = removed
+            .args()
+            .iter()
+            .map(|a| a.ty().signature().to_string())
+            .collect();
+        assert_eq!(removed_args, ["o", "as"]);
+    }
     let mut ifaces_added_stream = obj_manager_proxy.receive_interfaces_added().await?;
     debug!("Created: {:?}", ifaces_added_stream);
 
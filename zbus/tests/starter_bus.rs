@@ -0,0 +1,26 @@
+use std::{env, str::FromStr};
+
+use test_log::test;
+use zbus::{Address, Error};
+
+// This is in its own test binary since it changes the environment of the process.
+#[test]
+fn starter_bus_address_from_env() {
+    env::remove_var("DBUS_STARTER_ADDRESS");
+    env::remove_var("DBUS_STARTER_BUS_TYPE");
+    assert!(matches!(Address::starter_bus(), Err(Error::Address(_))));
+
+    env::set_var("DBUS_STARTER_BUS_TYPE", "session");
+    assert_eq!(Address::starter_bus().unwrap(), Address::session().unwrap());
+    env::set_var("DBUS_STARTER_BUS_TYPE", "system");
+    assert_eq!(Address::starter_bus().unwrap(), Address::system().unwrap());
+    env::set_var("DBUS_STARTER_BUS_TYPE", "starter");
+    assert!(matches!(Address::starter_bus(), Err(Error::Address(_))));
+
+    // The address takes precedence over the type.
+    env::set_var("DBUS_STARTER_ADDRESS", "tcp:host=localhost,port=4142");
+    assert_eq!(
+        Address::starter_bus().unwrap(),
+        Address::from_str("tcp:host=localhost,port=4142").unwrap()
+    );
+}
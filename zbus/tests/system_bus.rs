@@ -0,0 +1,52 @@
+#![cfg(all(unix, not(feature = "tokio")))]
+
+use std::{os::unix::net::UnixListener, thread};
+
+use ntest::timeout;
+use test_log::test;
+use zbus::{block_on, connection, dbus_interface, names::OwnedUniqueName, Connection, Guid};
+
+// Just enough of the bus interface for a client connection to be set up.
+struct FakeBus;
+
+#[dbus_interface(name = "org.freedesktop.DBus")]
+impl FakeBus {
+    fn hello(&self) -> OwnedUniqueName {
+        OwnedUniqueName::try_from(":zbus.fake.1").unwrap()
+    }
+}
+
+// This is in its own test binary since it changes the environment of the process.
+#[test]
+#[timeout(15000)]
+fn system_bus_address_from_env() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("system_bus_socket");
+    let listener = UnixListener::bind(&path).unwrap();
+    std::env::set_var(
+        "DBUS_SYSTEM_BUS_ADDRESS",
+        format!("unix:path={}", path.display()),
+    );
+
+    let (done_tx, done_rx) = std::sync::mpsc::channel::<()>();
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let _conn = block_on(
+            connection::Builder::unix_stream(stream)
+                .server(&Guid::generate())
+                .p2p()
+                .serve_at("/org/freedesktop/DBus", FakeBus)
+                .unwrap()
+                .build(),
+        )
+        .unwrap();
+        // Keep the connection around until the client is done with it.
+        done_rx.recv().unwrap();
+    });
+
+    let conn = block_on(Connection::system()).unwrap();
+    assert_eq!(conn.unique_name().unwrap().as_str(), ":zbus.fake.1");
+
+    done_tx.send(()).unwrap();
+    server.join().unwrap();
+}
@@ -4,7 +4,7 @@ use quick_xml::de::DeError;
 use std::error::Error;
 use test_log::test;
 
-use zbus::xml::{ArgDirection, Node};
+use zbus::xml::{ArgDirection, Node, PropertyAccess};
 
 #[test]
 fn serde() -> Result<(), Box<dyn Error>> {
@@ -39,3 +39,80 @@ fn invalid_arg_type() {
         Err(zbus::Error::QuickXml(DeError::Custom(_)))
     ));
 }
+
+#[test]
+fn diff() -> Result<(), Box<dyn Error>> {
+    let old = Node::try_from(
+        r#"<node>
+             <interface name="org.example.Removed"/>
+             <interface name="org.example.Same">
+               <method name="Ping"/>
+             </interface>
+             <interface name="org.example.Changed">
+               <method name="Kept"/>
+               <method name="Gone"/>
+               <method name="Connect">
+                 <arg name="address" type="s" direction="in"/>
+               </method>
+               <property name="Volume" type="u" access="read"/>
+               <signal name="Stopped"/>
+             </interface>
+           </node>"#,
+    )?;
+    let new = Node::try_from(
+        r#"<node>
+             <interface name="org.example.Same">
+               <method name="Ping"/>
+             </interface>
+             <interface name="org.example.Changed">
+               <method name="Connect">
+                 <arg name="address" type="s" direction="in"/>
+                 <arg name="timeout" type="u" direction="in"/>
+               </method>
+               <method name="Kept"/>
+               <method name="New"/>
+               <property name="Volume" type="u" access="readwrite"/>
+               <signal name="Stopped"/>
+             </interface>
+             <interface name="org.example.Added"/>
+           </node>"#,
+    )?;
+
+    assert!(old.diff(&old).is_empty());
+
+    let diff = old.diff(&new);
+    assert!(!diff.is_empty());
+    let names = |ifaces: &[&zbus::xml::Interface<'_>]| {
+        ifaces
+            .iter()
+            .map(|i| i.name().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(diff.added_interfaces()), ["org.example.Added"]);
+    assert_eq!(names(diff.removed_interfaces()), ["org.example.Removed"]);
+    assert_eq!(diff.changed_interfaces().len(), 1);
+
+    let iface = &diff.changed_interfaces()[0];
+    assert_eq!(iface.name(), "org.example.Changed");
+    let methods = iface.methods();
+    assert_eq!(methods.added().len(), 1);
+    assert_eq!(methods.added()[0].name(), "New");
+    assert_eq!(methods.removed().len(), 1);
+    assert_eq!(methods.removed()[0].name(), "Gone");
+    // Reordering members is not a change.
+    assert_eq!(methods.changed().len(), 1);
+    let connect = &methods.changed()[0];
+    assert_eq!(connect.before().name(), "Connect");
+    assert_eq!(connect.before().args().len(), 1);
+    assert_eq!(connect.after().args().len(), 2);
+
+    let properties = iface.properties();
+    assert!(properties.added().is_empty() && properties.removed().is_empty());
+    assert_eq!(
+        properties.changed()[0].after().access(),
+        PropertyAccess::ReadWrite
+    );
+    assert!(iface.signals().is_empty());
+
+    Ok(())
+}
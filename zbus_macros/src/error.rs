@@ -3,9 +3,9 @@ use quote::{quote, ToTokens};
 use syn::{spanned::Spanned, Data, DeriveInput, Error, Fields, Ident, Variant};
 use zvariant_utils::def_attrs;
 
-// FIXME: The list name should once be "zbus" instead of "dbus_error" (like in serde).
+// The list is also available as "zbus" (like in serde), the preferred name for new code.
 def_attrs! {
-    crate dbus_error;
+    crate dbus_error, zbus;
 
     pub StructAttributes("struct") {
         prefix str,
@@ -14,7 +14,8 @@ def_attrs! {
 
     pub VariantAttributes("enum variant") {
         name str,
-        zbus_error none
+        zbus_error none,
+        dynamic none
     };
 }
 
@@ -40,9 +41,36 @@ pub fn expand_derive(input: DeriveInput) -> Result<TokenStream, Error> {
     let mut error_converts = quote! {};
 
     let mut zbus_error_variant = None;
+    let mut dynamic_variant = None;
 
     for variant in data.variants {
-        let VariantAttributes { name, zbus_error } = VariantAttributes::parse(&variant.attrs)?;
+        let VariantAttributes {
+            name,
+            zbus_error,
+            dynamic,
+        } = VariantAttributes::parse(&variant.attrs)?;
+
+        if dynamic {
+            if zbus_error || name.is_some() {
+                return Err(Error::new(
+                    variant.span(),
+                    "`dynamic` can't be combined with `zbus_error` or `name`",
+                ));
+            }
+            if dynamic_variant.is_some() {
+                return Err(Error::new(
+                    variant.span(),
+                    "more than 1 `dynamic` variant found",
+                ));
+            }
+            let (names, errors, descriptions, reply) = gen_dynamic_variant(&variant)?;
+            error_names.extend(names);
+            error_descriptions.extend(descriptions);
+            replies.extend(reply);
+            dynamic_variant = Some(errors);
+
+            continue;
+        }
 
         let ident = &variant.ident;
         let name = name.unwrap_or_else(|| ident.to_string());
@@ -141,6 +169,13 @@ pub fn expand_derive(input: DeriveInput) -> Result<TokenStream, Error> {
         replies.extend(r);
     }
 
+    // Errors with unknown names are converted to the dynamic variant, if any.
+    let fallback_convert = dynamic_variant.unwrap_or_else(|| {
+        zbus_error_variant
+            .as_ref()
+            .map(|ident| quote! { _ => Self::#ident(value), })
+            .unwrap_or_default()
+    });
     let from_zbus_error_impl = zbus_error_variant
         .map(|ident| {
             quote! {
@@ -149,7 +184,7 @@ pub fn expand_derive(input: DeriveInput) -> Result<TokenStream, Error> {
                         if let #zbus::Error::MethodError(name, desc, _) = &value {
                             match name.as_str() {
                                 #error_converts
-                                _ => Self::#ident(value),
+                                #fallback_convert
                             }
                         } else {
                             Self::#ident(value)
@@ -204,6 +239,58 @@ pub fn expand_derive(input: DeriveInput) -> Result<TokenStream, Error> {
     })
 }
 
+// The match arms of the `name`, `description` and `create_reply` methods and of the `From<Error>`
+// conversion, for a variant whose error name is only known at runtime.
+fn gen_dynamic_variant(
+    variant: &Variant,
+) -> Result<(TokenStream, TokenStream, TokenStream, TokenStream), Error> {
+    let zbus = zbus_path();
+    let ident = &variant.ident;
+    let has_field = |field: &str| match &variant.fields {
+        Fields::Named(n) => n
+            .named
+            .iter()
+            .any(|f| f.ident.as_ref().map(|i| i == field).unwrap_or(false)),
+        _ => false,
+    };
+    if !has_field("name") || !has_field("message") {
+        return Err(Error::new(
+            variant.span(),
+            "`dynamic` variant must have `name` and `message` named fields",
+        ));
+    }
+
+    let names = quote! {
+        Self::#ident { name, .. } => {
+            #zbus::names::ErrorName::try_from(::std::convert::AsRef::<str>::as_ref(name))
+                .unwrap_or_else(|_| {
+                    #zbus::names::ErrorName::from_static_str_unchecked(
+                        "org.freedesktop.DBus.Error.Failed",
+                    )
+                })
+        }
+    };
+    let converts = quote! {
+        _ => Self::#ident {
+            name: ::std::string::ToString::to_string(name.as_str()),
+            message: ::std::clone::Clone::clone(desc).unwrap_or_default(),
+        },
+    };
+    let descriptions = quote! {
+        Self::#ident { message, .. } => Some(::std::convert::AsRef::<str>::as_ref(message)),
+    };
+    let reply = quote! {
+        Self::#ident { name: dynamic_name, message, .. } => {
+            // `name` falls back to a generic name if invalid, refuse to reply with it instead.
+            #zbus::names::ErrorName::try_from(::std::convert::AsRef::<str>::as_ref(dynamic_name))?;
+
+            #zbus::message::Builder::error(call, name)?.build(message)
+        }
+    };
+
+    Ok((names, converts, descriptions, reply))
+}
+
 fn gen_reply_for_variant(
     variant: &Variant,
     zbus_error_variant: bool,
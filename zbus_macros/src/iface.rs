@@ -3,28 +3,31 @@ use quote::{format_ident, quote};
 use std::collections::BTreeMap;
 use syn::{
     self, parse_quote, punctuated::Punctuated, spanned::Spanned, AngleBracketedGenericArguments,
-    AttributeArgs, Error, FnArg, GenericArgument, ImplItem, ItemImpl, Lit::Str, Meta,
-    Meta::NameValue, MetaList, MetaNameValue, NestedMeta, PatType, PathArguments, ReturnType,
-    Signature, Token, Type, TypePath,
+    Attribute, AttributeArgs, Error, FnArg, GenericArgument, ImplItem, ItemImpl, ItemTrait,
+    Lit::Str, Meta, Meta::NameValue, MetaNameValue, NestedMeta, PatType, PathArguments, ReturnType,
+    Signature, Token, TraitItem, Type, TypePath,
 };
 use zvariant_utils::{case, def_attrs};
 
 use crate::utils::*;
 
-// FIXME: The list name should once be "zbus" instead of "dbus_interface" (like in serde).
+// The list is also available as "zbus" (like in serde), the preferred name for new code.
 def_attrs! {
-    crate dbus_interface;
+    crate dbus_interface, zbus;
 
     pub TraitAttributes("trait") {
         interface str,
-        name str
+        name str,
+        managed_properties [str],
+        versions [str]
     };
 
     pub MethodAttributes("method") {
         name str,
         signal none,
         property none,
-        out_args [str]
+        out_args [str],
+        versions [str]
     };
 }
 
@@ -38,7 +41,10 @@ mod arg_attrs {
             object_server none,
             connection none,
             header none,
-            signal_context none
+            signal_context none,
+            credentials none,
+            arg_name str,
+            unnamed none
         };
     }
 }
@@ -73,8 +79,10 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
     let mut set_mut_dispatch = quote!();
     let mut get_dispatch = quote!();
     let mut get_all = quote!();
+    let mut get_all_by_name = BTreeMap::new();
     let mut call_dispatch = quote!();
     let mut call_mut_dispatch = quote!();
+    let mut method_names = vec![];
     let mut introspect = quote!();
     let mut generated_signals = quote!();
 
@@ -90,19 +98,27 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
         _ => return Err(Error::new_spanned(&input.self_ty, "Invalid type")),
     };
 
+    let TraitAttributes {
+        name,
+        interface,
+        managed_properties,
+        versions,
+    } = TraitAttributes::parse_nested_metas(&args)?;
+    let span = input.span();
     let iface_name =
         {
-            let TraitAttributes { name, interface } = TraitAttributes::parse_nested_metas(&args)?;
-
             match (name, interface) {
                 (Some(name), None) | (None, Some(name)) => name,
                 (None, None) => format!("org.freedesktop.{ty}"),
                 (Some(_), Some(_)) => return Err(syn::Error::new(
-                    input.span(),
+                    span,
                     "`name` and `interface` attributes should not be specified at the same time",
                 )),
             }
         };
+    if let Some(versions) = versions {
+        return expand_versions(args, input, &iface_name, &versions);
+    }
 
     for method in &mut input.items {
         let method = match method {
@@ -120,9 +136,15 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
         } = &mut method.sig;
 
         let attrs = MethodAttributes::parse(&method.attrs)?;
+        if attrs.versions.is_some() {
+            return Err(Error::new_spanned(
+                &method.sig,
+                "`versions` on a method requires `versions` on the `impl` block",
+            ));
+        }
         method
             .attrs
-            .retain(|attr| !attr.path.is_ident("dbus_interface"));
+            .retain(|attr| !attr.path.is_ident("dbus_interface") && !attr.path.is_ident("zbus"));
 
         let docs = get_doc_attrs(&method.attrs)
             .iter()
@@ -184,7 +206,7 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
         };
 
         let mut intro_args = quote!();
-        intro_args.extend(introspect_input_args(&typed_inputs, is_signal));
+        intro_args.extend(introspect_input_args(&typed_inputs, is_signal)?);
         let is_result_output = introspect_add_output_args(&mut intro_args, output, out_args)?;
 
         let (args_from_msg, args_names) = get_args_from_inputs(&typed_inputs, &zbus)?;
@@ -288,9 +310,14 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                         .unwrap_or_else(|| value_to_owned.clone()),
                     _ => value_to_owned,
                 };
+                // The conversion error is mapped right away so that it's not held across the
+                // `await` below, as it's not necessarily `Send` for generic property types.
                 let do_set = quote!({
                     let value = #value_arg;
-                    match ::std::convert::TryInto::try_into(value) {
+                    let val = ::std::convert::TryInto::try_into(value).map_err(|e| {
+                        #zbus::Error::Variant(::std::convert::Into::into(e))
+                    });
+                    match val {
                         ::std::result::Result::Ok(val) => {
                             match #set_call {
                                 ::std::result::Result::Ok(set_result) => {
@@ -304,9 +331,7 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                             }
                         }
                         ::std::result::Result::Err(e) => {
-                            ::std::result::Result::Err(
-                                ::std::convert::Into::into(#zbus::Error::Variant(::std::convert::Into::into(e))),
-                            )
+                            ::std::result::Result::Err(::std::convert::Into::into(e))
                         }
                     }
                 });
@@ -379,7 +404,8 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                     );)
                 };
 
-                get_all.extend(q);
+                get_all.extend(q.clone());
+                get_all_by_name.insert(member_name.clone(), q);
 
                 let prop_value_handled = if is_fallible_property {
                     quote!(self.#ident()#method_await?)
@@ -423,6 +449,7 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
         } else {
             introspect.extend(doc_comments);
             introspect.extend(introspect_method(&member_name, &intro_args));
+            method_names.push(member_name.clone());
 
             let m = quote! {
                 #member_name => {
@@ -448,6 +475,39 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
         }
     }
 
+    // Only the listed properties are advertised by the object manager, if there's a list.
+    let get_all_managed = match managed_properties {
+        Some(managed) => {
+            let mut get_all_managed = quote!();
+            for name in managed {
+                let q = get_all_by_name.get(&name).ok_or_else(|| {
+                    Error::new(
+                        span,
+                        format!("`{name}` in `managed_properties` is not a readable property"),
+                    )
+                })?;
+                get_all_managed.extend(q.clone());
+            }
+
+            quote! {
+                async fn get_all_managed(
+                    &self,
+                ) -> ::std::collections::HashMap<
+                    ::std::string::String,
+                    #zbus::zvariant::OwnedValue,
+                > {
+                    let mut props: ::std::collections::HashMap<
+                        ::std::string::String,
+                        #zbus::zvariant::OwnedValue,
+                    > = ::std::collections::HashMap::new();
+                    #get_all_managed
+                    props
+                }
+            }
+        }
+        None => quote!(),
+    };
+
     introspect_properties(&mut introspect, properties)?;
 
     let generics = &input.generics;
@@ -494,6 +554,8 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                 props
             }
 
+            #get_all_managed
+
             fn set<'call>(
                 &'call self,
                 property_name: &'call str,
@@ -544,6 +606,12 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
                 }
             }
 
+            fn has_method(&self, name: &str) -> bool {
+                let names: &[&str] = &[#(#method_names),*];
+
+                names.contains(&name)
+            }
+
             fn introspect_to_writer(&self, writer: &mut dyn ::std::fmt::Write, level: usize) {
                 ::std::writeln!(
                     writer,
@@ -564,6 +632,322 @@ pub fn expand(args: AttributeArgs, mut input: ItemImpl) -> syn::Result<TokenStre
     })
 }
 
+/// Expand the trait form: the interface is declared on a trait and served, for any implementor
+/// `T` of the trait, through a generated `<Trait>Interface<T>` wrapper.
+///
+/// The wrapper gets an impl block delegating every method to the trait, which is then expanded
+/// like any other impl block. Signals have no implementation to delegate to so they are only
+/// declared on the wrapper.
+pub fn expand_trait(mut args: AttributeArgs, mut input: ItemTrait) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "generic traits are not supported",
+        ));
+    }
+
+    let trait_ident = &input.ident;
+    let vis = &input.vis;
+    let wrapper = format_ident!("{trait_ident}Interface");
+
+    // The default interface name is based on the name of the trait, not of the wrapper.
+    let TraitAttributes {
+        name, interface, ..
+    } = TraitAttributes::parse_nested_metas(&args)?;
+    if name.is_none() && interface.is_none() {
+        let name = format!("org.freedesktop.{trait_ident}");
+        args.push(NestedMeta::Meta(parse_quote!(name = #name)));
+    }
+
+    let mut delegates = quote!();
+    let mut items = Vec::with_capacity(input.items.len());
+    for item in std::mem::take(&mut input.items) {
+        let mut method = match item {
+            TraitItem::Method(m) => m,
+            item => {
+                items.push(item);
+                continue;
+            }
+        };
+        let attrs = MethodAttributes::parse(&method.attrs)?;
+        let sig = &method.sig;
+        let method_attrs = &method.attrs;
+
+        if attrs.signal {
+            delegates.extend(quote!(#(#method_attrs)* #sig;));
+            continue;
+        }
+
+        let receiver = match sig.inputs.first() {
+            Some(FnArg::Receiver(r)) if r.reference.is_some() => {
+                if r.mutability.is_some() {
+                    quote!(&mut self.0)
+                } else {
+                    quote!(&self.0)
+                }
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    sig,
+                    "methods must take `&self` or `&mut self`",
+                ))
+            }
+        };
+        let args = sig
+            .inputs
+            .iter()
+            .filter_map(typed_arg)
+            .map(|arg| {
+                pat_ident(arg)
+                    .ok_or_else(|| Error::new_spanned(arg, "arguments must be plain identifiers"))
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        let ident = &sig.ident;
+        let method_await = sig.asyncness.map(|_| quote!(.await));
+        delegates.extend(quote!(
+            #(#method_attrs)*
+            #sig {
+                <T as #trait_ident>::#ident(#receiver, #(#args),*)#method_await
+            }
+        ));
+
+        method
+            .attrs
+            .retain(|attr| !attr.path.is_ident("dbus_interface") && !attr.path.is_ident("zbus"));
+        clean_input_args(&mut method.sig.inputs);
+        items.push(TraitItem::Method(method));
+    }
+    input.items = items;
+
+    let wrapper_doc = format!(
+        "Serves the D-Bus interface declared by [`{trait_ident}`], for any implementor of the trait."
+    );
+    let delegates: ItemImpl = parse_quote!(
+        impl<T> #wrapper<T>
+        where
+            T: #trait_ident + ::std::marker::Send + ::std::marker::Sync + 'static,
+        {
+            #delegates
+        }
+    );
+    let interface = expand(args, delegates)?;
+
+    Ok(quote!(
+        #input
+
+        #[doc = #wrapper_doc]
+        #[derive(Clone, Debug, Default)]
+        #vis struct #wrapper<T>(pub T);
+
+        #interface
+    ))
+}
+
+/// Expand an impl block with the `versions` attribute: the interface is served under one name per
+/// version, through a generated `<Type>V<version>` wrapper for each, that implements `Interface`.
+///
+/// The wrappers hold the instance behind an `Arc<RwLock<_>>`, so that all versions can share it.
+/// Each wrapper gets an impl block of async methods, delegating the members of its version to the
+/// impl block while holding the lock, which is then expanded like any other impl block. As for the
+/// trait form, signals have no implementation to delegate to so they are only declared on the
+/// wrappers.
+fn expand_versions(
+    args: AttributeArgs,
+    mut input: ItemImpl,
+    iface_name: &str,
+    versions: &[String],
+) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() || input.trait_.is_some() {
+        return Err(Error::new_spanned(
+            &input.self_ty,
+            "`versions` is only supported on non-generic inherent `impl` blocks",
+        ));
+    }
+    let ty = match input.self_ty.as_ref() {
+        Type::Path(p) if p.qself.is_none() && p.path.segments.len() == 1 => {
+            p.path.segments[0].ident.clone()
+        }
+        _ => {
+            return Err(Error::new_spanned(
+                &input.self_ty,
+                "`versions` is only supported on `impl` blocks of a type in scope, by its name",
+            ))
+        }
+    };
+    // The name of each version comes from `iface_name` so only the other attributes are kept.
+    let args: Vec<_> = args
+        .into_iter()
+        .filter(|arg| match arg {
+            NestedMeta::Meta(meta) => !["name", "interface", "versions"]
+                .iter()
+                .any(|name| meta.path().is_ident(name)),
+            NestedMeta::Lit(_) => true,
+        })
+        .collect();
+
+    let mut members = Vec::with_capacity(input.items.len());
+    let mut items = Vec::with_capacity(input.items.len());
+    for item in std::mem::take(&mut input.items) {
+        let mut method = match item {
+            ImplItem::Method(m) => m,
+            item => {
+                items.push(item);
+                continue;
+            }
+        };
+        let attrs = MethodAttributes::parse(&method.attrs)?;
+        if let Some(version) = attrs
+            .versions
+            .iter()
+            .flatten()
+            .find(|v| !versions.contains(v))
+        {
+            return Err(Error::new_spanned(
+                &method.sig,
+                format!("version `{version}` is not in the `versions` of the `impl` block"),
+            ));
+        }
+        let method_attrs = without_versions(&method.attrs)?;
+        let mut sig = method.sig.clone();
+
+        let delegate = if attrs.signal {
+            quote!(#(#method_attrs)* #sig;)
+        } else {
+            let receiver = match sig.inputs.first() {
+                Some(FnArg::Receiver(r)) if r.reference.is_some() => {
+                    if r.mutability.is_some() {
+                        quote!(&mut *self.0.write().await)
+                    } else {
+                        quote!(&*self.0.read().await)
+                    }
+                }
+                _ => {
+                    return Err(Error::new_spanned(
+                        &sig,
+                        "methods must take `&self` or `&mut self`",
+                    ))
+                }
+            };
+            let args = sig
+                .inputs
+                .iter()
+                .filter_map(typed_arg)
+                .map(|arg| {
+                    pat_ident(arg).ok_or_else(|| {
+                        Error::new_spanned(arg, "arguments must be plain identifiers")
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            let ident = &sig.ident;
+            let method_await = sig.asyncness.map(|_| quote!(.await));
+            // The delegates need to lock the shared instance.
+            sig.asyncness = Some(Default::default());
+
+            // The impl block keeps the implementation, without the D-Bus specifics.
+            method.attrs.retain(|attr| {
+                !attr.path.is_ident("dbus_interface") && !attr.path.is_ident("zbus")
+            });
+            clean_input_args(&mut method.sig.inputs);
+            items.push(ImplItem::Method(method));
+
+            quote!(
+                #(#method_attrs)*
+                #sig {
+                    #ty::#ident(#receiver, #(#args),*)#method_await
+                }
+            )
+        };
+        members.push((attrs.versions, delegate));
+    }
+    input.items = items;
+
+    let zbus = zbus_path();
+    let mut wrappers = quote!();
+    for version in versions {
+        if version.is_empty()
+            || !version
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(Error::new_spanned(
+                &input.self_ty,
+                format!(
+                    "invalid version `{version}`, only ASCII alphanumerics and `_` are allowed"
+                ),
+            ));
+        }
+        let wrapper = format_ident!("{ty}V{version}");
+        let name = format!("{iface_name}{version}");
+        let delegates = members
+            .iter()
+            .filter(|(member_versions, _)| {
+                member_versions
+                    .as_ref()
+                    .map_or(true, |v| v.contains(version))
+            })
+            .map(|(_, delegate)| delegate);
+        let mut args = args.clone();
+        args.push(NestedMeta::Meta(parse_quote!(name = #name)));
+        let interface = expand(args, parse_quote!(impl #wrapper { #(#delegates)* }))?;
+
+        let wrapper_doc = format!("Serves version {version} (`{name}`) of the [`{ty}`] interface.");
+        wrappers.extend(quote!(
+            #[doc = #wrapper_doc]
+            #[derive(Clone)]
+            pub struct #wrapper(pub ::std::sync::Arc<#zbus::export::RwLock<#ty>>);
+
+            impl ::std::convert::From<#ty> for #wrapper {
+                fn from(iface: #ty) -> Self {
+                    Self(::std::sync::Arc::new(#zbus::export::RwLock::new(iface)))
+                }
+            }
+
+            impl ::std::convert::From<::std::sync::Arc<#zbus::export::RwLock<#ty>>> for #wrapper {
+                fn from(iface: ::std::sync::Arc<#zbus::export::RwLock<#ty>>) -> Self {
+                    Self(iface)
+                }
+            }
+
+            #interface
+        ));
+    }
+
+    Ok(quote!(
+        #input
+
+        #wrappers
+    ))
+}
+
+// `attrs` without the `versions` attribute of the `dbus_interface` and `zbus` attributes.
+fn without_versions(attrs: &[Attribute]) -> syn::Result<Vec<Attribute>> {
+    let mut stripped = Vec::with_capacity(attrs.len());
+    for attr in attrs {
+        if !attr.path.is_ident("dbus_interface") && !attr.path.is_ident("zbus") {
+            stripped.push(attr.clone());
+            continue;
+        }
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            _ => {
+                stripped.push(attr.clone());
+                continue;
+            }
+        };
+        let nested: Punctuated<NestedMeta, Token![,]> = list
+            .nested
+            .into_iter()
+            .filter(|meta| !matches!(meta, NestedMeta::Meta(m) if m.path().is_ident("versions")))
+            .collect();
+        if !nested.is_empty() {
+            let path = &attr.path;
+            stripped.push(parse_quote!(#[#path(#nested)]));
+        }
+    }
+
+    Ok(stripped)
+}
+
 fn get_args_from_inputs(
     inputs: &[PatType],
     zbus: &TokenStream,
@@ -575,6 +959,7 @@ fn get_args_from_inputs(
         let mut conn_arg_decl = None;
         let mut header_arg_decl = None;
         let mut signal_context_arg_decl = None;
+        let mut credentials_arg_decl = None;
         let mut args_names = Vec::new();
         let mut tys = Vec::new();
 
@@ -636,6 +1021,26 @@ fn get_args_from_inputs(
                         }
                     };
                 });
+            } else if attrs.credentials {
+                if credentials_arg_decl.is_some() {
+                    return Err(Error::new_spanned(
+                        input,
+                        "There can only be one `credentials` argument",
+                    ));
+                }
+
+                let credentials_arg = &input.pat;
+
+                credentials_arg_decl = Some(quote! {
+                    let #credentials_arg = match c.caller_credentials(m).await {
+                        ::std::result::Result::Ok(creds) => creds,
+                        ::std::result::Result::Err(e) => {
+                            let hdr = m.header()?;
+                            let err = <#zbus::fdo::Error as ::std::convert::From<_>>::from(e);
+                            return c.reply_dbus_error(&hdr, err).await;
+                        }
+                    };
+                });
             } else {
                 args_names.push(pat_ident(input).unwrap());
                 tys.push(&input.ty);
@@ -651,6 +1056,8 @@ fn get_args_from_inputs(
 
             #signal_context_arg_decl
 
+            #credentials_arg_decl
+
             let (#(#args_names),*): (#(#tys),*) =
                 match m.body() {
                     ::std::result::Result::Ok(r) => r,
@@ -699,50 +1106,71 @@ fn introspect_method(name: &str, args: &TokenStream) -> TokenStream {
     )
 }
 
-fn introspect_input_args(
-    inputs: &[PatType],
-    is_signal: bool,
-) -> impl Iterator<Item = TokenStream> + '_ {
-    inputs
-        .iter()
-        .filter_map(move |pat_type @ PatType { ty, attrs, .. }| {
-            let is_special_arg = attrs.iter().any(|attr| {
-                if !attr.path.is_ident("zbus") {
-                    return false;
-                }
-
-                let meta = match attr.parse_meta() {
-                    ::std::result::Result::Ok(meta) => meta,
-                    ::std::result::Result::Err(_) => return false,
-                };
+fn introspect_input_args(inputs: &[PatType], is_signal: bool) -> syn::Result<TokenStream> {
+    let mut args = quote!();
+
+    for pat_type @ PatType { ty, attrs, .. } in inputs {
+        let ArgAttributes {
+            object_server,
+            connection,
+            header,
+            signal_context,
+            credentials,
+            arg_name,
+            unnamed,
+        } = ArgAttributes::parse(attrs)?;
+        if object_server || connection || header || signal_context || credentials {
+            continue;
+        }
 
-                let nested = match meta {
-                    Meta::List(MetaList { nested, .. }) => nested,
-                    _ => return false,
-                };
+        let name =
+            match (arg_name, unnamed) {
+                (Some(_), true) => return Err(Error::new_spanned(
+                    pat_type,
+                    "`arg_name` and `unnamed` attributes should not be specified at the same time",
+                )),
+                (Some(name), false) => {
+                    if !is_valid_arg_name(&name) {
+                        return Err(Error::new_spanned(
+                            pat_type,
+                            format!("`{name}` is not a valid argument name"),
+                        ));
+                    }
 
-                let res = nested.iter().any(|nested_meta| {
-                    matches!(
-                        nested_meta,
-                        NestedMeta::Meta(Meta::Path(path))
-                        if path.is_ident("object_server") || path.is_ident("connection") || path.is_ident("header") || path.is_ident("signal_context")
-                    )
-                });
+                    format!("name=\"{name}\" ")
+                }
+                (None, true) => String::new(),
+                (None, false) => {
+                    let ident = pat_ident(pat_type).unwrap();
 
-                res
-            });
-            if is_special_arg {
-                return None;
+                    format!("name=\"{}\" ", quote!(#ident))
+                }
+            };
+        let dir = if is_signal { "" } else { " direction=\"in\"" };
+        args.extend(quote!(
+            // Units have an empty signature and are not sent over the bus.
+            let signature = <#ty>::signature();
+            if !signature.is_empty() {
+                ::std::writeln!(writer, "{:indent$}<arg {}type=\"{}\"{}/>", "",
+                         #name, signature, #dir, indent = level).unwrap();
             }
+        ));
+    }
 
-            let ident = pat_ident(pat_type).unwrap();
-            let arg_name = quote!(#ident).to_string();
-            let dir = if is_signal { "" } else { " direction=\"in\"" };
-            Some(quote!(
-                ::std::writeln!(writer, "{:indent$}<arg name=\"{}\" type=\"{}\"{}/>", "",
-                         #arg_name, <#ty>::signature(), #dir, indent = level).unwrap();
-            ))
-        })
+    Ok(args)
+}
+
+/// Argument names follow the same rules as member names: only ASCII alphanumeric characters and
+/// `_`, not starting with a digit.
+fn is_valid_arg_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
 }
 
 fn introspect_output_arg(ty: &Type, arg_name: Option<&String>) -> TokenStream {
@@ -752,8 +1180,11 @@ fn introspect_output_arg(ty: &Type, arg_name: Option<&String>) -> TokenStream {
     };
 
     quote!(
-        ::std::writeln!(writer, "{:indent$}<arg {}type=\"{}\" direction=\"out\"/>", "",
-                 #arg_name, <#ty>::signature(), indent = level).unwrap();
+        let signature = <#ty>::signature();
+        if !signature.is_empty() {
+            ::std::writeln!(writer, "{:indent$}<arg {}type=\"{}\" direction=\"out\"/>", "",
+                     #arg_name, signature, indent = level).unwrap();
+        }
     )
 }
 
@@ -799,8 +1230,10 @@ fn introspect_add_output_args(
         if let Type::Tuple(t) = ty {
             if let Some(arg_names) = arg_names {
                 if t.elems.len() != arg_names.len() {
-                    // Turn into error
-                    panic!("Number of out arg names different from out args specified")
+                    return Err(Error::new_spanned(
+                        ty,
+                        "Number of out arg names different from out args specified",
+                    ));
                 }
             }
             for i in 0..t.elems.len() {
@@ -808,7 +1241,17 @@ fn introspect_add_output_args(
                 args.extend(introspect_output_arg(&t.elems[i], name));
             }
         } else {
-            args.extend(introspect_output_arg(ty, None));
+            let name = match arg_names {
+                Some([name]) => Some(name),
+                Some(_) => {
+                    return Err(Error::new_spanned(
+                        ty,
+                        "Number of out arg names different from out args specified",
+                    ))
+                }
+                None => None,
+            };
+            args.extend(introspect_output_arg(ty, name));
         }
     }
 
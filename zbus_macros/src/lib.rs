@@ -11,12 +11,14 @@
 )))]
 
 use proc_macro::TokenStream;
-use syn::{parse_macro_input, AttributeArgs, DeriveInput, ItemImpl, ItemTrait};
+use syn::{parse_macro_input, AttributeArgs, DeriveInput, Item, ItemTrait};
 
 mod error;
 mod iface;
 mod proxy;
 mod utils;
+#[cfg(feature = "xml")]
+mod xml;
 
 /// Attribute macro for defining D-Bus proxies (using [`zbus::Proxy`] and
 /// [`zbus::blocking::Proxy`]).
@@ -24,9 +26,14 @@ mod utils;
 /// The macro must be applied on a `trait T`. Two matching `impl T` will provide an asynchronous
 /// Proxy implementation, named `TraitNameProxy` and a blocking one, named `TraitNameProxyBlocking`.
 /// The proxy instances can be created with the associated `new()` or `builder()` methods. The
-/// former doesn't take any argument and uses the default service name and path. The later allows
+/// former only takes the connection and uses the default service name and path. The later allows
 /// you to specify non-default proxy arguments.
 ///
+/// When the service has a default name, `new_for_path()` is also provided to create a proxy for
+/// another object of the same service. Without a default service or path, `new()` takes the
+/// missing one(s) as arguments after the connection (destination first), so calling it with only
+/// the connection fails to compile and `builder()` is the way to go for anything more involved.
+///
 /// The following attributes are supported:
 ///
 /// * `interface` - the name of the D-Bus interface this proxy is for.
@@ -51,8 +58,31 @@ mod utils;
 ///   default to `false`. Please make sure to explicitly set either this attribute or the default
 ///   values, according to your needs.
 ///
+/// * `borrowed_replies` - let methods return data borrowed from the reply message, instead of
+///   copying it. Read the [Borrowed replies](#borrowed-replies) section below for details.
+///
+/// * `cached_properties` - the only properties to cache, by D-Bus name (e.g
+///   `cached_properties("Name", "Version")`). The other properties are always fetched from the
+///   peer, as are the ones with `emits_changed_signal = "false"`, even if listed. Like all the
+///   caching, this is overridden by `CacheProperties::No` on the proxy builder.
+///
+/// * `verify_on_build` - introspect the object when building the proxy, and fail with an
+///   `Error::Failure` listing the missing or mismatching members (or interface) if it doesn't
+///   provide everything the proxy expects, e.g because of a version mismatch or a wrong path. This
+///   costs an extra round trip and requires the `xml` feature of zbus.
+///
+/// * `derive` - traits to derive on the generated signal arguments structs (e.g
+///   `derive(Clone, PartialEq)`), whose fields are the signal arguments so these must implement the
+///   traits too. The signal arguments structs always implement `Debug`. The derives don't apply to
+///   the other generated structs (the signals and borrowed replies): these are thin wrappers around
+///   the message, which only implements `Debug` and `Clone`, so that's all they implement too.
+///
 /// Each trait method will be expanded to call to the associated D-Bus remote interface.
 ///
+/// Array arguments of method calls, declared as `&[T]` or `Vec<T>`, are accepted as
+/// `impl AsRef<[T]>` by the generated methods so you can pass a slice, an array or a `Vec`,
+/// without having to clone it first. This is not (yet) done if `T` has elided lifetimes.
+///
 /// Trait methods accept `dbus_proxy` attributes:
 ///
 /// * `name` - override the D-Bus name (pascal case form by default)
@@ -73,6 +103,14 @@ mod utils;
 ///     * `"false"` - change signal is not (guaranteed to be) emitted if the property changes. This
 ///       disables property value caching, and does not generate a listener method for the change
 ///       signal.
+///   * `interface` - the interface the property belongs to, if not the proxy's interface. This is
+///     useful for objects that spread their properties over multiple interfaces. Such properties
+///     are never cached, so no `cached_` or listener methods are generated for them.
+///   * `raw` - also generate a `<getter_name>_raw` method for a property getter, returning the
+///     value as a [`zvariant::OwnedValue`] instead of converting it to the declared type. Since
+///     the conversion fails if the service gives the property another type, e.g in a newer
+///     version, this lets clients fall back to handling the value themselves. The raw getter
+///     always calls the service, it never returns the cached value.
 ///
 /// * `signal` - declare a signal just like a D-Bus method. Read the [Signals](#signals) section
 ///   below for details.
@@ -83,7 +121,32 @@ mod utils;
 ///   the destination service if it is not already running.
 ///
 /// * `allow_interactive_auth` - declare a method call that is allowed to trigger an interactive
-///   prompt for authorization or confirmation from the receiver.
+///   prompt for authorization or confirmation from the receiver. Use the `allow_interactive_auth`
+///   method of the proxy builder to allow it for all the method calls and property accesses.
+///
+/// * `stream` - for methods returning an array (`Result<Vec<T>>`), also generate a
+///   `<method_name>_stream` method, yielding the elements of the array one by one through a
+///   [`zbus::proxy::ArrayStream`] ([`zbus::blocking::proxy::ArrayIterator`] for the blocking
+///   proxy). The elements are deserialized lazily, which avoids allocating the whole array when
+///   the elements only need to be processed one after the other.
+///
+/// * `raw_reply` - also generate a `<method_name>_raw` method, returning the reply
+///   [`zbus::message::Message`] instead of its deserialized body. This gives access to the headers
+///   of the reply (e.g its sender) and to its file descriptors, whose ownership the typed return
+///   value doesn't always capture well. The caller owns any file descriptors in the reply: they're
+///   closed when the message is dropped, unless taken out of it with [`Message::take_fds`].
+///
+/// * `with_headers` - also generate a `<method_name>_with_headers` method, returning the
+///   deserialized reply body along with a [`zbus::proxy::ReplyMeta`], which holds the sender and
+///   the serial numbers of the reply. Unlike with `raw_reply`, the return value can't borrow from
+///   the reply.
+///
+/// * `iter_args` - accept the array arguments (`&[T]` and `Vec<T>`) as
+///   `impl IntoIterator<Item = T> + Clone` instead of `impl AsRef<[T]>`, so the elements can be
+///   passed without collecting them first (e.g `names.iter().copied()` or `(0..n).map(f)`). The
+///   length of the array isn't needed upfront but the iterator is cloned since the body is
+///   serialized twice, see [`zbus::proxy::IterArg`] for details. Iterators that are expensive to
+///   clone are better collected into a `Vec` and passed as a slice, without this attribute.
 ///
 /// * `object` - methods that returns an [`ObjectPath`] can be annotated with the `object` attribute
 ///   to specify the proxy object to be constructed from the returned [`ObjectPath`].
@@ -105,6 +168,116 @@ mod utils;
 /// access to the signal arguments. It also implements `Deref<Target = Message>` to allow easy
 /// access to the underlying [`zbus::message::Message`].
 ///
+/// The arguments are returned by the `args` method of the wrapper, as a `<SignalName>Args` type
+/// with an accessor for each argument. `<SignalName>Args` can also be converted from and into a
+/// tuple of the arguments (a 1-tuple for single-argument signals), e.g to destructure them with
+/// `let (id, name): (u32, &str) = signal.args()?.into()`.
+///
+/// An argument of type `v`, which can hold a value of a different type in each emission, is
+/// declared as a [`zvariant::OwnedValue`], or a `zvariant::Value<'_>` borrowed from the message,
+/// and converted to the expected type (e.g with `TryFrom`) once the context tells which it is.
+///
+/// The `sender` method of the wrapper returns the unique name of the connection that sent the
+/// signal, and `<SignalName>::from_message_with_sender` only accepts a message sent by the given
+/// connection, e.g to make sure a signal comes from the service it claims to.
+///
+/// # Structure replies
+///
+/// The declared return type of a method is taken as is to deserialize the reply, through its
+/// [`zvariant::Type`] and serde `Deserialize` implementations. So instead of a tuple, a method with
+/// several output arguments (or a single structure one) can return a structure with named fields:
+///
+/// ```no_run
+/// use serde::Deserialize;
+/// use zbus::{dbus_proxy, zvariant::Type, Result};
+///
+/// // Both the `us` and `(us)` output arguments deserialize into this.
+/// #[derive(Debug, Deserialize, Type)]
+/// pub struct Summary {
+///     count: u32,
+///     label: String,
+/// }
+///
+/// #[dbus_proxy(
+///     interface = "org.example.Stats",
+///     default_service = "org.example.Stats",
+///     default_path = "/org/example/Stats",
+/// )]
+/// trait Stats {
+///     fn summary(&self) -> Result<Summary>;
+/// }
+///
+/// # zbus::block_on(async {
+/// let connection = zbus::Connection::session().await?;
+/// let summary = StatsProxy::new(&connection).await?.summary().await?;
+/// println!("{}: {}", summary.label, summary.count);
+/// # Ok::<(), zbus::Error>(())
+/// # }).unwrap();
+/// ```
+///
+/// [`zvariant::Type`]: https://docs.rs/zvariant/latest/zvariant/trait.Type.html
+///
+/// # Borrowed replies
+///
+/// By default, the values returned by the methods are owned, which means that strings and arrays
+/// are copied out of the reply message. When the `borrowed_replies` attribute is given, the
+/// methods declared with a return type that borrows (e.g `Result<Cow<'_, str>>`, `Result<&str>` or
+/// `Result<Vec<zvariant::Str<'_>>>`) instead return a wrapper of the reply message, named
+/// `<MethodName>Reply`. Its `body` method deserializes the returned value, borrowing the data from
+/// the message.
+///
+/// The lifetime contract is simple: the borrowed value can not outlive the `<MethodName>Reply`
+/// it's deserialized from, which keeps the message (and hence its buffer) alive. All the
+/// lifetimes in the declared return type are tied to that of the `<MethodName>Reply`. Each call
+/// to `body` deserializes the value anew so you'll want to keep the result around rather than call
+/// it repeatedly.
+///
+/// Note that since deserializing a [`Cow`] with serde always produces an owned value, only a
+/// top-level `Cow<'_, str>` or `Cow<'_, [u8]>` is deserialized as borrowed. Use `&str`, `&[u8]` or
+/// [`zvariant::Str`] for nested values.
+///
+/// `borrowed_replies` can't be combined with the `object`, `no_reply`, `no_autostart` and
+/// `allow_interactive_auth` method attributes.
+///
+/// ```no_run
+/// # use std::borrow::Cow;
+/// use zbus::{blocking::Connection, dbus_proxy, Result};
+///
+/// #[dbus_proxy(
+///     interface = "org.freedesktop.DBus.Introspectable",
+///     default_service = "org.freedesktop.DBus",
+///     default_path = "/org/freedesktop/DBus",
+///     borrowed_replies,
+/// )]
+/// trait Introspectable {
+///     fn introspect(&self) -> Result<Cow<'_, str>>;
+/// }
+///
+/// let connection = Connection::session()?;
+/// let proxy = IntrospectableProxyBlocking::new(&connection)?;
+/// let reply: IntrospectReply = proxy.introspect()?;
+/// let xml: Cow<'_, str> = reply.body()?;
+/// assert!(matches!(xml, Cow::Borrowed(_)));
+/// # Ok::<(), zbus::Error>(())
+/// ```
+///
+/// [`Cow`]: std::borrow::Cow
+/// [`zvariant::Str`]: https://docs.rs/zvariant/latest/zvariant/struct.Str.html
+///
+/// # File descriptor replies
+///
+/// Methods with `h` (unix-fd) output arguments must declare them as [`zvariant::OwnedFd`], anywhere
+/// in the return type (e.g `Result<(String, OwnedFd)>` or `Result<Vec<OwnedFd>>`). Each of them
+/// takes ownership of (a duplicate of) the file descriptor at its position in the reply, while the
+/// reply's own ones are closed once it's deserialized. A [`zvariant::Fd`] would only borrow the
+/// file descriptor from the reply, which is dropped before the method returns, so it's rejected
+/// at compile time when spelled by its path (`zvariant::Fd` or `zbus::zvariant::Fd`): a bare `Fd`
+/// isn't, since the macro can't tell it apart from other types of the same name. Use `raw_reply`
+/// to get at the file descriptors of the reply directly instead.
+///
+/// [`zvariant::OwnedFd`]: https://docs.rs/zvariant/latest/zvariant/struct.OwnedFd.html
+/// [`zvariant::Fd`]: https://docs.rs/zvariant/latest/zvariant/struct.Fd.html
+///
 /// # Example
 ///
 /// ```no_run
@@ -188,8 +361,14 @@ mod utils;
 /// [`zbus::message::Message`]: https://docs.rs/zbus/latest/zbus/message/struct.Message.html
 /// [`zbus::blocking::Proxy`]: https://docs.rs/zbus/latest/zbus/blocking/proxy/struct.Proxy.html
 /// [`zbus::SignalStream`]: https://docs.rs/zbus/latest/zbus/proxy/struct.SignalStream.html
+/// [`Message::take_fds`]: https://docs.rs/zbus/latest/zbus/message/struct.Message.html#method.take_fds
+/// [`zbus::proxy::IterArg`]: https://docs.rs/zbus/latest/zbus/proxy/struct.IterArg.html
+/// [`zbus::proxy::ArrayStream`]: https://docs.rs/zbus/latest/zbus/proxy/struct.ArrayStream.html
+/// [`zbus::blocking::proxy::ArrayIterator`]: https://docs.rs/zbus/latest/zbus/blocking/proxy/struct.ArrayIterator.html
 /// [`zbus::blocking::SignalIterator`]: https://docs.rs/zbus/latest/zbus/blocking/proxy/struct.SignalIterator.html
 /// [`ObjectPath`]: https://docs.rs/zvariant/latest/zvariant/struct.ObjectPath.html
+/// [`zbus::proxy::ReplyMeta`]: https://docs.rs/zbus/latest/zbus/proxy/struct.ReplyMeta.html
+/// [`zvariant::OwnedValue`]: https://docs.rs/zvariant/latest/zvariant/struct.OwnedValue.html
 /// [dbus_emits_changed_signal]: https://dbus.freedesktop.org/doc/dbus-specification.html#introspection-format
 #[proc_macro_attribute]
 pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -200,12 +379,136 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Alias of [`dbus_proxy`], the preferred name for new code.
+///
+/// Inside the trait, the `zbus` attribute can equally be used in place of `dbus_proxy`, e.g
+/// `#[zbus(property)]` on a method. Both names can be mixed in the same trait.
+#[proc_macro_attribute]
+pub fn proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
+    dbus_proxy(attr, item)
+}
+
+/// Generate a test checking that a proxy and an interface agree.
+///
+/// When both the client and server sides of an interface are maintained in the same crate, it's
+/// easy for the `dbus_proxy` trait and the `dbus_interface` implementation to drift apart. Given a
+/// proxy type and a value of the interface type, this macro generates a `#[test]` function, named
+/// after the proxy (e.g `greeter_proxy_matches_interface` for `GreeterProxy`), that introspects
+/// the interface and compares it with what the proxy expects:
+///
+/// * every method, signal and property of the proxy must be provided by the interface, with the
+///   same argument signatures (the interface may provide more members).
+/// * properties the proxy has a getter (setter) for must be readable (writable).
+///
+/// Proxy members with arguments of types that can only be named in their declaration, like
+/// generic arguments, can't be checked and are not part of the comparison.
+///
+/// The generated test uses the introspection XML parser of zbus, so its `xml` feature must be
+/// enabled (typically, for the dev-dependency only).
+///
+/// # Example
+///
+/// ```
+/// # // The generated test function is not compiled in doc tests.
+/// # #![allow(dead_code)]
+/// use zbus::{assert_proxy_matches_interface, dbus_interface, dbus_proxy, Result};
+///
+/// struct Greeter {
+///     name: String,
+/// }
+///
+/// #[dbus_interface(name = "org.zbus.Greeter")]
+/// impl Greeter {
+///     fn say_hello(&self, name: &str) -> String {
+///         format!("Hello {}!", name)
+///     }
+///
+///     #[dbus_interface(property)]
+///     fn name(&self) -> &str {
+///         &self.name
+///     }
+/// }
+///
+/// #[dbus_proxy(interface = "org.zbus.Greeter", assume_defaults = true)]
+/// trait Greeter {
+///     fn say_hello(&self, name: &str) -> Result<String>;
+///
+///     #[dbus_proxy(property)]
+///     fn name(&self) -> Result<String>;
+/// }
+///
+/// assert_proxy_matches_interface!(GreeterProxy, Greeter { name: "zbus".into() });
+/// ```
+#[proc_macro]
+pub fn assert_proxy_matches_interface(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as proxy::AssertMatchesArgs);
+    proxy::expand_assert_matches_interface(args)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Generate proxies from an introspection XML file, at compile time.
+///
+/// This expands to a [`dbus_proxy`] trait for each interface described in the file (including
+/// the interfaces of its child nodes), as `zbus-xmlgen` would generate them, except for the
+/// standard `org.freedesktop.DBus.*` interfaces, which zbus provides proxies for already. This
+/// keeps the proxies in sync with a checked-in XML file without committing generated code: the
+/// crate is rebuilt whenever the file changes.
+///
+/// The path of the file is relative to the root of the crate (the directory of its `Cargo.toml`).
+///
+/// Each trait is named after the last component of its interface name and declared with
+/// `assume_defaults = true`, so the service and path may need to be set when building the proxy.
+/// Unlike `zbus-xmlgen`, which generates a starting point to adapt, the generated traits can't be
+/// tweaked (e.g to use custom types), which makes this macro best suited for simple interfaces.
+///
+/// This macro is only available with the `xml` feature of zbus.
+///
+/// # Example
+///
+/// With a `greeter.xml` file at the root of the crate, describing an `org.zbus.Greeter`
+/// interface with a `SayHello` method:
+///
+/// ```ignore
+/// zbus::proxy_from_xml!("greeter.xml");
+///
+/// # async fn test(connection: &zbus::Connection) -> zbus::Result<()> {
+/// let proxy = GreeterProxy::builder(connection)
+///     .destination("org.zbus.MyGreeter")?
+///     .path("/org/zbus/MyGreeter")?
+///     .build()
+///     .await?;
+/// let reply = proxy.say_hello("Maria").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "xml")]
+#[proc_macro]
+pub fn proxy_from_xml(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as syn::LitStr);
+    xml::expand(path)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 /// Attribute macro for implementing a D-Bus interface.
 ///
 /// The macro must be applied on an `impl T`. All methods will be exported, either as methods,
 /// properties or signal depending on the item attributes. It will implement the [`Interface`] trait
 /// `for T` on your behalf, to handle the message dispatching and introspection support.
 ///
+/// The `impl` block accepts the `dbus_interface` attributes:
+///
+/// * `name` (or `interface`) - the D-Bus name of the interface (`org.freedesktop.T` by default)
+///
+/// * `managed_properties` - the only properties (by D-Bus name) to include when the object is
+///   advertised by an `org.freedesktop.DBus.ObjectManager`, e.g `managed_properties("Name")`. By
+///   default, all the readable properties are included, which can be undesirable if some are
+///   expensive to compute.
+///
+/// * `versions` - serve the interface under several versioned names, e.g `versions("1", "2")`.
+///   Read the [Versioned interfaces](#versioned-interfaces) section below for details.
+///
 /// The methods accepts the `dbus_interface` attributes:
 ///
 /// * `name` - override the D-Bus name (pascal case form of the method by default)
@@ -225,7 +528,11 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///   important. You can use `out_args` to specify their names.
 ///
 ///   In such case, your method must return a tuple containing
-///   your out arguments, in the same order as passed to `out_args`.
+///   your out arguments, in the same order as passed to `out_args`. A single name can also be given
+///   to name the out argument of a method returning a single value.
+///
+/// * `versions` - the versions of the interface the member is part of, e.g `versions("2")`. By
+///   default, it's part of all of them. Only valid with the `versions` attribute on the `impl`.
 ///
 /// The `struct_return` attribute (from zbus 1.x) is no longer supported. If you want to return a
 /// single structure from a method, declare it to return a tuple containing either a named structure
@@ -240,7 +547,8 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// method is also generated that much like `_changed` method, emits a "PropertyChanged" signal
 /// but does not send over the new value of the property along with it. It is usually best to avoid
 /// using this since it will force all interested peers to fetch the new value and hence result in
-/// excess traffic on the bus.
+/// excess traffic on the bus. When several properties change at once, prefer reporting them all
+/// in a single signal, through `SignalContext::properties_changed`.
 ///
 /// The method arguments support the following `zbus` attributes:
 ///
@@ -252,6 +560,21 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///   D-Bus method call being handled.
 /// * `signal_context` - This marks the method argument to receive a [`SignalContext`] instance,
 ///   which is needed for emitting signals the easy way.
+/// * `credentials` - This marks the method argument to receive the
+///   [`fdo::ConnectionCredentials`] of the caller (e.g its Unix user ID), as returned by
+///   [`Connection::caller_credentials`]. This is typically needed to authorize the call.
+/// * `arg_name` - The name of the argument in the introspection data. By default, the name of the
+///   Rust argument is used. This is mostly useful for signal arguments, since their names are part
+///   of the signal's API, as seen by the clients. The name must only contain ASCII alphanumeric
+///   characters or `_` and must not start with a digit.
+/// * `unnamed` - Omit the name of this argument from the introspection data.
+///
+/// The `impl` can be generic. The introspection data then describes the argument and property
+/// types of each concrete instance of the interface, e.g a `T` property is of type `u` for an
+/// `Example<u32>` and of type `s` for an `Example<String>`. Besides the [`zvariant::Type`] and
+/// `serde` bounds needed for the method arguments, a property of a generic type `T` requires
+/// `zvariant::Value<'static>: From<T>` for reading it, and `T: TryFrom<zvariant::Value<'static>>`
+/// (with an error convertible into [`zvariant::Error`]) for writing it.
 ///
 /// # Example
 ///
@@ -314,21 +637,152 @@ pub fn dbus_proxy(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// # Ok::<_, Box<dyn Error + Send + Sync>>(())
 /// ```
 ///
+/// # Declaring the interface on a trait
+///
+/// The interface can also be declared on a `trait`, so that any implementation of it can be
+/// served, e.g a mock one in tests. The attributes are the same as for the `impl` form. The macro
+/// then generates a `<TraitName>Interface<T>` wrapper type (with a public field holding the
+/// implementation) that implements [`Interface`] for any `T` implementing the trait, and is what
+/// gets registered on the [`ObjectServer`]. The default interface name is based on the name of the
+/// trait.
+///
+/// Signals have no implementation to provide so they're not part of the trait but of the wrapper:
+/// e.g a `changed` signal is emitted through `<TraitName>Interface::<T>::changed`. Since async
+/// methods are not supported in traits, async interface methods require the trait (and its
+/// implementations) to also be annotated with [`async_trait`], after the `dbus_interface`
+/// attribute.
+///
+/// ```
+/// # use std::error::Error;
+/// use zbus_macros::dbus_interface;
+/// use zbus::object_server::SignalContext;
+///
+/// #[dbus_interface(name = "org.myservice.Greeter")]
+/// trait Greeter {
+///     fn say_hello(&self, name: &str) -> String;
+///
+///     #[dbus_interface(property)]
+///     fn greeting(&self) -> String;
+///
+///     #[dbus_interface(signal)]
+///     async fn greeted(ctxt: &SignalContext<'_>, name: &str) -> zbus::Result<()>;
+/// }
+///
+/// struct English;
+///
+/// impl Greeter for English {
+///     fn say_hello(&self, name: &str) -> String {
+///         format!("Hello {}!", name)
+///     }
+///
+///     fn greeting(&self) -> String {
+///         String::from("Hello")
+///     }
+/// }
+///
+/// let _connection = zbus::blocking::connection::Builder::session()?
+///     .serve_at("/org/myservice/Greeter", GreeterInterface(English))?;
+/// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+/// ```
+///
+/// # Versioned interfaces
+///
+/// Services evolving their API often expose several versions of an interface side by side, e.g
+/// `org.myservice.Counter1` and `org.myservice.Counter2`, with most members in common. Instead of
+/// duplicating the code, give the versions through the `versions` attribute of the `impl` block:
+/// the names of the interfaces are the `name` followed by each version.
+///
+/// Since a type can only implement [`Interface`] once, the macro then generates a
+/// `<TypeName>V<version>` wrapper type per version, implementing [`Interface`] for that version,
+/// and that is what gets registered on the [`ObjectServer`]. The wrappers are public and the type
+/// must be in scope by its name. Their public field holds the implementation in an
+/// `Arc<RwLock<_>>`, so that all the versions can serve the same instance: create the first
+/// wrapper from the instance (through `From`) and the others from its field. The members are then
+/// called with the lock held, which also means that they can't return references into the
+/// instance.
+///
+/// All the members are part of all the versions, unless restricted to some with their own
+/// `versions` attribute: to add a member in a new version, only list the new version(s); to remove
+/// a member from a new version, only list the older one(s). As for traits, signals are declared on
+/// the wrappers (e.g `CounterV2::overflowed`), since each version emits them with its own
+/// interface name, and so are the `<property>_changed` methods.
+///
+/// ```
+/// # use std::error::Error;
+/// use zbus_macros::dbus_interface;
+/// use zbus::object_server::SignalContext;
+///
+/// struct Counter {
+///     count: u32,
+/// }
+///
+/// #[dbus_interface(name = "org.myservice.Counter", versions("1", "2"))]
+/// impl Counter {
+///     fn increment(&mut self) -> u32 {
+///         self.count += 1;
+///
+///         self.count
+///     }
+///
+///     // Added in version 2.
+///     #[dbus_interface(versions("2"))]
+///     fn reset(&mut self) {
+///         self.count = 0;
+///     }
+///
+///     // Removed in version 2.
+///     #[dbus_interface(property, versions("1"))]
+///     fn total(&self) -> u32 {
+///         self.count
+///     }
+///
+///     #[dbus_interface(signal, versions("2"))]
+///     async fn overflowed(ctxt: &SignalContext<'_>) -> zbus::Result<()>;
+/// }
+///
+/// // Both versions serve the same counter.
+/// let v1 = CounterV1::from(Counter { count: 0 });
+/// let v2 = CounterV2::from(v1.0.clone());
+/// let _connection = zbus::blocking::connection::Builder::session()?
+///     .serve_at("/org/myservice/Counter", v1)?
+///     .serve_at("/org/myservice/Counter", v2)?;
+/// # Ok::<_, Box<dyn Error + Send + Sync>>(())
+/// ```
+///
 /// See also [`ObjectServer`] documentation to learn how to export an interface over a `Connection`.
 ///
 /// [`ObjectServer`]: https://docs.rs/zbus/latest/zbus/object_server/struct.ObjectServer.html
 /// [`ObjectServer::with`]: https://docs.rs/zbus/latest/zbus/object_server/struct.ObjectServer.html#method.with
 /// [`Connection`]: https://docs.rs/zbus/latest/zbus/connection/struct.Connection.html
+/// [`Connection::caller_credentials`]: https://docs.rs/zbus/latest/zbus/connection/struct.Connection.html#method.caller_credentials
+/// [`fdo::ConnectionCredentials`]: https://docs.rs/zbus/latest/zbus/fdo/struct.ConnectionCredentials.html
 /// [`Connection::emit_signal()`]: https://docs.rs/zbus/latest/zbus/connection/struct.Connection.html#method.emit_signal
 /// [`SignalContext`]: https://docs.rs/zbus/latest/zbus/object_server/struct.SignalContext.html
 /// [`Interface`]: https://docs.rs/zbus/latest/zbus/object_server/trait.Interface.html
+/// [`zvariant::Type`]: https://docs.rs/zvariant/latest/zvariant/trait.Type.html
+/// [`zvariant::Error`]: https://docs.rs/zvariant/latest/zvariant/enum.Error.html
+/// [`async_trait`]: https://docs.rs/async-trait
 #[proc_macro_attribute]
 pub fn dbus_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
     let args = parse_macro_input!(attr as AttributeArgs);
-    let input = syn::parse_macro_input!(item as ItemImpl);
-    iface::expand(args, input)
-        .unwrap_or_else(|err| err.to_compile_error())
-        .into()
+    let res = match syn::parse_macro_input!(item as Item) {
+        Item::Impl(input) => iface::expand(args, input),
+        Item::Trait(input) => iface::expand_trait(args, input),
+        item => Err(syn::Error::new_spanned(
+            item,
+            "`dbus_interface` can only be applied to an `impl` block or a `trait`",
+        )),
+    };
+    res.unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+/// Alias of [`dbus_interface`], the preferred name for new code.
+///
+/// Inside the `impl` block, the `zbus` attribute can equally be used in place of
+/// `dbus_interface`, e.g `#[zbus(signal)]` on a method. Both names can be mixed in the same block.
+#[proc_macro_attribute]
+pub fn interface(attr: TokenStream, item: TokenStream) -> TokenStream {
+    dbus_interface(attr, item)
 }
 
 /// Derive macro for implementing [`zbus::DBusError`] trait.
@@ -341,9 +795,18 @@ pub fn dbus_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// [`zbus::Error`]. This implementation makes it possible for you to declare proxy methods to
 /// directly return this type, rather than [`zbus::Error`].
 ///
+/// The `zbus` attribute can be used in place of `dbus_error`, e.g `#[zbus(zbus_error)]`.
+///
 /// Each variant (except for the special `dbus_error` one) can optionally have a (named or unnamed)
 /// `String` field (which is used as the human-readable error description).
 ///
+/// A variant marked with the `dynamic` attribute takes its error name from its `name` field at
+/// runtime instead, and its description from its `message` field, both of type `String`. This is
+/// for forwarding arbitrary errors, e.g from an upstream service. Such a variant also catches all
+/// the errors with unknown names in the `From<zbus::Error>` conversion, instead of the
+/// `zbus_error` variant. Since the name isn't checked on construction, replying with an invalid
+/// error name fails and `name()` returns `org.freedesktop.DBus.Error.Failed` for it.
+///
 /// # Example
 ///
 /// ```
@@ -356,6 +819,8 @@ pub fn dbus_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     ZBus(zbus::Error),
 ///     FileNotFound(String),
 ///     OutOfMemory,
+///     #[dbus_error(dynamic)]
+///     Upstream { name: String, message: String },
 /// }
 /// ```
 ///
@@ -363,7 +828,7 @@ pub fn dbus_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// [`zbus::Error`]: https://docs.rs/zbus/latest/zbus/enum.Error.html
 /// [`zvariant::Type`]: https://docs.rs/zvariant/latest/zvariant/trait.Type.html
 /// [`serde::Serialize`]: https://docs.rs/serde/1.0.132/serde/trait.Serialize.html
-#[proc_macro_derive(DBusError, attributes(dbus_error))]
+#[proc_macro_derive(DBusError, attributes(dbus_error, zbus))]
 pub fn derive_dbus_error(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     error::expand_derive(input)
@@ -2,15 +2,16 @@ use crate::utils::{pat_ident, typed_arg, zbus_path};
 use proc_macro2::{Literal, Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
 use regex::Regex;
+use std::collections::BTreeMap;
 use syn::{
-    self, fold::Fold, parse_quote, spanned::Spanned, AttributeArgs, Error, FnArg, Ident, ItemTrait,
-    ReturnType, TraitItemMethod,
+    self, fold::Fold, parse_quote, spanned::Spanned, AttributeArgs, Error, FnArg, GenericArgument,
+    Ident, ItemTrait, Meta, NestedMeta, Path, PathArguments, ReturnType, TraitItemMethod, Type,
 };
 use zvariant_utils::{case, def_attrs};
 
-// FIXME: The list name should once be "zbus" instead of "dbus_proxy" (like in serde).
+// The list is also available as "zbus" (like in serde), the preferred name for new code.
 def_attrs! {
-    crate dbus_proxy;
+    crate dbus_proxy, zbus;
 
     pub ImplAttributes("impl block") {
         interface str,
@@ -21,14 +22,19 @@ def_attrs! {
         async_name str,
         blocking_name str,
         gen_async bool,
-        gen_blocking bool
+        gen_blocking bool,
+        borrowed_replies none,
+        cached_properties [str],
+        verify_on_build none
     };
 
     pub MethodAttributes("method") {
         name str,
         property {
             pub PropertyAttributes("property") {
-                emits_changed_signal str
+                emits_changed_signal str,
+                interface str,
+                raw none
             }
         },
         signal none,
@@ -37,7 +43,11 @@ def_attrs! {
         blocking_object str,
         no_reply none,
         no_autostart none,
-        allow_interactive_auth none
+        allow_interactive_auth none,
+        stream none,
+        iter_args none,
+        raw_reply none,
+        with_headers none
     };
 }
 
@@ -63,6 +73,8 @@ impl AsyncOpts {
 }
 
 pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Error> {
+    // `derive(...)` takes trait paths, which `def_attrs` doesn't handle, so it's parsed here.
+    let (derives, args) = split_derives(args)?;
     let ImplAttributes {
         interface,
         name,
@@ -73,6 +85,9 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
         blocking_name,
         gen_async,
         gen_blocking,
+        borrowed_replies,
+        cached_properties,
+        verify_on_build,
     } = ImplAttributes::parse_nested_metas(&args)?;
 
     let iface_name = match (interface, name) {
@@ -117,9 +132,13 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
             default_service.as_deref(),
             &proxy_name,
             true,
-            // Signal args structs are shared between the two proxies so always generate it for
-            // async proxy only unless async proxy generation is disabled.
+            // Signal args and reply structs are shared between the two proxies so always generate
+            // them for async proxy only unless async proxy generation is disabled.
             !gen_async,
+            borrowed_replies,
+            cached_properties.as_deref(),
+            verify_on_build,
+            &derives,
         )?
     } else {
         quote! {}
@@ -135,6 +154,10 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
             &proxy_name,
             false,
             true,
+            borrowed_replies,
+            cached_properties.as_deref(),
+            verify_on_build,
+            &derives,
         )?
     } else {
         quote! {}
@@ -147,6 +170,32 @@ pub fn expand(args: AttributeArgs, input: ItemTrait) -> Result<TokenStream, Erro
     })
 }
 
+// Takes the `derive(...)` attribute out of `args`, returning the traits to derive and the rest.
+fn split_derives(args: AttributeArgs) -> Result<(Vec<Path>, AttributeArgs), Error> {
+    let mut derives = vec![];
+    let mut rest = vec![];
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("derive") => {
+                for nested in list.nested {
+                    match nested {
+                        NestedMeta::Meta(Meta::Path(path)) => derives.push(path),
+                        _ => {
+                            return Err(Error::new(
+                                nested.span(),
+                                "`derive` only takes the paths of the traits to derive",
+                            ))
+                        }
+                    }
+                }
+            }
+            arg => rest.push(arg),
+        }
+    }
+
+    Ok((derives, rest))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn create_proxy(
     input: &ItemTrait,
@@ -157,13 +206,17 @@ pub fn create_proxy(
     proxy_name: &str,
     blocking: bool,
     gen_sig_args: bool,
+    borrowed_replies: bool,
+    cached_properties: Option<&[String]>,
+    verify_on_build: bool,
+    derives: &[Path],
 ) -> Result<TokenStream, Error> {
     let zbus = zbus_path();
 
     let other_attrs: Vec<_> = input
         .attrs
         .iter()
-        .filter(|a| !a.path.is_ident("dbus_proxy"))
+        .filter(|a| !a.path.is_ident("dbus_proxy") && !a.path.is_ident("zbus"))
         .collect();
     let proxy_name = Ident::new(proxy_name, Span::call_site());
     let ident = input.ident.to_string();
@@ -193,6 +246,8 @@ pub fn create_proxy(
     let mut stream_types = TokenStream::new();
     let mut has_properties = false;
     let mut uncached_properties: Vec<String> = vec![];
+    let mut introspection = TokenStream::new();
+    let mut expected_properties = BTreeMap::new();
 
     let async_opts = AsyncOpts::new(blocking);
 
@@ -227,9 +282,29 @@ pub fn create_proxy(
                     PropertyEmitsChangedSignal::True
                 };
 
-                if let PropertyEmitsChangedSignal::False = emits_changed_signal {
+                let interface = prop_attrs.interface.as_deref();
+                if let Some(interface) = interface {
+                    if !is_valid_interface_name(interface) {
+                        return Err(Error::new(
+                            m.span(),
+                            format!("`{interface}` is not a valid interface name"),
+                        ));
+                    }
+                } else if let PropertyEmitsChangedSignal::False = emits_changed_signal {
                     uncached_properties.push(member_name.clone());
                 }
+                if prop_attrs.raw && has_inputs {
+                    return Err(Error::new(
+                        m.span(),
+                        "`raw` only applies to property getters",
+                    ));
+                }
+                if interface.is_none() {
+                    let expected = expected_properties
+                        .entry(member_name.clone())
+                        .or_insert_with(ExpectedProperty::default);
+                    expected.add_accessor(m, has_inputs);
+                }
 
                 gen_proxy_property(
                     &member_name,
@@ -237,8 +312,11 @@ pub fn create_proxy(
                     m,
                     &async_opts,
                     emits_changed_signal,
+                    interface,
+                    prop_attrs.raw,
                 )
             } else if is_signal {
+                introspection.extend(introspect_expected_signal(&member_name, m));
                 let (method, types) = gen_proxy_signal(
                     &proxy_name,
                     &iface_name,
@@ -247,17 +325,61 @@ pub fn create_proxy(
                     m,
                     &async_opts,
                     gen_sig_args,
+                    derives,
                 );
                 stream_types.extend(types);
 
                 method
             } else {
-                gen_proxy_method_call(&member_name, &method_name, m, &attrs, &async_opts)
+                introspection.extend(introspect_expected_method(&member_name, m, &attrs));
+                let (method, types) = gen_proxy_method_call(
+                    &member_name,
+                    &method_name,
+                    m,
+                    &attrs,
+                    &async_opts,
+                    borrowed_replies,
+                    gen_sig_args,
+                )?;
+                stream_types.extend(types);
+
+                method
             };
             methods.extend(m);
         }
     }
 
+    let cached_properties_builder = match cached_properties {
+        Some(cached) => {
+            if let Some(name) = cached
+                .iter()
+                .find(|name| !expected_properties.contains_key(*name))
+            {
+                return Err(Error::new(
+                    input.span(),
+                    format!("`{name}` in `cached_properties` is not a property of the interface"),
+                ));
+            }
+
+            quote! { builder.cached_properties(&[#(#cached),*]) }
+        }
+        None => quote! { builder },
+    };
+
+    for (name, property) in expected_properties {
+        introspection.extend(property.introspect(&name));
+    }
+
+    let verify_builder = if verify_on_build {
+        quote! {
+            let mut expected = ::std::string::String::new();
+            Self::__zbus_introspect_expected(&mut expected, 0);
+            builder.verify_introspection(expected)
+        }
+    } else {
+        quote! { builder }
+    };
+
     let AsyncOpts { usage, wait, .. } = async_opts;
     let (proxy_struct, connection, builder) = if blocking {
         let connection = quote! { #zbus::blocking::Connection };
@@ -343,6 +465,18 @@ pub fn create_proxy(
                         .path(obj_path)?
                         .build()#wait
                 }
+
+                /// Creates a new proxy with the given path, and the default destination.
+                ///
+                /// This is the same as `new`, for consistency with proxies that have a default
+                /// path.
+                pub #usage fn new_for_path<P>(conn: &#connection, path: P) -> #zbus::Result<#proxy_name<'c>>
+                where
+                    P: ::std::convert::TryInto<#zbus::zvariant::ObjectPath<'static>>,
+                    P::Error: ::std::convert::Into<#zbus::Error>,
+                {
+                    Self::new(conn, path)#wait
+                }
             };
             (builder_new, proxydefault_impl, proxy_method_new)
         }
@@ -360,6 +494,18 @@ pub fn create_proxy(
                 pub #usage fn new(conn: &#connection) -> #zbus::Result<#proxy_name<'c>> {
                     Self::builder(conn).build()#wait
                 }
+
+                /// Creates a new proxy with the given path, and the default service.
+                pub #usage fn new_for_path<P>(conn: &#connection, path: P) -> #zbus::Result<#proxy_name<'c>>
+                where
+                    P: ::std::convert::TryInto<#zbus::zvariant::ObjectPath<'static>>,
+                    P::Error: ::std::convert::Into<#zbus::Error>,
+                {
+                    let obj_path = path.try_into().map_err(::std::convert::Into::into)?;
+                    Self::builder(conn)
+                        .path(obj_path)?
+                        .build()#wait
+                }
             };
             (builder_new, proxydefault_impl, proxy_method_new)
         }
@@ -378,13 +524,15 @@ pub fn create_proxy(
             /// Returns a customizable builder for this proxy.
             pub fn builder(conn: &#connection) -> #builder<'c, Self> {
                 let mut builder = #builder_new;
-                if #has_properties {
+                let builder = if #has_properties {
                     let uncached = vec![#(#uncached_properties),*];
-                    builder.cache_properties(#zbus::proxy::CacheProperties::default())
-                           .uncached_properties(&uncached)
+                    let builder = builder.cache_properties(#zbus::proxy::CacheProperties::default())
+                           .uncached_properties(&uncached);
+                    #cached_properties_builder
                 } else {
                     builder.cache_properties(#zbus::proxy::CacheProperties::No)
-                }
+                };
+                #verify_builder
             }
 
             /// Consumes `self`, returning the underlying `zbus::Proxy`.
@@ -397,6 +545,25 @@ pub fn create_proxy(
                 &self.0
             }
 
+            /// Writes the introspection XML of the interface, as this proxy expects it.
+            ///
+            /// This is what `assert_proxy_matches_interface` compares with the interface.
+            #[doc(hidden)]
+            pub fn __zbus_introspect_expected(writer: &mut dyn ::std::fmt::Write, level: usize) {
+                ::std::writeln!(
+                    writer,
+                    r#"{:indent$}<interface name="{}">"#,
+                    "",
+                    #iface_name,
+                    indent = level
+                ).unwrap();
+                {
+                    let level = level + 2;
+                    #introspection
+                }
+                ::std::writeln!(writer, r#"{:indent$}</interface>"#, "", indent = level).unwrap();
+            }
+
             #methods
         }
 
@@ -460,7 +627,9 @@ fn gen_proxy_method_call(
     m: &TraitItemMethod,
     attrs: &MethodAttributes,
     async_opts: &AsyncOpts,
-) -> TokenStream {
+    borrowed_replies: bool,
+    gen_reply_struct: bool,
+) -> syn::Result<(TokenStream, TokenStream)> {
     let AsyncOpts {
         usage,
         wait,
@@ -470,15 +639,46 @@ fn gen_proxy_method_call(
     let other_attrs: Vec<_> = m
         .attrs
         .iter()
-        .filter(|a| !a.path.is_ident("dbus_proxy"))
+        .filter(|a| !a.path.is_ident("dbus_proxy") && !a.path.is_ident("zbus"))
         .collect();
-    let args: Vec<_> = m
-        .sig
-        .inputs
-        .iter()
-        .filter_map(typed_arg)
-        .filter_map(pat_ident)
+    // Array arguments (`&[T]` and `Vec<T>`) are accepted as `impl AsRef<[T]>` so callers can pass
+    // whatever they have at hand (slices, arrays, vectors etc) without needing to clone. With
+    // `iter_args`, they're accepted as `impl IntoIterator<Item = T>` instead, so the elements
+    // don't even need to be collected.
+    let mut inputs = m.sig.inputs.clone();
+    let mut has_array_args = false;
+    let args: Vec<_> = inputs
+        .iter_mut()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(t) => Some(t),
+            _ => None,
+        })
+        .filter_map(|pat| {
+            let ident = pat_ident(pat)?.clone();
+            match array_elem_type(&pat.ty).cloned() {
+                Some(elem) if attrs.iter_args => {
+                    has_array_args = true;
+                    *pat.ty = parse_quote!(
+                        impl ::std::iter::IntoIterator<Item = #elem> + ::std::clone::Clone
+                    );
+
+                    Some(quote!(#zbus::proxy::IterArg::new(#ident)))
+                }
+                Some(elem) => {
+                    *pat.ty = parse_quote!(impl ::std::convert::AsRef<[#elem]>);
+
+                    Some(quote!(#ident.as_ref()))
+                }
+                None => Some(quote!(#ident)),
+            }
+        })
         .collect();
+    if attrs.iter_args && !has_array_args {
+        return Err(Error::new(
+            m.span(),
+            "`iter_args` requires at least one array (`&[T]` or `Vec<T>`) argument",
+        ));
+    }
 
     let proxy_object = attrs.object.as_ref().map(|o| {
         if *blocking {
@@ -532,7 +732,6 @@ fn gen_proxy_method_call(
     };
 
     let method = Ident::new(snake_case_name, Span::call_site());
-    let inputs = &m.sig.inputs;
     let mut generics = m.sig.generics.clone();
     let where_clause = generics.where_clause.get_or_insert(parse_quote!(where));
     for param in generics
@@ -540,7 +739,7 @@ fn gen_proxy_method_call(
         .iter()
         .filter(|a| matches!(a, syn::GenericParam::Type(_)))
     {
-        let is_input_type = inputs.iter().any(|arg| {
+        let is_input_type = m.sig.inputs.iter().any(|arg| {
             // FIXME: We want to only require `Serialize` from input types and `DeserializeOwned`
             // from output types but since we don't have type introspection, we employ this
             // workaround of regex matching on string reprepresention of the the types to figure out
@@ -564,7 +763,63 @@ fn gen_proxy_method_call(
     }
     let (_, ty_generics, where_clause) = generics.split_for_impl();
 
-    if let Some(proxy_name) = proxy_object {
+    if attrs.stream && (proxy_object.is_some() || method_flags.is_some()) {
+        return Err(Error::new(
+            m.span(),
+            "`stream` can't be combined with `object`, `no_reply`, `no_autostart` or \
+             `allow_interactive_auth`",
+        ));
+    }
+    if attrs.raw_reply && (proxy_object.is_some() || method_flags.is_some()) {
+        return Err(Error::new(
+            m.span(),
+            "`raw_reply` can't be combined with `object`, `no_reply`, `no_autostart` or \
+             `allow_interactive_auth`",
+        ));
+    }
+    if attrs.with_headers && (proxy_object.is_some() || method_flags.is_some()) {
+        return Err(Error::new(
+            m.span(),
+            "`with_headers` can't be combined with `object`, `no_reply`, `no_autostart` or \
+             `allow_interactive_auth`",
+        ));
+    }
+
+    // The fds of the reply are closed along with it, so they need to be owned by the return value.
+    if let ReturnType::Type(_, ty) = &m.sig.output {
+        if let Some(fd) = find_fd_type(ty) {
+            return Err(Error::new(
+                fd.span(),
+                "`Fd` can't be returned since it would be closed along with the reply, use \
+                 `zbus::zvariant::OwnedFd` instead",
+            ));
+        }
+    }
+
+    // With `borrowed_replies`, methods returning borrowed data return the reply message instead of
+    // the deserialized value, so that the value can borrow from it.
+    let borrowed_ok_type = match &m.sig.output {
+        ReturnType::Type(_, ty) if borrowed_replies => {
+            result_ok_type(ty).filter(|ty| has_lifetime(ty)).cloned()
+        }
+        _ => None,
+    };
+    if borrowed_ok_type.is_some() && (proxy_object.is_some() || method_flags.is_some()) {
+        return Err(Error::new(
+            m.span(),
+            "`borrowed_replies` can't be applied to methods with `object`, `no_reply`, \
+             `no_autostart` or `allow_interactive_auth`",
+        ));
+    }
+    let reply_name = format_ident!("{}Reply", method_name);
+    let reply_struct = match &borrowed_ok_type {
+        Some(ok_type) if gen_reply_struct => {
+            gen_reply_struct_decl(method_name, &reply_name, ok_type)
+        }
+        _ => quote!(),
+    };
+
+    let method = if let Some(proxy_name) = proxy_object {
         let proxy = Ident::new(&proxy_name, Span::call_site());
         let signature = quote! {
             fn #method #ty_generics(#inputs) -> #zbus::Result<#proxy<'c>>
@@ -606,7 +861,22 @@ fn gen_proxy_method_call(
             #where_clause
         };
 
-        if let Some(method_flags) = method_flags {
+        if borrowed_ok_type.is_some() {
+            let mut reply_output = m.sig.output.clone();
+            if let ReturnType::Type(_, ty) = &mut reply_output {
+                replace_result_ok_type(ty, parse_quote!(#reply_name));
+            }
+
+            quote! {
+                #(#other_attrs)*
+                pub #usage fn #method #ty_generics(#inputs) #reply_output
+                #where_clause
+                {
+                    let reply = self.0.call_method(#method_name, #body)#wait?;
+                    ::std::result::Result::Ok(#reply_name(reply))
+                }
+            }
+        } else if let Some(method_flags) = method_flags {
             if no_reply {
                 quote! {
                     #(#other_attrs)*
@@ -640,6 +910,335 @@ fn gen_proxy_method_call(
                 }
             }
         }
+    };
+    let body = if args.len() == 1 {
+        let arg = &args[0];
+        quote!(&(#arg,))
+    } else {
+        quote!(&(#(#args),*))
+    };
+
+    // Also generate a method returning the reply message as is.
+    let method = if attrs.raw_reply {
+        let mut raw_output = match &m.sig.output {
+            ReturnType::Type(_, ty) => (**ty).clone(),
+            ReturnType::Default => parse_quote!(#zbus::Result<()>),
+        };
+        replace_result_ok_type(
+            &mut raw_output,
+            parse_quote!(::std::sync::Arc<#zbus::message::Message>),
+        );
+        let raw_method = format_ident!("{}_raw", snake_case_name);
+        let doc = format!(
+            " Same as [`{snake_case_name}`](Self::{snake_case_name}), but returns the reply \
+             message instead of deserializing its body.\n\n \
+             The caller owns the file descriptors in the reply, if any. They're closed along \
+             with the message unless taken out of it with `Message::take_fds`."
+        );
+
+        quote! {
+            #method
+
+            #[doc = #doc]
+            pub #usage fn #raw_method #ty_generics(#inputs) -> #raw_output
+            #where_clause
+            {
+                self.0.call_method(#method_name, #body)#wait.map_err(::std::convert::Into::into)
+            }
+        }
+    } else {
+        method
+    };
+
+    // Also generate a method returning the reply headers along with the deserialized body.
+    let method = if attrs.with_headers {
+        let mut meta_output = match &m.sig.output {
+            ReturnType::Type(_, ty) => (**ty).clone(),
+            ReturnType::Default => parse_quote!(#zbus::Result<()>),
+        };
+        let ok_type = result_ok_type(&meta_output)
+            .cloned()
+            .unwrap_or_else(|| parse_quote!(()));
+        if has_lifetime(&ok_type) {
+            return Err(Error::new(
+                ok_type.span(),
+                "`with_headers` requires a return type that doesn't borrow from the reply",
+            ));
+        }
+        replace_result_ok_type(
+            &mut meta_output,
+            parse_quote!((#ok_type, #zbus::proxy::ReplyMeta)),
+        );
+        let meta_method = format_ident!("{}_with_headers", snake_case_name);
+        let doc = format!(
+            " Same as [`{snake_case_name}`](Self::{snake_case_name}), but also returns the \
+             headers of the reply, e.g its sender."
+        );
+
+        quote! {
+            #method
+
+            #[doc = #doc]
+            pub #usage fn #meta_method #ty_generics(#inputs) -> #meta_output
+            #where_clause
+            {
+                let reply = self.0.call_method(#method_name, #body)#wait?;
+                let meta = <#zbus::proxy::ReplyMeta as ::std::convert::TryFrom<_>>::try_from(&*reply)?;
+
+                ::std::result::Result::Ok((reply.body()?, meta))
+            }
+        }
+    } else {
+        method
+    };
+    if !attrs.stream {
+        return Ok((method, reply_struct));
+    }
+
+    // Also generate a method yielding the elements of the returned array one by one.
+    let (output, elem) = match &m.sig.output {
+        ReturnType::Type(_, ty) => (ty, result_array_elem_type(ty)),
+        ReturnType::Default => {
+            return Err(Error::new(
+                m.span(),
+                "`stream` requires the method to return an array",
+            ))
+        }
+    };
+    let elem = elem.ok_or_else(|| {
+        Error::new(
+            output.span(),
+            "`stream` requires a return type of the form `Result<Vec<T>>`",
+        )
+    })?;
+    let stream_type: Type = if *blocking {
+        parse_quote!(#zbus::blocking::proxy::ArrayIterator<#elem>)
+    } else {
+        parse_quote!(#zbus::proxy::ArrayStream<#elem>)
+    };
+    let mut stream_output = (**output).clone();
+    replace_result_ok_type(&mut stream_output, stream_type.clone());
+    let stream_method = format_ident!("{}_stream", snake_case_name);
+    let doc = format!(
+        " Same as [`{snake_case_name}`](Self::{snake_case_name}), but yields the elements \
+         of the returned array one by one, deserializing them lazily."
+    );
+
+    let method = quote! {
+        #method
+
+        #[doc = #doc]
+        pub #usage fn #stream_method #ty_generics(#inputs) -> #stream_output
+        #where_clause
+        {
+            let reply = self.0.call_method(#method_name, #body)#wait?;
+            <#stream_type>::new(reply).map_err(::std::convert::Into::into)
+        }
+    };
+
+    Ok((method, reply_struct))
+}
+
+/// The type retaining the reply of `method_name`, for methods returning borrowed data.
+fn gen_reply_struct_decl(method_name: &str, reply_name: &Ident, ok_type: &Type) -> TokenStream {
+    let zbus = zbus_path();
+    let ok_type_s = SetLifetimeS.fold_type(ok_type.clone());
+    // Deserializing a `Cow` always allocates so we deserialize the borrowed type instead.
+    let body = match cow_borrowed_type(&ok_type_s) {
+        Some(borrowed) => quote! {
+            self.0.body::<&'s #borrowed>().map(::std::borrow::Cow::Borrowed)
+        },
+        None => quote! {
+            self.0.body()
+        },
+    };
+    let doc = format!(
+        "The reply of a `{method_name}` method call.\n\
+         \n\
+         The reply message is kept around so that the returned data can be borrowed from it, \
+         instead of being copied. Use `body` to deserialize it."
+    );
+
+    // The `derive` attribute of the proxy doesn't apply here: `Message` implements nothing else.
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone)]
+        pub struct #reply_name(::std::sync::Arc<#zbus::message::Message>);
+
+        impl #reply_name {
+            /// The reply message.
+            pub fn message(&self) -> &::std::sync::Arc<#zbus::message::Message> {
+                &self.0
+            }
+
+            /// Deserialize the returned value, borrowing from the reply message.
+            pub fn body<'s>(&'s self) -> #zbus::Result<#ok_type_s> {
+                #body
+            }
+        }
+    }
+}
+
+/// The type `T` if `ty` is of the form `Result<T>`.
+fn result_ok_type(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Path(p) => match &p.path.segments.last()?.arguments {
+            PathArguments::AngleBracketed(args) => match args.args.first()? {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The type `T` if `ty` is `Cow<str>` or `Cow<[u8]>`.
+fn cow_borrowed_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(p) if p.qself.is_none() => p.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Cow" {
+        return None;
+    }
+    let borrowed = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })?,
+        _ => return None,
+    };
+    let is_str = matches!(borrowed, Type::Path(p) if p.path.is_ident("str"));
+    let is_bytes = matches!(
+        borrowed,
+        Type::Slice(s) if matches!(&*s.elem, Type::Path(p) if p.path.is_ident("u8"))
+    );
+
+    (is_str || is_bytes).then_some(borrowed)
+}
+
+/// The first `zvariant::Fd` type found in `ty`, if any.
+///
+/// Only the `zvariant::Fd` and `zbus::zvariant::Fd` paths are matched, since a bare `Fd` could be
+/// any type.
+fn find_fd_type(ty: &Type) -> Option<syn::TypePath> {
+    let mut finder = FindFd(None);
+    finder.fold_type(ty.clone());
+
+    finder.0
+}
+
+struct FindFd(Option<syn::TypePath>);
+
+impl Fold for FindFd {
+    fn fold_type_path(&mut self, node: syn::TypePath) -> syn::TypePath {
+        let segments: Vec<_> = node.path.segments.iter().collect();
+        let is_fd = node.qself.is_none()
+            && segments.iter().all(|s| s.arguments.is_empty())
+            && match segments.as_slice() {
+                [zvariant, fd] => zvariant.ident == "zvariant" && fd.ident == "Fd",
+                [zbus, zvariant, fd] => {
+                    zbus.ident == "zbus" && zvariant.ident == "zvariant" && fd.ident == "Fd"
+                }
+                _ => false,
+            };
+        if is_fd && self.0.is_none() {
+            self.0 = Some(node.clone());
+        }
+
+        syn::fold::fold_type_path(self, node)
+    }
+}
+
+/// Whether `ty` contains a reference or a lifetime.
+fn has_lifetime(ty: &Type) -> bool {
+    let mut finder = FindLifetime(false);
+    finder.fold_type(ty.clone());
+
+    finder.0
+}
+
+struct FindLifetime(bool);
+
+impl Fold for FindLifetime {
+    fn fold_type_reference(&mut self, node: syn::TypeReference) -> syn::TypeReference {
+        self.0 = true;
+        node
+    }
+
+    fn fold_lifetime(&mut self, node: syn::Lifetime) -> syn::Lifetime {
+        self.0 = true;
+        node
+    }
+}
+
+/// The element type `T` if `ty` is of the form `Result<Vec<T>>`.
+fn result_array_elem_type(ty: &Type) -> Option<&Type> {
+    match result_ok_type(ty)? {
+        ty @ Type::Path(_) => array_elem_type(ty),
+        _ => None,
+    }
+}
+
+/// Replace `T` in `ty` of the form `Result<T>` with `new`.
+fn replace_result_ok_type(ty: &mut Type, new: Type) {
+    if let Type::Path(p) = ty {
+        if let Some(PathArguments::AngleBracketed(args)) =
+            p.path.segments.last_mut().map(|s| &mut s.arguments)
+        {
+            if let Some(GenericArgument::Type(ty)) = args.args.first_mut() {
+                *ty = new;
+            }
+        }
+    }
+}
+
+/// The element type `T` if `ty` is `&[T]` or `Vec<T>`.
+///
+/// Element types with elided lifetimes are not handled since anonymous lifetimes are not allowed
+/// in `impl Trait`.
+fn array_elem_type(ty: &Type) -> Option<&Type> {
+    let elem = match ty {
+        Type::Reference(r) if r.mutability.is_none() => match &*r.elem {
+            Type::Slice(slice) => Some(&*slice.elem),
+            _ => None,
+        },
+        Type::Path(p) if p.qself.is_none() => {
+            let segment = p.path.segments.last()?;
+            if segment.ident != "Vec" {
+                return None;
+            }
+            match &segment.arguments {
+                PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                    match args.args.first()? {
+                        GenericArgument::Type(elem) => Some(elem),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }?;
+
+    let mut finder = FindElidedLifetime(false);
+    finder.fold_type(elem.clone());
+
+    (!finder.0).then_some(elem)
+}
+
+struct FindElidedLifetime(bool);
+
+impl Fold for FindElidedLifetime {
+    fn fold_type_reference(&mut self, node: syn::TypeReference) -> syn::TypeReference {
+        self.0 |= node.lifetime.is_none();
+        syn::fold::fold_type_reference(self, node)
+    }
+
+    fn fold_lifetime(&mut self, node: syn::Lifetime) -> syn::Lifetime {
+        self.0 |= node.ident == "_";
+        node
     }
 }
 
@@ -678,6 +1277,8 @@ fn gen_proxy_property(
     m: &TraitItemMethod,
     async_opts: &AsyncOpts,
     emits_changed_signal: PropertyEmitsChangedSignal,
+    interface: Option<&str>,
+    raw: bool,
 ) -> TokenStream {
     let AsyncOpts {
         usage,
@@ -688,16 +1289,22 @@ fn gen_proxy_property(
     let other_attrs: Vec<_> = m
         .attrs
         .iter()
-        .filter(|a| !a.path.is_ident("dbus_proxy"))
+        .filter(|a| !a.path.is_ident("dbus_proxy") && !a.path.is_ident("zbus"))
         .collect();
     let signature = &m.sig;
     if signature.inputs.len() > 1 {
         let value = pat_ident(typed_arg(signature.inputs.last().unwrap()).unwrap()).unwrap();
+        let set_call = match interface {
+            Some(interface) => quote! {
+                self.0.set_interface_property(#interface, #property_name, #value)
+            },
+            None => quote! { self.0.set_property(#property_name, #value) },
+        };
         quote! {
             #(#other_attrs)*
             #[allow(clippy::needless_question_mark)]
             pub #usage #signature {
-                ::std::result::Result::Ok(self.0.set_property(#property_name, #value)#wait?)
+                ::std::result::Result::Ok(#set_call #wait?)
             }
         }
     } else {
@@ -708,8 +1315,15 @@ fn gen_proxy_property(
         } else {
             signature.span()
         };
-        let body = quote_spanned! {body_span =>
-            ::std::result::Result::Ok(self.0.get_property(#property_name)#wait?)
+        let body = match interface {
+            Some(interface) => quote_spanned! {body_span =>
+                ::std::result::Result::Ok(
+                    self.0.get_interface_property(#interface, #property_name)#wait?
+                )
+            },
+            None => quote_spanned! {body_span =>
+                ::std::result::Result::Ok(self.0.get_property(#property_name)#wait?)
+            },
         };
         let ret_type = if let ReturnType::Type(_, ty) = &signature.output {
             Some(ty)
@@ -726,7 +1340,9 @@ fn gen_proxy_property(
             ("zbus::Proxy", quote! { #zbus::proxy::PropertyStream })
         };
 
+        // Only the properties of the proxy's own interface are cached and tracked for changes.
         let receive_method = match emits_changed_signal {
+            _ if interface.is_some() => quote! {},
             PropertyEmitsChangedSignal::True | PropertyEmitsChangedSignal::Invalidates => {
                 let (_, ty_generics, where_clause) = m.sig.generics.split_for_impl();
                 let receive = format_ident!("receive_{}_changed", method_name);
@@ -751,6 +1367,7 @@ fn gen_proxy_property(
         };
 
         let cached_getter_method = match emits_changed_signal {
+            _ if interface.is_some() => quote! {},
             PropertyEmitsChangedSignal::True
             | PropertyEmitsChangedSignal::Invalidates
             | PropertyEmitsChangedSignal::Const => {
@@ -771,6 +1388,35 @@ fn gen_proxy_property(
             PropertyEmitsChangedSignal::False => quote! {},
         };
 
+        let raw_getter_method = if raw {
+            let raw_getter = format_ident!("{}_raw", method_name);
+            let raw_doc = format!(
+                " Get the value of the `{property_name}` property as is, without converting it to \
+                 the declared type, e.g to cope with a service version giving it another type. \
+                 The value is always fetched from the service, never from the cache.",
+            );
+            let get_call = match interface {
+                Some(interface) => quote! {
+                    self.0.get_interface_property(#interface, #property_name)
+                },
+                // Not `get_property`, which would return the cached value.
+                None => quote! {
+                    self.0.get_interface_property(self.0.interface().clone(), #property_name)
+                },
+            };
+            quote! {
+                #[doc = #raw_doc]
+                pub #usage fn #raw_getter(&self) -> ::std::result::Result<
+                    #zbus::zvariant::OwnedValue,
+                    <#ret_type as #zbus::ResultAdapter>::Err>
+                {
+                    #get_call #wait.map_err(::std::convert::Into::into)
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         quote! {
             #(#other_attrs)*
             #[allow(clippy::needless_question_mark)]
@@ -780,11 +1426,31 @@ fn gen_proxy_property(
 
             #cached_getter_method
 
+            #raw_getter_method
+
             #receive_method
         }
     }
 }
 
+/// Interface names are made of at least two `.`-separated elements, each of only ASCII
+/// alphanumeric characters and `_`, not starting with a digit, and are at most 255 characters
+/// long.
+fn is_valid_interface_name(name: &str) -> bool {
+    name.len() <= 255
+        && name.split('.').count() >= 2
+        && name.split('.').all(|element| {
+            let mut chars = element.chars();
+
+            match chars.next() {
+                Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+                }
+                _ => false,
+            }
+        })
+}
+
 struct SetLifetimeS;
 
 impl Fold for SetLifetimeS {
@@ -799,6 +1465,179 @@ impl Fold for SetLifetimeS {
     }
 }
 
+/// The introspection of the method `m`, as expected by the proxy.
+///
+/// Methods with arguments that can't be named outside of their declaration (e.g generic ones) are
+/// left out.
+fn introspect_expected_method(
+    name: &str,
+    m: &TraitItemMethod,
+    attrs: &MethodAttributes,
+) -> Option<TokenStream> {
+    let zbus = zbus_path();
+    let mut args = TokenStream::new();
+    for arg in m.sig.inputs.iter().filter_map(typed_arg) {
+        args.extend(introspect_expected_arg(&arg.ty, m, Some("in"))?);
+    }
+    if attrs.object.is_some() {
+        let ty = parse_quote!(#zbus::zvariant::OwnedObjectPath);
+        args.extend(introspect_expected_arg(&ty, m, Some("out"))?);
+    } else if let ReturnType::Type(_, ty) = &m.sig.output {
+        match result_ok_type(ty)? {
+            Type::Tuple(t) => {
+                for ty in &t.elems {
+                    args.extend(introspect_expected_arg(ty, m, Some("out"))?);
+                }
+            }
+            ty => args.extend(introspect_expected_arg(ty, m, Some("out"))?),
+        }
+    }
+
+    Some(quote!(
+        ::std::writeln!(writer, "{:indent$}<method name=\"{}\">", "", #name, indent = level).unwrap();
+        {
+            let level = level + 2;
+            #args
+        }
+        ::std::writeln!(writer, "{:indent$}</method>", "", indent = level).unwrap();
+    ))
+}
+
+/// The introspection of the signal `m`, as expected by the proxy.
+fn introspect_expected_signal(name: &str, m: &TraitItemMethod) -> Option<TokenStream> {
+    let mut args = TokenStream::new();
+    for arg in m.sig.inputs.iter().filter_map(typed_arg) {
+        args.extend(introspect_expected_arg(&arg.ty, m, None)?);
+    }
+
+    Some(quote!(
+        ::std::writeln!(writer, "{:indent$}<signal name=\"{}\">", "", #name, indent = level).unwrap();
+        {
+            let level = level + 2;
+            #args
+        }
+        ::std::writeln!(writer, "{:indent$}</signal>", "", indent = level).unwrap();
+    ))
+}
+
+fn introspect_expected_arg(
+    ty: &Type,
+    m: &TraitItemMethod,
+    dir: Option<&str>,
+) -> Option<TokenStream> {
+    let zbus = zbus_path();
+    let ty = nameable_type(ty, m)?;
+    let dir = dir
+        .map(|d| format!(" direction=\"{d}\""))
+        .unwrap_or_default();
+
+    Some(quote!(
+        // Units have an empty signature and are not sent over the bus.
+        let signature = <#ty as #zbus::zvariant::Type>::signature();
+        if !signature.is_empty() {
+            ::std::writeln!(writer, "{:indent$}<arg type=\"{}\"{}/>", "",
+                     signature, #dir, indent = level).unwrap();
+        }
+    ))
+}
+
+/// What the proxy expects of a property, from its getter and setter.
+#[derive(Default)]
+struct ExpectedProperty {
+    ty: Option<Type>,
+    read: bool,
+    write: bool,
+}
+
+impl ExpectedProperty {
+    fn add_accessor(&mut self, m: &TraitItemMethod, is_setter: bool) {
+        let ty = if is_setter {
+            self.write = true;
+            m.sig
+                .inputs
+                .last()
+                .and_then(typed_arg)
+                .map(|arg| arg.ty.as_ref())
+        } else {
+            self.read = true;
+            match &m.sig.output {
+                ReturnType::Type(_, ty) => result_ok_type(ty),
+                ReturnType::Default => None,
+            }
+        };
+        if self.ty.is_none() {
+            self.ty = ty.and_then(|ty| nameable_type(ty, m));
+        }
+    }
+
+    fn introspect(&self, name: &str) -> Option<TokenStream> {
+        let zbus = zbus_path();
+        let ty = self.ty.as_ref()?;
+        let access = match (self.read, self.write) {
+            (true, true) => "readwrite",
+            (true, false) => "read",
+            (false, _) => "write",
+        };
+
+        Some(quote!(
+            ::std::writeln!(
+                writer,
+                "{:indent$}<property name=\"{}\" type=\"{}\" access=\"{}\"/>",
+                "", #name, <#ty as #zbus::zvariant::Type>::signature(), #access, indent = level,
+            ).unwrap();
+        ))
+    }
+}
+
+/// `ty`, as declared in `m`, in a form that can be named outside of `m`: all lifetimes are made
+/// `'static`. Returns `None` if that's not possible, i-e `ty` involves `impl Trait`, inferred types
+/// or the generic types of `m`.
+fn nameable_type(ty: &Type, m: &TraitItemMethod) -> Option<Type> {
+    let mut folder = StaticLifetimes {
+        generics: m
+            .sig
+            .generics
+            .type_params()
+            .map(|p| p.ident.clone())
+            .collect(),
+        nameable: true,
+    };
+    let ty = folder.fold_type(ty.clone());
+
+    folder.nameable.then_some(ty)
+}
+
+struct StaticLifetimes {
+    generics: Vec<Ident>,
+    nameable: bool,
+}
+
+impl Fold for StaticLifetimes {
+    fn fold_type(&mut self, node: Type) -> Type {
+        match &node {
+            Type::ImplTrait(_) | Type::Infer(_) => self.nameable = false,
+            Type::Path(p) if p.qself.is_none() => {
+                if let Some(ident) = p.path.get_ident() {
+                    self.nameable &= !self.generics.contains(ident) && ident != "Self";
+                }
+            }
+            _ => (),
+        }
+
+        syn::fold::fold_type(self, node)
+    }
+
+    fn fold_type_reference(&mut self, mut node: syn::TypeReference) -> syn::TypeReference {
+        node.lifetime = Some(syn::Lifetime::new("'static", Span::call_site()));
+        syn::fold::fold_type_reference(self, node)
+    }
+
+    fn fold_lifetime(&mut self, _node: syn::Lifetime) -> syn::Lifetime {
+        syn::Lifetime::new("'static", Span::call_site())
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn gen_proxy_signal(
     proxy_name: &Ident,
     iface_name: &str,
@@ -807,6 +1646,7 @@ fn gen_proxy_signal(
     method: &TraitItemMethod,
     async_opts: &AsyncOpts,
     gen_sig_args: bool,
+    derives: &[Path],
 ) -> (TokenStream, TokenStream) {
     let AsyncOpts {
         usage,
@@ -817,7 +1657,7 @@ fn gen_proxy_signal(
     let other_attrs: Vec<_> = method
         .attrs
         .iter()
-        .filter(|a| !a.path.is_ident("dbus_proxy"))
+        .filter(|a| !a.path.is_ident("dbus_proxy") && !a.path.is_ident("zbus"))
         .collect();
     let input_types: Vec<_> = method
         .sig
@@ -871,6 +1711,7 @@ fn gen_proxy_signal(
         trait_name,
         trait_link,
         signal_type,
+        signal_type_path,
     ) = if *blocking {
         (
             "zbus::blocking::Proxy",
@@ -879,6 +1720,7 @@ fn gen_proxy_signal(
             "Iterator",
             "https://doc.rust-lang.org/std/iter/trait.Iterator.html",
             quote! { blocking::proxy::SignalIterator },
+            "zbus::blocking::proxy::SignalIterator",
         )
     } else {
         (
@@ -888,6 +1730,7 @@ fn gen_proxy_signal(
             "Stream",
             "https://docs.rs/futures/0.3.15/futures/stream/trait.Stream.html",
             quote! { proxy::SignalStream },
+            "zbus::proxy::SignalStream",
         )
     };
     let receiver_name = format_ident!("receive_{snake_case_name}");
@@ -936,6 +1779,17 @@ fn gen_proxy_signal(
             \n\
             [`{trait_name}`]: {trait_link}",
     );
+    let wait_for_gen_doc = format!(
+        "Wait for the first `{signal_name}` signal for which `predicate` returns `true`.\n\
+            \n\
+            See [`{signal_type_path}::wait_for`] for details.",
+    );
+    let wait_for_timeout_gen_doc = format!(
+        "Wait for the first `{signal_name}` signal for which `predicate` returns `true`, for up \
+            to `timeout`.\n\
+            \n\
+            See [`{signal_type_path}::wait_for_timeout`] for details.",
+    );
     let signal_args_gen_doc = format!("`{signal_name}` signal arguments.");
     let args_struct_gen_doc = format!("A `{signal_name}` signal.");
     let args_struct_decl = if gen_sig_args {
@@ -984,6 +1838,29 @@ fn gen_proxy_signal(
                         _ => None,
                     }
                 }
+
+                #[doc = "Try to construct a "]
+                #[doc = #signal_name]
+                #[doc = " from a [::zbus::message::Message], only if it was sent by `sender`."]
+                #[doc = ""]
+                #[doc = "To only accept the signals of a service, pass the unique name of the current owner of"]
+                #[doc = "its well-known name, e.g from [::zbus::fdo::DBusProxy::get_name_owner]."]
+                pub fn from_message_with_sender<M>(
+                    msg: M,
+                    sender: &#zbus::names::UniqueName<'_>,
+                ) -> ::std::option::Option<Self>
+                where
+                    M: ::std::convert::Into<::std::sync::Arc<#zbus::message::Message>>,
+                {
+                    Self::from_message(msg).filter(|signal| signal.sender().as_ref() == Some(sender))
+                }
+
+                /// The unique name of the connection that sent the signal, if known.
+                ///
+                /// Only the messages routed through a bus are guaranteed to have a sender.
+                pub fn sender(&self) -> ::std::option::Option<#zbus::names::UniqueName<'_>> {
+                    self.0.header().ok()?.sender().ok().flatten().cloned()
+                }
             }
         }
     } else {
@@ -992,6 +1869,13 @@ fn gen_proxy_signal(
     let args_impl = if args.is_empty() || !gen_sig_args {
         quote!()
     } else {
+        // `Debug` is always implemented, without the (useless) phantom field.
+        let derives: Vec<_> = derives.iter().filter(|d| !d.is_ident("Debug")).collect();
+        let derive = if derives.is_empty() {
+            quote!()
+        } else {
+            quote! { #[derive(#(#derives),*)] }
+        };
         let arg_fields_init = if args.len() == 1 {
             quote! { #(#args)*: args }
         } else {
@@ -1009,6 +1893,7 @@ fn gen_proxy_signal(
             }
 
             #[doc = #signal_args_gen_doc]
+            #derive
             pub struct #signal_args #ty_generics {
                 phantom: std::marker::PhantomData<&'s ()>,
                 #(
@@ -1038,6 +1923,25 @@ fn gen_proxy_signal(
                 }
             }
 
+            impl #impl_generics ::std::convert::From<(#(#input_types_s,)*)> for #signal_args #ty_generics
+                #where_clause
+            {
+                fn from((#(#args,)*): (#(#input_types_s,)*)) -> Self {
+                    #signal_args {
+                        phantom: ::std::marker::PhantomData,
+                        #(#args),*
+                    }
+                }
+            }
+
+            impl #impl_generics ::std::convert::From<#signal_args #ty_generics> for (#(#input_types_s,)*)
+                #where_clause
+            {
+                fn from(args: #signal_args #ty_generics) -> Self {
+                    (#(args.#args,)*)
+                }
+            }
+
             impl #impl_generics ::std::convert::TryFrom<&'s #zbus::message::Message> for #signal_args #ty_generics
                 #where_clause
             {
@@ -1135,6 +2039,32 @@ fn gen_proxy_signal(
             pub fn inner(&self) -> & #zbus::#signal_type<'a> {
                 &self.0
             }
+
+            #[doc = #wait_for_gen_doc]
+            pub #usage fn wait_for<F>(self, mut predicate: F) -> ::std::option::Option<#signal_name_ident>
+            where
+                F: ::std::ops::FnMut(&#signal_name_ident) -> bool,
+            {
+                self.into_inner()
+                    .wait_for(|msg| predicate(&#signal_name_ident(msg.clone())))
+                    #wait
+                    .map(#signal_name_ident)
+            }
+
+            #[doc = #wait_for_timeout_gen_doc]
+            pub #usage fn wait_for_timeout<F>(
+                self,
+                timeout: ::std::time::Duration,
+                mut predicate: F,
+            ) -> ::std::option::Option<#signal_name_ident>
+            where
+                F: ::std::ops::FnMut(&#signal_name_ident) -> bool,
+            {
+                self.into_inner()
+                    .wait_for_timeout(timeout, |msg| predicate(&#signal_name_ident(msg.clone())))
+                    #wait
+                    .map(#signal_name_ident)
+            }
         }
 
         impl<'a> std::ops::Deref for #stream_name<'a> {
@@ -1160,3 +2090,48 @@ fn gen_proxy_signal(
 
     (receive_signal, stream_types)
 }
+
+/// The arguments of `assert_proxy_matches_interface`: a proxy type and an interface value.
+pub struct AssertMatchesArgs {
+    proxy: Type,
+    interface: syn::Expr,
+}
+
+impl syn::parse::Parse for AssertMatchesArgs {
+    fn parse(input: syn::parse::ParseStream<'_>) -> syn::Result<Self> {
+        let proxy = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let interface = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+        }
+
+        Ok(Self { proxy, interface })
+    }
+}
+
+pub fn expand_assert_matches_interface(args: AssertMatchesArgs) -> Result<TokenStream, Error> {
+    let zbus = zbus_path();
+    let AssertMatchesArgs { proxy, interface } = args;
+    let proxy_ident = match &proxy {
+        Type::Path(p) if p.qself.is_none() => p.path.segments.last().map(|s| &s.ident),
+        _ => None,
+    }
+    .ok_or_else(|| Error::new_spanned(&proxy, "expected the type of a proxy"))?;
+    let test_name = format_ident!(
+        "{}_matches_interface",
+        case::snake_case(&proxy_ident.to_string())
+    );
+
+    Ok(quote! {
+        #[test]
+        fn #test_name() {
+            let mut expected = ::std::string::String::new();
+            <#proxy>::__zbus_introspect_expected(&mut expected, 0);
+            let mut actual = ::std::string::String::new();
+            #zbus::object_server::Interface::introspect_to_writer(&#interface, &mut actual, 0);
+
+            #zbus::xml::assert_proxy_matches_interface(&expected, &actual);
+        }
+    })
+}
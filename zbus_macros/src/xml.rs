@@ -0,0 +1,239 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::Deserialize;
+use std::path::PathBuf;
+use syn::{Error, ItemTrait, LitStr};
+use zvariant_utils::{
+    case,
+    codegen::{self, snake_case, to_identifier},
+};
+
+use crate::utils::zbus_path;
+
+// Just enough of the introspection format to generate proxies. This mirrors `zbus::xml`, which
+// can't be used here since zbus depends on this crate.
+
+#[derive(Debug, Deserialize)]
+struct Node {
+    #[serde(rename = "interface", default)]
+    interfaces: Vec<Interface>,
+    #[serde(rename = "node", default)]
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Interface {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "method", default)]
+    methods: Vec<Member>,
+    #[serde(rename = "signal", default)]
+    signals: Vec<Member>,
+    #[serde(rename = "property", default)]
+    properties: Vec<Property>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "arg", default)]
+    args: Vec<Arg>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Arg {
+    #[serde(rename = "@name")]
+    name: Option<String>,
+    #[serde(rename = "@type")]
+    ty: String,
+    #[serde(rename = "@direction")]
+    direction: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Property {
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "@type")]
+    ty: String,
+    #[serde(rename = "@access")]
+    access: String,
+}
+
+pub fn expand(path: LitStr) -> Result<TokenStream, Error> {
+    let span = path.span();
+    let path = resolve_path(&path.value());
+    let xml = std::fs::read_to_string(&path).map_err(|e| {
+        Error::new(
+            span,
+            format!("failed to read `{}`: {e}", path.to_string_lossy()),
+        )
+    })?;
+    let node: Node = quick_xml::de::from_str(&xml).map_err(|e| {
+        Error::new(
+            span,
+            format!("failed to parse `{}`: {e}", path.to_string_lossy()),
+        )
+    })?;
+
+    // The interfaces of the child nodes are included as well, once. The proxies for the standard
+    // interfaces are provided by zbus already.
+    let mut ifaces: Vec<&Interface> = vec![];
+    let mut nodes = vec![&node];
+    while let Some(node) = nodes.pop() {
+        for iface in &node.interfaces {
+            if !iface.name.starts_with("org.freedesktop.DBus.")
+                && !ifaces.iter().any(|i| i.name == iface.name)
+            {
+                ifaces.push(iface);
+            }
+        }
+        nodes.extend(node.nodes.iter().rev());
+    }
+    if ifaces.is_empty() {
+        return Err(Error::new(span, "no (non-standard) interface found"));
+    }
+
+    let zbus = zbus_path();
+    // Ensure the crate is rebuilt when the file changes.
+    let file = path.to_string_lossy();
+    let mut tokens = quote! {
+        const _: &str = ::std::include_str!(#file);
+    };
+    for iface in ifaces {
+        let decl = gen_trait(iface)
+            .map_err(|e| Error::new(span, format!("in `{}` interface: {e}", iface.name)))?;
+        let name = &iface.name;
+        let decl: ItemTrait = syn::parse_str(&decl)?;
+        tokens.extend(quote! {
+            #[#zbus::dbus_proxy(interface = #name, assume_defaults = true)]
+            #decl
+        });
+    }
+
+    Ok(tokens)
+}
+
+// Relative paths are relative to the root of the crate, as for the `include_str` of a file at the
+// root.
+fn resolve_path(path: &str) -> PathBuf {
+    let path = PathBuf::from(path);
+    if path.is_absolute() {
+        return path;
+    }
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => PathBuf::from(dir).join(path),
+        None => path,
+    }
+}
+
+// The declaration of the proxy trait of `iface`, without its `dbus_proxy` attribute.
+fn gen_trait(iface: &Interface) -> Result<String, String> {
+    let name = iface.name.rsplit('.').next().unwrap_or(&iface.name);
+    let mut decl = format!("trait {name} {{\n");
+
+    for m in &iface.methods {
+        let mut inputs = vec!["&self".to_string()];
+        let mut outputs = vec![];
+        for (n, a) in m.args.iter().enumerate() {
+            match a.direction.as_deref() {
+                None | Some("in") => {
+                    let ty = to_rust_type(&a.ty, true, true)?;
+                    inputs.push(format!("{}: {ty}", arg_name(a, n)));
+                }
+                Some("out") => outputs.push(to_rust_type(&a.ty, false, false)?),
+                Some(d) => return Err(format!("invalid argument direction `{d}`")),
+            }
+        }
+        let output = match outputs.len() {
+            1 => outputs.remove(0),
+            _ => format!("({})", outputs.join(", ")),
+        };
+        decl.push_str(&format!("/// {} method\n", m.name));
+        let (fn_name, attr) = member_names(&m.name, None);
+        push_attr(&mut decl, attr.as_deref());
+        decl.push_str(&format!(
+            "fn {fn_name}({}) -> zbus::Result<{output}>;\n",
+            inputs.join(", ")
+        ));
+    }
+
+    for s in &iface.signals {
+        let mut inputs = vec!["&self".to_string()];
+        for (n, a) in s.args.iter().enumerate() {
+            let ty = to_rust_type(&a.ty, true, false)?;
+            inputs.push(format!("{}: {ty}", arg_name(a, n)));
+        }
+        decl.push_str(&format!("/// {} signal\n", s.name));
+        let (fn_name, attr) = member_names(&s.name, Some("signal"));
+        push_attr(&mut decl, attr.as_deref());
+        decl.push_str(&format!(
+            "fn {fn_name}({}) -> zbus::Result<()>;\n",
+            inputs.join(", ")
+        ));
+    }
+
+    for p in &iface.properties {
+        let (read, write) = match p.access.as_str() {
+            "read" => (true, false),
+            "write" => (false, true),
+            "readwrite" => (true, true),
+            a => return Err(format!("invalid property access `{a}`")),
+        };
+        let (fn_name, attr) = member_names(&p.name, Some("property"));
+        if read {
+            let output = to_rust_type(&p.ty, false, false)?;
+            decl.push_str(&format!("/// {} property\n", p.name));
+            push_attr(&mut decl, attr.as_deref());
+            decl.push_str(&format!("fn {fn_name}(&self) -> zbus::Result<{output}>;\n"));
+        }
+        if write {
+            let input = to_rust_type(&p.ty, true, true)?;
+            decl.push_str(&format!("/// Set the {} property\n", p.name));
+            push_attr(&mut decl, attr.as_deref());
+            let fn_name = fn_name.trim_end_matches('_');
+            decl.push_str(&format!(
+                "fn set_{fn_name}(&self, value: {input}) -> zbus::Result<()>;\n"
+            ));
+        }
+    }
+    decl.push('}');
+
+    // The types are spelled with `zbus::`, which may be renamed in the calling crate.
+    let zbus = zbus_path().to_string();
+    Ok(decl.replace("zbus::", &format!("{zbus}::")))
+}
+
+// The name of the method for `member` and the `dbus_proxy` attribute it needs, if any.
+fn member_names(member: &str, kind: Option<&str>) -> (String, Option<String>) {
+    let fn_name = to_identifier(&snake_case(member));
+    let renamed = case::pascal_or_camel_case(fn_name.trim_end_matches('_'), true) != member;
+    let attr = match (kind, renamed) {
+        (None, false) => None,
+        (None, true) => Some(format!("name = \"{member}\"")),
+        (Some(kind), false) => Some(kind.to_string()),
+        (Some(kind), true) => Some(format!("{kind}, name = \"{member}\"")),
+    };
+
+    (fn_name, attr)
+}
+
+fn push_attr(decl: &mut String, attr: Option<&str>) {
+    if let Some(attr) = attr {
+        decl.push_str(&format!("#[dbus_proxy({attr})]\n"));
+    }
+}
+
+fn arg_name(arg: &Arg, n: usize) -> String {
+    match &arg.name {
+        Some(name) => to_identifier(&snake_case(name)),
+        None => format!("arg_{}", n + 1),
+    }
+}
+
+// The Rust type for the D-Bus type `signature`, as with `zbus-xmlgen`.
+fn to_rust_type(signature: &str, input: bool, as_ref: bool) -> Result<String, String> {
+    codegen::to_rust_type(signature, input, as_ref)
+        .ok_or_else(|| format!("invalid or unsupported type `{signature}`"))
+}
@@ -4,7 +4,7 @@ use futures_util::{
 };
 use std::future::ready;
 use zbus::{block_on, fdo, object_server::SignalContext, proxy::CacheProperties};
-use zbus_macros::{dbus_interface, dbus_proxy, DBusError};
+use zbus_macros::{dbus_interface, dbus_proxy, interface, proxy, DBusError};
 
 #[test]
 fn test_proxy() {
@@ -43,6 +43,9 @@ fn test_proxy() {
         #[dbus_proxy(property(emits_changed_signal = "false"))]
         fn a_live_property(&self) -> fdo::Result<Vec<String>>;
 
+        #[dbus_proxy(property(raw))]
+        fn a_raw_property(&self) -> fdo::Result<Vec<String>>;
+
         #[dbus_proxy(property)]
         fn set_property(&self, val: u16) -> fdo::Result<()>;
 
@@ -92,6 +95,79 @@ fn test_proxy() {
     });
 }
 
+#[test]
+fn test_proxy_constructors() {
+    #[dbus_proxy(
+        interface = "org.freedesktop.zbus_macros.Defaults",
+        default_service = "org.freedesktop.zbus_macros",
+        default_path = "/org/freedesktop/zbus_macros/default"
+    )]
+    trait Defaults {
+        fn ping(&self) -> zbus::Result<()>;
+    }
+
+    #[dbus_proxy(
+        assume_defaults = false,
+        interface = "org.freedesktop.zbus_macros.NoPath",
+        default_service = "org.freedesktop.zbus_macros"
+    )]
+    trait NoPath {
+        fn ping(&self) -> zbus::Result<()>;
+    }
+
+    block_on(async move {
+        let connection = zbus::Connection::session().await.unwrap();
+
+        let proxy = DefaultsProxy::new(&connection).await.unwrap();
+        assert_eq!(proxy.inner().path(), "/org/freedesktop/zbus_macros/default");
+        let proxy = DefaultsProxy::new_for_path(&connection, "/org/freedesktop/zbus_macros/other")
+            .await
+            .unwrap();
+        assert_eq!(proxy.inner().path(), "/org/freedesktop/zbus_macros/other");
+        assert_eq!(proxy.inner().destination(), "org.freedesktop.zbus_macros");
+        assert!(DefaultsProxy::new_for_path(&connection, "not/a/path")
+            .await
+            .is_err());
+
+        let proxy = NoPathProxy::new_for_path(&connection, "/org/freedesktop/zbus_macros/other")
+            .await
+            .unwrap();
+        assert_eq!(proxy.inner().path(), "/org/freedesktop/zbus_macros/other");
+
+        let connection = zbus::blocking::Connection::from(connection);
+        let proxy =
+            DefaultsProxyBlocking::new_for_path(&connection, "/org/freedesktop/zbus_macros/other")
+                .unwrap();
+        assert_eq!(proxy.inner().path(), "/org/freedesktop/zbus_macros/other");
+    });
+}
+
+#[test]
+fn test_proxy_returning_other_fd_type() {
+    use serde::Deserialize;
+    use zbus::zvariant::Type;
+
+    // Not a `zvariant::Fd`, so it can be returned.
+    #[derive(Debug, Deserialize, Type, PartialEq)]
+    struct Fd(u32);
+
+    #[dbus_proxy(
+        interface = "org.freedesktop.zbus_macros.Files",
+        default_service = "org.freedesktop.zbus_macros",
+        default_path = "/org/freedesktop/zbus_macros/files"
+    )]
+    trait Files {
+        fn open(&self, name: &str) -> zbus::Result<Fd>;
+    }
+
+    block_on(async move {
+        let connection = zbus::Connection::session().await.unwrap();
+        let proxy = FilesProxy::new(&connection).await.unwrap();
+        assert_eq!(proxy.inner().path(), "/org/freedesktop/zbus_macros/files");
+        assert!(proxy.open("file").await.is_err());
+    });
+}
+
 #[test]
 fn test_derive_error() {
     #[derive(Debug, DBusError)]
@@ -108,6 +184,143 @@ fn test_derive_error() {
     }
 }
 
+#[test]
+fn test_derive_dynamic_error() {
+    use zbus::DBusError;
+
+    #[derive(Debug, DBusError)]
+    #[dbus_error(prefix = "org.freedesktop.zbus.Gateway")]
+    enum Gateway {
+        #[dbus_error(zbus_error)]
+        ZBus(zbus::Error),
+        Unavailable,
+        #[dbus_error(dynamic)]
+        Upstream {
+            name: String,
+            message: String,
+        },
+    }
+
+    // Replies can only be created for calls that were actually sent.
+    let service = zbus::blocking::Connection::session().unwrap();
+    let mut calls = zbus::blocking::MessageIterator::from(&service);
+    let client = zbus::blocking::Connection::session().unwrap();
+    let call = zbus::message::Builder::method_call("/org/freedesktop/zbus/Gateway", "Forward")
+        .unwrap()
+        .destination(service.unique_name().unwrap())
+        .unwrap()
+        .build(&())
+        .unwrap();
+    client.send_message(call).unwrap();
+    let call = calls
+        .find(|m| {
+            m.as_ref()
+                .unwrap()
+                .member()
+                .map_or(false, |m| m == "Forward")
+        })
+        .unwrap()
+        .unwrap();
+    let call = call.header().unwrap();
+
+    let upstream = Gateway::Upstream {
+        name: "org.example.Upstream.Error.Busy".into(),
+        message: "try again later".into(),
+    };
+    assert_eq!(upstream.name(), "org.example.Upstream.Error.Busy");
+    assert_eq!(upstream.description(), Some("try again later"));
+    assert_eq!(
+        upstream.to_string(),
+        "org.example.Upstream.Error.Busy: try again later"
+    );
+    let reply = upstream.create_reply(&call).unwrap();
+    assert_eq!(
+        reply.header().unwrap().error_name().unwrap().unwrap(),
+        "org.example.Upstream.Error.Busy"
+    );
+    assert_eq!(reply.body::<&str>().unwrap(), "try again later");
+
+    // Errors with unknown names are forwarded as is, the known ones are still mapped.
+    match Gateway::from(zbus::Error::from(reply)) {
+        Gateway::Upstream { name, message } => {
+            assert_eq!(name, "org.example.Upstream.Error.Busy");
+            assert_eq!(message, "try again later");
+        }
+        e => panic!("unexpected error: {e:?}"),
+    }
+    let reply = Gateway::Unavailable.create_reply(&call).unwrap();
+    assert!(matches!(
+        Gateway::from(zbus::Error::from(reply)),
+        Gateway::Unavailable
+    ));
+
+    let invalid = Gateway::Upstream {
+        name: "not a valid name".into(),
+        message: "oops".into(),
+    };
+    assert_eq!(invalid.name(), "org.freedesktop.DBus.Error.Failed");
+    assert!(matches!(
+        invalid.create_reply(&call),
+        Err(zbus::Error::Names(_))
+    ));
+}
+
+#[test]
+fn test_zbus_attribute_name() {
+    use zbus::object_server::Interface;
+
+    // The new attribute names can be mixed with the old ones.
+    #[proxy(
+        interface = "org.freedesktop.zbus_macros.Mixed",
+        default_service = "org.freedesktop.zbus_macros",
+        default_path = "/org/freedesktop/zbus_macros/mixed"
+    )]
+    trait Mixed {
+        #[zbus(name = "CheckRENAMING")]
+        fn check_renaming(&self) -> zbus::Result<Vec<u8>>;
+
+        #[zbus(property(emits_changed_signal = "const"))]
+        fn a_property(&self) -> fdo::Result<u32>;
+
+        #[dbus_proxy(signal)]
+        fn a_signal(&self, arg: u8) -> fdo::Result<()>;
+    }
+
+    #[derive(Debug, DBusError)]
+    #[zbus(prefix = "org.freedesktop.zbus_macros.Mixed")]
+    enum MixedError {
+        #[zbus(zbus_error)]
+        ZBus(zbus::Error),
+        #[dbus_error(name = "Oops")]
+        Failed(String),
+    }
+
+    struct MixedIface;
+
+    #[interface(name = "org.freedesktop.zbus_macros.Mixed")]
+    impl MixedIface {
+        #[zbus(name = "CheckRENAMING")]
+        fn check_renaming(&self) -> Result<Vec<u8>, MixedError> {
+            Err(MixedError::Failed("oops".into()))
+        }
+
+        #[dbus_interface(property)]
+        fn a_property(&self) -> u32 {
+            42
+        }
+
+        #[zbus(signal)]
+        async fn a_signal(ctxt: &SignalContext<'_>, arg: u8) -> zbus::Result<()>;
+    }
+
+    assert_eq!(MixedIface::name(), "org.freedesktop.zbus_macros.Mixed");
+    let mut xml = String::new();
+    MixedIface.introspect_to_writer(&mut xml, 0);
+    assert!(xml.contains(r#"<method name="CheckRENAMING">"#));
+    assert!(xml.contains(r#"<property name="AProperty" type="u" access="read"/>"#));
+    assert!(xml.contains(r#"<signal name="ASignal">"#));
+}
+
 #[test]
 fn test_interface() {
     use serde::{Deserialize, Serialize};
@@ -153,6 +366,25 @@ fn test_interface() {
             unimplemented!()
         }
 
+        fn input_only(&mut self, id: u32, name: &str) {
+            self.something = format!("{name}-{id}");
+        }
+
+        async fn input_only_result(&mut self, name: String) -> zbus::fdo::Result<()> {
+            self.something = name;
+            Ok(())
+        }
+
+        #[allow(clippy::unused_unit)]
+        fn input_only_unit(&mut self, #[zbus(arg_name = "Name")] name: String) -> () {
+            self.something = name;
+        }
+
+        #[dbus_interface(out_args("name"))]
+        fn output_only(&self) -> zbus::fdo::Result<String> {
+            Ok(self.something.clone())
+        }
+
         #[dbus_interface(property)]
         fn my_custom_property(&self) -> MyCustomPropertyType {
             unimplemented!()
@@ -185,6 +417,14 @@ fn test_interface() {
         /// Emit a signal.
         #[dbus_interface(signal)]
         async fn signal(ctxt: &SignalContext<'_>, arg: u8, other: &str) -> zbus::Result<()>;
+
+        /// Emit a signal with custom argument names.
+        #[dbus_interface(signal)]
+        async fn signal_arg_names(
+            ctxt: &SignalContext<'_>,
+            #[zbus(arg_name = "Value")] value: u8,
+            #[zbus(unnamed)] other: &str,
+        ) -> zbus::Result<()>;
     }
 
     const EXPECTED_XML: &str = r#"<interface name="org.freedesktop.zbus.Test">
@@ -204,6 +444,19 @@ fn test_interface() {
   <method name="PairOutput">
     <arg type="(us)" direction="out"/>
   </method>
+  <method name="InputOnly">
+    <arg name="id" type="u" direction="in"/>
+    <arg name="name" type="s" direction="in"/>
+  </method>
+  <method name="InputOnlyResult">
+    <arg name="name" type="s" direction="in"/>
+  </method>
+  <method name="InputOnlyUnit">
+    <arg name="Name" type="s" direction="in"/>
+  </method>
+  <method name="OutputOnly">
+    <arg name="name" type="s" direction="out"/>
+  </method>
   <method name="CheckVEC">
     <arg type="ay" direction="out"/>
   </method>
@@ -214,6 +467,13 @@ fn test_interface() {
     <arg name="arg" type="y"/>
     <arg name="other" type="s"/>
   </signal>
+  <!--
+   Emit a signal with custom argument names.
+   -->
+  <signal name="SignalArgNames">
+    <arg name="Value" type="y"/>
+    <arg type="s"/>
+  </signal>
   <property name="MyCustomProperty" type="u" access="readwrite"/>
   <!--
    Testing my_prop documentation is reflected in XML.
@@ -250,14 +510,132 @@ fn test_interface() {
             let _ = t.call(&s, &c, &m, "StrU32".try_into().unwrap());
             let ctxt = SignalContext::new(&c, "/does/not/matter").unwrap();
             block_on(Test::<u32>::signal(&ctxt, 23, "ergo sum")).unwrap();
+            block_on(Test::<u32>::signal_arg_names(&ctxt, 23, "ergo sum")).unwrap();
         });
     }
 }
 
+#[test]
+fn test_generic_interface() {
+    use zbus::{
+        object_server::Interface,
+        zvariant::{OwnedValue, Type, Value},
+    };
+
+    struct Generic<T, U> {
+        value: T,
+        values: Vec<U>,
+    }
+
+    #[dbus_interface(name = "org.freedesktop.zbus.Generic")]
+    impl<T, U> Generic<T, U>
+    where
+        T: Type + Clone + serde::Serialize + TryFrom<Value<'static>> + Send + Sync + 'static,
+        <T as TryFrom<Value<'static>>>::Error: Into<zbus::zvariant::Error>,
+        Value<'static>: From<T> + From<Vec<U>>,
+        U: Type + Clone + serde::Serialize + Send + Sync + 'static,
+    {
+        fn values_at(&self, index: u32) -> zbus::fdo::Result<U> {
+            self.values
+                .get(index as usize)
+                .cloned()
+                .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("No value at {index}")))
+        }
+
+        #[dbus_interface(property)]
+        fn value(&self) -> T {
+            self.value.clone()
+        }
+
+        #[dbus_interface(property)]
+        fn set_value(&mut self, value: T) {
+            self.value = value;
+        }
+
+        #[dbus_interface(property)]
+        fn values(&self) -> Vec<U> {
+            self.values.clone()
+        }
+
+        #[dbus_interface(signal)]
+        async fn value_reset(ctxt: &SignalContext<'_>, old: T) -> zbus::Result<()>;
+    }
+
+    fn introspect<T, U>(iface: &Generic<T, U>) -> String
+    where
+        Generic<T, U>: Interface,
+    {
+        let mut xml = String::new();
+        iface.introspect_to_writer(&mut xml, 0);
+
+        xml
+    }
+
+    // The types in the introspection are those of each monomorphization.
+    let with_u32 = Generic {
+        value: 42u32,
+        values: vec![String::from("forty-two")],
+    };
+    let with_str = Generic {
+        value: String::from("forty-two"),
+        values: vec![(42u8, 42i64)],
+    };
+    assert_eq!(
+        introspect(&with_u32),
+        r#"<interface name="org.freedesktop.zbus.Generic">
+  <method name="ValuesAt">
+    <arg name="index" type="u" direction="in"/>
+    <arg type="s" direction="out"/>
+  </method>
+  <signal name="ValueReset">
+    <arg name="old" type="u"/>
+  </signal>
+  <property name="Value" type="u" access="readwrite"/>
+  <property name="Values" type="as" access="read"/>
+</interface>
+"#
+    );
+    assert_eq!(
+        introspect(&with_str),
+        r#"<interface name="org.freedesktop.zbus.Generic">
+  <method name="ValuesAt">
+    <arg name="index" type="u" direction="in"/>
+    <arg type="(yx)" direction="out"/>
+  </method>
+  <signal name="ValueReset">
+    <arg name="old" type="s"/>
+  </signal>
+  <property name="Value" type="s" access="readwrite"/>
+  <property name="Values" type="a(yx)" access="read"/>
+</interface>
+"#
+    );
+    assert_eq!(
+        Generic::<u32, String>::name(),
+        "org.freedesktop.zbus.Generic"
+    );
+    assert_eq!(
+        Generic::<String, (u8, i64)>::name(),
+        "org.freedesktop.zbus.Generic"
+    );
+
+    // The property values are those of the concrete types too.
+    block_on(async {
+        assert_eq!(
+            with_u32.get("Value").await.unwrap().unwrap(),
+            OwnedValue::from(42u32)
+        );
+        assert_eq!(
+            with_str.get("Value").await.unwrap().unwrap(),
+            OwnedValue::from(Value::from("forty-two"))
+        );
+    });
+}
+
 mod signal_from_message {
     use super::*;
     use std::sync::Arc;
-    use zbus::message::Builder;
+    use zbus::{message::Builder, names::UniqueName};
 
     #[dbus_proxy(
         interface = "org.freedesktop.zbus_macros.Test",
@@ -270,6 +648,9 @@ mod signal_from_message {
 
         #[dbus_proxy(signal)]
         fn signal_string(&self, arg: String) -> fdo::Result<()>;
+
+        #[dbus_proxy(signal)]
+        fn signal_pair(&self, id: u32, name: &str) -> fdo::Result<()>;
     }
 
     #[test]
@@ -318,6 +699,37 @@ mod signal_from_message {
         );
     }
 
+    #[test]
+    fn signal_sender() {
+        let sender = UniqueName::from_static_str(":1.42").unwrap();
+        let message = |sender: Option<&str>| {
+            let mut builder = Builder::signal(
+                "/org/freedesktop/zbus_macros/test",
+                "org.freedesktop.zbus_macros.Test",
+                "SignalU8",
+            )
+            .expect("Failed to create signal message builder");
+            if let Some(sender) = sender {
+                builder = builder.sender(sender).expect("Invalid sender");
+            }
+            Arc::new(
+                builder
+                    .build(&(1u8,))
+                    .expect("Failed to build signal message"),
+            )
+        };
+
+        let signal = SignalU8::from_message(message(Some(":1.42"))).unwrap();
+        assert_eq!(signal.sender(), Some(sender.clone()));
+        assert!(SignalU8::from_message_with_sender(message(Some(":1.42")), &sender).is_some());
+        assert!(SignalU8::from_message_with_sender(message(Some(":1.43")), &sender).is_none());
+
+        // A message without a sender never matches.
+        let signal = SignalU8::from_message(message(None)).unwrap();
+        assert_eq!(signal.sender(), None);
+        assert!(SignalU8::from_message_with_sender(message(None), &sender).is_none());
+    }
+
     #[test]
     fn wrong_data() {
         let message = Arc::new(
@@ -336,4 +748,336 @@ mod signal_from_message {
             .args()
             .expect_err("Message does not have correct data");
     }
+
+    #[test]
+    fn args_tuple_conversions() {
+        let args = SignalPairArgs::from((7u32, "seven"));
+        assert_eq!(*args.id(), 7);
+        assert_eq!(*args.name(), "seven");
+        let (id, name) = args.into();
+        assert_eq!((id, name), (7u32, "seven"));
+
+        let args: SignalU8Args<'_> = (1u8,).into();
+        let (arg,): (u8,) = args.into();
+        assert_eq!(arg, 1);
+
+        // The arguments of a signal message can be compared with the expected ones at once.
+        let message = Arc::new(
+            Builder::signal(
+                "/org/freedesktop/zbus_macros/test",
+                "org.freedesktop.zbus_macros.Test",
+                "SignalPair",
+            )
+            .expect("Failed to create signal message builder")
+            .build(&(7u32, "seven"))
+            .expect("Failed to build signal message"),
+        );
+        let signal = SignalPair::from_message(message).expect("Message is a SignalPair");
+        let args: (u32, &str) = signal.args().unwrap().into();
+        assert_eq!(args, (7, "seven"));
+    }
+
+    mod derives {
+        use zbus_macros::dbus_proxy;
+
+        #[dbus_proxy(
+            interface = "org.freedesktop.zbus_macros.Derives",
+            assume_defaults = true,
+            derive(Clone, Debug, PartialEq)
+        )]
+        trait Derives {
+            #[dbus_proxy(signal)]
+            fn changed(&self, id: u32, names: Vec<String>) -> zbus::Result<()>;
+        }
+    }
+
+    #[test]
+    fn args_derives() {
+        let args = derives::ChangedArgs::from((7u32, vec![String::from("seven")]));
+        let clone = args.clone();
+        assert_eq!(clone, args);
+        assert_ne!(derives::ChangedArgs::from((7u32, vec![])), args);
+        assert_eq!(
+            format!("{args:?}"),
+            r#"Changed { id: 7, names: ["seven"] }"#
+        );
+    }
+}
+
+mod proxy_matches_interface {
+    use zbus::{assert_proxy_matches_interface, object_server::SignalContext};
+    use zbus_macros::{dbus_interface, dbus_proxy};
+
+    struct Counter {
+        count: u32,
+    }
+
+    #[dbus_interface(name = "org.freedesktop.zbus_macros.Counter")]
+    impl Counter {
+        fn add(&mut self, values: Vec<u32>) -> (u32, bool) {
+            self.count += values.iter().sum::<u32>();
+
+            (self.count, self.count > 100)
+        }
+
+        fn reset(&mut self) {
+            self.count = 0;
+        }
+
+        #[dbus_interface(property)]
+        fn count(&self) -> u32 {
+            self.count
+        }
+
+        #[dbus_interface(property)]
+        fn set_count(&mut self, count: u32) {
+            self.count = count;
+        }
+
+        #[dbus_interface(property)]
+        fn label(&self) -> String {
+            String::new()
+        }
+
+        #[dbus_interface(signal)]
+        async fn overflowed(ctxt: &SignalContext<'_>, count: u32, by: &str) -> zbus::Result<()>;
+    }
+
+    #[dbus_proxy(
+        interface = "org.freedesktop.zbus_macros.Counter",
+        assume_defaults = true
+    )]
+    trait Counter {
+        #[dbus_proxy(iter_args)]
+        fn add(&self, values: &[u32]) -> zbus::Result<(u32, bool)>;
+
+        // Generic arguments can't be checked so this is left out.
+        #[dbus_proxy(name = "Add")]
+        fn add_generic<T>(&self, values: &T) -> zbus::Result<(u32, bool)>;
+
+        #[dbus_proxy(property)]
+        fn count(&self) -> zbus::Result<u32>;
+
+        #[dbus_proxy(property)]
+        fn set_count(&self, count: u32) -> zbus::Result<()>;
+
+        #[dbus_proxy(signal)]
+        fn overflowed(&self, count: u32, by: &str) -> zbus::Result<()>;
+    }
+
+    assert_proxy_matches_interface!(CounterProxy, Counter { count: 0 });
+    assert_proxy_matches_interface!(CounterProxyBlocking, Counter { count: 0 });
+
+    mod drifted {
+        use zbus_macros::dbus_proxy;
+
+        #[dbus_proxy(
+            interface = "org.freedesktop.zbus_macros.Counter",
+            assume_defaults = true
+        )]
+        trait Drifted {
+            fn add(&self, values: &[u32]) -> zbus::Result<u32>;
+
+            fn remove(&self, value: u32) -> zbus::Result<()>;
+
+            #[dbus_proxy(property)]
+            fn set_label(&self, label: &str) -> zbus::Result<()>;
+
+            #[dbus_proxy(signal)]
+            fn overflowed(&self, count: u64) -> zbus::Result<()>;
+        }
+    }
+
+    #[test]
+    #[should_panic = "the proxy doesn't match the interface:
+  method `Add` takes `au` and returns `ub`, not `au` and `u`
+  method `Remove` is missing
+  signal `Overflowed` has arguments `us`, not `t`
+  property `Label` isn't writable"]
+    fn drifted() {
+        let mut expected = String::new();
+        drifted::DriftedProxy::__zbus_introspect_expected(&mut expected, 0);
+        let mut actual = String::new();
+        zbus::object_server::Interface::introspect_to_writer(&Counter { count: 0 }, &mut actual, 0);
+
+        zbus::xml::assert_proxy_matches_interface(&expected, &actual);
+    }
+}
+
+#[test]
+fn test_trait_interface() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use zbus::{object_server::Interface, zvariant::OwnedValue};
+
+    #[dbus_interface(name = "org.freedesktop.zbus.Counter")]
+    #[zbus::export::async_trait::async_trait]
+    trait Counter {
+        /// Add `by` to the counter.
+        async fn increment(&self, by: u32) -> u32;
+
+        #[dbus_interface(property)]
+        fn count(&self) -> u32;
+
+        #[dbus_interface(property)]
+        fn set_count(&mut self, count: u32);
+
+        #[dbus_interface(signal)]
+        async fn overflowed(
+            ctxt: &SignalContext<'_>,
+            #[zbus(arg_name = "at")] count: u32,
+        ) -> zbus::Result<()>;
+    }
+
+    #[derive(Default)]
+    struct Real(AtomicU32);
+
+    #[zbus::export::async_trait::async_trait]
+    impl Counter for Real {
+        async fn increment(&self, by: u32) -> u32 {
+            self.0.fetch_add(by, Ordering::SeqCst) + by
+        }
+
+        fn count(&self) -> u32 {
+            self.0.load(Ordering::SeqCst)
+        }
+
+        fn set_count(&mut self, count: u32) {
+            *self.0.get_mut() = count;
+        }
+    }
+
+    // A mock that always reports the same count.
+    struct Mock;
+
+    #[zbus::export::async_trait::async_trait]
+    impl Counter for Mock {
+        async fn increment(&self, _by: u32) -> u32 {
+            7
+        }
+
+        fn count(&self) -> u32 {
+            7
+        }
+
+        fn set_count(&mut self, _count: u32) {}
+    }
+
+    fn introspect<I: Interface>(iface: &I) -> String {
+        let mut xml = String::new();
+        iface.introspect_to_writer(&mut xml, 0);
+
+        xml
+    }
+
+    let real = CounterInterface(Real::default());
+    let mock = CounterInterface(Mock);
+    let expected = r#"<interface name="org.freedesktop.zbus.Counter">
+  <!--
+   Add `by` to the counter.
+   -->
+  <method name="Increment">
+    <arg name="by" type="u" direction="in"/>
+    <arg type="u" direction="out"/>
+  </method>
+  <signal name="Overflowed">
+    <arg name="at" type="u"/>
+  </signal>
+  <property name="Count" type="u" access="readwrite"/>
+</interface>
+"#;
+    assert_eq!(introspect(&real), expected);
+    assert_eq!(introspect(&mock), expected);
+    assert_eq!(
+        CounterInterface::<Mock>::name(),
+        "org.freedesktop.zbus.Counter"
+    );
+
+    block_on(async {
+        assert_eq!(real.0.increment(3).await, 3);
+        assert_eq!(
+            real.get("Count").await.unwrap().unwrap(),
+            OwnedValue::from(3u32)
+        );
+        assert_eq!(
+            mock.get("Count").await.unwrap().unwrap(),
+            OwnedValue::from(7u32)
+        );
+    });
+}
+
+mod proxy_from_xml {
+    use std::collections::HashMap;
+    use zbus::{
+        assert_proxy_matches_interface,
+        object_server::SignalContext,
+        zvariant::{OwnedObjectPath, Value},
+    };
+    use zbus_macros::dbus_interface;
+
+    zbus::proxy_from_xml!("tests/data/proxy_from_xml.xml");
+
+    struct Inventory;
+
+    #[dbus_interface(name = "org.freedesktop.zbus_macros.Inventory")]
+    impl Inventory {
+        fn add(&self, _name: &str, count: u32) -> (OwnedObjectPath, u32) {
+            (OwnedObjectPath::try_from("/item").unwrap(), count)
+        }
+
+        fn list(&self) -> Vec<(String, u32)> {
+            vec![]
+        }
+
+        #[dbus_interface(property)]
+        fn count(&self) -> u32 {
+            0
+        }
+
+        #[dbus_interface(property)]
+        fn label(&self) -> String {
+            String::new()
+        }
+
+        #[dbus_interface(property)]
+        fn set_label(&self, _label: String) {}
+
+        #[dbus_interface(signal)]
+        async fn item_added(
+            ctxt: &SignalContext<'_>,
+            name: &str,
+            properties: HashMap<&str, Value<'_>>,
+        ) -> zbus::Result<()>;
+    }
+
+    struct Item;
+
+    #[dbus_interface(name = "org.freedesktop.zbus_macros.Inventory.Item")]
+    impl Item {
+        #[dbus_interface(name = "GetID")]
+        fn get_id(&self) -> u64 {
+            42
+        }
+
+        #[dbus_interface(property, name = "Type")]
+        fn kind(&self) -> &str {
+            "item"
+        }
+    }
+
+    // The interfaces of the root and child nodes are generated, not the standard ones.
+    assert_proxy_matches_interface!(InventoryProxy, Inventory);
+    assert_proxy_matches_interface!(ItemProxyBlocking, Item);
+
+    #[test]
+    fn generated_api() {
+        use zbus::zvariant::Type;
+
+        let _ = ItemAddedArgs::name;
+        let _ = ItemAddedArgs::properties;
+        assert_eq!(<(OwnedObjectPath, u32)>::signature(), "(ou)");
+        let _ = ItemProxy::get_id;
+        let _ = ItemProxy::type_;
+        let _ = InventoryProxy::set_label;
+        let _ = InventoryProxyBlocking::list;
+    }
 }
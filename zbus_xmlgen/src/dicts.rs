@@ -0,0 +1,227 @@
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use zbus::xml::{Arg, Node};
+use zvariant::{CompleteType, Signature};
+use zvariant_utils::codegen::{snake_case, to_identifier};
+
+use crate::{enums::check_identifier, to_rust_type};
+
+/// User-supplied structures to represent `a{sv}` arguments with known keys, keyed by
+/// `Interface.Member.argument`.
+///
+/// The mapping is typically read from a TOML file, e.g:
+///
+/// ```toml
+/// ["org.freedesktop.Example1.Connect.options"]
+/// name = "ConnectOptions"
+/// fields = { Timeout = "u", Interface = "s" }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Dicts(HashMap<String, Dict>);
+
+/// A structure representing the known entries of an `a{sv}` argument.
+#[derive(Debug, Clone)]
+pub struct Dict {
+    name: String,
+    fields: Vec<DictField>,
+}
+
+#[derive(Debug, Clone)]
+struct DictField {
+    key: String,
+    name: String,
+    ty: CompleteType<'static>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DictDef {
+    name: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl Dicts {
+    /// Parse the structures from the TOML source.
+    pub fn from_toml(source: &str) -> Result<Self, Box<dyn Error>> {
+        let defs: HashMap<String, DictDef> = toml::from_str(source)?;
+        let mut dicts = HashMap::new();
+        for (key, def) in defs {
+            // The interface name alone has at least two elements.
+            if key.split('.').count() < 4 {
+                return Err(
+                    format!("`{key}` is not of the form `Interface.Member.argument`").into(),
+                );
+            }
+            check_identifier(&def.name)?;
+            let mut fields = def
+                .fields
+                .into_iter()
+                .map(|(field_key, ty)| {
+                    // Keywords get a `_` suffix, the key being kept by the `rename` attribute.
+                    let name = to_identifier(&snake_case(&field_key));
+                    check_identifier(&name)?;
+                    let ty = Signature::try_from(ty)
+                        .map_err(Box::<dyn Error>::from)
+                        .and_then(|s| Ok(CompleteType::try_from(s)?))
+                        .map_err(|e| format!("Invalid type of `{field_key}` in `{key}`: {e}"))?;
+
+                    Ok(DictField {
+                        key: field_key,
+                        name,
+                        ty,
+                    })
+                })
+                .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+            if fields.is_empty() {
+                return Err(format!("No fields given for `{key}`").into());
+            }
+            fields.sort_by(|a, b| a.name.cmp(&b.name));
+            for pair in fields.windows(2) {
+                if pair[0].name == pair[1].name {
+                    return Err(format!(
+                        "`{}` and `{}` keys of `{key}` map to the same field",
+                        pair[0].key, pair[1].key,
+                    )
+                    .into());
+                }
+            }
+            if let Some(f) = fields.iter().find(|f| f.name == "extra") {
+                return Err(format!(
+                    "`{}` key of `{key}` clashes with the field for the unknown entries",
+                    f.key
+                )
+                .into());
+            }
+            dicts.insert(
+                key,
+                Dict {
+                    name: def.name,
+                    fields,
+                },
+            );
+        }
+        // A structure can be used for more than one argument, but it's only generated once.
+        for (key, d) in &dicts {
+            let conflict = dicts
+                .iter()
+                .find(|(_, other)| other.name == d.name && !other.same_fields(d));
+            if let Some((other_key, _)) = conflict {
+                return Err(format!(
+                    "Structure `{}` is declared with different fields for `{key}` and `{other_key}`",
+                    d.name,
+                )
+                .into());
+            }
+        }
+
+        Ok(Self(dicts))
+    }
+
+    /// Get the structure for the given argument of the given member of the given interface.
+    pub fn get(&self, interface: &str, member: &str, arg: &str) -> Option<&Dict> {
+        self.0.get(&format!("{interface}.{member}.{arg}"))
+    }
+
+    /// Ensure that the structures targeting an interface of `node` refer to `a{sv}` arguments.
+    pub fn validate(&self, node: &Node<'_>) -> Result<(), Box<dyn Error>> {
+        for (key, d) in &self.0 {
+            let (iface, rest) = match node.interfaces().iter().find_map(|i| {
+                key.strip_prefix(i.name().as_str())
+                    .and_then(|rest| rest.strip_prefix('.'))
+                    .map(|rest| (i, rest))
+            }) {
+                Some(found) => found,
+                None => continue,
+            };
+            let (member, arg_name) = rest
+                .split_once('.')
+                .ok_or_else(|| format!("`{key}` is not of the form `Interface.Member.argument`"))?;
+            let args: &[Arg<'_>] = if let Some(m) =
+                iface.methods().iter().find(|m| m.name() == member)
+            {
+                m.args()
+            } else if let Some(s) = iface.signals().iter().find(|s| s.name() == member) {
+                s.args()
+            } else {
+                return Err(format!("No method or signal `{member}` in `{}`", iface.name()).into());
+            };
+            let arg = args
+                .iter()
+                .find(|a| a.name() == Some(arg_name))
+                .ok_or_else(|| format!("No argument `{arg_name}` in `{member}`"))?;
+            if arg.ty().signature() != "a{sv}" {
+                return Err(format!(
+                    "Argument `{key}` of type `{}` can't be represented by structure `{}`",
+                    arg.ty().signature(),
+                    d.name,
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Dict {
+    /// The name of the structure.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn same_fields(&self, other: &Dict) -> bool {
+        self.fields.len() == other.fields.len()
+            && self
+                .fields
+                .iter()
+                .zip(&other.fields)
+                .all(|(a, b)| a.key == b.key && a.ty.signature() == b.ty.signature())
+    }
+}
+
+/// Generates the code for a [`Dict`] representing an `a{sv}` argument.
+pub struct GenDict<'d> {
+    pub dict: &'d Dict,
+    pub member: &'d str,
+    pub arg: &'d str,
+}
+
+impl<'d> Display for GenDict<'d> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "/// Entries of the `{}` argument of `{}`.",
+            self.arg, self.member
+        )?;
+        writeln!(
+            f,
+            "#[derive(Debug, Default, PartialEq, zbus::zvariant::SerializeDict, \
+             zbus::zvariant::DeserializeDict, zbus::zvariant::Type)]"
+        )?;
+        writeln!(f, "#[zvariant(signature = \"a{{sv}}\")]")?;
+        writeln!(f, "pub struct {} {{", self.dict.name)?;
+        for field in &self.dict.fields {
+            if field.name != field.key {
+                writeln!(f, "    #[zvariant(rename = \"{}\")]", field.key)?;
+            }
+            writeln!(
+                f,
+                "    pub {}: Option<{}>,",
+                field.name,
+                to_rust_type(&field.ty, false, false)
+            )?;
+        }
+        writeln!(f, "    /// The entries not listed above.")?;
+        writeln!(f, "    #[zvariant(flatten)]")?;
+        writeln!(
+            f,
+            "    pub extra: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,"
+        )?;
+        writeln!(f, "}}")
+    }
+}
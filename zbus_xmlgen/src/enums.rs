@@ -0,0 +1,194 @@
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use zbus::xml::Node;
+
+/// User-supplied enums to represent integer properties, keyed by `Interface.Property`.
+///
+/// The mapping is typically read from a TOML file, e.g:
+///
+/// ```toml
+/// ["org.freedesktop.Example1.State"]
+/// name = "State"
+/// values = { 0 = "Idle", 1 = "Busy" }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Enums(HashMap<String, Enum>);
+
+/// An enum representing the values of an integer property.
+#[derive(Debug, Clone)]
+pub struct Enum {
+    name: String,
+    values: Vec<(i64, String)>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EnumDef {
+    name: String,
+    values: BTreeMap<String, String>,
+}
+
+impl Enums {
+    /// Parse the enums from the TOML source.
+    pub fn from_toml(source: &str) -> Result<Self, Box<dyn Error>> {
+        let defs: HashMap<String, EnumDef> = toml::from_str(source)?;
+        let mut enums = HashMap::new();
+        for (key, def) in defs {
+            if !key.contains('.') {
+                return Err(format!("`{key}` is not of the form `Interface.Property`").into());
+            }
+            check_identifier(&def.name)?;
+            let mut values = def
+                .values
+                .into_iter()
+                .map(|(value, variant)| {
+                    let value = value
+                        .parse()
+                        .map_err(|_| format!("`{value}` value of `{key}` is not an integer"))?;
+                    check_identifier(&variant)?;
+
+                    Ok((value, variant))
+                })
+                .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+            if values.is_empty() {
+                return Err(format!("No values given for `{key}`").into());
+            }
+            values.sort();
+            enums.insert(
+                key,
+                Enum {
+                    name: def.name,
+                    values,
+                },
+            );
+        }
+
+        Ok(Self(enums))
+    }
+
+    /// Get the enum for the given property of the given interface.
+    pub fn get(&self, interface: &str, property: &str) -> Option<&Enum> {
+        self.0.get(&format!("{interface}.{property}"))
+    }
+
+    /// Ensure that the enums targeting an interface of `node` refer to integer properties.
+    pub fn validate(&self, node: &Node<'_>) -> Result<(), Box<dyn Error>> {
+        for (key, e) in &self.0 {
+            let (iface_name, prop_name) = key.rsplit_once('.').expect("validated on parse");
+            let iface = match node.interfaces().iter().find(|i| i.name() == iface_name) {
+                Some(iface) => iface,
+                None => continue,
+            };
+            let prop = iface
+                .properties()
+                .iter()
+                .find(|p| p.name() == prop_name)
+                .ok_or_else(|| format!("No property `{prop_name}` in `{iface_name}`"))?;
+            let repr = integer_type(prop.ty().signature().as_str()).ok_or_else(|| {
+                format!(
+                    "Property `{key}` of type `{}` can't be represented by enum `{}`",
+                    prop.ty().signature(),
+                    e.name,
+                )
+            })?;
+            if let Some((value, variant)) = e.values.iter().find(|(v, _)| !fits(*v, repr)) {
+                return Err(format!(
+                    "Value `{value}` of `{}::{variant}` doesn't fit in the `{repr}` type of `{key}`",
+                    e.name,
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Enum {
+    /// The name of the enum.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Generates the code for an [`Enum`] representing a property of the given D-Bus type.
+pub struct GenEnum<'e> {
+    pub enum_: &'e Enum,
+    pub property: &'e str,
+    pub repr: &'e str,
+}
+
+impl<'e> Display for GenEnum<'e> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Name the offending value, rather than leave it to the errors of the literal overflow.
+        for (value, variant) in &self.enum_.values {
+            if !fits(*value, self.repr) {
+                writeln!(
+                    f,
+                    "compile_error!(\"value `{value}` of `{}::{variant}` doesn't fit in `{}`\");",
+                    self.enum_.name, self.repr,
+                )?;
+            }
+        }
+        writeln!(f, "/// Values of the `{}` property.", self.property)?;
+        writeln!(
+            f,
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, zbus::zvariant::Type, \
+             zbus::zvariant::Value, zbus::zvariant::OwnedValue)]"
+        )?;
+        writeln!(f, "#[repr({})]", self.repr)?;
+        writeln!(f, "pub enum {} {{", self.enum_.name)?;
+        for (value, variant) in &self.enum_.values {
+            writeln!(f, "    {variant} = {value},")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// The Rust integer type corresponding to the D-Bus type `signature`, if it's an integer type.
+pub(crate) fn integer_type(signature: &str) -> Option<&'static str> {
+    let ty = match signature {
+        "y" => "u8",
+        "n" => "i16",
+        "q" => "u16",
+        "i" => "i32",
+        "u" => "u32",
+        "x" => "i64",
+        "t" => "u64",
+        _ => return None,
+    };
+
+    Some(ty)
+}
+
+pub(crate) fn check_identifier(id: &str) -> Result<(), Box<dyn Error>> {
+    let mut chars = id.chars();
+    let valid = chars
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or(false)
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(format!("`{id}` is not a valid identifier").into());
+    }
+
+    Ok(())
+}
+
+/// Whether `value` is in the range of the integer type `repr`.
+fn fits(value: i64, repr: &str) -> bool {
+    match repr {
+        "u8" => u8::try_from(value).is_ok(),
+        "i16" => i16::try_from(value).is_ok(),
+        "u16" => u16::try_from(value).is_ok(),
+        "i32" => i32::try_from(value).is_ok(),
+        "u32" => u32::try_from(value).is_ok(),
+        "u64" => value >= 0,
+        _ => true,
+    }
+}
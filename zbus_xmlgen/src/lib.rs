@@ -1,19 +1,33 @@
-use snakecase::ascii::to_snakecase;
 use std::fmt::{Display, Formatter};
 
+mod dicts;
+pub use dicts::{Dict, Dicts, GenDict};
+mod enums;
+pub use enums::{Enum, Enums, GenEnum};
+mod schema;
+pub use schema::Schema;
+
 use zbus::{
-    names::BusName,
-    xml::{Arg, ArgDirection, Interface},
-};
-use zvariant::{
-    Basic, CompleteType, ObjectPath, Signature, ARRAY_SIGNATURE_CHAR, DICT_ENTRY_SIG_END_CHAR,
-    DICT_ENTRY_SIG_START_CHAR, STRUCT_SIG_END_CHAR, STRUCT_SIG_START_CHAR, VARIANT_SIGNATURE_CHAR,
+    names::{BusName, InterfaceName},
+    xml::{Arg, ArgDirection, Interface, Property},
 };
+use zvariant::{CompleteType, ObjectPath};
+use zvariant_utils::codegen::{self, snake_case, to_identifier};
 
 pub struct GenTrait<'i> {
     pub interface: &'i Interface<'i>,
     pub service: Option<&'i BusName<'i>>,
     pub path: Option<&'i ObjectPath<'i>>,
+    /// Enums to represent integer properties with.
+    pub enums: Option<&'i Enums>,
+    /// Structures to represent `a{sv}` arguments with.
+    pub dicts: Option<&'i Dicts>,
+    /// Interfaces of the objects found through recursive introspection, for which proxies are
+    /// generated as well.
+    ///
+    /// Object path arguments of methods are documented with the proxy to build from them, when
+    /// their name refers to one of these interfaces.
+    pub object_interfaces: Option<&'i [InterfaceName<'i>]>,
 }
 
 impl<'i> Display for GenTrait<'i> {
@@ -22,6 +36,52 @@ impl<'i> Display for GenTrait<'i> {
         let idx = iface.name().rfind('.').unwrap() + 1;
         let name = &iface.name()[idx..];
 
+        let mut props = iface.properties().to_vec();
+        props.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
+        let prop_enum = |p: &Property<'_>| {
+            let repr = enums::integer_type(p.ty().signature().as_str())?;
+            self.enums?
+                .get(iface.name().as_str(), p.name().as_str())
+                .map(|e| (e, repr))
+        };
+        for p in &props {
+            if let Some((enum_, repr)) = prop_enum(p) {
+                let name = p.name();
+                let gen = GenEnum {
+                    enum_,
+                    property: name.as_str(),
+                    repr,
+                };
+                writeln!(f, "{gen}")?;
+            }
+        }
+
+        let arg_dict =
+            |member: &str, a: &Arg<'_>| self.dicts?.get(iface.name().as_str(), member, a.name()?);
+        let members = iface
+            .methods()
+            .iter()
+            .map(|m| (m.name(), m.args()))
+            .chain(iface.signals().iter().map(|s| (s.name(), s.args())));
+        let mut dicts: Vec<&str> = vec![];
+        for (member, args) in members {
+            for a in args {
+                if let Some(dict) = arg_dict(member.as_str(), a) {
+                    // The same structure can be used for more than one argument.
+                    if dicts.contains(&dict.name()) {
+                        continue;
+                    }
+                    dicts.push(dict.name());
+                    let gen = GenDict {
+                        dict,
+                        member: member.as_str(),
+                        arg: a.name().unwrap(),
+                    };
+                    writeln!(f, "{gen}")?;
+                }
+            }
+        }
+
         write!(f, "#[dbus_proxy(interface = \"{}\"", iface.name())?;
         if let Some(service) = self.service {
             write!(f, ", default_service = \"{service}\"")?;
@@ -38,10 +98,14 @@ impl<'i> Display for GenTrait<'i> {
         let mut methods = iface.methods().to_vec();
         methods.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
         for m in &methods {
-            let (inputs, output) = inputs_output_from_args(m.args());
-            let name = to_identifier(&to_snakecase(m.name().as_str()));
+            let (inputs, output) =
+                inputs_output_from_args(m.args(), |a| arg_dict(m.name().as_str(), a));
+            let name = to_identifier(&snake_case(m.name().as_str()));
             writeln!(f)?;
             writeln!(f, "    /// {} method", m.name())?;
+            if let Some(ifaces) = self.object_interfaces {
+                write_object_path_docs(f, m.name().as_str(), m.args(), ifaces)?;
+            }
             if pascal_case(&name) != m.name().as_str() {
                 writeln!(f, "    #[dbus_proxy(name = \"{}\")]", m.name())?;
             }
@@ -51,8 +115,8 @@ impl<'i> Display for GenTrait<'i> {
         let mut signals = iface.signals().to_vec();
         signals.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
         for signal in &signals {
-            let args = parse_signal_args(signal.args());
-            let name = to_identifier(&to_snakecase(signal.name().as_str()));
+            let args = parse_signal_args(signal.args(), |a| arg_dict(signal.name().as_str(), a));
+            let name = to_identifier(&snake_case(signal.name().as_str()));
             writeln!(f)?;
             writeln!(f, "    /// {} signal", signal.name())?;
             if pascal_case(&name) != signal.name().as_str() {
@@ -63,26 +127,33 @@ impl<'i> Display for GenTrait<'i> {
             writeln!(f, "    fn {name}({args}) -> zbus::Result<()>;",)?;
         }
 
-        let mut props = iface.properties().to_vec();
-        props.sort_by(|a, b| a.name().partial_cmp(&b.name()).unwrap());
-        for p in props {
-            let name = to_identifier(&to_snakecase(p.name().as_str()));
+        for p in &props {
+            let name = to_identifier(&snake_case(p.name().as_str()));
 
             writeln!(f)?;
             writeln!(f, "    /// {} property", p.name())?;
-            if pascal_case(&name) != p.name().as_str() {
-                writeln!(f, "    #[dbus_proxy(property, name = \"{}\")]", p.name())?;
+            // Both the getter and the setter need the attribute.
+            let attr = if pascal_case(&name) != p.name().as_str() {
+                format!("    #[dbus_proxy(property, name = \"{}\")]", p.name())
             } else {
-                writeln!(f, "    #[dbus_proxy(property)]")?;
-            }
+                "    #[dbus_proxy(property)]".to_string()
+            };
 
             if p.access().read() {
-                let output = to_rust_type(p.ty(), false, false);
+                let output = match prop_enum(p) {
+                    Some((enum_, _)) => enum_.name().to_string(),
+                    None => to_rust_type(p.ty(), false, false),
+                };
+                writeln!(f, "{attr}")?;
                 writeln!(f, "    fn {name}(&self) -> zbus::Result<{output}>;",)?;
             }
 
             if p.access().write() {
-                let input = to_rust_type(p.ty(), true, true);
+                let input = match prop_enum(p) {
+                    Some((enum_, _)) => enum_.name().to_string(),
+                    None => to_rust_type(p.ty(), true, true),
+                };
+                writeln!(f, "{attr}")?;
                 writeln!(
                     f,
                     "    fn set_{name}(&self, value: {input}) -> zbus::Result<()>;",
@@ -93,7 +164,64 @@ impl<'i> Display for GenTrait<'i> {
     }
 }
 
-fn inputs_output_from_args(args: &[Arg]) -> (String, String) {
+// Document which proxy to build from the object path arguments, for those whose name (or the
+// method's, if unnamed) refers to one of `ifaces`. The longest match wins so that e.g
+// `active_connection` refers to `ActiveConnection` rather than `Connection`.
+fn write_object_path_docs(
+    f: &mut Formatter<'_>,
+    method: &str,
+    args: &[Arg<'_>],
+    ifaces: &[InterfaceName<'_>],
+) -> std::fmt::Result {
+    let mut first = true;
+    for a in args {
+        let plural = match a.ty().signature().as_str() {
+            "o" => false,
+            "ao" => true,
+            _ => continue,
+        };
+        let arg_name = snake_case(a.name().unwrap_or(method));
+        let proxy = ifaces
+            .iter()
+            .map(|i| &i[i.rfind('.').unwrap() + 1..])
+            .filter(|name| arg_name.contains(&snake_case(name)))
+            .max_by_key(|name| name.len());
+        let proxy = match proxy {
+            Some(name) => format!("{name}Proxy"),
+            None => continue,
+        };
+        if first {
+            writeln!(f, "    ///")?;
+            first = false;
+        }
+        let arg = a.name().map(|n| format!("`{n}` ")).unwrap_or_default();
+        match (a.direction(), plural) {
+            (Some(ArgDirection::Out), false) => writeln!(
+                f,
+                "    /// [`{proxy}`] can be built from the returned {arg}object path."
+            )?,
+            (Some(ArgDirection::Out), true) => writeln!(
+                f,
+                "    /// [`{proxy}`]s can be built from the returned {arg}object paths."
+            )?,
+            (_, false) => writeln!(
+                f,
+                "    /// The {arg}argument takes the object path of the [`{proxy}`]."
+            )?,
+            (_, true) => writeln!(
+                f,
+                "    /// The {arg}argument takes the object paths of the [`{proxy}`]s."
+            )?,
+        }
+    }
+
+    Ok(())
+}
+
+fn inputs_output_from_args<'d>(
+    args: &[Arg<'_>],
+    dict: impl Fn(&Arg<'_>) -> Option<&'d Dict>,
+) -> (String, String) {
     let mut inputs = vec!["&self".to_string()];
     let mut output = vec![];
     let mut n = 0;
@@ -105,7 +233,10 @@ fn inputs_output_from_args(args: &[Arg]) -> (String, String) {
     for a in args {
         match a.direction() {
             None | Some(ArgDirection::In) => {
-                let ty = to_rust_type(a.ty(), true, true);
+                let ty = match dict(a) {
+                    Some(dict) => format!("&{}", dict.name()),
+                    None => to_rust_type(a.ty(), true, true),
+                };
                 let arg = if let Some(name) = a.name() {
                     to_identifier(name)
                 } else {
@@ -114,7 +245,10 @@ fn inputs_output_from_args(args: &[Arg]) -> (String, String) {
                 inputs.push(format!("{arg}: {ty}"));
             }
             Some(ArgDirection::Out) => {
-                let ty = to_rust_type(a.ty(), false, false);
+                let ty = match dict(a) {
+                    Some(dict) => dict.name().to_string(),
+                    None => to_rust_type(a.ty(), false, false),
+                };
                 output.push(ty);
             }
         }
@@ -129,7 +263,7 @@ fn inputs_output_from_args(args: &[Arg]) -> (String, String) {
     (inputs.join(", "), format!(" -> zbus::Result<{output}>"))
 }
 
-fn parse_signal_args(args: &[Arg]) -> String {
+fn parse_signal_args<'d>(args: &[Arg<'_>], dict: impl Fn(&Arg<'_>) -> Option<&'d Dict>) -> String {
     let mut inputs = vec!["&self".to_string()];
     let mut n = 0;
     let mut gen_name = || {
@@ -138,7 +272,10 @@ fn parse_signal_args(args: &[Arg]) -> String {
     };
 
     for a in args {
-        let ty = to_rust_type(a.ty(), true, false);
+        let ty = match dict(a) {
+            Some(dict) => dict.name().to_string(),
+            None => to_rust_type(a.ty(), true, false),
+        };
         let arg = if let Some(name) = a.name() {
             to_identifier(name)
         } else {
@@ -150,119 +287,32 @@ fn parse_signal_args(args: &[Arg]) -> String {
     inputs.join(", ")
 }
 
-fn to_rust_type(ty: &CompleteType, input: bool, as_ref: bool) -> String {
-    // can't haz recursive closure, yet
-    fn iter_to_rust_type(
-        it: &mut std::iter::Peekable<std::slice::Iter<'_, u8>>,
-        input: bool,
-        as_ref: bool,
-    ) -> String {
-        let c = it.next().unwrap();
-        match *c as char {
-            u8::SIGNATURE_CHAR => "u8".into(),
-            bool::SIGNATURE_CHAR => "bool".into(),
-            i16::SIGNATURE_CHAR => "i16".into(),
-            u16::SIGNATURE_CHAR => "u16".into(),
-            i32::SIGNATURE_CHAR => "i32".into(),
-            u32::SIGNATURE_CHAR => "u32".into(),
-            i64::SIGNATURE_CHAR => "i64".into(),
-            u64::SIGNATURE_CHAR => "u64".into(),
-            f64::SIGNATURE_CHAR => "f64".into(),
-            // xmlgen accepts 'h' on Windows, only for code generation
-            'h' => (if input {
-                "zbus::zvariant::Fd"
-            } else {
-                "zbus::zvariant::OwnedFd"
-            })
-            .into(),
-            <&str>::SIGNATURE_CHAR => (if input || as_ref { "&str" } else { "String" }).into(),
-            ObjectPath::SIGNATURE_CHAR => (if input {
-                if as_ref {
-                    "&zbus::zvariant::ObjectPath<'_>"
-                } else {
-                    "zbus::zvariant::ObjectPath<'_>"
-                }
-            } else {
-                "zbus::zvariant::OwnedObjectPath"
-            })
-            .into(),
-            Signature::SIGNATURE_CHAR => (if input {
-                if as_ref {
-                    "&zbus::zvariant::Signature<'_>"
-                } else {
-                    "zbus::zvariant::Signature<'_>"
-                }
-            } else {
-                "zbus::zvariant::OwnedSignature"
-            })
-            .into(),
-            VARIANT_SIGNATURE_CHAR => (if input {
-                if as_ref {
-                    "&zbus::zvariant::Value<'_>"
-                } else {
-                    "zbus::zvariant::Value<'_>"
-                }
-            } else {
-                "zbus::zvariant::OwnedValue"
-            })
-            .into(),
-            ARRAY_SIGNATURE_CHAR => {
-                let c = it.peek().unwrap();
-                match **c as char {
-                    '{' => format!(
-                        "std::collections::HashMap<{}>",
-                        iter_to_rust_type(it, input, false)
-                    ),
-                    _ => {
-                        let ty = iter_to_rust_type(it, input, false);
-                        if input {
-                            format!("&[{ty}]")
-                        } else {
-                            format!("{}Vec<{}>", if as_ref { "&" } else { "" }, ty)
-                        }
-                    }
-                }
-            }
-            c @ STRUCT_SIG_START_CHAR | c @ DICT_ENTRY_SIG_START_CHAR => {
-                let dict = c == '{';
-                let mut vec = vec![];
-                loop {
-                    let c = it.peek().unwrap();
-                    match **c as char {
-                        STRUCT_SIG_END_CHAR | DICT_ENTRY_SIG_END_CHAR => break,
-                        _ => vec.push(iter_to_rust_type(it, input, false)),
-                    }
-                }
-                if dict {
-                    vec.join(", ")
-                } else if vec.len() > 1 {
-                    format!("{}({})", if as_ref { "&" } else { "" }, vec.join(", "))
-                } else {
-                    vec[0].to_string()
-                }
-            }
-            _ => unimplemented!(),
-        }
+/// Check that the types of the `interface` members can be represented in Rust.
+///
+/// `max_depth` limits the nesting of arrays and structures, see [`codegen::try_to_rust_type`].
+pub fn check_types(interface: &Interface<'_>, max_depth: usize) -> Result<(), codegen::TypeError> {
+    let methods = interface.methods().iter().flat_map(|m| m.args());
+    let signals = interface.signals().iter().flat_map(|s| s.args());
+    let types = methods
+        .chain(signals)
+        .map(|a| a.ty())
+        .chain(interface.properties().iter().map(|p| p.ty()));
+    for ty in types {
+        codegen::try_to_rust_type(ty.signature().as_str(), false, false, max_depth)?;
     }
 
-    let mut it = ty.signature().as_bytes().iter().peekable();
-    iter_to_rust_type(&mut it, input, as_ref)
+    Ok(())
 }
 
-static KWORDS: &[&str] = &[
-    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
-    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl",
-    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
-    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
-    "union", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
-];
-
-fn to_identifier(id: &str) -> String {
-    if KWORDS.contains(&id) {
-        format!("{id}_")
-    } else {
-        id.replace('-', "_")
-    }
+fn to_rust_type(ty: &CompleteType, input: bool, as_ref: bool) -> String {
+    // Types not checked with `check_types` beforehand fail to compile instead.
+    codegen::try_to_rust_type(
+        ty.signature().as_str(),
+        input,
+        as_ref,
+        codegen::MAX_CONTAINER_DEPTH,
+    )
+    .unwrap_or_else(|e| format!("compile_error!(\"`{ty}` type: {e}\")"))
 }
 
 // This function is the same as zbus_macros::utils::pascal_case
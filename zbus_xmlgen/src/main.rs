@@ -3,7 +3,7 @@
 use std::{
     env::args,
     error::Error,
-    fs::File,
+    fs::{read_to_string, File},
     io::Write,
     path::Path,
     process::{Command, Stdio},
@@ -11,40 +11,91 @@ use std::{
 };
 
 use zbus::{
-    blocking::{connection, proxy::Builder as ProxyBuilder, Connection},
+    blocking::{connection, fdo::IntrospectableProxy, proxy::Builder as ProxyBuilder, Connection},
     names::BusName,
     xml::{Interface, Node},
 };
 
-use zbus_xmlgen::GenTrait;
+use zbus_xmlgen::{check_types, Dicts, Enums, GenTrait, Schema};
 use zvariant::ObjectPath;
+use zvariant_utils::codegen;
 
 fn main() -> Result<(), Box<dyn Error>> {
     let input_src;
 
-    let proxy = |conn: Connection, service, path| -> zbus::blocking::fdo::IntrospectableProxy<'_> {
-        ProxyBuilder::new(&conn)
-            .destination(service)
-            .expect("invalid destination")
-            .path(path)
-            .expect("invalid path")
-            .build()
-            .unwrap()
+    let mut args: Vec<String> = args().skip(1).collect();
+    let enums = match args.iter().position(|a| a == "--enums") {
+        Some(i) => {
+            args.remove(i);
+            let path = (i < args.len())
+                .then(|| args.remove(i))
+                .expect("Missing param for enums file");
+            Some(Enums::from_toml(&read_to_string(path)?)?)
+        }
+        None => None,
+    };
+    let dicts = match args.iter().position(|a| a == "--dicts") {
+        Some(i) => {
+            args.remove(i);
+            let path = (i < args.len())
+                .then(|| args.remove(i))
+                .expect("Missing param for dicts file");
+            Some(Dicts::from_toml(&read_to_string(path)?)?)
+        }
+        None => None,
+    };
+    let max_depth = match args.iter().position(|a| a == "--max-depth") {
+        Some(i) => {
+            args.remove(i);
+            (i < args.len())
+                .then(|| args.remove(i))
+                .expect("Missing param for max depth")
+                .parse()?
+        }
+        None => codegen::MAX_CONTAINER_DEPTH,
     };
+    let no_header = match args.iter().position(|a| a == "--no-header") {
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+        None => false,
+    };
+    let recurse = match args.iter().position(|a| a == "--recurse") {
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+        None => false,
+    };
+    let xml_only = match args.iter().position(|a| a == "--xml-only") {
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+        None => false,
+    };
+    let mut only_ifaces = vec![];
+    while let Some(i) = args.iter().position(|a| a == "--interface") {
+        args.remove(i);
+        let name = (i < args.len())
+            .then(|| args.remove(i))
+            .expect("Missing param for interface");
+        only_ifaces.push(name);
+    }
+    let mut args = args.into_iter();
 
-    let (node, service, path) = match args().nth(1) {
+    let (node, children, service, path) = match args.next() {
         Some(bus) if bus == "--system" || bus == "--session" => {
             let connection = if bus == "--system" {
                 Connection::system()?
             } else {
                 Connection::session()?
             };
-            let service: BusName<'_> = args()
-                .nth(2)
-                .expect("Missing param for service")
-                .try_into()?;
-            let path: ObjectPath<'_> = args()
-                .nth(3)
+            let service: BusName<'_> =
+                args.next().expect("Missing param for service").try_into()?;
+            let path: ObjectPath<'_> = args
+                .next()
                 .expect("Missing param for object path")
                 .try_into()?;
 
@@ -55,21 +106,24 @@ fn main() -> Result<(), Box<dyn Error>> {
                 bus.trim_start_matches("--")
             );
 
-            let xml = proxy(connection, service.clone(), path.clone()).introspect()?;
-            (
-                Node::from_reader(xml.as_bytes())?,
-                Some(service),
-                Some(path),
-            )
+            if xml_only {
+                print!("{}", introspect_xml(&connection, &service, &path)?);
+                return Ok(());
+            }
+            let node = introspect(&connection, &service, &path)?;
+            let children = if recurse {
+                introspect_children(&connection, &service, &path, &node)?
+            } else {
+                vec![]
+            };
+            (node, children, Some(service), Some(path))
         }
         Some(address) if address == "--address" => {
-            let address = args().nth(2).expect("Missing param for address path");
-            let service: BusName<'_> = args()
-                .nth(3)
-                .expect("Missing param for service")
-                .try_into()?;
-            let path: ObjectPath<'_> = args()
-                .nth(4)
+            let address = args.next().expect("Missing param for address path");
+            let service: BusName<'_> =
+                args.next().expect("Missing param for service").try_into()?;
+            let path: ObjectPath<'_> = args
+                .next()
                 .expect("Missing param for object path")
                 .try_into()?;
 
@@ -77,12 +131,22 @@ fn main() -> Result<(), Box<dyn Error>> {
 
             input_src = format!("Interface '{path}' from service '{service}'");
 
-            let xml = proxy(connection, service.clone(), path.clone()).introspect()?;
-            (
-                Node::from_reader(xml.as_bytes())?,
-                Some(service),
-                Some(path),
-            )
+            if xml_only {
+                print!("{}", introspect_xml(&connection, &service, &path)?);
+                return Ok(());
+            }
+            let node = introspect(&connection, &service, &path)?;
+            let children = if recurse {
+                introspect_children(&connection, &service, &path, &node)?
+            } else {
+                vec![]
+            };
+            (node, children, Some(service), Some(path))
+        }
+        Some(_) if xml_only => {
+            return Err(
+                "`--xml-only` needs a service to introspect, on a bus or an address".into(),
+            );
         }
         Some(path) => {
             input_src = Path::new(&path)
@@ -90,88 +154,171 @@ fn main() -> Result<(), Box<dyn Error>> {
                 .unwrap()
                 .to_string_lossy()
                 .to_string();
-            let f = File::open(path)?;
-            (Node::from_reader(f)?, None, None)
+            let node = match Path::new(&path).extension().and_then(|e| e.to_str()) {
+                Some("toml") => Schema::from_toml(&read_to_string(&path)?)?.to_node()?,
+                Some("json") => Schema::from_json(&read_to_string(&path)?)?.to_node()?,
+                _ => Node::from_reader(File::open(&path)?)?,
+            };
+            let children = if recurse {
+                // Only the child nodes inlined in the XML can be looked into.
+                let mut children = vec![];
+                flatten_children(&node, &mut children);
+                children
+            } else {
+                vec![]
+            };
+            (node, children, None, None)
         }
         None => {
             eprintln!(
                 r#"Usage:
-  zbus-xmlgen <interface.xml>
-  zbus-xmlgen --system|--session <service> <object_path>
-  zbus-xmlgen --address <address> <service> <object_path>
+  zbus-xmlgen [OPTIONS] <interface.xml>
+  zbus-xmlgen [OPTIONS] <schema.toml|schema.json>
+  zbus-xmlgen [OPTIONS] --system|--session <service> <object_path>
+  zbus-xmlgen [OPTIONS] --address <address> <service> <object_path>
+
+Options:
+  --enums <enums.toml>  Represent integer properties with the enums declared in the given file
+  --dicts <dicts.toml>  Represent `a{{sv}}` arguments with the structures declared in the given file
+  --recurse             Also generate code for the interfaces of the child objects, and document
+                        which proxy to build from object paths referring to them
+  --no-header           Don't start the output with the module documentation, e.g to embed it in a
+                        larger module
+  --max-depth <n>       Fail on types nesting more than this many arrays and structures (64 by
+                        default, the limit of the D-Bus specification)
+  --interface <name>    Only generate code for the given interface, can be repeated to select more
+                        than one (all the interfaces are generated by default)
+  --xml-only            Print the introspection XML of the object instead of generating code, only
+                        for a service on a bus or an address
 "#
             );
             return Ok(());
         }
     };
 
+    if let Some(enums) = &enums {
+        enums.validate(&node)?;
+    }
+    if let Some(dicts) = &dicts {
+        dicts.validate(&node)?;
+    }
+    for name in &only_ifaces {
+        let found = node
+            .interfaces()
+            .iter()
+            .chain(children.iter().flat_map(|c| c.interfaces()))
+            .any(|i| i.name() == name.as_str());
+        if !found {
+            let hint = if recurse {
+                ""
+            } else {
+                " (the interfaces of child objects need `--recurse`)"
+            };
+            return Err(format!("Interface `{name}` not found{hint}").into());
+        }
+    }
+
+    for iface in node
+        .interfaces()
+        .iter()
+        .chain(children.iter().flat_map(|c| c.interfaces()))
+    {
+        check_types(iface, max_depth).map_err(|e| format!("Interface `{}`: {e}", iface.name()))?;
+    }
+
     let mut process = match Command::new("rustfmt").stdin(Stdio::piped()).spawn() {
         Err(why) => panic!("couldn't spawn rustfmt: {}", why),
         Ok(process) => process,
     };
     let rustfmt_stdin = process.stdin.as_mut().unwrap();
     let fdo_iface_prefix = "org.freedesktop.DBus";
+    let selected = |iface: &Interface<'_>| {
+        only_ifaces.is_empty() || only_ifaces.iter().any(|name| iface.name() == name.as_str())
+    };
     let (fdo_standard_ifaces, needed_ifaces): (Vec<&Interface<'_>>, Vec<&Interface<'_>>) = node
         .interfaces()
         .iter()
+        .filter(|i| selected(i))
         .partition(|&i| i.name().starts_with(fdo_iface_prefix));
-
-    if let Some((first_iface, following_ifaces)) = needed_ifaces.split_first() {
-        if following_ifaces.is_empty() {
-            writeln!(
-                rustfmt_stdin,
-                "//! # DBus interface proxy for: `{}`",
-                first_iface.name()
-            )?;
-        } else {
-            write!(
-                rustfmt_stdin,
-                "//! # DBus interface proxies for: `{}`",
-                first_iface.name()
-            )?;
-            for iface in following_ifaces {
-                write!(rustfmt_stdin, ", `{}`", iface.name())?;
-            }
-            writeln!(rustfmt_stdin)?;
+    let mut child_ifaces: Vec<&Interface<'_>> = vec![];
+    for iface in children.iter().flat_map(|c| c.interfaces()) {
+        if selected(iface)
+            && !iface.name().starts_with(fdo_iface_prefix)
+            && !needed_ifaces
+                .iter()
+                .chain(&child_ifaces)
+                .any(|i| i.name() == iface.name())
+        {
+            child_ifaces.push(iface);
         }
     }
+    let object_interfaces: Option<Vec<_>> = recurse.then(|| {
+        needed_ifaces
+            .iter()
+            .chain(&child_ifaces)
+            .map(|i| i.name())
+            .collect()
+    });
 
-    write!(
-        rustfmt_stdin,
-        "//!
-         //! This code was generated by `{}` `{}` from DBus introspection data.
-         //! Source: `{}`.
-         //!
-         //! You may prefer to adapt it, instead of using it verbatim.
-         //!
-         //! More information can be found in the
-         //! [Writing a client proxy](https://dbus2.github.io/zbus/client.html)
-         //! section of the zbus documentation.
-         //!
-        ",
-        env!("CARGO_BIN_NAME"),
-        env!("CARGO_PKG_VERSION"),
-        input_src,
-    )?;
-    if !fdo_standard_ifaces.is_empty() {
-        write!(rustfmt_stdin,
-            "//! This DBus object implements
-             //! [standard DBus interfaces](https://dbus.freedesktop.org/doc/dbus-specification.html),
-             //! (`org.freedesktop.DBus.*`) for which the following zbus proxies can be used:
-             //!
-            ")?;
-        for iface in &fdo_standard_ifaces {
-            let idx = iface.name().rfind('.').unwrap() + 1;
-            let name = &iface.name()[idx..];
-            writeln!(rustfmt_stdin, "//! * [`zbus::fdo::{name}Proxy`]")?;
+    let all_ifaces: Vec<_> = needed_ifaces.iter().chain(&child_ifaces).collect();
+    if !no_header {
+        if let Some((first_iface, following_ifaces)) = all_ifaces.split_first() {
+            if following_ifaces.is_empty() {
+                writeln!(
+                    rustfmt_stdin,
+                    "//! # DBus interface proxy for: `{}`",
+                    first_iface.name()
+                )?;
+            } else {
+                write!(
+                    rustfmt_stdin,
+                    "//! # DBus interface proxies for: `{}`",
+                    first_iface.name()
+                )?;
+                for iface in following_ifaces {
+                    write!(rustfmt_stdin, ", `{}`", iface.name())?;
+                }
+                writeln!(rustfmt_stdin)?;
+            }
         }
+
         write!(
             rustfmt_stdin,
             "//!
-             //! …consequently `{}` did not generate code for the above interfaces.
+             //! This code was generated by `{}` `{}` from DBus introspection data.
+             //! Source: `{}`.
+             //!
+             //! You may prefer to adapt it, instead of using it verbatim.
+             //!
+             //! More information can be found in the
+             //! [Writing a client proxy](https://dbus2.github.io/zbus/client.html)
+             //! section of the zbus documentation.
+             //!
             ",
-            env!("CARGO_BIN_NAME")
+            env!("CARGO_BIN_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            input_src,
         )?;
+        if !fdo_standard_ifaces.is_empty() {
+            write!(rustfmt_stdin,
+                "//! This DBus object implements
+                 //! [standard DBus interfaces](https://dbus.freedesktop.org/doc/dbus-specification.html),
+                 //! (`org.freedesktop.DBus.*`) for which the following zbus proxies can be used:
+                 //!
+                ")?;
+            for iface in &fdo_standard_ifaces {
+                let idx = iface.name().rfind('.').unwrap() + 1;
+                let name = &iface.name()[idx..];
+                writeln!(rustfmt_stdin, "//! * [`zbus::fdo::{name}Proxy`]")?;
+            }
+            write!(
+                rustfmt_stdin,
+                "//!
+                 //! …consequently `{}` did not generate code for the above interfaces.
+                ",
+                env!("CARGO_BIN_NAME")
+            )?;
+        }
     }
     write!(
         rustfmt_stdin,
@@ -179,12 +326,17 @@ fn main() -> Result<(), Box<dyn Error>> {
         use zbus::dbus_proxy;
         "
     )?;
-    for iface in &needed_ifaces {
+    for iface in all_ifaces {
         writeln!(rustfmt_stdin)?;
+        // The interfaces of the child objects are typically implemented at more than one path.
+        let is_child = child_ifaces.iter().any(|i| i.name() == iface.name());
         let gen = GenTrait {
             interface: iface,
             service: service.as_ref(),
-            path: path.as_ref(),
+            path: if is_child { None } else { path.as_ref() },
+            enums: enums.as_ref(),
+            dicts: dicts.as_ref(),
+            object_interfaces: object_interfaces.as_deref(),
         }
         .to_string();
         rustfmt_stdin.write_all(gen.as_bytes())?;
@@ -192,3 +344,55 @@ fn main() -> Result<(), Box<dyn Error>> {
     process.wait()?;
     Ok(())
 }
+
+fn introspect(
+    conn: &Connection,
+    service: &BusName<'_>,
+    path: &ObjectPath<'_>,
+) -> Result<Node<'static>, Box<dyn Error>> {
+    let xml = introspect_xml(conn, service, path)?;
+
+    Ok(Node::from_reader(xml.as_bytes())?)
+}
+
+fn introspect_xml(
+    conn: &Connection,
+    service: &BusName<'_>,
+    path: &ObjectPath<'_>,
+) -> Result<String, Box<dyn Error>> {
+    let proxy: IntrospectableProxy<'_> = ProxyBuilder::new(conn)
+        .destination(service)?
+        .path(path)?
+        .build()?;
+
+    Ok(proxy.introspect()?)
+}
+
+// Introspect all the descendants of the object at `path`, given its `node`.
+fn introspect_children(
+    conn: &Connection,
+    service: &BusName<'_>,
+    path: &ObjectPath<'_>,
+    node: &Node<'_>,
+) -> Result<Vec<Node<'static>>, Box<dyn Error>> {
+    let mut children = vec![];
+    for name in node.nodes().iter().filter_map(|n| n.name()) {
+        let child_path = match path.as_str() {
+            "/" => format!("/{name}"),
+            path => format!("{path}/{name}"),
+        };
+        let child_path = ObjectPath::try_from(child_path)?;
+        let child = introspect(conn, service, &child_path)?;
+        children.extend(introspect_children(conn, service, &child_path, &child)?);
+        children.push(child);
+    }
+
+    Ok(children)
+}
+
+fn flatten_children<'n>(node: &Node<'n>, children: &mut Vec<Node<'n>>) {
+    for child in node.nodes() {
+        children.push(child.clone());
+        flatten_children(child, children);
+    }
+}
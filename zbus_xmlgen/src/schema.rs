@@ -0,0 +1,262 @@
+use serde::Deserialize;
+use std::{collections::BTreeMap, error::Error, fmt::Write};
+
+use zbus::{
+    names::{InterfaceName, MemberName},
+    xml::{Node, PropertyAccess},
+};
+use zvariant::{CompleteType, Signature};
+
+/// A description of D-Bus interfaces, in a simpler format than the introspection XML.
+///
+/// The schema is typically read from a TOML file, e.g:
+///
+/// ```toml
+/// [[interfaces]]
+/// name = "org.freedesktop.Example1"
+///
+/// [[interfaces.methods]]
+/// name = "Connect"
+/// in = [{ name = "address", type = "s" }]
+/// out = [{ name = "id", type = "u" }]
+///
+/// [[interfaces.properties]]
+/// name = "State"
+/// type = "u"
+/// access = "read"
+///
+/// [[interfaces.signals]]
+/// name = "Disconnected"
+/// args = [{ name = "id", type = "u" }]
+/// annotations = { "org.freedesktop.DBus.Deprecated" = "true" }
+/// ```
+///
+/// or from the equivalent JSON document. It's turned into an introspection [`Node`] to generate
+/// the code from.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schema {
+    interfaces: Vec<InterfaceDef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InterfaceDef {
+    name: String,
+    #[serde(default)]
+    methods: Vec<MethodDef>,
+    #[serde(default)]
+    properties: Vec<PropertyDef>,
+    #[serde(default)]
+    signals: Vec<SignalDef>,
+    #[serde(default)]
+    annotations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MethodDef {
+    name: String,
+    #[serde(rename = "in", default)]
+    in_args: Vec<ArgDef>,
+    #[serde(rename = "out", default)]
+    out_args: Vec<ArgDef>,
+    #[serde(default)]
+    annotations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PropertyDef {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+    access: PropertyAccess,
+    #[serde(default)]
+    annotations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SignalDef {
+    name: String,
+    #[serde(default)]
+    args: Vec<ArgDef>,
+    #[serde(default)]
+    annotations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArgDef {
+    name: Option<String>,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+impl Schema {
+    /// Parse the schema from the TOML source.
+    pub fn from_toml(source: &str) -> Result<Self, Box<dyn Error>> {
+        let schema: Self = toml::from_str(source)?;
+        schema.validate()?;
+
+        Ok(schema)
+    }
+
+    /// Parse the schema from the JSON source.
+    pub fn from_json(source: &str) -> Result<Self, Box<dyn Error>> {
+        let schema: Self = serde_json::from_str(source)?;
+        schema.validate()?;
+
+        Ok(schema)
+    }
+
+    /// Convert the schema into the node of an object implementing all its interfaces.
+    pub fn to_node(&self) -> Result<Node<'static>, Box<dyn Error>> {
+        let mut xml = String::from("<node>\n");
+        for iface in &self.interfaces {
+            writeln!(xml, r#"  <interface name="{}">"#, iface.name)?;
+            for m in &iface.methods {
+                writeln!(xml, r#"    <method name="{}">"#, m.name)?;
+                write_args(&mut xml, &m.in_args, Some("in"))?;
+                write_args(&mut xml, &m.out_args, Some("out"))?;
+                write_annotations(&mut xml, &m.annotations, "      ")?;
+                writeln!(xml, "    </method>")?;
+            }
+            for p in &iface.properties {
+                let access = match p.access {
+                    PropertyAccess::Read => "read",
+                    PropertyAccess::Write => "write",
+                    PropertyAccess::ReadWrite => "readwrite",
+                };
+                writeln!(
+                    xml,
+                    r#"    <property name="{}" type="{}" access="{access}">"#,
+                    p.name,
+                    escape(&p.ty),
+                )?;
+                write_annotations(&mut xml, &p.annotations, "      ")?;
+                writeln!(xml, "    </property>")?;
+            }
+            for s in &iface.signals {
+                writeln!(xml, r#"    <signal name="{}">"#, s.name)?;
+                write_args(&mut xml, &s.args, None)?;
+                write_annotations(&mut xml, &s.annotations, "      ")?;
+                writeln!(xml, "    </signal>")?;
+            }
+            write_annotations(&mut xml, &iface.annotations, "    ")?;
+            writeln!(xml, "  </interface>")?;
+        }
+        xml.push_str("</node>\n");
+
+        Ok(Node::from_reader(xml.as_bytes())?)
+    }
+
+    fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if self.interfaces.is_empty() {
+            return Err("No interfaces given".into());
+        }
+        for (i, iface) in self.interfaces.iter().enumerate() {
+            let name = &iface.name;
+            InterfaceName::try_from(name.as_str())
+                .map_err(|e| format!("Invalid interface name `{name}`: {e}"))?;
+            if self.interfaces[..i].iter().any(|other| &other.name == name) {
+                return Err(format!("Interface `{name}` is declared more than once").into());
+            }
+
+            let members: [(&str, Vec<&String>); 3] = [
+                ("method", iface.methods.iter().map(|m| &m.name).collect()),
+                (
+                    "property",
+                    iface.properties.iter().map(|p| &p.name).collect(),
+                ),
+                ("signal", iface.signals.iter().map(|s| &s.name).collect()),
+            ];
+            for (kind, names) in members {
+                for (j, member) in names.iter().enumerate() {
+                    MemberName::try_from(member.as_str())
+                        .map_err(|e| format!("Invalid {kind} name `{member}` in `{name}`: {e}"))?;
+                    if names[..j].contains(member) {
+                        return Err(format!(
+                            "The `{member}` {kind} of `{name}` is declared more than once"
+                        )
+                        .into());
+                    }
+                }
+            }
+
+            for m in &iface.methods {
+                for arg in m.in_args.iter().chain(&m.out_args) {
+                    check_type(&arg.ty, || arg_desc(arg, name, &m.name))?;
+                }
+            }
+            for p in &iface.properties {
+                check_type(&p.ty, || format!("the `{}.{}` property", name, p.name))?;
+            }
+            for s in &iface.signals {
+                for arg in &s.args {
+                    check_type(&arg.ty, || arg_desc(arg, name, &s.name))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn arg_desc(arg: &ArgDef, iface: &str, member: &str) -> String {
+    match &arg.name {
+        Some(arg_name) => format!("the `{arg_name}` argument of `{iface}.{member}`"),
+        None => format!("an argument of `{iface}.{member}`"),
+    }
+}
+
+fn check_type<F>(ty: &str, desc: F) -> Result<(), Box<dyn Error>>
+where
+    F: FnOnce() -> String,
+{
+    Signature::try_from(ty)
+        .map_err(Box::<dyn Error>::from)
+        .and_then(|s| Ok(CompleteType::try_from(s).map(|_| ())?))
+        .map_err(|e| format!("Invalid type `{ty}` of {}: {e}", desc()).into())
+}
+
+fn write_args(xml: &mut String, args: &[ArgDef], direction: Option<&str>) -> std::fmt::Result {
+    for arg in args {
+        write!(xml, "      <arg")?;
+        if let Some(name) = &arg.name {
+            write!(xml, r#" name="{}""#, escape(name))?;
+        }
+        write!(xml, r#" type="{}""#, escape(&arg.ty))?;
+        if let Some(direction) = direction {
+            write!(xml, r#" direction="{direction}""#)?;
+        }
+        writeln!(xml, "/>")?;
+    }
+
+    Ok(())
+}
+
+fn write_annotations(
+    xml: &mut String,
+    annotations: &BTreeMap<String, String>,
+    indent: &str,
+) -> std::fmt::Result {
+    for (name, value) in annotations {
+        writeln!(
+            xml,
+            r#"{indent}<annotation name="{}" value="{}"/>"#,
+            escape(name),
+            escape(value),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
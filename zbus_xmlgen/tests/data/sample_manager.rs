@@ -0,0 +1,25 @@
+#[dbus_proxy(interface = "org.freedesktop.zbus.Manager", assume_defaults = true)]
+trait Manager {
+
+    /// Activate method
+    ///
+    /// The `device` argument takes the object path of the [`DeviceProxy`].
+    /// [`ActiveDeviceProxy`] can be built from the returned `active_device` object path.
+    fn activate(&self, device: &zbus::zvariant::ObjectPath<'_>) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
+
+    /// GetDeviceByName method
+    ///
+    /// [`DeviceProxy`] can be built from the returned object path.
+    fn get_device_by_name(&self, name: &str) -> zbus::Result<zbus::zvariant::OwnedObjectPath>;
+
+    /// GetDevices method
+    ///
+    /// [`DeviceProxy`]s can be built from the returned `devices` object paths.
+    fn get_devices(&self) -> zbus::Result<Vec<zbus::zvariant::OwnedObjectPath>>;
+
+    /// Lookup method
+    fn lookup(&self, path: &zbus::zvariant::ObjectPath<'_>) -> zbus::Result<()>;
+
+    /// Reset method
+    fn reset(&self, mode: u32) -> zbus::Result<()>;
+}
@@ -0,0 +1,14 @@
+#[dbus_proxy(interface = "org.freedesktop.zbus.Maps", assume_defaults = true)]
+trait Maps {
+
+    /// Lookup method
+    fn lookup(&self, ids: std::collections::HashMap<u32, zbus::zvariant::Value<'_>>) -> zbus::Result<std::collections::HashMap<zbus::zvariant::OwnedObjectPath, zbus::zvariant::OwnedValue>>;
+
+    /// Updated signal
+    #[dbus_proxy(signal)]
+    fn updated(&self, objects: std::collections::HashMap<zbus::zvariant::ObjectPath<'_>, zbus::zvariant::Value<'_>>) -> zbus::Result<()>;
+
+    /// Counts property
+    #[dbus_proxy(property)]
+    fn counts(&self) -> zbus::Result<std::collections::HashMap<u64, u32>>;
+}
@@ -0,0 +1,45 @@
+/// Entries of the `options` argument of `Connect`.
+#[derive(Debug, Default, PartialEq, zbus::zvariant::SerializeDict, zbus::zvariant::DeserializeDict, zbus::zvariant::Type)]
+#[zvariant(signature = "a{sv}")]
+pub struct ConnectOptions {
+    #[zvariant(rename = "Allow-Roaming")]
+    pub allow_roaming: Option<bool>,
+    #[zvariant(rename = "Tags")]
+    pub tags: Option<Vec<String>>,
+    #[zvariant(rename = "Timeout")]
+    pub timeout: Option<u32>,
+    /// The entries not listed above.
+    #[zvariant(flatten)]
+    pub extra: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+}
+
+/// Entries of the `connection` argument of `Connect`.
+#[derive(Debug, Default, PartialEq, zbus::zvariant::SerializeDict, zbus::zvariant::DeserializeDict, zbus::zvariant::Type)]
+#[zvariant(signature = "a{sv}")]
+pub struct ConnectionInfo {
+    pub path: Option<zbus::zvariant::OwnedObjectPath>,
+    #[zvariant(rename = "Properties")]
+    pub properties: Option<std::collections::HashMap<String, zbus::zvariant::OwnedValue>>,
+    #[zvariant(rename = "type")]
+    pub type_: Option<String>,
+    /// The entries not listed above.
+    #[zvariant(flatten)]
+    pub extra: std::collections::HashMap<String, zbus::zvariant::OwnedValue>,
+}
+
+#[dbus_proxy(interface = "org.freedesktop.zbus.Options", assume_defaults = true)]
+trait Options {
+
+    /// Configure method
+    fn configure(&self, settings: std::collections::HashMap<&str, zbus::zvariant::Value<'_>>) -> zbus::Result<()>;
+
+    /// Connect method
+    fn connect(&self, address: &str, options: &ConnectOptions) -> zbus::Result<ConnectionInfo>;
+
+    /// Reconnect method
+    fn reconnect(&self, options: &ConnectOptions) -> zbus::Result<()>;
+
+    /// Connected signal
+    #[dbus_proxy(signal)]
+    fn connected(&self, connection: ConnectionInfo) -> zbus::Result<()>;
+}
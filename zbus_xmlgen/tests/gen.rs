@@ -1,11 +1,21 @@
 use pretty_assertions::assert_eq;
 use std::{env, error::Error, io::Write, path::Path, result::Result};
 
-use zbus::xml::Node;
-use zbus_xmlgen::GenTrait;
+use zbus::{names::InterfaceName, xml::Node};
+use zbus_xmlgen::{check_types, Dicts, Enums, GenTrait, Schema};
+use zvariant_utils::codegen::{TypeError, MAX_CONTAINER_DEPTH};
 
 macro_rules! gen_diff {
     ($infile:literal, $outfile:literal) => {{
+        gen_diff!($infile, $outfile, None)
+    }};
+    ($infile:literal, $outfile:literal, $enums:expr) => {{
+        gen_diff!($infile, $outfile, $enums, None)
+    }};
+    ($infile:literal, $outfile:literal, $enums:expr, $object_interfaces:expr) => {{
+        gen_diff!($infile, $outfile, $enums, $object_interfaces, None)
+    }};
+    ($infile:literal, $outfile:literal, $enums:expr, $object_interfaces:expr, $dicts:expr) => {{
         let input = include_str!(concat!("data/", $infile));
         let expected = include_str!(concat!("data/", $outfile));
         #[cfg(windows)]
@@ -15,6 +25,9 @@ macro_rules! gen_diff {
             interface: &node.interfaces()[0],
             path: None,
             service: None,
+            enums: $enums,
+            dicts: $dicts,
+            object_interfaces: $object_interfaces,
         }
         .to_string();
 
@@ -41,3 +54,168 @@ macro_rules! gen_diff {
 fn sample_object0() -> Result<(), Box<dyn Error>> {
     gen_diff!("sample_object0.xml", "sample_object0.rs")
 }
+
+#[test]
+fn sample_object0_enums() -> Result<(), Box<dyn Error>> {
+    let enums = Enums::from_toml(include_str!("data/sample_object0_enums.toml"))?;
+    gen_diff!(
+        "sample_object0.xml",
+        "sample_object0_enums.rs",
+        Some(&enums)
+    )
+}
+
+#[test]
+fn enum_value_out_of_range() -> Result<(), Box<dyn Error>> {
+    let enums = Enums::from_toml(
+        r#"
+        ["com.example.SampleInterface0.Bar"]
+        name = "BarState"
+        values = { 0 = "Off", 300 = "Overflow" }
+        "#,
+    )?;
+    let node = Node::from_reader(include_str!("data/sample_object0.xml").as_bytes())?;
+    let err = enums.validate(&node).unwrap_err();
+    assert!(err.to_string().contains("`300`"), "{err}");
+
+    // Generating the code anyway makes it fail to build.
+    let gen = GenTrait {
+        interface: &node.interfaces()[0],
+        path: None,
+        service: None,
+        enums: Some(&enums),
+        dicts: None,
+        object_interfaces: None,
+    }
+    .to_string();
+    assert!(gen
+        .contains("compile_error!(\"value `300` of `BarState::Overflow` doesn't fit in `u8`\");"));
+
+    Ok(())
+}
+
+#[test]
+fn sample_manager_object_interfaces() -> Result<(), Box<dyn Error>> {
+    let ifaces = [
+        InterfaceName::from_static_str("org.freedesktop.zbus.Manager")?,
+        InterfaceName::from_static_str("org.freedesktop.zbus.Device")?,
+        InterfaceName::from_static_str("org.freedesktop.zbus.ActiveDevice")?,
+    ];
+    gen_diff!(
+        "sample_manager.xml",
+        "sample_manager.rs",
+        None,
+        Some(&ifaces)
+    )
+}
+
+#[test]
+fn sample_options_dicts() -> Result<(), Box<dyn Error>> {
+    let dicts = Dicts::from_toml(include_str!("data/sample_options_dicts.toml"))?;
+    dicts.validate(&Node::from_reader(
+        include_str!("data/sample_options.xml").as_bytes(),
+    )?)?;
+    gen_diff!(
+        "sample_options.xml",
+        "sample_options_dicts.rs",
+        None,
+        None,
+        Some(&dicts)
+    )
+}
+
+#[test]
+fn deeply_nested_type() -> Result<(), Box<dyn Error>> {
+    let xml = |ty: &str| {
+        format!(
+            r#"<node><interface name="org.freedesktop.zbus.Nested">
+                 <method name="Get"><arg type="{ty}" direction="out"/></method>
+               </interface></node>"#
+        )
+    };
+
+    // Pathological nesting is rejected when parsing, rather than overflowing the stack later.
+    let sig = format!("{}y", "a".repeat(250));
+    assert_eq!(
+        zvariant::Signature::try_from(sig.as_str()).unwrap_err(),
+        zvariant::Error::MaxDepthExceeded(zvariant::MaxDepthExceeded::Array),
+    );
+    let err = Node::from_reader(xml(&sig).as_bytes()).unwrap_err();
+    assert!(matches!(err, zbus::Error::QuickXml(_)), "{err}");
+
+    let node = Node::from_reader(xml(&format!("{}y", "a".repeat(32))).as_bytes())?;
+    let iface = &node.interfaces()[0];
+    assert_eq!(check_types(iface, 8), Err(TypeError::MaxDepthExceeded(8)));
+    check_types(iface, MAX_CONTAINER_DEPTH)?;
+    let gen = GenTrait {
+        interface: iface,
+        path: None,
+        service: None,
+        enums: None,
+        dicts: None,
+        object_interfaces: None,
+    }
+    .to_string();
+    let ty = format!("{}u8{}", "Vec<".repeat(32), ">".repeat(32));
+    assert!(gen.contains(&format!("fn get(&self) -> zbus::Result<{ty}>;")));
+
+    Ok(())
+}
+
+#[test]
+fn sample_maps() -> Result<(), Box<dyn Error>> {
+    gen_diff!("sample_maps.xml", "sample_maps.rs")
+}
+
+#[test]
+fn sample_object0_schema() -> Result<(), Box<dyn Error>> {
+    let expected = include_str!("data/sample_object0.rs");
+    #[cfg(windows)]
+    let expected = expected.replace("\r\n", "\n");
+    let schemas = [
+        Schema::from_toml(include_str!("data/sample_object0_schema.toml"))?,
+        Schema::from_json(include_str!("data/sample_object0_schema.json"))?,
+    ];
+    // The schemas describe the same interface as `sample_object0.xml`.
+    for schema in schemas {
+        let node = schema.to_node()?;
+        let gen = GenTrait {
+            interface: &node.interfaces()[0],
+            path: None,
+            service: None,
+            enums: None,
+            dicts: None,
+            object_interfaces: None,
+        }
+        .to_string();
+        assert_eq!(gen, expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn invalid_schema() {
+    let err = |toml: &str| Schema::from_toml(toml).unwrap_err().to_string();
+
+    assert_eq!(err("interfaces = []"), "No interfaces given");
+    assert!(err(r#"[[interfaces]]
+                   name = "Example""#)
+    .starts_with("Invalid interface name `Example`"));
+    assert!(err(r#"[[interfaces]]
+                   name = "org.zbus.Example"
+                   [[interfaces.methods]]
+                   name = "Get"
+                   in = [{ name = "key", type = "a" }]"#)
+    .starts_with("Invalid type `a` of the `key` argument of `org.zbus.Example.Get`"));
+    assert_eq!(
+        err(r#"[[interfaces]]
+               name = "org.zbus.Example"
+               signals = [{ name = "Changed" }, { name = "Changed" }]"#),
+        "The `Changed` signal of `org.zbus.Example` is declared more than once",
+    );
+    assert!(err(r#"[[interfaces]]
+                   name = "org.zbus.Example"
+                   properties = [{ name = "Count", type = "u", access = "readonly" }]"#)
+    .contains("unknown variant `readonly`"));
+}
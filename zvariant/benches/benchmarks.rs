@@ -1,4 +1,4 @@
-use byteorder::LE;
+use byteorder::{ByteOrder, BE, LE};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -25,6 +25,36 @@ fn fixed_size_array(c: &mut Criterion) {
                     .unwrap();
         })
     });
+
+    // Now arrays of other fixed-size numeric types, in both byte orders.
+    let ai: Vec<i32> = (0..100_000).collect();
+    let ad: Vec<f64> = (0..100_000).map(|i| i as f64 / 3.0).collect();
+    numeric_array(c, "int_array", Context::<LE>::new_dbus(0), &ai);
+    numeric_array(c, "int_array_be", Context::<BE>::new_dbus(0), &ai);
+    numeric_array(c, "double_array", Context::<LE>::new_dbus(0), &ad);
+    numeric_array(c, "double_array_be", Context::<BE>::new_dbus(0), &ad);
+}
+
+fn numeric_array<B, T>(c: &mut Criterion, name: &str, ctxt: Context<B>, array: &Vec<T>)
+where
+    B: ByteOrder,
+    T: Serialize + for<'de> Deserialize<'de> + Type,
+{
+    let signature = Vec::<T>::signature();
+    c.bench_function(&format!("{name}_ser"), |b| {
+        b.iter(|| {
+            to_bytes_for_signature(black_box(ctxt), black_box(&signature), black_box(array))
+                .unwrap()
+        })
+    });
+    let enc = to_bytes_for_signature(ctxt, &signature, array).unwrap();
+    c.bench_function(&format!("{name}_de"), |b| {
+        b.iter(|| {
+            let _: (Vec<T>, _) =
+                from_slice_for_signature(black_box(&enc), black_box(ctxt), black_box(&signature))
+                    .unwrap();
+        })
+    });
 }
 
 fn big_array_ser_and_de(c: &mut Criterion) {
@@ -32,6 +32,11 @@ impl ContainerDepths {
         self
     }
 
+    #[cfg(feature = "gvariant")]
+    pub fn in_structure(self) -> bool {
+        self.structure > 0
+    }
+
     pub fn inc_array(mut self) -> Result<Self> {
         self.array += 1;
         self.check()
@@ -7,8 +7,10 @@ use std::{marker::PhantomData, str};
 use std::os::unix::io::RawFd;
 
 use crate::{
-    de::ValueParseStage, signature_parser::SignatureParser, utils::*, Basic, EncodingContext,
-    EncodingFormat, Error, ObjectPath, Result, Signature,
+    de::{UnitsDeserializer, ValueParseStage},
+    signature_parser::SignatureParser,
+    utils::*,
+    Basic, EncodingContext, EncodingFormat, Error, ObjectPath, Result, Signature,
 };
 
 #[cfg(unix)]
@@ -153,10 +155,26 @@ where
     deserialize_as!(deserialize_string => deserialize_str);
     deserialize_as!(deserialize_tuple(_l: usize) => deserialize_struct("", &[]));
     deserialize_as!(deserialize_tuple_struct(n: &'static str, _l: usize) => deserialize_struct(n, &[]));
-    deserialize_as!(deserialize_struct(_n: &'static str, _f: &'static [&'static str]) => deserialize_seq());
     deserialize_as!(deserialize_map => deserialize_seq);
     deserialize_as!(deserialize_ignored_any => deserialize_any);
 
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.sig_parser.done() || self.0.sig_parser.next_char()? == STRUCT_SIG_END_CHAR {
+            // Nothing left in the signature for this struct, so all its fields must be units.
+            return visitor.visit_seq(UnitsDeserializer);
+        }
+
+        self.deserialize_seq(visitor)
+    }
+
     fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
@@ -297,7 +315,10 @@ where
                 self.0.sig_parser.skip_char()?;
 
                 self.0.container_depths = self.0.container_depths.inc_structure()?;
-                let v = visitor.visit_seq(StructureDeserializer { de: self });
+                let v = visitor.visit_seq(StructureDeserializer {
+                    de: self,
+                    ended: false,
+                });
                 self.0.container_depths = self.0.container_depths.dec_structure();
 
                 v
@@ -306,7 +327,10 @@ where
                 // Empty struct: encoded as a `0u8`.
                 let _: u8 = serde::Deserialize::deserialize(&mut *self)?;
 
-                visitor.visit_seq(StructureDeserializer { de: self })
+                visitor.visit_seq(StructureDeserializer {
+                    de: self,
+                    ended: true,
+                })
             }
             c => Err(de::Error::invalid_type(
                 de::Unexpected::Char(c),
@@ -378,6 +402,8 @@ struct ArrayDeserializer<'d, 'de, 'sig, 'f, B> {
     element_alignment: usize,
     // where value signature starts
     element_signature_len: usize,
+    // Signature character of the element if it's a fixed-size numeric type.
+    fixed_size_element: Option<char>,
 }
 
 impl<'d, 'de, 'sig, 'f, B> ArrayDeserializer<'d, 'de, 'sig, 'f, B>
@@ -392,6 +418,7 @@ where
         let element_signature = de.0.sig_parser.next_signature()?;
         let element_alignment = alignment_for_signature(&element_signature, EncodingFormat::DBus)?;
         let mut element_signature_len = element_signature.len();
+        let fixed_size_element = fixed_size_numeric_char(&element_signature);
 
         // D-Bus requires padding for the first element even when there is no first element
         // (i-e empty array) so we parse padding already.
@@ -409,12 +436,20 @@ where
             start,
             element_alignment,
             element_signature_len,
+            fixed_size_element,
         })
     }
 
     fn next<T>(&mut self, seed: T, sig_parser: SignatureParser<'_>) -> Result<T::Value>
     where
         T: DeserializeSeed<'de>,
+    {
+        self.next_with(sig_parser, |de| seed.deserialize(de))
+    }
+
+    fn next_with<'s, F, R>(&mut self, sig_parser: SignatureParser<'s>, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut Deserializer<'de, 's, 'f, B>) -> Result<R>,
     {
         let ctxt = EncodingContext::new_dbus(self.de.0.ctxt.position() + self.de.0.pos);
 
@@ -427,7 +462,7 @@ where
             container_depths: self.de.0.container_depths,
             b: PhantomData,
         });
-        let v = seed.deserialize(&mut de);
+        let v = f(&mut de);
         self.de.0.pos += de.0.pos;
         // No need for retaking the container depths as the child can't be incomplete.
 
@@ -464,6 +499,23 @@ where
         self.next(seed, sig_parser).map(Some)
     }
 
+    // Get the bytes of the next array element of `size` bytes.
+    //
+    // Only to be used for fixed-size numeric elements, which never need any padding.
+    fn next_fixed_size_slice(&mut self, size: usize) -> Result<&'de [u8]> {
+        let end = self.de.0.pos + size;
+        if end > self.start + self.len {
+            return Err(serde::de::Error::invalid_length(
+                self.len,
+                &format!(">= {}", end - self.start).as_str(),
+            ));
+        }
+        let slice = subslice(self.de.0.bytes, self.de.0.pos..end)?;
+        self.de.0.pos = end;
+
+        Ok(slice)
+    }
+
     fn done(&self) -> bool {
         self.de.0.pos == self.start + self.len
     }
@@ -496,8 +548,159 @@ where
     where
         T: DeserializeSeed<'de>,
     {
-        let sig_parser = self.0.de.0.sig_parser.clone();
-        self.0.next_element(seed, sig_parser)
+        match self.0.fixed_size_element {
+            Some(signature_char) if !self.0.done() => seed
+                .deserialize(FixedSizeElementDeserializer {
+                    array: &mut self.0,
+                    signature_char,
+                })
+                .map(Some),
+            _ => {
+                let sig_parser = self.0.de.0.sig_parser.clone();
+                self.0.next_element(seed, sig_parser)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        // The size of fixed-size numeric types is the same as their alignment.
+        self.0.fixed_size_element.map(|_| {
+            (self.0.start + self.0.len).saturating_sub(self.0.de.0.pos) / self.0.element_alignment
+        })
+    }
+}
+
+// Deserializer for the elements of arrays of fixed-size numeric types.
+//
+// Such elements are read straight from the array bytes, without the overhead of setting up a full
+// `Deserializer` for each element. Any request not matching the element type is handed to one.
+struct FixedSizeElementDeserializer<'a, 'd, 'de, 'sig, 'f, B> {
+    array: &'a mut ArrayDeserializer<'d, 'de, 'sig, 'f, B>,
+    signature_char: char,
+}
+
+impl<'a, 'd, 'de, 'sig, 'f, B> FixedSizeElementDeserializer<'a, 'd, 'de, 'sig, 'f, B>
+where
+    B: byteorder::ByteOrder,
+{
+    fn next_slice<T>(&mut self) -> Result<Option<&'de [u8]>>
+    where
+        T: Basic,
+    {
+        if self.signature_char != T::SIGNATURE_CHAR {
+            return Ok(None);
+        }
+
+        self.array
+            .next_fixed_size_slice(T::alignment(EncodingFormat::DBus))
+            .map(Some)
+    }
+
+    fn fallback<F, R>(self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut Deserializer<'de, 'sig, 'f, B>) -> Result<R>,
+    {
+        let sig_parser = self.array.de.0.sig_parser.clone();
+
+        self.array.next_with(sig_parser, f)
+    }
+}
+
+macro_rules! deserialize_fixed_size {
+    ($method:ident $read_method:ident $visitor_method:ident($type:ty)) => {
+        fn $method<V>(mut self, visitor: V) -> Result<V::Value>
+        where
+            V: Visitor<'de>,
+        {
+            match self.next_slice::<$type>()? {
+                Some(bytes) => visitor.$visitor_method(B::$read_method(bytes)),
+                None => self.fallback(|de| de.$method(visitor)),
+            }
+        }
+    };
+}
+
+macro_rules! deserialize_fallback {
+    ($($method:ident($($arg:ident: $type:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V>(self, $($arg: $type,)* visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                self.fallback(|de| de.$method($($arg,)* visitor))
+            }
+        )*
+    };
+}
+
+impl<'a, 'd, 'de, 'sig, 'f, B> de::Deserializer<'de>
+    for FixedSizeElementDeserializer<'a, 'd, 'de, 'sig, 'f, B>
+where
+    B: byteorder::ByteOrder,
+{
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let c = self.signature_char;
+
+        crate::de::deserialize_any::<Self, V>(self, c, visitor)
+    }
+
+    fn deserialize_u8<V>(mut self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_slice::<u8>()? {
+            Some(bytes) => visitor.visit_u8(bytes[0]),
+            None => self.fallback(|de| de.deserialize_u8(visitor)),
+        }
+    }
+
+    fn deserialize_f32<V>(mut self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_slice::<f64>()? {
+            Some(bytes) => visitor.visit_f32(f64_to_f32(B::read_f64(bytes))),
+            None => self.fallback(|de| de.deserialize_f32(visitor)),
+        }
+    }
+
+    deserialize_fixed_size!(deserialize_i16 read_i16 visit_i16(i16));
+    deserialize_fixed_size!(deserialize_u16 read_u16 visit_u16(u16));
+    deserialize_fixed_size!(deserialize_i32 read_i32 visit_i32(i32));
+    deserialize_fixed_size!(deserialize_u32 read_u32 visit_u32(u32));
+    deserialize_fixed_size!(deserialize_i64 read_i64 visit_i64(i64));
+    deserialize_fixed_size!(deserialize_u64 read_u64 visit_u64(u64));
+    deserialize_fixed_size!(deserialize_f64 read_f64 visit_f64(f64));
+
+    deserialize_fallback!(
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(n: &'static str),
+        deserialize_newtype_struct(n: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(l: usize),
+        deserialize_tuple_struct(n: &'static str, l: usize),
+        deserialize_map(),
+        deserialize_struct(n: &'static str, f: &'static [&'static str]),
+        deserialize_enum(n: &'static str, v: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    );
+
+    fn is_human_readable(&self) -> bool {
+        false
     }
 }
 
@@ -531,6 +734,8 @@ where
 #[derive(Debug)]
 struct StructureDeserializer<'d, 'de, 'sig, 'f, B> {
     de: &'d mut Deserializer<'de, 'sig, 'f, B>,
+    // Whether the end of the struct signature was already reached. Any fields left are units.
+    ended: bool,
 }
 
 impl<'d, 'de, 'sig, 'f, B> SeqAccess<'de> for StructureDeserializer<'d, 'de, 'sig, 'f, B>
@@ -543,11 +748,16 @@ where
     where
         T: DeserializeSeed<'de>,
     {
+        if self.ended {
+            return seed.deserialize(UnitsDeserializer).map(Some);
+        }
+
         let v = seed.deserialize(&mut *self.de).map(Some);
 
         if self.de.0.sig_parser.next_char()? == STRUCT_SIG_END_CHAR {
             // Last item in the struct
             self.de.0.sig_parser.skip_char()?;
+            self.ended = true;
         }
 
         v
@@ -262,6 +262,7 @@ where
         let element_signature = self.0.sig_parser.next_signature()?;
         let element_signature_len = element_signature.len();
         let element_alignment = alignment_for_signature(&element_signature, self.0.ctxt.format())?;
+        let fixed_size_element = fixed_size_numeric_char(&element_signature);
 
         // D-Bus expects us to add padding for the first element even when there is no first
         // element (i-e empty array) so we add padding already.
@@ -275,6 +276,7 @@ where
             element_alignment,
             element_signature_len,
             first_padding,
+            fixed_size_element,
         })
     }
 
@@ -311,6 +313,11 @@ where
             return StructSerializer::unit(self).map(StructSeqSerializer::Struct);
         }
 
+        if self.0.sig_parser.done() || self.0.sig_parser.next_char()? == STRUCT_SIG_END_CHAR {
+            // Nothing left in the signature for this struct, so all its fields must be units.
+            return StructSerializer::unit_fields(self).map(StructSeqSerializer::Struct);
+        }
+
         match self.0.sig_parser.next_char()? {
             VARIANT_SIGNATURE_CHAR => {
                 StructSerializer::variant(self).map(StructSeqSerializer::Struct)
@@ -347,6 +354,8 @@ pub struct SeqSerializer<'ser, 'sig, 'b, B, W> {
     element_signature_len: usize,
     // First element's padding
     first_padding: usize,
+    // Signature character of the element if it's a fixed-size numeric type.
+    fixed_size_element: Option<char>,
 }
 
 impl<'ser, 'sig, 'b, B, W> SeqSerializer<'ser, 'sig, 'b, B, W>
@@ -398,6 +407,13 @@ where
     where
         T: ?Sized + Serialize,
     {
+        if let Some(signature_char) = self.fixed_size_element {
+            return value.serialize(FixedSizeElementSerializer {
+                ser: &mut *self.ser,
+                signature_char,
+            });
+        }
+
         // We want to keep parsing the same signature repeatedly for each element so we use a
         // disposable clone.
         let sig_parser = self.ser.0.sig_parser.clone();
@@ -414,6 +430,211 @@ where
     }
 }
 
+// Serializer for the elements of arrays of fixed-size numeric types.
+//
+// Such elements are written straight to the output, without going through the signature parsing and
+// padding logic of the `Serializer` for each element. Any other request is handed to the latter.
+struct FixedSizeElementSerializer<'s, 'ser, 'sig, B, W> {
+    ser: &'s mut Serializer<'ser, 'sig, B, W>,
+    signature_char: char,
+}
+
+impl<'s, 'ser, 'sig, B, W> FixedSizeElementSerializer<'s, 'ser, 'sig, B, W>
+where
+    B: byteorder::ByteOrder,
+    W: Write + Seek,
+{
+    fn fallback<F>(self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut Serializer<'ser, 'sig, B, W>) -> Result<()>,
+    {
+        // Same as in `SeqSerializer::serialize_element`.
+        let sig_parser = self.ser.0.sig_parser.clone();
+        f(&mut *self.ser)?;
+        self.ser.0.sig_parser = sig_parser;
+
+        Ok(())
+    }
+
+    fn mismatch<T>(self) -> Result<T> {
+        Err(Error::SignatureMismatch(
+            Signature::from_string_unchecked(self.signature_char.to_string()),
+            "a container signature".to_string(),
+        ))
+    }
+}
+
+macro_rules! serialize_fixed_size {
+    ($method:ident($type:ty) $write_method:ident) => {
+        serialize_fixed_size!($method($type) $write_method($type));
+    };
+    ($method:ident($type:ty) $write_method:ident($as:ty)) => {
+        fn $method(self, v: $type) -> Result<()> {
+            if self.signature_char != <$as>::SIGNATURE_CHAR {
+                return self.fallback(|ser| ser.$method(v));
+            }
+
+            self.ser
+                .0
+                .$write_method::<B>(v as $as)
+                .map_err(|e| Error::InputOutput(e.into()))
+        }
+    };
+}
+
+impl<'s, 'ser, 'sig, B, W> ser::Serializer for FixedSizeElementSerializer<'s, 'ser, 'sig, B, W>
+where
+    B: byteorder::ByteOrder,
+    W: Write + Seek,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<(), Error>;
+    type SerializeTuple = ser::Impossible<(), Error>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = ser::Impossible<(), Error>;
+    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    serialize_fixed_size!(serialize_i16(i16) write_i16);
+    serialize_fixed_size!(serialize_u16(u16) write_u16);
+    serialize_fixed_size!(serialize_i32(i32) write_i32);
+    serialize_fixed_size!(serialize_u32(u32) write_u32);
+    serialize_fixed_size!(serialize_i64(i64) write_i64);
+    serialize_fixed_size!(serialize_u64(u64) write_u64);
+    serialize_fixed_size!(serialize_f32(f32) write_f64(f64));
+    serialize_fixed_size!(serialize_f64(f64) write_f64);
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        if self.signature_char != u8::SIGNATURE_CHAR {
+            return self.fallback(|ser| ser.serialize_u8(v));
+        }
+
+        self.ser
+            .0
+            .write_u8(v)
+            .map_err(|e| Error::InputOutput(e.into()))
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.fallback(|ser| ser.serialize_bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.fallback(|ser| ser.serialize_i8(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.fallback(|ser| ser.serialize_char(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.fallback(|ser| ser.serialize_str(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.fallback(|ser| ser.serialize_bytes(v))
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.fallback(|ser| ser.serialize_none())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.fallback(|ser| ser.serialize_some(value))
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.fallback(|ser| ser.serialize_unit())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<()> {
+        self.fallback(|ser| ser.serialize_unit_struct(name))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<()> {
+        self.fallback(|ser| ser.serialize_unit_variant(name, variant_index, variant))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.fallback(|ser| ser.serialize_newtype_variant(name, variant_index, variant, value))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        self.mismatch()
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        self.mismatch()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.mismatch()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        self.mismatch()
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        self.mismatch()
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        self.mismatch()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        self.mismatch()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
 #[doc(hidden)]
 pub struct StructSerializer<'ser, 'sig, 'b, B, W> {
     ser: &'b mut Serializer<'ser, 'sig, B, W>,
@@ -478,6 +699,16 @@ where
         })
     }
 
+    fn unit_fields(ser: &'b mut Serializer<'ser, 'sig, B, W>) -> Result<Self> {
+        // Units are encoded as nothing, so there is no padding or signature to handle.
+        let container_depths = ser.0.container_depths;
+        Ok(Self {
+            ser,
+            end_parens: 0,
+            container_depths,
+        })
+    }
+
     fn enum_variant(ser: &'b mut Serializer<'ser, 'sig, B, W>) -> Result<Self> {
         let mut ser = Self::structure(ser)?;
         ser.end_parens += 1;
@@ -1,6 +1,6 @@
 use serde::{
-    de::{self, DeserializeSeed, VariantAccess, Visitor},
-    Deserialize,
+    de::{self, DeserializeSeed, SeqAccess, VariantAccess, Visitor},
+    forward_to_deserialize_any, Deserialize,
 };
 use static_assertions::assert_impl_all;
 
@@ -75,6 +75,31 @@ where
 /// assert_eq!(decoded, "hello world");
 /// ```
 ///
+/// Strings (as well as object paths and signatures) can be borrowed from `bytes`, including the
+/// elements of arrays, so no allocation is needed for each of them:
+///
+/// ```
+/// # use zvariant::{to_bytes, from_slice, EncodingContext};
+/// #
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &vec!["hello", "world"]).unwrap();
+/// let decoded: Vec<&str> = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(decoded, ["hello", "world"]);
+/// assert!(encoded.as_ptr_range().contains(&decoded[1].as_ptr()));
+/// ```
+///
+/// The borrowed strings can't outlive `bytes`:
+///
+/// ```compile_fail
+/// # use zvariant::{to_bytes, from_slice, EncodingContext};
+/// #
+/// let ctxt = EncodingContext::<byteorder::LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &vec!["hello", "world"]).unwrap();
+/// let decoded: Vec<&str> = from_slice(&encoded, ctxt).unwrap().0;
+/// drop(encoded);
+/// assert_eq!(decoded, ["hello", "world"]);
+/// ```
+///
 /// # Return value
 ///
 /// A tuple containing the deserialized value and the number of bytes parsed from `bytes`.
@@ -706,3 +731,77 @@ where
         de::Deserializer::deserialize_struct(self.de, self.name, fields, visitor)
     }
 }
+
+// Deserializes the fields of a struct that are left once the end of its signature is reached.
+// These can only be units, or structs of units, since they have no signature and no encoding.
+pub(crate) struct UnitsDeserializer;
+
+impl<'de> de::Deserializer<'de> for UnitsDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(self)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(self)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf option unit
+        unit_struct seq map enum identifier ignored_any
+    }
+}
+
+impl<'de> SeqAccess<'de> for UnitsDeserializer {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        // The caller knows how many fields are left.
+        seed.deserialize(UnitsDeserializer).map(Some)
+    }
+}
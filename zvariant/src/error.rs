@@ -32,6 +32,49 @@ impl fmt::Display for MaxDepthExceeded {
     }
 }
 
+/// Details on why a signature is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidSignature {
+    signature: String,
+    position: usize,
+    reason: String,
+}
+
+impl InvalidSignature {
+    pub(crate) fn new(signature: &[u8], position: usize, reason: impl Into<String>) -> Self {
+        Self {
+            signature: String::from_utf8_lossy(signature).into_owned(),
+            position,
+            reason: reason.into(),
+        }
+    }
+
+    /// The invalid signature.
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// The (0-based) position in the signature where the problem was found.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// What is wrong at [`InvalidSignature::position`].
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for InvalidSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid signature `{}` at position {}: {}",
+            self.signature, self.position, self.reason,
+        )
+    }
+}
+
 /// Error type used by zvariant API.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -60,6 +103,8 @@ pub enum Error {
     OutOfBounds,
     /// The maximum allowed depth for containers in encoding was exceeded.
     MaxDepthExceeded(MaxDepthExceeded),
+    /// The signature is not a valid D-Bus (or GVariant) signature.
+    InvalidSignature(InvalidSignature),
 }
 
 assert_impl_all!(Error: Send, Sync, Unpin);
@@ -74,6 +119,7 @@ impl PartialEq for Error {
             (Error::PaddingNot0(p), Error::PaddingNot0(other)) => p == other,
             (Error::UnknownFd, Error::UnknownFd) => true,
             (Error::MaxDepthExceeded(max1), Error::MaxDepthExceeded(max2)) => max1 == max2,
+            (Error::InvalidSignature(e1), Error::InvalidSignature(e2)) => e1 == e2,
             (_, _) => false,
         }
     }
@@ -115,6 +161,7 @@ impl fmt::Display for Error {
                 "Out of bounds range specified",
             ),
             Error::MaxDepthExceeded(max) => write!(f, "{max}"),
+            Error::InvalidSignature(e) => write!(f, "{e}"),
         }
     }
 }
@@ -137,6 +184,7 @@ impl Clone for Error {
             }
             Error::OutOfBounds => Error::OutOfBounds,
             Error::MaxDepthExceeded(max) => Error::MaxDepthExceeded(*max),
+            Error::InvalidSignature(e) => Error::InvalidSignature(e.clone()),
         }
     }
 }
@@ -7,9 +7,12 @@ use std::{ffi::CStr, marker::PhantomData, str};
 use std::os::unix::io::RawFd;
 
 use crate::{
-    de::ValueParseStage, framing_offset_size::FramingOffsetSize, framing_offsets::FramingOffsets,
-    signature_parser::SignatureParser, utils::*, Basic, EncodingContext, EncodingFormat, Error,
-    Result, Signature,
+    de::{UnitsDeserializer, ValueParseStage},
+    framing_offset_size::FramingOffsetSize,
+    framing_offsets::FramingOffsets,
+    signature_parser::SignatureParser,
+    utils::*,
+    Basic, EncodingContext, EncodingFormat, Error, Result, Signature,
 };
 
 /// Our GVariant deserialization implementation.
@@ -145,10 +148,26 @@ where
     deserialize_as!(deserialize_string => deserialize_str);
     deserialize_as!(deserialize_tuple(_l: usize) => deserialize_struct("", &[]));
     deserialize_as!(deserialize_tuple_struct(n: &'static str, _l: usize) => deserialize_struct(n, &[]));
-    deserialize_as!(deserialize_struct(_n: &'static str, _f: &'static [&'static str]) => deserialize_seq());
     deserialize_as!(deserialize_map => deserialize_seq);
     deserialize_as!(deserialize_ignored_any => deserialize_any);
 
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.sig_parser.done() || self.0.sig_parser.next_char()? == STRUCT_SIG_END_CHAR {
+            // Nothing left in the signature for this struct, so all its fields must be units.
+            return visitor.visit_seq(UnitsDeserializer);
+        }
+
+        self.deserialize_seq(visitor)
+    }
+
     fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
@@ -248,6 +267,11 @@ where
     where
         V: Visitor<'de>,
     {
+        if self.0.container_depths.in_structure() {
+            // Units have no signature, so they're not part of the encoding of structs.
+            return visitor.visit_unit();
+        }
+
         let byte = *subslice(self.0.bytes, self.0.pos)?;
         if byte != 0 {
             return Err(de::Error::invalid_value(
@@ -315,6 +339,7 @@ where
                     end,
                     offsets_len: 0,
                     offset_size,
+                    ended: false,
                 });
                 self.0.container_depths = self.0.container_depths.dec_structure();
 
@@ -332,6 +357,7 @@ where
                     end,
                     offsets_len: 0,
                     offset_size: FramingOffsetSize::U8,
+                    ended: true,
                 })
             }
             c => Err(de::Error::invalid_type(
@@ -672,6 +698,8 @@ struct StructureDeserializer<'d, 'de, 'sig, 'f, B> {
     offsets_len: usize,
     // size of the framing offset
     offset_size: FramingOffsetSize,
+    // Whether the end of the struct signature was already reached. Any fields left are units.
+    ended: bool,
 }
 
 impl<'d, 'de, 'sig, 'f, B> SeqAccess<'de> for StructureDeserializer<'d, 'de, 'sig, 'f, B>
@@ -684,6 +712,10 @@ where
     where
         T: DeserializeSeed<'de>,
     {
+        if self.ended {
+            return seed.deserialize(UnitsDeserializer).map(Some);
+        }
+
         let ctxt = EncodingContext::new(
             self.de.0.ctxt.format(),
             self.de.0.ctxt.position() + self.de.0.pos,
@@ -739,6 +771,7 @@ where
 
             // Skip over the framing offsets (if any)
             self.de.0.pos += self.offsets_len;
+            self.ended = true;
         }
 
         self.de.0.sig_parser = de.0.sig_parser;
@@ -211,6 +211,11 @@ where
     }
 
     fn serialize_unit(self) -> Result<()> {
+        if self.0.container_depths.in_structure() {
+            // Units have no signature, so they're not part of the encoding of structs.
+            return Ok(());
+        }
+
         self.0
             .write_all(&b"\0"[..])
             .map_err(|e| Error::InputOutput(e.into()))
@@ -320,6 +325,11 @@ where
             return StructSerializer::unit(self).map(StructSeqSerializer::Struct);
         }
 
+        if self.0.sig_parser.done() || self.0.sig_parser.next_char()? == STRUCT_SIG_END_CHAR {
+            // Nothing left in the signature for this struct, so all its fields must be units.
+            return StructSerializer::unit_fields(self).map(StructSeqSerializer::Struct);
+        }
+
         match self.0.sig_parser.next_char()? {
             VARIANT_SIGNATURE_CHAR => {
                 StructSerializer::variant(self).map(StructSeqSerializer::Struct)
@@ -509,6 +519,22 @@ where
         })
     }
 
+    fn unit_fields(ser: &'b mut Serializer<'ser, 'sig, B, W>) -> Result<Self> {
+        // Units are encoded as nothing, so there is no padding, framing offsets or signature to
+        // handle.
+        let start = ser.0.bytes_written;
+        let container_depths = ser.0.container_depths;
+        ser.0.container_depths = ser.0.container_depths.inc_structure()?;
+
+        Ok(Self {
+            ser,
+            end_parens: 0,
+            offsets: None,
+            start,
+            container_depths,
+        })
+    }
+
     fn enum_variant(ser: &'b mut Serializer<'ser, 'sig, B, W>) -> Result<Self> {
         let mut ser = Self::structure(ser)?;
         ser.end_parens += 1;
@@ -558,6 +584,12 @@ where
 
                 Ok(())
             }
+            _ if self.ser.0.sig_parser.done()
+                || self.ser.0.sig_parser.next_char()? == STRUCT_SIG_END_CHAR =>
+            {
+                // Nothing left in the signature for this struct, so this field must be a unit.
+                value.serialize(&mut *self.ser)
+            }
             _ => {
                 let element_signature = self.ser.0.sig_parser.next_signature()?;
                 let fixed_sized_element =
@@ -90,6 +90,11 @@ pub use into_value::*;
 mod owned_value;
 pub use owned_value::*;
 
+#[cfg(feature = "timestamp")]
+mod timestamp;
+#[cfg(feature = "timestamp")]
+pub use timestamp::*;
+
 #[cfg(feature = "gvariant")]
 mod framing_offset_size;
 #[cfg(feature = "gvariant")]
@@ -98,7 +103,9 @@ mod signature_parser;
 
 mod container_depths;
 
-pub use zvariant_derive::{DeserializeDict, OwnedValue, SerializeDict, Type, Value};
+pub use zvariant_derive::{
+    DeserializeDict, DeserializeVariant, OwnedValue, SerializeDict, Type, Value,
+};
 
 // Required for the macros to function within this crate.
 extern crate self as zvariant;
@@ -513,6 +520,27 @@ mod tests {
         assert_eq!(&decoded, "hello world!");
     }
 
+    #[test]
+    fn borrowed_str_array() {
+        let strings: Vec<String> = (0..100).map(|i| format!("string {i}")).collect();
+
+        fn check<B: ByteOrder>(ctxt: Context<B>, strings: &[String]) {
+            let encoded = to_bytes(ctxt, &strings).unwrap();
+            let decoded: Vec<&str> = from_slice(&encoded, ctxt).unwrap().0;
+            assert_eq!(decoded, strings);
+            // No copies, all the strings point into the encoded data.
+            let range = encoded.as_ptr_range();
+            for s in &decoded {
+                assert!(range.contains(&s.as_ptr()));
+            }
+        }
+
+        check(Context::<LE>::new_dbus(0), &strings);
+        check(Context::<BE>::new_dbus(0), &strings);
+        #[cfg(feature = "gvariant")]
+        check(Context::<LE>::new_gvariant(0), &strings);
+    }
+
     #[test]
     fn signature_value() {
         let sig = Signature::try_from("yys").unwrap();
@@ -598,6 +626,121 @@ mod tests {
             .0;
     }
 
+    #[test]
+    fn unit_fields() {
+        // D-Bus has no empty structs so tuples of units are units too.
+        assert_eq!(<((),)>::signature(), "");
+        assert_eq!(<((), ((),))>::signature(), "");
+        assert_eq!(<(u32, ())>::signature(), "(u)");
+        assert_eq!(<(u32, ((),))>::signature(), "(u)");
+        assert_eq!(<((u32, ()), ())>::signature(), "((u))");
+        assert!(Signature::try_from(<(u32, ((),))>::signature().as_str()).is_ok());
+
+        let ctxt = Context::<LE>::new_dbus(0);
+        check_unit_fields(ctxt);
+        let s = (1u8, (2u32, ()), ());
+        let encoded = to_bytes(ctxt, &s).unwrap();
+        assert_eq!(encoded, [1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0]);
+        let decoded: (u8, (u32, ()), ()) = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, s);
+
+        // The units are not encoded in GVariant format either.
+        #[cfg(feature = "gvariant")]
+        {
+            let ctxt = Context::<LE>::new_gvariant(0);
+            check_unit_fields(ctxt);
+            let s = (1u8, (2u32, ()), ());
+            let encoded = to_bytes(ctxt, &s).unwrap();
+            assert_eq!(encoded, [1, 0, 0, 0, 2, 0, 0, 0]);
+            let decoded: (u8, (u32, ()), ()) = from_slice(&encoded, ctxt).unwrap().0;
+            assert_eq!(decoded, s);
+        }
+    }
+
+    fn check_unit_fields(ctxt: Context<LE>) {
+        let encoded = to_bytes(ctxt, &((), ((),))).unwrap();
+        assert_eq!(encoded.len(), 0);
+        let _: ((), ((),)) = from_slice(&encoded, ctxt).unwrap().0;
+
+        let s = (7u32, ((),));
+        let encoded = to_bytes(ctxt, &s).unwrap();
+        assert_eq!(encoded, [7, 0, 0, 0]);
+        let decoded: (u32, ((),)) = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, s);
+
+        let s = ((), 7u32, ());
+        let encoded = to_bytes(ctxt, &s).unwrap();
+        assert_eq!(encoded, [7, 0, 0, 0]);
+        let decoded: ((), u32, ()) = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, s);
+
+        // Units after the end of a struct signature are not confused with the following fields.
+        let s = ((2u32, ((),)), 3u8);
+        let encoded = to_bytes(ctxt, &s).unwrap();
+        assert_eq!(encoded, [2, 0, 0, 0, 3]);
+        let decoded: ((u32, ((),)), u8) = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, s);
+    }
+
+    #[test]
+    fn fixed_size_numeric_array() {
+        // In D-Bus format, elements of arrays of fixed-size numeric types skip signature parsing.
+        let ai = vec![1i32, -2];
+        let ctxt = Context::<BE>::new_dbus(0);
+        let encoded = to_bytes(ctxt, &ai).unwrap();
+        assert_eq!(
+            encoded,
+            [0, 0, 0, 8, 0, 0, 0, 1, 0xFF, 0xFF, 0xFF, 0xFE],
+            "invalid big-endian encoding"
+        );
+        let decoded: Vec<i32> = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, ai);
+
+        // Padding before the elements and non-native byte order.
+        let s = (
+            7u8,
+            vec![1.5f64, -2.25],
+            vec![0xABBAu16, 0x00FF],
+            vec![u64::MAX, 0],
+        );
+        for (ctxt, len) in [
+            (Context::<BE>::new_dbus(0), 56),
+            (Context::<BE>::new_dbus(3), 61),
+        ] {
+            let encoded = to_bytes(ctxt, &s).unwrap();
+            assert_eq!(encoded.len(), len);
+            let decoded: (u8, Vec<f64>, Vec<u16>, Vec<u64>) = from_slice(&encoded, ctxt).unwrap().0;
+            assert_eq!(decoded, s);
+            let le_encoded = to_bytes(Context::<LE>::new_dbus(ctxt.position()), &s).unwrap();
+            assert_ne!(encoded, le_encoded);
+        }
+
+        // Through `Value`, which deserializes its elements without knowing their type.
+        let v = Value::from(vec![-5i64, 6]);
+        let encoded = to_bytes(ctxt, &v).unwrap();
+        let decoded: Value<'_> = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, v);
+        assert_eq!(<Vec<i64>>::try_from(decoded).unwrap(), [-5, 6]);
+
+        // Elements of other types than the one in the signature are handled as before.
+        #[derive(Deserialize, Serialize, Type, PartialEq, Debug)]
+        struct Newtype(u32);
+        let au = vec![Newtype(1), Newtype(2)];
+        let encoded = to_bytes(ctxt, &au).unwrap();
+        let decoded: Vec<Newtype> = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, au);
+        let ab = vec![true, false];
+        let encoded = to_bytes(ctxt, &ab).unwrap();
+        let decoded: Vec<bool> = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, ab);
+
+        // Truncated data.
+        let encoded = to_bytes(ctxt, &ai).unwrap();
+        let mut truncated = encoded[..encoded.len() - 1].to_vec();
+        truncated[3] = 7;
+        from_slice::<_, Vec<i32>>(&truncated, ctxt).unwrap_err();
+    }
+
     #[test]
     fn array_value() {
         // Let's use D-Bus/GVariant terms
@@ -1066,6 +1209,44 @@ mod tests {
         assert_eq!(decoded, [1u32, 2u32]);
     }
 
+    #[test]
+    fn dict_non_string_keys() {
+        use crate::{OwnedObjectPath, OwnedValue};
+
+        let ctxt = Context::<LE>::new_dbus(0);
+
+        // a{uv}
+        let mut map: HashMap<u32, OwnedValue> = HashMap::new();
+        map.insert(1, Value::from("one").into());
+        map.insert(2, Value::from(2u8).into());
+        assert_eq!(<HashMap<u32, OwnedValue>>::signature(), "a{uv}");
+        let encoded = to_bytes(ctxt, &map).unwrap();
+        let decoded: HashMap<u32, OwnedValue> = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, map);
+        let value = OwnedValue::from(Value::from(map.clone()));
+        assert_eq!(HashMap::<u32, OwnedValue>::try_from(value).unwrap(), map);
+
+        // a{ov}
+        let mut map: HashMap<OwnedObjectPath, OwnedValue> = HashMap::new();
+        let path = OwnedObjectPath::try_from("/org/zbus/One").unwrap();
+        map.insert(path, Value::from("one").into());
+        let path = OwnedObjectPath::try_from("/org/zbus/Two").unwrap();
+        map.insert(path, Value::from(2u8).into());
+        assert_eq!(<HashMap<OwnedObjectPath, OwnedValue>>::signature(), "a{ov}");
+        let encoded = to_bytes(ctxt, &map).unwrap();
+        let decoded: HashMap<OwnedObjectPath, OwnedValue> = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, map);
+        let decoded: HashMap<ObjectPath<'_>, Value<'_>> = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(
+            decoded[&ObjectPath::try_from("/org/zbus/Two").unwrap()],
+            Value::U8(2)
+        );
+        let value = OwnedValue::from(Value::from(map.clone()));
+        assert_eq!(value.value_signature(), "a{ov}");
+        let decoded = HashMap::<OwnedObjectPath, OwnedValue>::try_from(value).unwrap();
+        assert_eq!(decoded, map);
+    }
+
     #[test]
     fn dict_value() {
         let mut map: HashMap<i64, &str> = HashMap::new();
@@ -1899,6 +2080,54 @@ mod tests {
         assert_eq!(date, decoded);
     }
 
+    #[test]
+    #[cfg(feature = "timestamp")]
+    fn timestamp() {
+        use crate::{Microseconds, OwnedValue, Timestamp};
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let ctxt = Context::<LE>::new_dbus(0);
+        assert_eq!(Timestamp::signature(), "t");
+        assert_eq!(Microseconds::signature(), "t");
+
+        // Round-trip at microsecond precision.
+        let time = Timestamp::new(UNIX_EPOCH + Duration::from_micros(1_600_000_000_123_456));
+        let encoded = to_bytes(ctxt, &time).unwrap();
+        assert_eq!(encoded.len(), 8);
+        assert_eq!(LE::read_u64(&encoded), 1_600_000_000_123_456);
+        let decoded: Timestamp = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, time);
+
+        // Sub-microsecond precision is truncated, out of range times are saturated.
+        let time = Timestamp::new(UNIX_EPOCH + Duration::from_nanos(1_999));
+        assert_eq!(time.as_micros(), 1);
+        let time = Timestamp::new(UNIX_EPOCH - Duration::from_secs(1));
+        let encoded = to_bytes(ctxt, &time).unwrap();
+        let decoded: Timestamp = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded.system_time(), UNIX_EPOCH);
+
+        let duration = Microseconds::new(Duration::from_millis(1_500));
+        let encoded = to_bytes(ctxt, &duration).unwrap();
+        assert_eq!(LE::read_u64(&encoded), 1_500_000);
+        let decoded: Microseconds = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(Duration::from(decoded), Duration::from_millis(1_500));
+        assert_eq!(Microseconds::new(Duration::MAX).as_micros(), u64::MAX);
+
+        // In a structure and through `Value`.
+        let encoded = to_bytes(ctxt, &(Timestamp::from_micros(42).unwrap(), duration)).unwrap();
+        let decoded: (u64, u64) = from_slice(&encoded, ctxt).unwrap().0;
+        assert_eq!(decoded, (42, 1_500_000));
+        let value = Value::from(Timestamp::from_micros(42).unwrap());
+        assert_eq!(value, Value::U64(42));
+        assert_eq!(
+            Timestamp::try_from(value).unwrap(),
+            Timestamp::from_micros(42).unwrap()
+        );
+        let owned = OwnedValue::from(duration);
+        assert_eq!(Microseconds::try_from(owned).unwrap(), duration);
+        assert!(Timestamp::try_from(Value::from("now")).is_err());
+    }
+
     #[test]
     fn recursion_limits() {
         let ctxt = Context::<LE>::new_dbus(0);
@@ -1955,4 +2184,36 @@ mod tests {
         // * Test deserializers.
         // * Test gvariant format.
     }
+
+    #[test]
+    fn signature_depth_limits() {
+        // Array depth exceeds limit (32)
+        let sig = format!("{}y", "a".repeat(254));
+        assert_eq!(
+            Signature::try_from(sig.as_str()),
+            Err(Error::MaxDepthExceeded(MaxDepthExceeded::Array))
+        );
+        let sig = format!("{}y", "a".repeat(33));
+        assert_eq!(
+            Signature::try_from(sig.as_str()),
+            Err(Error::MaxDepthExceeded(MaxDepthExceeded::Array))
+        );
+        assert!(Signature::try_from(&sig[1..]).is_ok());
+
+        // Struct depth exceeds limit (32)
+        let sig = format!("{}y{}", "(".repeat(33), ")".repeat(33));
+        assert_eq!(
+            Signature::try_from(sig.as_str()),
+            Err(Error::MaxDepthExceeded(MaxDepthExceeded::Structure))
+        );
+        let sig = format!("{}y{}", "(".repeat(32), ")".repeat(32));
+        assert!(Signature::try_from(sig.as_str()).is_ok());
+
+        // Dict entries nest in arrays.
+        let sig = format!("{}y{}", "a{sa".repeat(17), "}".repeat(17));
+        assert_eq!(
+            Signature::try_from(sig.as_str()),
+            Err(Error::MaxDepthExceeded(MaxDepthExceeded::Array))
+        );
+    }
 }
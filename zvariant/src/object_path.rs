@@ -112,6 +112,115 @@ impl<'a> ObjectPath<'a> {
     pub fn into_owned(self) -> ObjectPath<'static> {
         ObjectPath(self.0.into_owned())
     }
+
+    /// The parent of this object path.
+    ///
+    /// Returns `None` for the root path (`/`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// let path = ObjectPath::try_from("/org/freedesktop/DBus").unwrap();
+    /// assert_eq!(path.parent().unwrap(), "/org/freedesktop");
+    /// let path = ObjectPath::try_from("/org").unwrap();
+    /// assert_eq!(path.parent().unwrap(), "/");
+    /// assert!(ObjectPath::default().parent().is_none());
+    /// ```
+    pub fn parent(&self) -> Option<ObjectPath<'_>> {
+        let path = self.as_str();
+        if path == "/" {
+            return None;
+        }
+
+        // A valid object path always starts with `/`.
+        let idx = path.rfind('/').unwrap();
+        let parent = if idx == 0 { "/" } else { &path[..idx] };
+
+        Some(ObjectPath::from_str_unchecked(parent))
+    }
+
+    /// If this object path is a descendant of (or the same as) `ancestor`.
+    ///
+    /// Unlike [`str::starts_with`], the comparison is done on whole path elements, so `/org/foo`
+    /// is not a descendant of `/org/fo`. Every path is a descendant of the root path (`/`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// let path = ObjectPath::try_from("/org/freedesktop/DBus").unwrap();
+    /// assert!(path.is_descendant_of(&ObjectPath::try_from("/org/freedesktop").unwrap()));
+    /// assert!(path.is_descendant_of(&ObjectPath::try_from("/").unwrap()));
+    /// assert!(!path.is_descendant_of(&ObjectPath::try_from("/org/free").unwrap()));
+    /// ```
+    pub fn is_descendant_of(&self, ancestor: &ObjectPath<'_>) -> bool {
+        let ancestor = ancestor.as_str();
+        if ancestor == "/" {
+            return true;
+        }
+
+        match self.as_str().strip_prefix(ancestor) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+
+    /// An iterator over the elements of this object path.
+    ///
+    /// The root path (`/`) has no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// let path = ObjectPath::try_from("/org/freedesktop/DBus").unwrap();
+    /// assert_eq!(
+    ///     path.components().collect::<Vec<_>>(),
+    ///     ["org", "freedesktop", "DBus"],
+    /// );
+    /// assert_eq!(ObjectPath::default().components().count(), 0);
+    /// ```
+    pub fn components(&self) -> impl DoubleEndedIterator<Item = &str> + '_ {
+        self.as_str().split('/').filter(|c| !c.is_empty())
+    }
+
+    /// Create a new object path by appending `segment` to this one.
+    ///
+    /// `segment` is relative to `self` and can contain more than one element, separated by `/`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the resulting object path is invalid, e.g. if `segment` is empty, starts or ends
+    /// with a `/` or contains characters not allowed in an object path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::ObjectPath;
+    ///
+    /// let path = ObjectPath::default().join("org").unwrap();
+    /// assert_eq!(path, "/org");
+    /// let path = path.join("freedesktop/DBus").unwrap();
+    /// assert_eq!(path, "/org/freedesktop/DBus");
+    /// path.join("").unwrap_err();
+    /// path.join("/Bus").unwrap_err();
+    /// path.join("Bus.1").unwrap_err();
+    /// ```
+    pub fn join(&self, segment: &str) -> Result<ObjectPath<'static>> {
+        if segment.is_empty() {
+            return Err(serde::de::Error::invalid_length(0, &"> 0 character"));
+        }
+        let path = match self.as_str() {
+            "/" => format!("/{segment}"),
+            path => format!("{path}/{segment}"),
+        };
+
+        ObjectPath::try_from(path)
+    }
 }
 
 impl std::default::Default for ObjectPath<'_> {
@@ -312,6 +421,15 @@ impl OwnedObjectPath {
     }
 }
 
+impl Basic for OwnedObjectPath {
+    const SIGNATURE_CHAR: char = ObjectPath::SIGNATURE_CHAR;
+    const SIGNATURE_STR: &'static str = ObjectPath::SIGNATURE_STR;
+
+    fn alignment(format: EncodingFormat) -> usize {
+        ObjectPath::alignment(format)
+    }
+}
+
 impl std::ops::Deref for OwnedObjectPath {
     type Target = ObjectPath<'static>;
 
@@ -387,4 +505,34 @@ mod unit {
         let json_str = "\"/some/path\"";
         serde_json::de::from_reader::<_, OwnedObjectPath>(json_str.as_bytes()).unwrap();
     }
+
+    #[test]
+    fn tree_navigation() {
+        let root = ObjectPath::try_from("/").unwrap();
+        assert!(root.parent().is_none());
+        assert_eq!(root.components().count(), 0);
+        assert!(root.is_descendant_of(&root));
+
+        let path = ObjectPath::try_from("/a/b_1/c").unwrap();
+        let parent = path.parent().unwrap();
+        assert_eq!(parent, "/a/b_1");
+        assert_eq!(parent.parent().unwrap(), "/a");
+        assert_eq!(parent.parent().unwrap().parent().unwrap(), root);
+        assert_eq!(path.components().collect::<Vec<_>>(), ["a", "b_1", "c"]);
+        assert_eq!(path.components().next_back(), Some("c"));
+
+        assert!(path.is_descendant_of(&root));
+        assert!(path.is_descendant_of(&path));
+        assert!(path.is_descendant_of(&parent));
+        assert!(!path.is_descendant_of(&ObjectPath::try_from("/a/b").unwrap()));
+        assert!(!parent.is_descendant_of(&path));
+
+        assert_eq!(root.join("a").unwrap(), "/a");
+        assert_eq!(parent.join("c").unwrap(), path);
+        assert_eq!(root.join("a/b_1/c").unwrap(), path);
+        root.join("").unwrap_err();
+        for segment in ["", "/", "/c", "c/", "c//d", "c-d"] {
+            parent.join(segment).unwrap_err();
+        }
+    }
 }
@@ -30,6 +30,76 @@ impl OwnedValue {
     pub(crate) fn inner(&self) -> &Value<'_> {
         &self.0
     }
+
+    /// Try to get the underlying type `T`.
+    ///
+    /// This is the conversion of [`TryFrom<OwnedValue>`], except that if the value is not of type
+    /// `T`, the returned [`Error::SignatureMismatch`] names the signature of both the value and
+    /// `T`. Unlike [`Value::downcast`], an enclosed [`Value::Value`] is not unwrapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{Error, OwnedValue, Value};
+    ///
+    /// let v = OwnedValue::from(Value::new(42u32));
+    /// assert_eq!(v.clone().downcast::<u32>().unwrap(), 42);
+    ///
+    /// let e = v.downcast::<String>().unwrap_err();
+    /// assert!(matches!(e, Error::SignatureMismatch(..)));
+    /// assert_eq!(e.to_string(), "Signature mismatch: got `u`, expected `s`");
+    /// ```
+    ///
+    /// [`TryFrom<OwnedValue>`]: https://doc.rust-lang.org/std/convert/trait.TryFrom.html
+    /// [`Error::SignatureMismatch`]: crate::Error::SignatureMismatch
+    pub fn downcast<T>(self) -> crate::Result<T>
+    where
+        T: TryFrom<OwnedValue> + Type,
+        T::Error: Into<crate::Error>,
+    {
+        let signature = self.value_signature().to_owned();
+
+        T::try_from(self).map_err(|e| mismatch_error::<T>(signature, e.into()))
+    }
+
+    /// Try to get a reference to the underlying type `T`.
+    ///
+    /// Same as [`OwnedValue::downcast`] except it doesn't consume `self` and gets a reference to
+    /// the underlying value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::{OwnedValue, Value};
+    ///
+    /// let v = OwnedValue::from(Value::from("hello"));
+    /// assert_eq!(v.downcast_ref::<str>().unwrap(), "hello");
+    ///
+    /// let e = v.downcast_ref::<u32>().unwrap_err();
+    /// assert_eq!(e.to_string(), "Signature mismatch: got `s`, expected `u`");
+    /// ```
+    pub fn downcast_ref<'a, T>(&'a self) -> crate::Result<&'a T>
+    where
+        T: Type + ?Sized,
+        &'a T: TryFrom<&'a Value<'a>>,
+        <&'a T as TryFrom<&'a Value<'a>>>::Error: Into<crate::Error>,
+    {
+        <&T>::try_from(&self.0)
+            .map_err(|e| mismatch_error::<T>(self.value_signature().to_owned(), e.into()))
+    }
+}
+
+// Turns the terse `IncorrectType` error of a failed conversion into one that names the signatures.
+fn mismatch_error<T>(signature: Signature<'static>, e: crate::Error) -> crate::Error
+where
+    T: Type + ?Sized,
+{
+    match e {
+        crate::Error::IncorrectType => {
+            crate::Error::SignatureMismatch(signature, format!("`{}`", T::signature()))
+        }
+        e => e,
+    }
 }
 
 macro_rules! ov_try_from {
@@ -82,7 +82,16 @@ impl<'b> std::ops::Deref for Bytes<'b> {
 /// assert_eq!(s, "a{sd}");
 /// # assert_eq!(s.n_complete_types(), Ok(1));
 ///
-/// // Invalid signatures
+/// // Invalid signatures, with details on what's wrong and where
+/// let e = Signature::try_from("a{yz}").unwrap_err();
+/// assert_eq!(
+///     e.to_string(),
+///     "Invalid signature `a{yz}` at position 3: unexpected character `z`",
+/// );
+/// match e {
+///     zvariant::Error::InvalidSignature(e) => assert_eq!(e.position(), 3),
+///     _ => unreachable!(),
+/// }
 /// Signature::try_from("z").unwrap_err();
 /// Signature::try_from("(xs").unwrap_err();
 /// Signature::try_from("xs)").unwrap_err();
@@ -301,15 +310,83 @@ impl<'a> Signature<'a> {
     /// If the signature is invalid, returns the first error.
     pub fn n_complete_types(&self) -> Result<usize> {
         let mut count = 0;
-        // SAFETY: the parser is only used to do counting
-        for s in unsafe { SignatureParser::from_bytes_unchecked(self.as_bytes())? } {
+        for s in self.complete_types() {
             s?;
             count += 1;
         }
         Ok(count)
     }
+
+    /// An iterator over the signatures of the complete types in the signature.
+    ///
+    /// If the signature is invalid (it can only be if it was created through one of the unchecked
+    /// constructors), the iterator yields the error and then stops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Signature;
+    ///
+    /// let sig = Signature::try_from("ya{sv}(us)").unwrap();
+    /// let types = sig.complete_types().collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert_eq!(types, ["y", "a{sv}", "(us)"]);
+    /// ```
+    pub fn complete_types(&self) -> CompleteTypes<'_> {
+        CompleteTypes {
+            parser: Some(SignatureParser::new(self.as_ref())),
+        }
+    }
+
+    /// Creates a new signature with the complete types of `self`, followed by those of `other`.
+    ///
+    /// # Errors
+    ///
+    /// If the resulting signature is invalid, for example because it's too long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use zvariant::Signature;
+    ///
+    /// let sig = Signature::try_from("sa{sv}").unwrap();
+    /// let sig = sig.concat(&Signature::try_from("as").unwrap()).unwrap();
+    /// assert_eq!(sig, "sa{sv}as");
+    /// ```
+    pub fn concat(&self, other: &Signature<'_>) -> Result<Signature<'static>> {
+        let mut signature = String::with_capacity(self.len() + other.len());
+        signature.push_str(self.as_str());
+        signature.push_str(other.as_str());
+
+        Signature::try_from(signature)
+    }
 }
 
+/// An iterator over the complete types of a [`Signature`].
+///
+/// Use [`Signature::complete_types`] to create an instance of this type.
+#[derive(Debug, Clone)]
+pub struct CompleteTypes<'a> {
+    // `None` once an error has been returned.
+    parser: Option<SignatureParser<'a>>,
+}
+
+assert_impl_all!(CompleteTypes<'_>: Send, Sync, Unpin);
+
+impl<'a> Iterator for CompleteTypes<'a> {
+    type Item = Result<Signature<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.parser.as_mut()?.next();
+        if let Some(Err(_)) = next {
+            self.parser = None;
+        }
+
+        next
+    }
+}
+
+impl<'a> std::iter::FusedIterator for CompleteTypes<'a> {}
+
 impl<'a> Debug for Signature<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_tuple("Signature").field(&self.as_str()).finish()
@@ -361,6 +438,14 @@ impl<'a> TryFrom<&'a str> for Signature<'a> {
     }
 }
 
+impl std::str::FromStr for Signature<'static> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::try_from(s.to_string())
+    }
+}
+
 /// Try to create a Signature from a `Cow<str>.`
 impl<'a> TryFrom<Cow<'a, str>> for Signature<'a> {
     type Error = Error;
@@ -530,4 +615,58 @@ mod tests {
         assert_eq!(slice, "t");
         assert_eq!(slice.slice(1..), "");
     }
+
+    #[test]
+    fn invalid_signature_position() {
+        for (signature, position, reason) in [
+            ("a", 1, "expected a complete type"),
+            ("(xs", 3, "expected `)`"),
+            ("xs)", 2, "unexpected character `)`"),
+            ("s/", 1, "unexpected character `/`"),
+            ("(yu()", 4, "expected at least one field signature"),
+            ("a{yz}", 3, "unexpected character `z`"),
+            ("a{ys", 4, "expected `}`"),
+            (
+                "a{(y)s}",
+                2,
+                "dict-entry key's signature can only be a single character",
+            ),
+        ] {
+            match Signature::try_from(signature) {
+                Err(crate::Error::InvalidSignature(e)) => {
+                    assert_eq!(e.signature(), signature);
+                    assert_eq!(e.position(), position, "{signature}");
+                    assert_eq!(e.reason(), reason);
+                }
+                r => panic!("unexpected result for `{signature}`: {r:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn complete_types() {
+        let sig = Signature::from_static_str("sa(yv)a{s(ii)}").unwrap();
+        let types: Vec<_> = sig.complete_types().map(Result::unwrap).collect();
+        assert_eq!(types, ["s", "a(yv)", "a{s(ii)}"]);
+        assert_eq!(
+            Signature::from_str_unchecked("").complete_types().count(),
+            0
+        );
+
+        // Iteration stops at the first error.
+        let invalid = Signature::from_str_unchecked("yzs");
+        let mut types = invalid.complete_types();
+        assert_eq!(types.next().unwrap().unwrap(), "y");
+        types.next().unwrap().unwrap_err();
+        assert!(types.next().is_none());
+
+        let sig = sig
+            .concat(&Signature::from_static_str("u").unwrap())
+            .unwrap();
+        assert_eq!(sig, "sa(yv)a{s(ii)}u");
+        let sig: Signature<'static> = "a{sv}".parse().unwrap();
+        assert_eq!(sig, "a{sv}");
+        let long = Signature::try_from("y".repeat(200)).unwrap();
+        long.concat(&long).unwrap_err();
+    }
 }
@@ -1,6 +1,9 @@
 use std::ops::{Bound, RangeBounds};
 
-use crate::{subslice, Basic, ObjectPath, Result, Signature, STRUCT_SIG_END_CHAR};
+use crate::{
+    container_depths::ContainerDepths, subslice, Basic, Error, InvalidSignature, ObjectPath,
+    Result, Signature, STRUCT_SIG_END_CHAR,
+};
 
 #[cfg(unix)]
 use crate::Fd;
@@ -17,6 +20,9 @@ pub(crate) struct SignatureParser<'s> {
     signature: Signature<'s>,
     pos: usize,
     end: usize,
+    // The depths of the containers around the signature, so the nesting limits of the D-Bus
+    // specification are enforced (and pathological signatures don't blow the stack).
+    depths: ContainerDepths,
 }
 
 impl<'s> SignatureParser<'s> {
@@ -27,15 +33,17 @@ impl<'s> SignatureParser<'s> {
             signature,
             pos: 0,
             end,
+            depths: ContainerDepths::default(),
         }
     }
 
     pub(crate) unsafe fn from_bytes_unchecked(signature: &'s [u8]) -> Result<Self> {
         if signature.len() > 255 {
-            return Err(serde::de::Error::invalid_length(
-                signature.len(),
-                &"<= 255 characters",
-            ));
+            return Err(Error::InvalidSignature(InvalidSignature::new(
+                signature,
+                255,
+                "longer than 255 characters",
+            )));
         }
 
         let signature = Signature::from_bytes_unchecked(signature);
@@ -146,9 +154,8 @@ impl<'s> SignatureParser<'s> {
             .as_bytes()
             .first()
             .map(|b| *b as char)
-            .ok_or_else(|| -> crate::Error {
-                serde::de::Error::invalid_length(0, &">= 1 character")
-            })? {
+            .ok_or_else(|| self.invalid(0, "expected a complete type"))?
+        {
             u8::SIGNATURE_CHAR
             | bool::SIGNATURE_CHAR
             | i16::SIGNATURE_CHAR
@@ -169,10 +176,7 @@ impl<'s> SignatureParser<'s> {
             DICT_ENTRY_SIG_START_CHAR => self.next_dict_entry_signature(),
             #[cfg(feature = "gvariant")]
             MAYBE_SIGNATURE_CHAR => self.next_maybe_signature(),
-            c => Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Char(c),
-                &"a valid signature character",
-            )),
+            c => Err(self.invalid(0, format!("unexpected character `{c}`"))),
         }
     }
 
@@ -180,30 +184,22 @@ impl<'s> SignatureParser<'s> {
         &self,
         expected_sig_prefix: char,
     ) -> Result<Signature<'_>> {
-        let signature = self.signature();
-
-        if signature.len() < 2 {
-            return Err(serde::de::Error::invalid_length(
-                signature.len(),
-                &">= 2 characters",
-            ));
-        }
-
-        // We can't get None here cause we already established there is are least 2 chars above
-        let c = signature
-            .as_bytes()
-            .first()
-            .map(|b| *b as char)
-            .expect("empty signature");
+        let c = self.next_char()?;
         if c != expected_sig_prefix {
-            return Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Char(c),
-                &expected_sig_prefix.to_string().as_str(),
-            ));
+            return Err(self.invalid(0, format!("expected `{expected_sig_prefix}`")));
         }
 
         // There should be a valid complete signature after 'a' but not more than 1
-        let child_parser = self.slice(1..);
+        let mut child_parser = self.slice(1..);
+        #[cfg(feature = "gvariant")]
+        let depths = if expected_sig_prefix == MAYBE_SIGNATURE_CHAR {
+            self.depths.inc_maybe()?
+        } else {
+            self.depths.inc_array()?
+        };
+        #[cfg(not(feature = "gvariant"))]
+        let depths = self.depths.inc_array()?;
+        child_parser.depths = depths;
         let child_len = child_parser.next_signature()?.len();
 
         Ok(self.signature_slice(0, child_len + 1))
@@ -219,42 +215,22 @@ impl<'s> SignatureParser<'s> {
     }
 
     fn next_structure_signature(&self) -> Result<Signature<'_>> {
-        let signature = self.signature();
-
-        if signature.len() < 3 {
-            return Err(serde::de::Error::invalid_length(
-                signature.len(),
-                &">= 2 characters",
-            ));
-        }
-
-        let mut bytes = signature.as_bytes().iter();
-        // We can't get None here cause we already established there are at least 2 chars above
-        let c = bytes.next().map(|b| *b as char).expect("empty signature");
+        let c = self.next_char()?;
         if c != STRUCT_SIG_START_CHAR {
-            return Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Char(c),
-                &crate::STRUCT_SIG_START_STR,
-            ));
+            return Err(self.invalid(0, format!("expected `{STRUCT_SIG_START_CHAR}`")));
         }
-        if bytes.next().map(|b| *b as char).expect("empty signature") == STRUCT_SIG_END_CHAR {
-            return Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Str("()"),
-                &"at least one field signature between `(` and `)`",
-            ));
+        if self.signature().as_bytes().get(1).map(|b| *b as char) == Some(STRUCT_SIG_END_CHAR) {
+            return Err(self.invalid(1, "expected at least one field signature"));
         }
 
         let mut fields_sig_len = 0;
         let mut fields_parser = self.slice(1..);
+        fields_parser.depths = self.depths.inc_structure()?;
         while !fields_parser.done() && fields_parser.next_char()? != STRUCT_SIG_END_CHAR {
             fields_sig_len += fields_parser.parse_next_signature()?.len();
         }
-        let c = fields_parser.next_char()?;
-        if c != STRUCT_SIG_END_CHAR {
-            return Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Char(c),
-                &crate::STRUCT_SIG_END_STR,
-            ));
+        if fields_parser.done() {
+            return Err(fields_parser.invalid(0, format!("expected `{STRUCT_SIG_END_CHAR}`")));
         }
 
         // The `(`, fields signatures and `)`.
@@ -263,31 +239,18 @@ impl<'s> SignatureParser<'s> {
 
     fn next_dict_entry_signature(&self) -> Result<Signature<'_>> {
         let signature = self.signature();
-
-        if signature.len() < 4 {
-            return Err(serde::de::Error::invalid_length(
-                signature.len(),
-                &">= 4 characters",
-            ));
-        }
-
-        // We can't get None here cause we already established there are at least 4 chars above
-        let bytes = signature.as_bytes();
-        let c = bytes.first().map(|b| *b as char).expect("empty signature");
+        let c = self.next_char()?;
         if c != DICT_ENTRY_SIG_START_CHAR {
-            return Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Char(c),
-                &crate::DICT_ENTRY_SIG_START_STR,
-            ));
+            return Err(self.invalid(0, format!("expected `{DICT_ENTRY_SIG_START_CHAR}`")));
         }
 
         let key_parser = self.slice(1..);
         let key_signature = key_parser.next_signature()?;
         // Key's signature will always be just 1 character.
         if key_signature.len() != 1 {
-            return Err(serde::de::Error::invalid_length(
-                key_signature.len(),
-                &"dict-entry key's signature can only be a single character",
+            return Err(self.invalid(
+                1,
+                "dict-entry key's signature can only be a single character",
             ));
         }
 
@@ -297,22 +260,22 @@ impl<'s> SignatureParser<'s> {
         // signature of value + `{` + 1 char of the key signature + `}`
         let end = value_len + 3;
 
-        if signature.len() < end {
-            return Err(serde::de::Error::invalid_length(
-                signature.len(),
-                &format!(">= {end} characters").as_str(),
-            ));
-        }
-        if bytes[end - 1] as char != DICT_ENTRY_SIG_END_CHAR {
-            return Err(serde::de::Error::invalid_value(
-                serde::de::Unexpected::Char(c),
-                &crate::DICT_ENTRY_SIG_END_STR,
-            ));
+        if signature.as_bytes().get(end - 1).map(|b| *b as char) != Some(DICT_ENTRY_SIG_END_CHAR) {
+            return Err(self.invalid(end - 1, format!("expected `{DICT_ENTRY_SIG_END_CHAR}`")));
         }
 
         Ok(self.signature_slice(0, end))
     }
 
+    // The error for a problem found `idx` characters after the current position.
+    fn invalid(&self, idx: usize, reason: impl Into<String>) -> Error {
+        Error::InvalidSignature(InvalidSignature::new(
+            self.signature.as_bytes(),
+            self.pos + idx,
+            reason,
+        ))
+    }
+
     fn signature_slice(&self, idx: usize, end: usize) -> Signature<'_> {
         self.signature.slice(self.pos + idx..self.pos + end)
     }
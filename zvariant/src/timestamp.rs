@@ -0,0 +1,223 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use static_assertions::assert_impl_all;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Error, OwnedValue, Signature, Type, Value};
+
+/// A [`SystemTime`], encoded as the number of microseconds since the UNIX epoch (`t`).
+///
+/// This is the most common encoding of timestamps on D-Bus (e.g the `*USec` properties of
+/// systemd). Note that [`SystemTime`] itself implements [`Type`] as well but is encoded by serde as
+/// a `(tu)` structure of seconds and nanoseconds, which is rarely what D-Bus APIs expect.
+///
+/// The sub-microsecond part of the time is truncated on encoding. Times before the epoch are
+/// encoded as `0` and times too far after it to fit in 64-bit as `u64::MAX`, which is also how
+/// D-Bus APIs often represent unset and infinite times, respectively.
+///
+/// # Example
+///
+/// ```
+/// use byteorder::LE;
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use zvariant::{from_slice, to_bytes, EncodingContext, Timestamp};
+///
+/// let ctxt = EncodingContext::<LE>::new_dbus(0);
+/// let time = Timestamp::new(UNIX_EPOCH + Duration::from_millis(1500));
+/// let encoded = to_bytes(ctxt, &time).unwrap();
+/// assert_eq!(from_slice::<_, u64>(&encoded, ctxt).unwrap().0, 1_500_000);
+///
+/// let decoded: Timestamp = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(decoded, time);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(SystemTime);
+
+const OUT_OF_RANGE: &str = "timestamp out of range of the system time";
+
+assert_impl_all!(Timestamp: Send, Sync, Unpin);
+
+impl Timestamp {
+    /// Create a timestamp for `time`.
+    pub fn new(time: SystemTime) -> Self {
+        Self(time)
+    }
+
+    /// Create a timestamp `micros` microseconds after the UNIX epoch.
+    ///
+    /// Returns `None` if the time can't be represented by [`SystemTime`] on this platform.
+    pub fn from_micros(micros: u64) -> Option<Self> {
+        UNIX_EPOCH
+            .checked_add(Duration::from_micros(micros))
+            .map(Self)
+    }
+
+    /// The current time.
+    pub fn now() -> Self {
+        Self(SystemTime::now())
+    }
+
+    /// The number of microseconds since the UNIX epoch, as encoded.
+    pub fn as_micros(&self) -> u64 {
+        match self.0.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => since_epoch.as_micros().try_into().unwrap_or(u64::MAX),
+            Err(_) => 0,
+        }
+    }
+
+    /// The wrapped time.
+    pub fn system_time(&self) -> SystemTime {
+        self.0
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        Self(time)
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(time: Timestamp) -> Self {
+        time.0
+    }
+}
+
+impl Type for Timestamp {
+    fn signature() -> Signature<'static> {
+        u64::signature()
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.as_micros())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let micros = u64::deserialize(deserializer)?;
+
+        Self::from_micros(micros).ok_or_else(|| de::Error::custom(OUT_OF_RANGE))
+    }
+}
+
+/// A [`Duration`], encoded as a number of microseconds (`t`).
+///
+/// Like for [`Timestamp`], this is what D-Bus APIs usually expect while [`Duration`] itself is
+/// encoded by serde as a `(tu)` structure of seconds and nanoseconds.
+///
+/// The sub-microsecond part of the duration is truncated on encoding. Durations too long to fit in
+/// 64-bit (more than half a million years) are encoded as `u64::MAX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Microseconds(Duration);
+
+assert_impl_all!(Microseconds: Send, Sync, Unpin);
+
+impl Microseconds {
+    /// Create a value for `duration`.
+    pub fn new(duration: Duration) -> Self {
+        Self(duration)
+    }
+
+    /// The number of microseconds, as encoded.
+    pub fn as_micros(&self) -> u64 {
+        self.0.as_micros().try_into().unwrap_or(u64::MAX)
+    }
+
+    /// The wrapped duration.
+    pub fn duration(&self) -> Duration {
+        self.0
+    }
+}
+
+impl From<Duration> for Microseconds {
+    fn from(duration: Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<Microseconds> for Duration {
+    fn from(duration: Microseconds) -> Self {
+        duration.0
+    }
+}
+
+impl Type for Microseconds {
+    fn signature() -> Signature<'static> {
+        u64::signature()
+    }
+}
+
+impl Serialize for Microseconds {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.as_micros())
+    }
+}
+
+impl<'de> Deserialize<'de> for Microseconds {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(|micros| Self(Duration::from_micros(micros)))
+    }
+}
+
+// Conversions from and to `Value`, so they can be used as properties or in dictionaries.
+
+macro_rules! micros_value {
+    ($ty:ty, $from_micros:expr) => {
+        impl<'a> From<$ty> for Value<'a> {
+            fn from(v: $ty) -> Self {
+                Value::U64(v.as_micros())
+            }
+        }
+
+        impl<'a> TryFrom<Value<'a>> for $ty {
+            type Error = Error;
+
+            fn try_from(value: Value<'a>) -> Result<Self, Error> {
+                u64::try_from(value).and_then($from_micros)
+            }
+        }
+
+        impl<'a> TryFrom<&'a Value<'a>> for $ty {
+            type Error = Error;
+
+            fn try_from(value: &'a Value<'a>) -> Result<Self, Error> {
+                u64::try_from(value).and_then($from_micros)
+            }
+        }
+
+        impl From<$ty> for OwnedValue {
+            fn from(v: $ty) -> Self {
+                Value::from(v).into()
+            }
+        }
+
+        impl TryFrom<OwnedValue> for $ty {
+            type Error = Error;
+
+            fn try_from(value: OwnedValue) -> Result<Self, Error> {
+                Self::try_from(Value::from(value))
+            }
+        }
+    };
+}
+
+micros_value!(Timestamp, |micros| {
+    Timestamp::from_micros(micros).ok_or_else(|| Error::Message(OUT_OF_RANGE.to_string()))
+});
+micros_value!(Microseconds, |micros| Ok(Microseconds::new(
+    Duration::from_micros(micros)
+)));
@@ -245,6 +245,10 @@ macro_rules! tuple_impls {
                         sig.push_str($name::signature().as_str());
                     )+
                     sig.push(STRUCT_SIG_END_CHAR);
+                    if sig.len() == 2 {
+                        // D-Bus has no empty structs so a tuple of unit types is a unit type too.
+                        sig.clear();
+                    }
 
                     Signature::from_string_unchecked(sig)
                 }
@@ -86,6 +86,32 @@ pub(crate) fn f64_to_f32(value: f64) -> f32 {
     value as f32
 }
 
+// If `signature` is that of a fixed-size numeric type (i-e one encoded as just its bytes in the
+// encoding byte order, with a size equal to its alignment), returns its signature character.
+//
+// In the D-Bus format, the elements of arrays of such types are (de)serialized one by one without
+// the signature parsing and padding logic of the (de)serializer. This is not a bulk copy: serde
+// hands the elements over one at a time.
+pub(crate) fn fixed_size_numeric_char(signature: &Signature<'_>) -> Option<char> {
+    match signature.as_bytes() {
+        [c] => {
+            let c = *c as char;
+            match c {
+                u8::SIGNATURE_CHAR
+                | i16::SIGNATURE_CHAR
+                | u16::SIGNATURE_CHAR
+                | i32::SIGNATURE_CHAR
+                | u32::SIGNATURE_CHAR
+                | i64::SIGNATURE_CHAR
+                | u64::SIGNATURE_CHAR
+                | f64::SIGNATURE_CHAR => Some(c),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 // `signature` must be **one** complete and correct signature. Expect panics otherwise!
 pub(crate) fn alignment_for_signature(
     signature: &Signature<'_>,
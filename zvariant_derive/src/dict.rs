@@ -43,9 +43,18 @@ pub fn expand_serialize_derive(input: DeriveInput) -> Result<TokenStream, Error>
     let mut num_entries: usize = 0;
 
     for f in &data.fields {
-        let FieldAttributes { rename } = FieldAttributes::parse(&f.attrs)?;
+        let FieldAttributes { rename, flatten } = FieldAttributes::parse(&f.attrs)?;
 
         let name = &f.ident;
+        if flatten {
+            entries.extend(quote! {
+                for (key, value) in &self.#name {
+                    map.serialize_entry(key, value)?;
+                }
+            });
+
+            continue;
+        }
         let dict_name = dict_name_for_field(f, rename, rename_all.as_deref())?;
 
         let is_option = macros::ty_is_option(&f.ty);
@@ -108,11 +117,29 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
     let mut req_fields = Vec::new();
     let mut dict_names = Vec::new();
     let mut entries = Vec::new();
+    let mut flattened = None;
 
     for f in &data.fields {
-        let FieldAttributes { rename } = FieldAttributes::parse(&f.attrs)?;
+        let FieldAttributes { rename, flatten } = FieldAttributes::parse(&f.attrs)?;
 
         let name = &f.ident;
+        if flatten {
+            if flattened.is_some() {
+                return Err(Error::new(
+                    f.span(),
+                    "only one field can have the `flatten` attribute",
+                ));
+            }
+            if deny_unknown_fields {
+                return Err(Error::new(
+                    f.span(),
+                    "`flatten` and `deny_unknown_fields` attributes can't be used together",
+                ));
+            }
+            flattened = Some((name, &f.ty));
+
+            continue;
+        }
         let dict_name = dict_name_for_field(f, rename, rename_all.as_deref())?;
 
         let is_option = macros::ty_is_option(&f.ty);
@@ -132,7 +159,14 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
         }
     }
 
-    let fallback = if deny_unknown_fields {
+    let fallback = if let Some((flattened, _)) = flattened {
+        quote! {
+            unknown => {
+                let value = access.next_value::<#zv::OwnedValue>()?;
+                #flattened.insert(::std::string::ToString::to_string(unknown), value);
+            }
+        }
+    } else if deny_unknown_fields {
         quote! {
             field => {
                 return ::std::result::Result::Err(
@@ -152,6 +186,15 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
     };
     entries.push(fallback);
 
+    // The flattened field collects the unknown entries, so it's never missing.
+    let (flattened_decl, flattened_field) = match flattened {
+        Some((name, ty)) => (
+            quote! { let mut #name: #ty = ::std::default::Default::default(); },
+            quote! { #name },
+        ),
+        None => (quote! {}, quote! {}),
+    };
+
     let (_, ty_generics, _) = input.generics.split_for_impl();
     let mut generics = input.generics.clone();
     let def = syn::LifetimeDef {
@@ -193,6 +236,7 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
                         M: #zv::export::serde::de::MapAccess<'de>,
                     {
                         #( let mut #fields = ::std::default::Default::default(); )*
+                        #flattened_decl
 
                         // does not check duplicated fields, since those shouldn't exist in stream
                         while let ::std::option::Option::Some(key) = access.next_key::<&str>()? {
@@ -211,7 +255,7 @@ pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Erro
                             );
                         };)*
 
-                        ::std::result::Result::Ok(#name { #(#fields),* })
+                        ::std::result::Result::Ok(#name { #(#fields,)* #flattened_field })
                     }
                 }
 
@@ -17,6 +17,7 @@ mod dict;
 mod r#type;
 mod utils;
 mod value;
+mod variant;
 
 /// Derive macro to add [`Type`] implementation to structs and enums.
 ///
@@ -158,7 +159,37 @@ mod value;
 /// assert_eq!(decoded, StrEnum::Variant2);
 /// ```
 ///
+/// # Transparent wrappers
+///
+/// A newtype (a tuple struct with a single field) is already encoded just like its inner type.
+/// The `transparent` attribute extends this to any structure with a single field, so that the
+/// wrapper doesn't appear on the wire. The [`Value`] and [`OwnedValue`] derives honor it as well.
+/// Since serde only encodes the newtypes transparently, named fields are to be combined with
+/// `#[serde(transparent)]`:
+///
+/// ```
+/// use zvariant::{EncodingContext, from_slice, to_bytes, Type};
+/// use serde::{Deserialize, Serialize};
+/// use byteorder::LE;
+///
+/// #[derive(Deserialize, Serialize, Type, PartialEq, Debug)]
+/// #[serde(transparent)]
+/// #[zvariant(transparent)]
+/// struct Temperature {
+///     celsius: i32,
+/// }
+///
+/// assert_eq!(Temperature::signature(), "i");
+/// let ctxt = EncodingContext::<LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &Temperature { celsius: -7 }).unwrap();
+/// assert_eq!(encoded, to_bytes(ctxt, &-7i32).unwrap());
+/// let decoded: Temperature = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(decoded, Temperature { celsius: -7 });
+/// ```
+///
 /// [`Type`]: https://docs.rs/zvariant/latest/zvariant/trait.Type.html
+/// [`Value`]: derive.Value.html
+/// [`OwnedValue`]: derive.OwnedValue.html
 /// [`Serialize`]: https://docs.serde.rs/serde/trait.Serialize.html
 /// [`Deserialize`]: https://docs.serde.rs/serde/de/trait.Deserialize.html
 /// [serde_repr]: https://crates.io/crates/serde_repr
@@ -228,6 +259,12 @@ pub fn type_macro_derive(input: TokenStream) -> TokenStream {
 /// * `"camelCase"`
 /// * `"snake_case"`
 ///
+/// # Additional entries
+///
+/// A field of type `HashMap<String, OwnedValue>` can be marked with the `#[zvariant(flatten)]`
+/// attribute. Its entries are then serialized along with the other fields. See
+/// [`DeserializeDict`] for its use on the deserialization side.
+///
 /// [`Serialize`]: https://docs.serde.rs/serde/trait.Serialize.html
 #[proc_macro_derive(SerializeDict, attributes(zvariant))]
 pub fn serialize_dict_macro_derive(input: TokenStream) -> TokenStream {
@@ -296,6 +333,29 @@ pub fn serialize_dict_macro_derive(input: TokenStream) -> TokenStream {
 /// * `"camelCase"`
 /// * `"snake_case"`
 ///
+/// # Unknown entries
+///
+/// Entries that don't correspond to any field are ignored by default, or rejected if the
+/// `#[zvariant(deny_unknown_fields)]` attribute is specified on the structure. Alternatively, they
+/// can be preserved in a field of type `HashMap<String, OwnedValue>`, marked with the
+/// `#[zvariant(flatten)]` attribute:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zvariant::{DeserializeDict, OwnedValue, SerializeDict, Type};
+///
+/// #[derive(DeserializeDict, SerializeDict, Type)]
+/// #[zvariant(signature = "a{sv}")]
+/// ##[allow(unused)]
+/// struct Struct {
+///     field1: u16,
+///     #[zvariant(flatten)]
+///     extra: HashMap<String, OwnedValue>,
+/// }
+/// ```
+///
+/// Only one field can have this attribute and it can't be combined with `deny_unknown_fields`.
+///
 /// [`Deserialize`]: https://docs.serde.rs/serde/de/trait.Deserialize.html
 #[proc_macro_derive(DeserializeDict, attributes(zvariant))]
 pub fn deserialize_dict_macro_derive(input: TokenStream) -> TokenStream {
@@ -305,6 +365,56 @@ pub fn deserialize_dict_macro_derive(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Adds [`Deserialize`] implementation to enums to be deserialized from a variant (`v`) type.
+///
+/// Each variant of the enum must have a single unnamed field, whose type is one of the types the
+/// variant can hold. The enum variant is chosen by comparing the signature of the value contained
+/// in the variant with the [`Type`] signature of the fields, in the order of declaration, so this
+/// avoids having to deserialize to a generic [`Value`] and then try converting it to each type.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant::{from_slice, to_bytes, DeserializeVariant, EncodingContext, Type, Value};
+/// use serde::Deserialize;
+/// use byteorder::LE;
+///
+/// #[derive(Deserialize, Type, Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// #[derive(DeserializeVariant, Type, Debug, PartialEq)]
+/// #[zvariant(signature = "v")]
+/// enum Position {
+///     Name(String),
+///     Point(Point),
+/// }
+///
+/// let ctxt = EncodingContext::<LE>::new_dbus(0);
+/// let encoded = to_bytes(ctxt, &Value::from("home")).unwrap();
+/// let position: Position = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(position, Position::Name("home".to_string()));
+///
+/// let encoded = to_bytes(ctxt, &Value::from((4i32, 2i32))).unwrap();
+/// let position: Position = from_slice(&encoded, ctxt).unwrap().0;
+/// assert_eq!(position, Position::Point(Point { x: 4, y: 2 }));
+/// ```
+///
+/// A value of any other type results in an error.
+///
+/// [`Deserialize`]: https://docs.serde.rs/serde/de/trait.Deserialize.html
+/// [`Value`]: https://docs.rs/zvariant/latest/zvariant/enum.Value.html
+/// [`Type`]: derive.Type.html
+#[proc_macro_derive(DeserializeVariant)]
+pub fn deserialize_variant_macro_derive(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = syn::parse(input).unwrap();
+    variant::expand_deserialize_derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 /// Implements conversions for your type to/from [`Value`].
 ///
 /// Implements `TryFrom<Value>` and `Into<Value>` for your type.
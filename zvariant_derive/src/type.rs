@@ -7,9 +7,33 @@ use syn::{
 use crate::utils::*;
 
 pub fn expand_derive(ast: DeriveInput) -> Result<TokenStream, Error> {
-    let StructAttributes { signature, .. } = StructAttributes::parse(&ast.attrs)?;
+    let StructAttributes {
+        signature,
+        transparent,
+        ..
+    } = StructAttributes::parse(&ast.attrs)?;
 
     let zv = zvariant_path();
+    if transparent {
+        if signature.is_some() {
+            return Err(Error::new(
+                ast.span(),
+                "`transparent` and `signature` attributes are mutually exclusive",
+            ));
+        }
+        let field_type = &transparent_field(&ast)?.ty;
+        let name = &ast.ident;
+        let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+        return Ok(quote! {
+            impl #impl_generics #zv::Type for #name #ty_generics #where_clause {
+                #[inline]
+                fn signature() -> #zv::Signature<'static> {
+                    <#field_type as #zv::Type>::signature()
+                }
+            }
+        });
+    }
     if let Some(signature) = signature {
         let signature = match signature.as_str() {
             "dict" => "a{sv}".to_string(),
@@ -102,6 +126,10 @@ fn signature_for_struct(
                 s.push_str(<#field_types as #zv::Type>::signature().as_str());
             )*
             s.push_str(")");
+            if s.len() == 2 {
+                // D-Bus has no empty structs so a struct of unit fields is a unit type too.
+                s.clear();
+            }
 
             #zv::Signature::from_string_unchecked(s)
         }
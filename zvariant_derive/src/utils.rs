@@ -1,6 +1,7 @@
 use proc_macro2::TokenStream;
 use proc_macro_crate::{crate_name, FoundCrate};
 use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Field};
 use zvariant_utils::def_attrs;
 
 pub fn zvariant_path() -> TokenStream {
@@ -19,7 +20,20 @@ def_attrs! {
     crate zvariant;
 
     /// Attributes defined on structures.
-    pub StructAttributes("struct") { signature str, rename_all str, deny_unknown_fields none };
+    pub StructAttributes("struct") {
+        signature str, rename_all str, deny_unknown_fields none, transparent none
+    };
     /// Attributes defined on fields.
-    pub FieldAttributes("field") { rename str };
+    pub FieldAttributes("field") { rename str, flatten none };
+}
+
+/// The single field of a `transparent` structure.
+pub fn transparent_field(ast: &DeriveInput) -> Result<&Field, Error> {
+    match &ast.data {
+        Data::Struct(ds) if ds.fields.len() == 1 => Ok(ds.fields.iter().next().unwrap()),
+        _ => Err(Error::new(
+            ast.span(),
+            "`transparent` is only supported on structures with a single field",
+        )),
+    }
 }
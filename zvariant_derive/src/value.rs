@@ -14,11 +14,18 @@ pub enum ValueType {
 
 pub fn expand_derive(ast: DeriveInput, value_type: ValueType) -> Result<TokenStream, Error> {
     let zv = zvariant_path();
+    let StructAttributes {
+        signature,
+        transparent,
+        ..
+    } = StructAttributes::parse(&ast.attrs)?;
+    if transparent {
+        transparent_field(&ast)?;
+    }
 
     match &ast.data {
         Data::Struct(ds) => match &ds.fields {
             Fields::Named(_) | Fields::Unnamed(_) => {
-                let StructAttributes { signature, .. } = StructAttributes::parse(&ast.attrs)?;
                 let signature = signature.map(|signature| match signature.as_str() {
                     "dict" => "a{sv}".to_string(),
                     _ => signature,
@@ -30,6 +37,7 @@ pub fn expand_derive(ast: DeriveInput, value_type: ValueType) -> Result<TokenStr
                     ast.generics,
                     &ds.fields,
                     signature,
+                    transparent,
                     &zv,
                 )
             }
@@ -49,6 +57,7 @@ fn impl_struct(
     generics: Generics,
     fields: &Fields,
     signature: Option<String>,
+    transparent: bool,
     zv: &TokenStream,
 ) -> Result<TokenStream, Error> {
     let statc_lifetime = LifetimeDef::new(Lifetime::new("'static", Span::call_site()));
@@ -92,6 +101,31 @@ fn impl_struct(
     };
     let (impl_generics, ty_generics, _) = generics.split_for_impl();
     match fields {
+        Fields::Named(_) if transparent => {
+            // Only the value of the single field is represented.
+            let field_name = fields.iter().next().and_then(|field| field.ident.as_ref());
+            Ok(quote! {
+                impl #impl_generics ::std::convert::TryFrom<#value_type> for #name #ty_generics
+                    #from_value_where_clause
+                {
+                    type Error = #zv::Error;
+
+                    #[inline]
+                    fn try_from(value: #value_type) -> #zv::Result<Self> {
+                        ::std::convert::TryInto::try_into(value).map(|#field_name| Self { #field_name })
+                    }
+                }
+
+                impl #impl_generics From<#name #ty_generics> for #value_type
+                    #into_value_where_clause
+                {
+                    #[inline]
+                    fn from(s: #name #ty_generics) -> Self {
+                        s.#field_name.into()
+                    }
+                }
+            })
+        }
         Fields::Named(_) => {
             let field_names: Vec<_> = fields
                 .iter()
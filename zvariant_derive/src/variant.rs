@@ -0,0 +1,103 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{punctuated::Punctuated, spanned::Spanned, Data, DeriveInput, Error, Fields};
+
+use crate::utils::*;
+
+pub fn expand_deserialize_derive(input: DeriveInput) -> Result<TokenStream, Error> {
+    let (name, data) = match input.data {
+        Data::Enum(data) => (input.ident, data),
+        _ => return Err(Error::new(input.span(), "only enums supported")),
+    };
+
+    let zv = zvariant_path();
+    let visitor = format_ident!("{}Visitor", name);
+    let mut arms = vec![];
+
+    for variant in &data.variants {
+        let ty = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => {
+                return Err(Error::new(
+                    variant.span(),
+                    "only variants with a single unnamed field are supported",
+                ))
+            }
+        };
+        let variant_name = &variant.ident;
+
+        arms.push(quote! {
+            if sig == <#ty as #zv::Type>::signature() {
+                return seq
+                    .next_element::<#ty>()?
+                    .map(#name::#variant_name)
+                    .ok_or_else(|| #zv::export::serde::de::Error::invalid_length(1, &self));
+            }
+        });
+    }
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    let def = syn::LifetimeDef {
+        attrs: Vec::new(),
+        lifetime: syn::Lifetime::new("'de", Span::call_site()),
+        colon_token: None,
+        bounds: Punctuated::new(),
+    };
+    generics.params = Some(syn::GenericParam::Lifetime(def))
+        .into_iter()
+        .chain(generics.params)
+        .collect();
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[allow(deprecated)]
+        impl #impl_generics #zv::export::serde::de::Deserialize<'de> for #name #ty_generics
+        #where_clause
+        {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: #zv::export::serde::de::Deserializer<'de>,
+            {
+                struct #visitor #ty_generics(::std::marker::PhantomData<#name #ty_generics>);
+
+                impl #impl_generics #zv::export::serde::de::Visitor<'de> for #visitor #ty_generics {
+                    type Value = #name #ty_generics;
+
+                    fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                        formatter.write_str("a variant")
+                    }
+
+                    fn visit_seq<V>(
+                        self,
+                        mut seq: V,
+                    ) -> ::std::result::Result<Self::Value, V::Error>
+                    where
+                        V: #zv::export::serde::de::SeqAccess<'de>,
+                    {
+                        let sig: #zv::Signature<'_> = seq
+                            .next_element()?
+                            .ok_or_else(|| #zv::export::serde::de::Error::invalid_length(0, &self))?;
+
+                        #(#arms)*
+
+                        ::std::result::Result::Err(
+                            #zv::export::serde::de::Error::invalid_value(
+                                #zv::export::serde::de::Unexpected::Str(&sig),
+                                &"the signature of one of the enum variants",
+                            ),
+                        )
+                    }
+                }
+
+                const FIELDS: &[&str] = &["zvariant::Value::Signature", "zvariant::Value::Value"];
+                deserializer.deserialize_struct(
+                    "zvariant::Value",
+                    FIELDS,
+                    #visitor(::std::marker::PhantomData),
+                )
+            }
+        }
+    })
+}
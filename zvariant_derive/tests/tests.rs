@@ -1,9 +1,11 @@
 #![allow(dead_code)]
 
 use byteorder::LE;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use zvariant::{
-    DeserializeDict, EncodingContext, EncodingFormat, OwnedValue, SerializeDict, Type, Value,
+    DeserializeDict, DeserializeVariant, EncodingContext, EncodingFormat, OwnedValue,
+    SerializeDict, Type, Value,
 };
 
 #[test]
@@ -14,6 +16,36 @@ fn derive_unit_struct() {
     assert_eq!(FooF::signature(), "d")
 }
 
+#[test]
+fn derive_unit_fields() {
+    #[derive(Type)]
+    struct Unit;
+    assert_eq!(Unit::signature(), "");
+
+    #[derive(Type)]
+    struct Empty {}
+    assert_eq!(Empty::signature(), "y");
+
+    // D-Bus has no empty structs so structs of units are units too.
+    #[derive(Type, Serialize, Deserialize, Debug, PartialEq)]
+    struct Units {
+        a: (),
+        b: ((),),
+    }
+    assert_eq!(Units::signature(), "");
+
+    #[derive(Type, Serialize, Deserialize, Debug, PartialEq)]
+    struct Mixed(u32, (), Units);
+    assert_eq!(Mixed::signature(), "(u)");
+
+    let ctxt = EncodingContext::<LE>::new_dbus(0);
+    let mixed = Mixed(7, (), Units { a: (), b: ((),) });
+    let encoded = zvariant::to_bytes(ctxt, &mixed).unwrap();
+    assert_eq!(encoded, [7, 0, 0, 0]);
+    let decoded: Mixed = zvariant::from_slice(&encoded, ctxt).unwrap().0;
+    assert_eq!(decoded, mixed);
+}
+
 #[test]
 fn derive_struct() {
     #[derive(Type)]
@@ -26,6 +58,60 @@ fn derive_struct() {
     assert_eq!(TestStruct::signature(), "(syay)")
 }
 
+#[test]
+fn derive_transparent() {
+    #[derive(Type, Value, OwnedValue, Serialize, Deserialize, Debug, PartialEq)]
+    #[zvariant(transparent)]
+    struct Id(u32);
+
+    #[derive(Type, Value, Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(transparent)]
+    #[zvariant(transparent)]
+    struct Name {
+        name: String,
+    }
+
+    assert_eq!(Id::signature(), "u");
+    assert_eq!(Name::signature(), "s");
+
+    let ctxt = EncodingContext::<LE>::new(EncodingFormat::DBus, 0);
+    let serialized = zvariant::to_bytes(
+        ctxt,
+        &(
+            Id(7),
+            Name {
+                name: "seven".into(),
+            },
+        ),
+    )
+    .unwrap();
+    assert_eq!(
+        serialized,
+        zvariant::to_bytes(ctxt, &(7u32, "seven")).unwrap()
+    );
+    let deserialized: (Id, Name) = zvariant::from_slice(&serialized, ctxt).unwrap().0;
+    assert_eq!(
+        deserialized,
+        (
+            Id(7),
+            Name {
+                name: "seven".into()
+            }
+        )
+    );
+
+    assert_eq!(Value::from(Id(7)), Value::U32(7));
+    assert_eq!(
+        Value::from(Name {
+            name: "seven".into()
+        }),
+        Value::from("seven")
+    );
+    let name = Name::try_from(Value::from("seven")).unwrap();
+    assert_eq!(name.name, "seven");
+    assert_eq!(Id::try_from(OwnedValue::from(7u32)).unwrap(), Id(7));
+}
+
 #[test]
 fn derive_enum() {
     #[repr(u32)]
@@ -74,3 +160,80 @@ fn derive_dict() {
 
     assert_eq!(Test::signature(), "a{sv}")
 }
+
+#[test]
+fn derive_dict_flatten() {
+    #[derive(SerializeDict, DeserializeDict, Type, Debug, PartialEq)]
+    #[zvariant(signature = "a{sv}", rename_all = "PascalCase")]
+    struct Known {
+        field_a: u32,
+        field_b: Option<String>,
+        #[zvariant(flatten)]
+        extra: HashMap<String, OwnedValue>,
+    }
+
+    let mut dict: HashMap<&str, Value<'_>> = HashMap::new();
+    dict.insert("FieldA", Value::from(1u32));
+    dict.insert("FieldC", Value::from("unknown"));
+    dict.insert("FieldD", Value::from(true));
+
+    let ctxt = EncodingContext::<LE>::new(EncodingFormat::DBus, 0);
+    let serialized = zvariant::to_bytes(ctxt, &dict).unwrap();
+    let known: Known = zvariant::from_slice(&serialized, ctxt).unwrap().0;
+    assert_eq!(known.field_a, 1);
+    assert_eq!(known.field_b, None);
+    assert_eq!(known.extra.len(), 2);
+    assert_eq!(known.extra["FieldC"], Value::from("unknown").into());
+    assert_eq!(known.extra["FieldD"], Value::from(true).into());
+
+    // The unknown entries are serialized back along with the known ones.
+    let serialized = zvariant::to_bytes(ctxt, &known).unwrap();
+    let deserialized: HashMap<String, OwnedValue> =
+        zvariant::from_slice(&serialized, ctxt).unwrap().0;
+    assert_eq!(deserialized.len(), 3);
+    assert_eq!(deserialized["FieldA"], Value::from(1u32).into());
+    assert_eq!(deserialized["FieldC"], Value::from("unknown").into());
+    let deserialized: Known = zvariant::from_slice(&serialized, ctxt).unwrap().0;
+    assert_eq!(deserialized, known);
+}
+
+#[test]
+fn derive_deserialize_variant() {
+    #[derive(Deserialize, Serialize, Type, Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(DeserializeVariant, Type, Debug, PartialEq)]
+    #[zvariant(signature = "v")]
+    enum StrOrPoint {
+        Str(String),
+        Point(Point),
+    }
+
+    assert_eq!(StrOrPoint::signature(), "v");
+
+    let ctxt = EncodingContext::<LE>::new(EncodingFormat::DBus, 0);
+    let serialized = zvariant::to_bytes(ctxt, &Value::from("hello")).unwrap();
+    let v: StrOrPoint = zvariant::from_slice(&serialized, ctxt).unwrap().0;
+    assert_eq!(v, StrOrPoint::Str("hello".to_string()));
+
+    let point = Point { x: 3, y: -4 };
+    let serialized = zvariant::to_bytes(ctxt, &zvariant::SerializeValue(&point)).unwrap();
+    let v: StrOrPoint = zvariant::from_slice(&serialized, ctxt).unwrap().0;
+    assert_eq!(v, StrOrPoint::Point(point));
+
+    // Other types are rejected.
+    let serialized = zvariant::to_bytes(ctxt, &Value::from(42u32)).unwrap();
+    zvariant::from_slice::<_, StrOrPoint>(&serialized, ctxt).unwrap_err();
+
+    // Typical use as property values.
+    let mut dict: HashMap<&str, Value<'_>> = HashMap::new();
+    dict.insert("Str", Value::from("hi"));
+    dict.insert("Point", Value::from((1i32, 2i32)));
+    let serialized = zvariant::to_bytes(ctxt, &dict).unwrap();
+    let props: HashMap<String, StrOrPoint> = zvariant::from_slice(&serialized, ctxt).unwrap().0;
+    assert_eq!(props["Str"], StrOrPoint::Str("hi".to_string()));
+    assert_eq!(props["Point"], StrOrPoint::Point(Point { x: 1, y: 2 }));
+}
@@ -0,0 +1,196 @@
+//! Contains utilities used to generate Rust code from D-Bus introspection data.
+//!
+//! These are shared by the `proxy_from_xml` macro of `zbus_macros` and by `zbus-xmlgen`, so that
+//! both generate the same API for a given interface.
+
+use std::fmt::{self, Display, Formatter};
+
+/// The maximum nesting depth of containers in a signature.
+///
+/// The D-Bus specification allows up to 32 nested arrays and 32 nested structures.
+pub const MAX_CONTAINER_DEPTH: usize = 64;
+
+/// The reason a D-Bus type has no Rust type, as returned by [`try_to_rust_type`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TypeError {
+    /// The type nests more containers than the given maximum depth.
+    MaxDepthExceeded(usize),
+    /// The signature isn't a single complete type, or it contains an empty structure.
+    Unsupported,
+}
+
+impl Display for TypeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeError::MaxDepthExceeded(max) => {
+                write!(f, "more than {max} nested containers")
+            }
+            TypeError::Unsupported => write!(f, "unsupported type"),
+        }
+    }
+}
+
+impl std::error::Error for TypeError {}
+
+/// The Rust type for the complete D-Bus type `signature`.
+///
+/// Types of `input` arguments are borrowed where possible and, with `as_ref`, the outer type is
+/// borrowed as well. The types of the `zvariant` crate are spelled with a `zbus::zvariant::`
+/// prefix.
+///
+/// Returns `None` if `signature` isn't a single complete type, if it contains an empty structure
+/// or if it nests more than [`MAX_CONTAINER_DEPTH`] containers.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant_utils::codegen::to_rust_type;
+///
+/// assert_eq!(to_rust_type("a{sv}", false, false).unwrap(), "std::collections::HashMap<String, zbus::zvariant::OwnedValue>");
+/// assert_eq!(to_rust_type("(su)", true, true).unwrap(), "&(&str, u32)");
+/// assert_eq!(to_rust_type("as", true, true).unwrap(), "&[&str]");
+/// assert!(to_rust_type("su", false, false).is_none());
+///
+/// let nested = format!("{}u{}", "(".repeat(1000), ")".repeat(1000));
+/// assert!(to_rust_type(&nested, false, false).is_none());
+/// ```
+pub fn to_rust_type(signature: &str, input: bool, as_ref: bool) -> Option<String> {
+    try_to_rust_type(signature, input, as_ref, MAX_CONTAINER_DEPTH).ok()
+}
+
+/// Same as [`to_rust_type`], with the given maximum nesting depth of containers.
+///
+/// # Examples
+///
+/// ```
+/// use zvariant_utils::codegen::{try_to_rust_type, TypeError};
+///
+/// assert_eq!(try_to_rust_type("aau", false, false, 2).unwrap(), "Vec<Vec<u32>>");
+/// assert_eq!(
+///     try_to_rust_type("aaau", false, false, 2),
+///     Err(TypeError::MaxDepthExceeded(2)),
+/// );
+/// assert_eq!(try_to_rust_type("()", false, false, 2), Err(TypeError::Unsupported));
+/// ```
+pub fn try_to_rust_type(
+    signature: &str,
+    input: bool,
+    as_ref: bool,
+    max_depth: usize,
+) -> Result<String, TypeError> {
+    let mut it = signature.chars().peekable();
+    let ty = next_type(&mut it, input, as_ref, 0, max_depth)?;
+
+    match it.next() {
+        None => Ok(ty),
+        Some(_) => Err(TypeError::Unsupported),
+    }
+}
+
+fn next_type(
+    it: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    input: bool,
+    as_ref: bool,
+    depth: usize,
+    max_depth: usize,
+) -> Result<String, TypeError> {
+    let ty = match it.next().ok_or(TypeError::Unsupported)? {
+        'y' => "u8".into(),
+        'b' => "bool".into(),
+        'n' => "i16".into(),
+        'q' => "u16".into(),
+        'i' => "i32".into(),
+        'u' => "u32".into(),
+        'x' => "i64".into(),
+        't' => "u64".into(),
+        'd' => "f64".into(),
+        'h' if input => "zbus::zvariant::Fd".into(),
+        'h' => "zbus::zvariant::OwnedFd".into(),
+        's' if input || as_ref => "&str".into(),
+        's' => "String".into(),
+        'o' if input && as_ref => "&zbus::zvariant::ObjectPath<'_>".into(),
+        'o' if input => "zbus::zvariant::ObjectPath<'_>".into(),
+        'o' => "zbus::zvariant::OwnedObjectPath".into(),
+        'g' if input && as_ref => "&zbus::zvariant::Signature<'_>".into(),
+        'g' if input => "zbus::zvariant::Signature<'_>".into(),
+        'g' => "zbus::zvariant::OwnedSignature".into(),
+        'v' if input && as_ref => "&zbus::zvariant::Value<'_>".into(),
+        'v' if input => "zbus::zvariant::Value<'_>".into(),
+        'v' => "zbus::zvariant::OwnedValue".into(),
+        'a' | '(' if depth == max_depth => return Err(TypeError::MaxDepthExceeded(max_depth)),
+        'a' if it.peek() == Some(&'{') => {
+            it.next();
+            let key = next_type(it, input, false, depth + 1, max_depth)?;
+            let value = next_type(it, input, false, depth + 1, max_depth)?;
+            if it.next() != Some('}') {
+                return Err(TypeError::Unsupported);
+            }
+            format!("std::collections::HashMap<{key}, {value}>")
+        }
+        'a' => {
+            let ty = next_type(it, input, false, depth + 1, max_depth)?;
+            match (input, as_ref) {
+                (true, _) => format!("&[{ty}]"),
+                (false, true) => format!("&Vec<{ty}>"),
+                (false, false) => format!("Vec<{ty}>"),
+            }
+        }
+        '(' => {
+            let mut fields = vec![];
+            while it.peek().ok_or(TypeError::Unsupported)? != &')' {
+                fields.push(next_type(it, input, false, depth + 1, max_depth)?);
+            }
+            it.next();
+            match fields.len() {
+                0 => return Err(TypeError::Unsupported),
+                1 => fields.remove(0),
+                _ => format!("{}({})", if as_ref { "&" } else { "" }, fields.join(", ")),
+            }
+        }
+        _ => return Err(TypeError::Unsupported),
+    };
+
+    Ok(ty)
+}
+
+/// Convert a D-Bus member or argument name to snake case.
+///
+/// Unlike [`crate::case::snake_case`], this keeps acronyms together (e.g `GetID` is `get_id`).
+pub fn snake_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut snake = String::new();
+    for (i, ch) in chars.iter().enumerate() {
+        if ch.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map_or(false, |c| c.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.push(ch.to_ascii_lowercase());
+    }
+
+    snake
+}
+
+/// The (strict and reserved) keywords of Rust.
+pub static KEYWORDS: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "union", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Make a Rust identifier of `id`, by appending `_` to keywords and replacing `-` with `_`.
+pub fn to_identifier(id: &str) -> String {
+    if KEYWORDS.contains(&id) {
+        format!("{id}_")
+    } else {
+        id.replace('-', "_")
+    }
+}
@@ -1,4 +1,5 @@
 //! Various utilities used by the `zvariant` crate and others.
 
 pub mod case;
+pub mod codegen;
 pub mod macros;
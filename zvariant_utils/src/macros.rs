@@ -228,6 +228,22 @@ pub fn iter_meta_lists(
 /// The syntax for inner attributes is the same as for the outer attributes, but you can specify
 /// only one inner attribute per outer attribute.
 ///
+/// # Aliases
+///
+/// More than one name can be given for the attribute lists, e.g to support a new name while
+/// keeping the old one. The attributes are then looked up in the lists with any of these names:
+///
+/// ```
+/// # use zvariant_utils::def_attrs;
+/// def_attrs! {
+///     crate zvariant, zv;
+///
+///     pub StructAttributes("struct") { foo str };
+/// }
+/// ```
+///
+/// An attribute can't be specified in more than one of these lists at once.
+///
 /// # Calling the macro multiple times
 ///
 /// The macro generates an array called `ALLOWED_ATTRS` that contains a list of allowed attributes.
@@ -357,12 +373,12 @@ macro_rules! def_attrs {
             }
         }
     };
-    (@def_ty $list_name:ident str) => {};
-    (@def_ty $list_name:ident bool) => {};
-    (@def_ty $list_name:ident [str]) => {};
-    (@def_ty $list_name:ident none) => {};
+    (@def_ty $list_names:tt str) => {};
+    (@def_ty $list_names:tt bool) => {};
+    (@def_ty $list_names:tt [str]) => {};
+    (@def_ty $list_names:tt none) => {};
     (
-        @def_ty $list_name:ident {
+        @def_ty $list_names:tt {
             $(#[$m:meta])*
             $vis:vis $name:ident($what:literal) {
                 $($attr_name:ident $kind:tt),+
@@ -370,11 +386,11 @@ macro_rules! def_attrs {
         }
     ) => {
         // Recurse further to potentially define nested lists.
-        $($crate::def_attrs!(@def_ty $attr_name $kind);)+
+        $($crate::def_attrs!(@def_ty [$attr_name] $kind);)+
 
         $crate::def_attrs!(
             @def_struct
-            $list_name
+            $list_names
             $(#[$m])*
             $vis $name($what) {
                 $($attr_name $kind),+
@@ -383,7 +399,7 @@ macro_rules! def_attrs {
     };
     (
         @def_struct
-        $list_name:ident
+        [$list_name:ident $(, $alias:ident)*]
         $(#[$m:meta])*
         $vis:vis $name:ident($what:literal) {
             $($attr_name:ident $kind:tt),+
@@ -445,19 +461,22 @@ macro_rules! def_attrs {
 
             pub fn parse(attrs: &[::syn::Attribute]) -> ::syn::Result<Self> {
                 let mut parsed = $name::default();
-                for nested_meta in $crate::macros::iter_meta_lists(attrs, ::std::stringify!($list_name))? {
-                    match &nested_meta {
-                        ::syn::NestedMeta::Meta(meta) => parsed.parse_meta(meta),
-                        ::syn::NestedMeta::Lit(lit) => {
-                            ::std::result::Result::Err(::syn::Error::new(
-                                lit.span(),
-                                ::std::concat!(
-                                    "attribute `", ::std::stringify!($list_name),
-                                    "` does not support literals in meta lists"
-                                )
-                            ))
-                        }
-                    }?;
+                let list_names = [::std::stringify!($list_name) $(, ::std::stringify!($alias))*];
+                for list_name in list_names.iter() {
+                    for nested_meta in $crate::macros::iter_meta_lists(attrs, list_name)? {
+                        match &nested_meta {
+                            ::syn::NestedMeta::Meta(meta) => parsed.parse_meta(meta),
+                            ::syn::NestedMeta::Lit(lit) => {
+                                ::std::result::Result::Err(::syn::Error::new(
+                                    lit.span(),
+                                    ::std::format!(
+                                        "attribute `{}` does not support literals in meta lists",
+                                        list_name,
+                                    )
+                                ))
+                            }
+                        }?;
+                    }
                 }
 
                 Ok(parsed)
@@ -465,7 +484,7 @@ macro_rules! def_attrs {
         }
     };
     (
-        crate $list_name:ident;
+        @def_groups $list_names:tt;
         $(
             $(#[$m:meta])*
             $vis:vis $name:ident($what:literal) {
@@ -480,7 +499,7 @@ macro_rules! def_attrs {
         $(
             $crate::def_attrs!(
                 @def_ty
-                $list_name {
+                $list_names {
                     $(#[$m])*
                     $vis $name($what) {
                         $($attr_name $kind),+
@@ -488,7 +507,13 @@ macro_rules! def_attrs {
                 }
             );
         )+
-    }
+    };
+    (
+        crate $list_name:ident $(, $alias:ident)*;
+        $($groups:tt)+
+    ) => {
+        $crate::def_attrs!(@def_groups [$list_name $(, $alias)*]; $($groups)+);
+    };
 }
 
 /// Checks if a [`Type`]'s identifier is "Option".